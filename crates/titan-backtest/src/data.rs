@@ -0,0 +1,157 @@
+//! Historical market data.
+//!
+//! The CSV format is the one `titan-replay` sends to the gateway:
+//!
+//! ```text
+//! timestamp,symbol,type,side,price,qty
+//! 1000,1,limit,buy,10000,100
+//! 1500,1,ioc,sell,9990,50
+//! ```
+//!
+//! Prices are in ticks. Order ids are assigned from the 1-based row number.
+
+use std::fmt;
+use std::path::Path;
+
+use titan_core::{OrderType, Price, Quantity, Side, SymbolId};
+
+/// One replayed market event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketEvent {
+    /// Historical order submitted to the book.
+    Order {
+        timestamp: u64,
+        order_id: u64,
+        side: Side,
+        order_type: OrderType,
+        price: Price,
+        quantity: Quantity,
+    },
+    /// Historical cancel of a previously replayed order.
+    Cancel { timestamp: u64, order_id: u64 },
+}
+
+impl MarketEvent {
+    /// Event time (nanoseconds).
+    #[inline]
+    pub fn timestamp(&self) -> u64 {
+        match *self {
+            MarketEvent::Order { timestamp, .. } | MarketEvent::Cancel { timestamp, .. } => timestamp,
+        }
+    }
+}
+
+/// Market data load error.
+#[derive(Debug)]
+pub enum DataError {
+    /// File could not be read or parsed as CSV.
+    Csv(csv::Error),
+    /// Unknown side or order type (1-based data row).
+    InvalidRow { row: usize, message: String },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Csv(e) => write!(f, "CSV error: {}", e),
+            DataError::InvalidRow { row, message } => write!(f, "row {}: {}", row, message),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<csv::Error> for DataError {
+    fn from(e: csv::Error) -> Self {
+        DataError::Csv(e)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CsvOrder {
+    timestamp: u64,
+    symbol: u64,
+    #[serde(rename = "type")]
+    order_type: String,
+    side: String,
+    price: u64,
+    qty: u64,
+}
+
+/// Load the events for `symbol` from a CSV file.
+pub fn load_csv<P: AsRef<Path>>(path: P, symbol: SymbolId) -> Result<Vec<MarketEvent>, DataError> {
+    read_csv(csv::Reader::from_path(path)?, symbol)
+}
+
+/// Parse events for `symbol` from CSV text.
+pub fn parse_csv(text: &str, symbol: SymbolId) -> Result<Vec<MarketEvent>, DataError> {
+    read_csv(csv::Reader::from_reader(text.as_bytes()), symbol)
+}
+
+fn read_csv<R: std::io::Read>(mut reader: csv::Reader<R>, symbol: SymbolId) -> Result<Vec<MarketEvent>, DataError> {
+    let mut events = Vec::new();
+
+    for (idx, record) in reader.deserialize::<CsvOrder>().enumerate() {
+        let row = idx + 1;
+        let record = record?;
+        if record.symbol != symbol.0 as u64 {
+            continue;
+        }
+
+        let invalid = |message: String| DataError::InvalidRow { row, message };
+        let side = match record.side.to_lowercase().as_str() {
+            "buy" => Side::Buy,
+            "sell" => Side::Sell,
+            other => return Err(invalid(format!("unknown side `{}`", other))),
+        };
+        let order_type = match record.order_type.to_lowercase().as_str() {
+            "limit" => OrderType::Limit,
+            "ioc" => OrderType::IOC,
+            "fok" => OrderType::FOK,
+            "post_only" => OrderType::PostOnly,
+            "market" => OrderType::Market,
+            other => return Err(invalid(format!("unknown order type `{}`", other))),
+        };
+
+        events.push(MarketEvent::Order {
+            timestamp: record.timestamp,
+            order_id: row as u64,
+            side,
+            order_type,
+            price: Price::from_ticks(record.price),
+            quantity: Quantity(record.qty),
+        });
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let text = "timestamp,symbol,type,side,price,qty\n\
+                    1000,1,limit,buy,100,10\n\
+                    1100,2,limit,sell,200,10\n\
+                    1200,1,IOC,Sell,99,5\n";
+        let events = parse_csv(text, SymbolId(1)).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1],
+            MarketEvent::Order {
+                timestamp: 1200,
+                order_id: 3,
+                side: Side::Sell,
+                order_type: OrderType::IOC,
+                price: Price::from_ticks(99),
+                quantity: Quantity(5),
+            }
+        );
+
+        let bad = "timestamp,symbol,type,side,price,qty\n1,1,limit,hold,1,1\n";
+        assert!(matches!(parse_csv(bad, SymbolId(1)), Err(DataError::InvalidRow { row: 1, .. })));
+    }
+}
@@ -0,0 +1,116 @@
+//! Simulated order entry gateway.
+//!
+//! Strategy actions are delayed by a fixed one-way latency before they
+//! reach the engine. With a constant latency, arrival order equals send
+//! order, so a FIFO is enough.
+
+use std::collections::VecDeque;
+
+use titan_core::{OrderId, OrderType, Price, Quantity, Side};
+
+/// Strategy order ids have the top bit set so they never collide with
+/// replayed order ids; bits 40..63 carry the strategy index.
+const STRATEGY_ORDER_BIT: u64 = 1 << 63;
+const STRATEGY_SHIFT: u32 = 40;
+
+/// Order action sent by a strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Submit {
+        order_id: OrderId,
+        side: Side,
+        order_type: OrderType,
+        price: Price,
+        quantity: Quantity,
+    },
+    Cancel { order_id: OrderId },
+}
+
+/// Action in flight to the engine.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct InFlight {
+    pub strategy: usize,
+    pub sent: u64,
+    pub arrival: u64,
+    /// Mid when the action was sent (slippage reference).
+    pub arrival_mid: Option<Price>,
+    pub action: Action,
+}
+
+/// Latency model between strategies and the engine.
+#[derive(Debug)]
+pub struct SimGateway {
+    latency_ns: u64,
+    pending: VecDeque<InFlight>,
+    next_seq: Vec<u64>,
+}
+
+impl SimGateway {
+    /// Create a gateway with a fixed one-way latency.
+    pub fn new(latency_ns: u64) -> Self {
+        Self { latency_ns, pending: VecDeque::new(), next_seq: Vec::new() }
+    }
+
+    /// One-way latency (nanoseconds).
+    pub fn latency_ns(&self) -> u64 {
+        self.latency_ns
+    }
+
+    /// Actions not yet delivered.
+    pub fn in_flight(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether `order_id` was issued by a strategy.
+    #[inline]
+    pub fn is_strategy_order(order_id: OrderId) -> bool {
+        order_id.0 & STRATEGY_ORDER_BIT != 0
+    }
+
+    pub(crate) fn next_order_id(&mut self, strategy: usize) -> OrderId {
+        if self.next_seq.len() <= strategy {
+            self.next_seq.resize(strategy + 1, 0);
+        }
+        self.next_seq[strategy] += 1;
+        OrderId(STRATEGY_ORDER_BIT | ((strategy as u64) << STRATEGY_SHIFT) | self.next_seq[strategy])
+    }
+
+    pub(crate) fn send(&mut self, strategy: usize, now: u64, arrival_mid: Option<Price>, action: Action) {
+        self.pending.push_back(InFlight {
+            strategy,
+            sent: now,
+            arrival: now + self.latency_ns,
+            arrival_mid,
+            action,
+        });
+    }
+
+    /// Next action arriving at or before `until`.
+    pub(crate) fn pop_due(&mut self, until: u64) -> Option<InFlight> {
+        match self.pending.front() {
+            Some(next) if next.arrival <= until => self.pending.pop_front(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_and_ids() {
+        let mut gateway = SimGateway::new(500);
+        let a = gateway.next_order_id(0);
+        let b = gateway.next_order_id(2);
+        assert!(SimGateway::is_strategy_order(a) && SimGateway::is_strategy_order(b));
+        assert!(!SimGateway::is_strategy_order(OrderId(42)));
+        assert_ne!(a, b);
+
+        gateway.send(0, 1_000, None, Action::Cancel { order_id: a });
+        assert!(gateway.pop_due(1_499).is_none());
+        let due = gateway.pop_due(1_500).unwrap();
+        assert_eq!((due.sent, due.arrival), (1_000, 1_500));
+        assert_eq!(gateway.in_flight(), 0);
+    }
+}
@@ -0,0 +1,26 @@
+//! Strategy backtesting on the real matching engine.
+//!
+//! Historical order flow is replayed through a `MatchingEngine`; strategy
+//! orders travel through a simulated gateway with configurable latency and
+//! match against the replayed book, so fills, queue position and market
+//! impact come from the engine itself rather than a fill model.
+//!
+//! ## Components
+//! - `Strategy`: quote/trade/fill callbacks with a `Context` for orders
+//! - `SimGateway`: latency model between strategy and engine
+//! - `PnL`: fixed-point average-cost position accounting
+//! - `StrategyReport`: per-strategy P&L, latency and slippage
+
+pub mod data;
+pub mod strategy;
+pub mod gateway;
+pub mod pnl;
+pub mod report;
+pub mod backtest;
+
+pub use data::{load_csv, parse_csv, DataError, MarketEvent};
+pub use strategy::{Context, Quote, Strategy, StrategyFill, Trade};
+pub use gateway::{Action, SimGateway};
+pub use pnl::PnL;
+pub use report::StrategyReport;
+pub use backtest::{Backtest, BacktestConfig};
@@ -0,0 +1,102 @@
+//! Fixed-point P&L accounting.
+//!
+//! Amounts are `price.as_raw() * qty` in `i128`, so nothing is lost to
+//! floating point. Realized P&L uses the average cost of the open position.
+
+use titan_core::{Price, Quantity, Side};
+
+/// Position and P&L for one strategy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PnL {
+    /// Signed position (positive = long).
+    position: i64,
+    /// Signed cost of the open position.
+    cost: i128,
+    /// Realized P&L.
+    realized: i128,
+    /// Total traded quantity.
+    volume: u64,
+}
+
+impl PnL {
+    /// Create flat accounting.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a fill.
+    pub fn on_fill(&mut self, side: Side, price: Price, qty: Quantity) {
+        let px = price.as_raw() as i128;
+        let mut qty = qty.0 as i128;
+        let dir: i128 = if side.is_buy() { 1 } else { -1 };
+        self.volume += qty as u64;
+
+        // Close against the open position first
+        let pos = self.position as i128;
+        if pos != 0 && pos.signum() != dir {
+            let close = qty.min(pos.abs());
+            let portion = self.cost * close / pos.abs();
+            self.realized += pos.signum() * close * px - portion;
+            self.cost -= portion;
+            self.position -= (pos.signum() * close) as i64;
+            qty -= close;
+        }
+
+        // Remainder opens or extends
+        self.cost += dir * qty * px;
+        self.position += (dir * qty) as i64;
+    }
+
+    /// Signed position.
+    #[inline]
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Realized P&L.
+    #[inline]
+    pub fn realized(&self) -> i128 {
+        self.realized
+    }
+
+    /// Unrealized P&L of the open position at `mark`.
+    pub fn unrealized(&self, mark: Price) -> i128 {
+        self.position as i128 * mark.as_raw() as i128 - self.cost
+    }
+
+    /// Realized plus unrealized at `mark`.
+    pub fn total(&self, mark: Price) -> i128 {
+        self.realized + self.unrealized(mark)
+    }
+
+    /// Total traded quantity.
+    #[inline]
+    pub fn volume(&self) -> u64 {
+        self.volume
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips() {
+        let mut pnl = PnL::new();
+        pnl.on_fill(Side::Buy, Price(100), Quantity(10));
+        pnl.on_fill(Side::Sell, Price(110), Quantity(4));
+        assert_eq!(pnl.position(), 6);
+        assert_eq!(pnl.realized(), 40);
+        assert_eq!(pnl.unrealized(Price(105)), 30);
+
+        // Flip through flat to short
+        pnl.on_fill(Side::Sell, Price(90), Quantity(10));
+        assert_eq!(pnl.position(), -4);
+        assert_eq!(pnl.realized(), 40 - 60);
+        pnl.on_fill(Side::Buy, Price(80), Quantity(4));
+        assert_eq!(pnl.position(), 0);
+        assert_eq!(pnl.realized(), -20 + 40);
+        assert_eq!(pnl.total(Price(1)), 20);
+        assert_eq!(pnl.volume(), 28);
+    }
+}
@@ -0,0 +1,83 @@
+//! Per-strategy results.
+
+use titan_core::{Price, Side};
+use titan_metrics::LatencyHistogram;
+
+use crate::pnl::PnL;
+
+/// Results for one strategy.
+pub struct StrategyReport {
+    pub name: String,
+    pub orders_sent: u64,
+    pub cancels_sent: u64,
+    pub rejects: u64,
+    pub fills: u64,
+    pub pnl: PnL,
+    /// Last trade or mid, used to mark the open position.
+    pub mark: Price,
+    /// Wall-clock time spent in strategy callbacks (nanoseconds).
+    pub decision: LatencyHistogram,
+    /// Simulated time from send to first fill (nanoseconds).
+    pub fill_latency: LatencyHistogram,
+    /// Signed slippage vs. mid at send time; positive is adverse.
+    pub slippage: i128,
+    /// Mid-at-send notional of fills that had a reference mid.
+    pub slippage_notional: i128,
+}
+
+impl StrategyReport {
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            orders_sent: 0,
+            cancels_sent: 0,
+            rejects: 0,
+            fills: 0,
+            pnl: PnL::new(),
+            mark: Price::ZERO,
+            decision: LatencyHistogram::new(),
+            fill_latency: LatencyHistogram::new(),
+            slippage: 0,
+            slippage_notional: 0,
+        }
+    }
+
+    /// Accumulate slippage for a fill against the mid at send time.
+    pub(crate) fn record_slippage(&mut self, side: Side, price: Price, qty: u64, arrival_mid: Option<Price>) {
+        let Some(mid) = arrival_mid else {
+            return;
+        };
+        let diff = price.as_raw() as i128 - mid.as_raw() as i128;
+        let signed = if side.is_buy() { diff } else { -diff };
+        self.slippage += signed * qty as i128;
+        self.slippage_notional += mid.as_raw() as i128 * qty as i128;
+    }
+
+    /// Average slippage in basis points of the reference notional.
+    pub fn slippage_bps(&self) -> f64 {
+        if self.slippage_notional == 0 {
+            return 0.0;
+        }
+        self.slippage as f64 * 10_000.0 / self.slippage_notional as f64
+    }
+
+    /// Realized plus unrealized P&L at the final mark.
+    pub fn total_pnl(&self) -> i128 {
+        self.pnl.total(self.mark)
+    }
+
+    /// Print a summary.
+    pub fn print_summary(&self) {
+        println!("📈 Strategy: {}", self.name);
+        println!("   Orders:     {:>12} sent, {} cancels, {} rejects", self.orders_sent, self.cancels_sent, self.rejects);
+        println!("   Fills:      {:>12} ({} volume)", self.fills, self.pnl.volume());
+        println!("   Position:   {:>12}", self.pnl.position());
+        println!("   Realized:   {:>12}", self.pnl.realized());
+        println!("   Total P&L:  {:>12} (mark {})", self.total_pnl(), self.mark.as_raw());
+        println!("   Slippage:   {:>12.2} bps", self.slippage_bps());
+        self.decision.print_summary("   Decision");
+        if self.fill_latency.count() > 0 {
+            self.fill_latency.print_summary("   Send-to-fill");
+        }
+    }
+}
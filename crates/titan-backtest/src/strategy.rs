@@ -0,0 +1,119 @@
+//! Strategy interface.
+
+use titan_core::{OrderId, OrderType, Price, Quantity, Side};
+
+use crate::gateway::{Action, SimGateway};
+
+/// Top of book after it changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    pub bid: Option<Price>,
+    pub ask: Option<Price>,
+    pub timestamp: u64,
+}
+
+impl Quote {
+    /// Midpoint, or the one-sided price when only one side is present.
+    pub fn mid(&self) -> Option<Price> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) => Some(Price((bid.0 + ask.0) / 2)),
+            (one, None) | (None, one) => one,
+        }
+    }
+}
+
+/// Public trade.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trade {
+    pub price: Price,
+    pub quantity: Quantity,
+    /// Side of the incoming (aggressing) order.
+    pub aggressor: Side,
+    pub timestamp: u64,
+}
+
+/// Execution of one of the strategy's own orders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StrategyFill {
+    pub order_id: OrderId,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+    /// Quantity still open on the order.
+    pub leaves: Quantity,
+    /// Whether the order was resting (provided liquidity).
+    pub maker: bool,
+    pub timestamp: u64,
+}
+
+/// Trading strategy under test.
+///
+/// Callbacks run in simulated time; orders placed through the `Context`
+/// reach the engine after the gateway latency.
+pub trait Strategy {
+    /// Name used in reports.
+    fn name(&self) -> &str {
+        "strategy"
+    }
+
+    /// Top of book changed.
+    fn on_quote(&mut self, _ctx: &mut Context<'_>, _quote: &Quote) {}
+
+    /// A trade printed (including the strategy's own).
+    fn on_trade(&mut self, _ctx: &mut Context<'_>, _trade: &Trade) {}
+
+    /// One of the strategy's orders was filled.
+    fn on_fill(&mut self, _ctx: &mut Context<'_>, _fill: &StrategyFill) {}
+}
+
+/// Order entry and state available to a strategy callback.
+pub struct Context<'a> {
+    pub(crate) strategy: usize,
+    pub(crate) now: u64,
+    pub(crate) position: i64,
+    pub(crate) quote: Quote,
+    pub(crate) gateway: &'a mut SimGateway,
+}
+
+impl Context<'_> {
+    /// Current simulated time (nanoseconds).
+    #[inline]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Signed position (positive = long).
+    #[inline]
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Last known top of book.
+    #[inline]
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    /// Send a new order; returns its id.
+    pub fn submit(&mut self, side: Side, order_type: OrderType, price: Price, quantity: Quantity) -> OrderId {
+        let order_id = self.gateway.next_order_id(self.strategy);
+        let action = Action::Submit { order_id, side, order_type, price, quantity };
+        self.gateway.send(self.strategy, self.now, self.quote.mid(), action);
+        order_id
+    }
+
+    /// Send a limit buy.
+    pub fn buy(&mut self, price: Price, quantity: Quantity) -> OrderId {
+        self.submit(Side::Buy, OrderType::Limit, price, quantity)
+    }
+
+    /// Send a limit sell.
+    pub fn sell(&mut self, price: Price, quantity: Quantity) -> OrderId {
+        self.submit(Side::Sell, OrderType::Limit, price, quantity)
+    }
+
+    /// Request cancellation of a resting order.
+    pub fn cancel(&mut self, order_id: OrderId) {
+        self.gateway.send(self.strategy, self.now, self.quote.mid(), Action::Cancel { order_id });
+    }
+}
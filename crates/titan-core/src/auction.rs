@@ -0,0 +1,101 @@
+//! Call auctions.
+//!
+//! During the call phase orders rest without matching and the book may
+//! cross. The uncross executes everything that can trade at a single
+//! equilibrium price: the one maximising executable volume, then
+//! minimising the unmatched surplus, then closest to the reference price
+//! (lowest price if there is none).
+
+use core::cmp::Reverse;
+use crate::fixed::{Price, Quantity};
+
+/// Trading phase of an engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TradingPhase {
+    /// Orders match on arrival.
+    #[default]
+    Continuous,
+    /// Orders accumulate until `uncross`.
+    Auction,
+}
+
+/// Equilibrium of a crossed book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Uncross {
+    /// Auction price.
+    pub price: Price,
+    /// Quantity executable at `price`.
+    pub volume: Quantity,
+    /// Buy quantity willing to trade at `price`.
+    pub bid_qty: Quantity,
+    /// Sell quantity willing to trade at `price`.
+    pub ask_qty: Quantity,
+}
+
+impl Uncross {
+    /// Quantity left unmatched at the auction price.
+    #[inline(always)]
+    pub const fn surplus(&self) -> Quantity {
+        Quantity(self.bid_qty.0.abs_diff(self.ask_qty.0))
+    }
+}
+
+/// Find the equilibrium from crossing levels, each `(price, quantity)`
+/// best first: bids descending, asks ascending.
+///
+/// Returns `None` if nothing can trade.
+pub fn equilibrium(
+    bids: &[(Price, Quantity)],
+    asks: &[(Price, Quantity)],
+    reference: Option<Price>,
+) -> Option<Uncross> {
+    let distance = |p: Price| reference.map_or(p.0, |r| p.0.abs_diff(r.0));
+    let rank = |u: &Uncross| (u.volume, Reverse(u.surplus()), Reverse(distance(u.price)));
+    let mut best: Option<Uncross> = None;
+
+    for &(price, _) in bids.iter().chain(asks) {
+        let bid_qty = bids.iter().take_while(|(p, _)| p.0 >= price.0).map(|(_, q)| q.0).sum();
+        let ask_qty = asks.iter().take_while(|(p, _)| p.0 <= price.0).map(|(_, q)| q.0).sum();
+        let candidate = Uncross {
+            price,
+            volume: Quantity(u64::min(bid_qty, ask_qty)),
+            bid_qty: Quantity(bid_qty),
+            ask_qty: Quantity(ask_qty),
+        };
+        if candidate.volume.is_zero() {
+            continue;
+        }
+
+        if best.as_ref().is_none_or(|current| rank(&candidate) > rank(current)) {
+            best = Some(candidate);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(raw: &[(u64, u64)]) -> alloc::vec::Vec<(Price, Quantity)> {
+        raw.iter().map(|&(p, q)| (Price::from_ticks(p), Quantity(q))).collect()
+    }
+
+    #[test]
+    fn test_equilibrium() {
+        let bids = levels(&[(103, 10), (102, 20), (100, 30)]);
+        let asks = levels(&[(99, 15), (101, 25), (103, 40)]);
+
+        // 101 and 102 both trade 30 with 10 left over; lowest wins by default
+        let uncross = equilibrium(&bids, &asks, None).unwrap();
+        assert_eq!(uncross.volume.0, 30);
+        assert_eq!(uncross.price, Price::from_ticks(101));
+        assert_eq!(uncross.surplus().0, 10);
+
+        let uncross = equilibrium(&bids, &asks, Some(Price::from_ticks(102))).unwrap();
+        assert_eq!(uncross.price, Price::from_ticks(102));
+
+        assert!(equilibrium(&levels(&[(98, 10)]), &asks, None).is_none());
+    }
+}
@@ -0,0 +1,51 @@
+//! Price bands (collars).
+//!
+//! Orders priced too far from the reference price are rejected before
+//! they can trade. The reference follows the last trade unless a mark is
+//! supplied externally.
+
+use crate::fixed::Price;
+
+/// Maximum distance an order price may stray from the reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceBand {
+    /// Fixed number of ticks either side.
+    Ticks(u64),
+    /// Basis points of the reference price either side.
+    Bps(u32),
+}
+
+impl PriceBand {
+    /// Allowed distance (raw price units) around `reference` on a
+    /// `tick_size` price grid.
+    #[inline(always)]
+    pub fn width(self, reference: Price, tick_size: u64) -> u64 {
+        match self {
+            PriceBand::Ticks(ticks) => ticks.saturating_mul(tick_size),
+            PriceBand::Bps(bps) => (reference.0 as u128 * bps as u128 / 10_000) as u64,
+        }
+    }
+
+    /// Check if `price` lies within the band around `reference`.
+    #[inline(always)]
+    pub fn contains(self, reference: Price, price: Price, tick_size: u64) -> bool {
+        price.0.abs_diff(reference.0) <= self.width(reference, tick_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_width() {
+        let tick = Price::TICK_SIZE;
+        let reference = Price::from_ticks(1_000);
+        assert!(PriceBand::Ticks(5).contains(reference, Price::from_ticks(1_005), tick));
+        assert!(!PriceBand::Ticks(5).contains(reference, Price::from_ticks(994), tick));
+
+        // 5% of 1000 ticks = 50 ticks
+        assert!(PriceBand::Bps(500).contains(reference, Price::from_ticks(950), tick));
+        assert!(!PriceBand::Bps(500).contains(reference, Price::from_ticks(1_051), tick));
+    }
+}
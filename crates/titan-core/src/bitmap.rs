@@ -0,0 +1,162 @@
+//! Two-level occupancy bitmap.
+//!
+//! One bit per slot in `u64` words, plus a summary with one bit per
+//! non-zero word. Finding the nearest set bit in either direction takes a
+//! `trailing_zeros`/`leading_zeros` on the word, then on the summary, then
+//! on the word it points at; only the (short) summary is ever scanned.
+
+use alloc::vec;
+use alloc::boxed::Box;
+
+/// Occupancy bitmap over slots `0..len`.
+pub struct LevelBitmap {
+    /// One bit per slot.
+    words: Box<[u64]>,
+    /// One bit per non-zero word.
+    summary: Box<[u64]>,
+}
+
+/// Bits `from..64` of a word.
+#[inline(always)]
+fn mask_from(bit: usize) -> u64 {
+    !0u64 << bit
+}
+
+/// Bits `0..=to` of a word.
+#[inline(always)]
+fn mask_to(bit: usize) -> u64 {
+    !0u64 >> (63 - bit)
+}
+
+impl LevelBitmap {
+    /// Create an empty bitmap of `len` slots.
+    pub fn new(len: usize) -> Self {
+        let words = len.div_ceil(64).max(1);
+        Self {
+            words: vec![0; words].into_boxed_slice(),
+            summary: vec![0; words.div_ceil(64)].into_boxed_slice(),
+        }
+    }
+
+    /// Extend to slots `0..len` (the new slots free).
+    pub fn grow(&mut self, len: usize) {
+        let words = len.div_ceil(64).max(1);
+        if words <= self.words.len() {
+            return;
+        }
+        let mut grown = core::mem::take(&mut self.words).into_vec();
+        grown.resize(words, 0);
+        self.words = grown.into_boxed_slice();
+        let mut summary = core::mem::take(&mut self.summary).into_vec();
+        summary.resize(words.div_ceil(64), 0);
+        self.summary = summary.into_boxed_slice();
+    }
+
+    /// Mark slot `idx` occupied.
+    #[inline(always)]
+    pub fn set(&mut self, idx: usize) {
+        let word = idx / 64;
+        self.words[word] |= 1 << (idx % 64);
+        self.summary[word / 64] |= 1 << (word % 64);
+    }
+
+    /// Mark slot `idx` free.
+    #[inline(always)]
+    pub fn clear(&mut self, idx: usize) {
+        let word = idx / 64;
+        self.words[word] &= !(1 << (idx % 64));
+        if self.words[word] == 0 {
+            self.summary[word / 64] &= !(1 << (word % 64));
+        }
+    }
+
+    /// Check if slot `idx` is occupied.
+    #[inline(always)]
+    pub fn contains(&self, idx: usize) -> bool {
+        self.words[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Free every slot.
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+        self.summary.fill(0);
+    }
+
+    /// Lowest occupied slot at or above `from`.
+    pub fn first_from(&self, from: usize) -> Option<usize> {
+        let word = from / 64;
+        if word >= self.words.len() {
+            return None;
+        }
+        let bits = self.words[word] & mask_from(from % 64);
+        if bits != 0 {
+            return Some(word * 64 + bits.trailing_zeros() as usize);
+        }
+
+        let start = word + 1;
+        let mut group = start / 64;
+        let mut bits = *self.summary.get(group)? & mask_from(start % 64);
+        loop {
+            if bits != 0 {
+                let word = group * 64 + bits.trailing_zeros() as usize;
+                return Some(word * 64 + self.words[word].trailing_zeros() as usize);
+            }
+            group += 1;
+            bits = *self.summary.get(group)?;
+        }
+    }
+
+    /// Highest occupied slot at or below `to`.
+    pub fn last_to(&self, to: usize) -> Option<usize> {
+        let word = (to / 64).min(self.words.len() - 1);
+        let bit = if word == to / 64 { to % 64 } else { 63 };
+        let bits = self.words[word] & mask_to(bit);
+        if bits != 0 {
+            return Some(word * 64 + 63 - bits.leading_zeros() as usize);
+        }
+
+        let end = word.checked_sub(1)?;
+        let mut group = end / 64;
+        let mut bits = self.summary[group] & mask_to(end % 64);
+        loop {
+            if bits != 0 {
+                let word = group * 64 + 63 - bits.leading_zeros() as usize;
+                return Some(word * 64 + 63 - self.words[word].leading_zeros() as usize);
+            }
+            group = group.checked_sub(1)?;
+            bits = self.summary[group];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_set_bit() {
+        let mut bits = LevelBitmap::new(65536);
+        assert_eq!(bits.first_from(0), None);
+        assert_eq!(bits.last_to(65535), None);
+
+        for idx in [3, 64, 4095, 4096, 60000] {
+            bits.set(idx);
+        }
+        assert_eq!(bits.first_from(0), Some(3));
+        assert_eq!(bits.first_from(4), Some(64));
+        assert_eq!(bits.first_from(65), Some(4095));
+        assert_eq!(bits.first_from(4097), Some(60000));
+        assert_eq!(bits.first_from(60001), None);
+        assert_eq!(bits.last_to(65535), Some(60000));
+        assert_eq!(bits.last_to(59999), Some(4096));
+        assert_eq!(bits.last_to(4095), Some(4095));
+        assert_eq!(bits.last_to(63), Some(3));
+        assert_eq!(bits.last_to(2), None);
+
+        bits.clear(4095);
+        bits.clear(4096);
+        assert!(!bits.contains(4096));
+        assert_eq!(bits.first_from(65), Some(60000));
+        assert_eq!(bits.last_to(59999), Some(64));
+    }
+}
@@ -1,40 +1,121 @@
 //! Order book data structures.
 //!
 //! The order book maintains two sides (bids and asks) with price levels
-//! indexed by price for O(1) access.
+//! indexed by price for O(1) access. Each side keeps a dense window of
+//! `MAX_LEVELS` levels around its best price; levels far behind it live
+//! in a sorted overflow and move into the window as the price gets there.
 
+use core::cell::Cell;
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use arrayvec::ArrayVec;
+use crate::bitmap::LevelBitmap;
+use crate::checksum::{self, CHECKSUM_DEPTH};
 use crate::fixed::{Price, Quantity};
-use crate::order::{Order, Side};
-use crate::pool::OrderHandle;
-use crate::level::PriceLevel;
+use crate::order::{Order, OrderId, Side};
+use crate::pool::{OrderHandle, OrderPool};
+use crate::level::{PriceLevel, QueueLinks};
+use crate::memory::MemoryPolicy;
 
 /// Maximum number of price levels per side.
 /// For a stock with $0.01 ticks and $1000 range: 100,000 levels.
 /// Using 65536 (2^16) for efficient indexing.
 pub const MAX_LEVELS: usize = 65536;
 
+/// Aggregated view of one price level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DepthLevel {
+    /// Level price.
+    pub price: Price,
+    /// Displayed quantity at the level.
+    pub qty: Quantity,
+    /// Orders queued at the level.
+    pub orders: u32,
+}
+
+/// Resting quantity and order count over a band of levels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Liquidity {
+    /// Displayed quantity.
+    pub qty: Quantity,
+    /// Orders queued.
+    pub orders: u32,
+}
+
+/// Quantity and order count of one price level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LevelInfo {
+    /// Displayed quantity (zero once the level is gone).
+    pub qty: Quantity,
+    /// Orders queued.
+    pub order_count: u32,
+}
+
+/// Best `N` levels of each side, best first. Lives on the stack.
+#[derive(Clone, Debug, Default)]
+pub struct DepthSnapshot<const N: usize> {
+    /// Bid levels, highest price first.
+    pub bids: ArrayVec<DepthLevel, N>,
+    /// Ask levels, lowest price first.
+    pub asks: ArrayVec<DepthLevel, N>,
+}
+
+/// One resting order in an order-by-order (L3) view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L3Order {
+    /// Level price.
+    pub price: Price,
+    /// Order ID.
+    pub order_id: OrderId,
+    /// Open quantity, hidden iceberg reserve included.
+    pub remaining_qty: Quantity,
+    /// Time the order took its place in the queue.
+    pub timestamp: u64,
+}
+
 /// One side of the order book (Bids or Asks).
 pub struct BookSide {
     /// Price levels indexed by tick offset from base price.
     /// Index = (price - base_price) / tick_size
     levels: Box<[Option<PriceLevel>]>,
     
+    /// Which `levels` slots are populated.
+    occupied: LevelBitmap,
+    
+    /// Levels outside the window, keyed by raw price. Always behind the
+    /// best level.
+    overflow: BTreeMap<u64, PriceLevel>,
+    
     /// Best price level index (None if side is empty).
     best_idx: Option<u32>,
     
     /// Side indicator for price comparison.
     side: Side,
     
-    /// Base price for indexing (lowest price in range).
+    /// Base price for indexing (lowest price in range). Moves when the
+    /// window re-anchors.
     base_price: Price,
     
+    /// Price increment between adjacent levels (raw units).
+    tick_size: u64,
+    
     /// Total order count on this side.
     order_count: u64,
     
     /// Total quantity on this side.
     total_qty: Quantity,
+    
+    /// Total notional (price × displayed quantity) on this side.
+    notional: u128,
+    
+    /// Bumped on every mutation (checksum invalidation).
+    revision: u64,
+    
+    /// Record changed levels for `flush_changes`.
+    tracking: bool,
+    
+    /// Prices of levels changed since the last flush (may repeat).
+    changed: alloc::vec::Vec<Price>,
 }
 
 impl BookSide {
@@ -43,20 +124,85 @@ impl BookSide {
     /// `base_price` is the minimum price that can be represented.
     /// Prices below this cannot be used.
     pub fn new(side: Side, base_price: Price) -> Self {
+        Self::with_tick_size(side, base_price, Price::TICK_SIZE)
+    }
+    
+    /// Create a book side whose levels are `tick_size` raw units apart.
+    pub fn with_tick_size(side: Side, base_price: Price, tick_size: u64) -> Self {
         // Allocate with all None (no levels initially)
         let mut levels_vec = alloc::vec::Vec::with_capacity(MAX_LEVELS);
         levels_vec.resize_with(MAX_LEVELS, || None);
         
         Self {
             levels: levels_vec.into_boxed_slice(),
+            occupied: LevelBitmap::new(MAX_LEVELS),
+            overflow: BTreeMap::new(),
             best_idx: None,
             side,
             base_price,
+            tick_size: tick_size.max(1),
             order_count: 0,
             total_qty: Quantity::ZERO,
+            notional: 0,
+            revision: 0,
+            tracking: false,
+            changed: alloc::vec::Vec::new(),
+        }
+    }
+    
+    /// Record a mutation.
+    #[inline(always)]
+    fn touch(&mut self) {
+        self.revision = self.revision.wrapping_add(1);
+    }
+    
+    /// Note a change to level `price` if changes are tracked.
+    #[inline(always)]
+    fn mark(&mut self, price: Price) {
+        if self.tracking && self.changed.last() != Some(&price) {
+            self.changed.push(price);
+        }
+    }
+    
+    /// Start or stop recording which levels change.
+    ///
+    /// Off by default; once on, call `flush_changes` regularly.
+    pub fn track_changes(&mut self, enabled: bool) {
+        self.tracking = enabled;
+        if !enabled {
+            self.changed.clear();
         }
     }
     
+    /// Levels changed since the last flush, in price order, with their
+    /// current state (zero quantity for a level that is gone), appended
+    /// to `out`.
+    pub fn flush_changes(&mut self, out: &mut alloc::vec::Vec<(Price, LevelInfo)>) {
+        let mut changed = core::mem::take(&mut self.changed);
+        changed.sort_unstable();
+        changed.dedup();
+        out.extend(changed.iter().map(|&price| (price, self.level_info(price).unwrap_or_default())));
+        changed.clear();
+        self.changed = changed;
+    }
+    
+    /// Quantity and order count at `price`, `None` if nothing rests there.
+    pub fn level_info(&self, price: Price) -> Option<LevelInfo> {
+        let level = match self.price_to_idx(price) {
+            Some(idx) => self.levels[idx].as_ref(),
+            None => self.overflow.get(&price.0),
+        };
+        level
+            .filter(|level| !level.is_empty())
+            .map(|level| LevelInfo { qty: level.total_qty, order_count: level.len() as u32 })
+    }
+    
+    /// Counter bumped by every mutation of this side.
+    #[inline(always)]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+    
     /// Convert price to level index.
     #[inline(always)]
     fn price_to_idx(&self, price: Price) -> Option<usize> {
@@ -64,40 +210,229 @@ impl BookSide {
             return None;
         }
         let offset = price.0 - self.base_price.0;
-        let idx = (offset / Price::TICK_SIZE) as usize;
+        let idx = (offset / self.tick_size) as usize;
         if idx < MAX_LEVELS { Some(idx) } else { None }
     }
     
     /// Convert level index back to price.
     #[inline(always)]
     fn idx_to_price(&self, idx: usize) -> Price {
-        Price(self.base_price.0 + (idx as u64 * Price::TICK_SIZE))
+        Price(self.base_price.0 + (idx as u64 * self.tick_size))
+    }
+    
+    /// Lowest price the level window currently covers.
+    #[inline(always)]
+    pub fn base_price(&self) -> Price {
+        self.base_price
+    }
+    
+    /// Place the level window per `policy`. Returns `false` if the hints
+    /// could not all be applied.
+    pub fn set_memory_policy(&mut self, policy: MemoryPolicy) -> bool {
+        policy.apply(&self.levels)
+    }
+    
+    /// End of the level window (first price past it).
+    #[inline(always)]
+    fn window_end(&self) -> u64 {
+        self.base_price.0.saturating_add(MAX_LEVELS as u64 * self.tick_size)
+    }
+    
+    /// Check if `a` has priority over `b` on this side.
+    #[inline(always)]
+    fn is_better(&self, a: Price, b: Price) -> bool {
+        match self.side {
+            Side::Buy => a > b,
+            Side::Sell => a < b,
+        }
+    }
+    
+    /// Slide the level window so `price` fits.
+    ///
+    /// The window is re-centred on the live levels plus `price` when they
+    /// fit in it, otherwise on `price` itself if it would be the new best.
+    /// Fails for a far price behind the best level, which belongs in the
+    /// overflow.
+    fn reanchor(&mut self, price: Price) -> bool {
+        let tick = self.tick_size as i128;
+        let base = self.base_price.0 as i128;
+        let target = (price.0 as i128 - base).div_euclid(tick);
+        let window = MAX_LEVELS as i128;
+        // Never slide below price zero
+        let floor = -(base / tick);
+        
+        let (lo, hi) = match self.live_bounds() {
+            Some((lo, hi)) => (target.min(lo as i128), target.max(hi as i128)),
+            None => (target, target),
+        };
+        
+        let shift = if hi - lo < window {
+            ((lo + hi) / 2 - window / 2).clamp(hi - (window - 1), lo).max(floor)
+        } else if self.best_price().is_none_or(|best| self.is_better(price, best)) {
+            (target - window / 2).max(floor)
+        } else {
+            return false;
+        };
+        self.shift_window(shift);
+        true
+    }
+    
+    /// Lowest and highest non-empty levels in the window.
+    fn live_bounds(&self) -> Option<(usize, usize)> {
+        let is_live = |idx: &usize| self.levels[*idx].as_ref().is_some_and(|level| !level.is_empty());
+        let lo = core::iter::successors(self.occupied.first_from(0), |&idx| self.occupied.first_from(idx + 1))
+            .find(is_live)?;
+        let hi = core::iter::successors(self.occupied.last_to(MAX_LEVELS - 1), |&idx| {
+            idx.checked_sub(1).and_then(|below| self.occupied.last_to(below))
+        }).find(is_live)?;
+        Some((lo, hi))
+    }
+    
+    /// Move the window `shift` levels up (down if negative).
+    ///
+    /// Live levels leaving the window spill into the overflow and overflow
+    /// levels now inside it move in; empty levels are dropped. Levels move
+    /// in place, visiting only occupied slots.
+    fn shift_window(&mut self, shift: i128) {
+        let tick = self.tick_size as i128;
+        let window = MAX_LEVELS as i128;
+        let old_base = self.base_price.0 as i128;
+        
+        // Walk against the direction levels move, so every destination
+        // slot has already been vacated
+        let mut next = if shift > 0 { self.occupied.first_from(0) } else { self.occupied.last_to(MAX_LEVELS - 1) };
+        while let Some(idx) = next {
+            next = if shift > 0 {
+                self.occupied.first_from(idx + 1)
+            } else {
+                idx.checked_sub(1).and_then(|below| self.occupied.last_to(below))
+            };
+            self.occupied.clear(idx);
+            let Some(level) = self.levels[idx].take().filter(|level| !level.is_empty()) else {
+                continue;
+            };
+            let dest = idx as i128 - shift;
+            if (0..window).contains(&dest) {
+                self.levels[dest as usize] = Some(level);
+                self.occupied.set(dest as usize);
+            } else {
+                self.overflow.insert((old_base + idx as i128 * tick) as u64, level);
+            }
+        }
+        
+        self.best_idx = self.best_idx
+            .map(|idx| idx as i128 - shift)
+            .filter(|idx| (0..window).contains(idx))
+            .map(|idx| idx as u32);
+        self.base_price = Price((old_base + shift * tick) as u64);
+        
+        while let Some(price) = self.overflow.range(self.base_price.0..self.window_end()).next().map(|(&price, _)| price) {
+            let level = self.overflow.remove(&price).filter(|level| !level.is_empty());
+            if let (Some(level), Some(idx)) = (level, self.price_to_idx(Price(price))) {
+                self.levels[idx] = Some(level);
+                self.occupied.set(idx);
+            }
+        }
+        self.touch();
+    }
+    
+    /// Pull the best overflow level into the window and make it the best
+    /// level. Called once the window has run dry.
+    fn promote_overflow(&mut self) {
+        let next = match self.side {
+            Side::Buy => self.overflow.range(..self.base_price.0).rev().find(|(_, level)| !level.is_empty()),
+            Side::Sell => self.overflow.range(self.window_end()..).find(|(_, level)| !level.is_empty()),
+        };
+        let Some(price) = next.map(|(&price, _)| Price(price)) else {
+            self.overflow.clear();
+            return;
+        };
+        
+        let tick = self.tick_size as i128;
+        let base = self.base_price.0 as i128;
+        let target = (price.0 as i128 - base).div_euclid(tick);
+        self.shift_window((target - MAX_LEVELS as i128 / 2).max(-(base / tick)));
+        self.best_idx = self.price_to_idx(price).map(|idx| idx as u32);
     }
     
     /// Add order to appropriate price level.
+    ///
+    /// A price outside the level window re-anchors the window around it,
+    /// or goes to the overflow if it is far behind the best level.
     #[inline]
-    pub fn add_order(&mut self, handle: OrderHandle, order: &Order) -> bool {
+    pub fn add_order(&mut self, handle: OrderHandle, order: &Order, links: &mut QueueLinks) -> bool {
         let idx = match self.price_to_idx(order.price) {
-            Some(i) => i,
-            None => return false,
+            Some(i) => Some(i),
+            None if self.reanchor(order.price) => self.price_to_idx(order.price),
+            None => None,
         };
         
         // Get or create level
-        let level = self.levels[idx].get_or_insert_with(PriceLevel::new);
+        let level = match idx {
+            Some(idx) => {
+                self.occupied.set(idx);
+                self.levels[idx].get_or_insert_with(PriceLevel::new)
+            }
+            None => self.overflow.entry(order.price.0).or_default(),
+        };
         
-        if !level.push_back(handle, order.remaining_qty) {
+        // Icebergs only show their current clip
+        let displayed = order.displayed_qty();
+        if !level.push_back(handle, displayed, links) {
             return false;
         }
         
         self.order_count += 1;
-        self.total_qty = self.total_qty.saturating_add(order.remaining_qty);
+        self.total_qty = self.total_qty.saturating_add(displayed);
+        self.notional += order.price.notional(displayed);
+        self.mark(order.price);
+        self.touch();
         
         // Update best price
-        self.update_best_after_add(idx);
+        if let Some(idx) = idx {
+            self.update_best_after_add(idx);
+        }
         
         true
     }
     
+    /// Unlink a queued order showing `qty` at `price`.
+    ///
+    /// Drops the level once it empties and moves the best price on at
+    /// once if it was the best. Returns `false` if the handle is not
+    /// queued at `price`.
+    pub fn remove_order(&mut self, handle: OrderHandle, price: Price, qty: Quantity, links: &mut QueueLinks) -> bool {
+        let idx = self.price_to_idx(price);
+        let level = match idx {
+            Some(idx) => self.levels[idx].as_mut(),
+            None => self.overflow.get_mut(&price.0),
+        };
+        let Some(level) = level else {
+            return false;
+        };
+        if !level.remove(handle, links) {
+            return false;
+        }
+        level.reduce_qty(qty);
+        let emptied = level.is_empty();
+        
+        self.order_count = self.order_count.saturating_sub(1);
+        self.reduce_qty(price, qty);
+        if emptied {
+            match idx {
+                Some(idx) if self.best_idx == Some(idx as u32) => self.find_next_best(),
+                Some(idx) => {
+                    self.levels[idx] = None;
+                    self.occupied.clear(idx);
+                }
+                None => {
+                    self.overflow.remove(&price.0);
+                }
+            }
+        }
+        true
+    }
+    
     /// Update best price after adding at index.
     #[inline]
     fn update_best_after_add(&mut self, new_idx: usize) {
@@ -127,6 +462,7 @@ impl BookSide {
     /// Get the best price level for matching (mutable).
     #[inline(always)]
     pub fn best_level_mut(&mut self) -> Option<&mut PriceLevel> {
+        self.touch();
         self.best_idx
             .and_then(|idx| self.levels[idx as usize].as_mut())
     }
@@ -155,6 +491,7 @@ impl BookSide {
     
     /// Find next best price after current is exhausted.
     pub fn find_next_best(&mut self) {
+        self.touch();
         let current = match self.best_idx {
             Some(idx) => idx as usize,
             None => return,
@@ -167,41 +504,67 @@ impl BookSide {
         {
             // Clear the empty level
             self.levels[current] = None;
+            self.occupied.clear(current);
         } else {
             // Level still has orders, keep it as best
             return;
         }
         
-        // Search for next best
+        // Search for next best, dropping levels emptied on the way
         self.best_idx = None;
+        let mut next = self.next_occupied(current);
+        while let Some(idx) = next {
+            if self.levels[idx].as_ref().is_some_and(|l| !l.is_empty()) {
+                self.best_idx = Some(idx as u32);
+                break;
+            }
+            self.levels[idx] = None;
+            self.occupied.clear(idx);
+            next = self.next_occupied(idx);
+        }
         
+        if self.best_idx.is_none() && !self.overflow.is_empty() {
+            self.promote_overflow();
+        }
+    }
+    
+    /// Nearest populated slot behind `idx` in priority order.
+    #[inline(always)]
+    fn next_occupied(&self, idx: usize) -> Option<usize> {
         match self.side {
             // Bids: search downward (lower indices = lower prices)
-            Side::Buy => {
-                for idx in (0..current).rev() {
-                    if self.levels[idx].as_ref().map_or(false, |l| !l.is_empty()) {
-                        self.best_idx = Some(idx as u32);
-                        break;
-                    }
-                }
-            }
+            Side::Buy => self.occupied.last_to(idx.checked_sub(1)?),
             // Asks: search upward (higher indices = higher prices)
-            Side::Sell => {
-                for idx in (current + 1)..MAX_LEVELS {
-                    if self.levels[idx].as_ref().map_or(false, |l| !l.is_empty()) {
-                        self.best_idx = Some(idx as u32);
-                        break;
-                    }
-                }
-            }
+            Side::Sell => self.occupied.first_from(idx + 1),
         }
     }
     
+    /// Non-empty levels in priority order: bids descending, asks
+    /// ascending. Does not allocate.
+    ///
+    /// Hops between populated slots through the occupancy bitmap, then
+    /// continues into the overflow.
+    pub fn iter_levels(&self) -> impl Iterator<Item = (Price, &PriceLevel)> + '_ {
+        let dense = core::iter::successors(self.best_idx.map(|idx| idx as usize), move |&idx| self.next_occupied(idx));
+        
+        // Overflow levels sit past the far end of the window
+        let below = self.overflow.range(..self.base_price.0).rev().filter(move |_| self.side == Side::Buy);
+        let above = self.overflow.range(self.window_end()..).filter(move |_| self.side == Side::Sell);
+        
+        dense
+            .filter_map(move |idx| self.levels[idx].as_ref().map(|level| (self.idx_to_price(idx), level)))
+            .chain(below.chain(above).map(|(&price, level)| (Price(price), level)))
+            .filter(|(_, level)| !level.is_empty())
+    }
+    
     /// Get level at specific price (mutable).
     #[inline]
     pub fn level_at_price_mut(&mut self, price: Price) -> Option<&mut PriceLevel> {
-        let idx = self.price_to_idx(price)?;
-        self.levels[idx].as_mut()
+        self.touch();
+        match self.price_to_idx(price) {
+            Some(idx) => self.levels[idx].as_mut(),
+            None => self.overflow.get_mut(&price.0),
+        }
     }
     
     /// Check if side is empty.
@@ -210,6 +573,30 @@ impl BookSide {
         self.best_idx.is_none()
     }
     
+    /// Drop every level and order reference.
+    ///
+    /// The orders themselves are not released; that is up to the caller.
+    pub fn clear(&mut self) {
+        if self.tracking {
+            let live: alloc::vec::Vec<Price> = self.iter_levels().map(|(price, _)| price).collect();
+            self.changed.extend(live);
+        }
+        self.levels.iter_mut().for_each(|level| *level = None);
+        self.occupied.clear_all();
+        self.overflow.clear();
+        self.best_idx = None;
+        self.order_count = 0;
+        self.total_qty = Quantity::ZERO;
+        self.notional = 0;
+        self.touch();
+    }
+    
+    /// Price increment between levels (raw units).
+    #[inline(always)]
+    pub fn tick_size(&self) -> u64 {
+        self.tick_size
+    }
+    
     /// Get order count.
     #[inline(always)]
     pub fn order_count(&self) -> u64 {
@@ -222,65 +609,168 @@ impl BookSide {
         self.total_qty
     }
     
-    /// Reduce total quantity (after fill).
+    /// Total notional (price × displayed quantity), kept up to date on
+    /// every change.
     #[inline(always)]
-    pub fn reduce_qty(&mut self, qty: Quantity) {
+    pub fn notional(&self) -> u128 {
+        self.notional
+    }
+    
+    /// Reduce total quantity by `qty` taken from level `price` (after fill).
+    #[inline(always)]
+    pub fn reduce_qty(&mut self, price: Price, qty: Quantity) {
         self.total_qty = self.total_qty.saturating_sub(qty);
+        self.notional = self.notional.saturating_sub(price.notional(qty));
+        self.mark(price);
+        self.touch();
+    }
+    
+    /// Increase total quantity by `qty` at level `price` (iceberg
+    /// replenishment).
+    #[inline(always)]
+    pub fn add_qty(&mut self, price: Price, qty: Quantity) {
+        self.total_qty = self.total_qty.saturating_add(qty);
+        self.notional += price.notional(qty);
+        self.mark(price);
+        self.touch();
     }
     
     /// Decrement order count.
     #[inline(always)]
     pub fn decrement_order_count(&mut self) {
         self.order_count = self.order_count.saturating_sub(1);
+        self.touch();
     }
     
-    /// Get top N price levels for L2 depth metrics.
-    /// Returns (Price, Quantity) pairs for the best N levels.
-    /// For bids: highest prices first. For asks: lowest prices first.
-    pub fn top_n_levels<const N: usize>(&self) -> arrayvec::ArrayVec<(Price, Quantity), N> {
-        let mut result = arrayvec::ArrayVec::new();
-        
-        let Some(start_idx) = self.best_idx else {
-            return result;
-        };
-        
-        match self.side {
-            Side::Buy => {
-                // Bids: search downward from best (highest) price
-                let mut idx = start_idx as usize;
-                while result.len() < N && idx > 0 {
-                    if let Some(level) = &self.levels[idx] {
-                        if !level.is_empty() {
-                            result.push((self.idx_to_price(idx), level.total_qty));
-                        }
-                    }
-                    idx = idx.saturating_sub(1);
-                }
-                // Check index 0
-                if result.len() < N {
-                    if let Some(level) = &self.levels[0] {
-                        if !level.is_empty() {
-                            result.push((self.idx_to_price(0), level.total_qty));
-                        }
-                    }
+    /// Every resting order in price-time priority (best level first,
+    /// oldest first within a level), looked up in `pool`.
+    pub fn orders<'a>(&'a self, pool: &'a OrderPool) -> impl Iterator<Item = L3Order> + 'a {
+        self.iter_levels().flat_map(move |(price, level)| {
+            level.iter(pool.links()).map(move |handle| {
+                let order = &pool[handle];
+                L3Order {
+                    price,
+                    order_id: order.order_id,
+                    remaining_qty: order.remaining_qty,
+                    timestamp: order.timestamp,
                 }
+            })
+        })
+    }
+    
+    /// Panic if the side's counters or best level disagree with its
+    /// levels, or a queued order is out of place.
+    ///
+    /// Walks every level; for tests and fuzzing only.
+    #[cfg(any(test, feature = "invariants"))]
+    pub fn verify_invariants(&self, pool: &OrderPool) {
+        let mut best = None;
+        let mut orders = 0u64;
+        let mut total = Quantity::ZERO;
+        let mut notional = 0u128;
+        
+        let dense = self.levels.iter().enumerate().map(|(idx, level)| (Some(idx as u32), self.idx_to_price(idx), level.as_ref()));
+        let overflow = self.overflow.iter().map(|(&price, level)| (None, Price(price), Some(level)));
+        for (idx, level) in self.levels.iter().enumerate() {
+            assert_eq!(self.occupied.contains(idx), level.is_some(), "{:?} occupancy bit {} out of sync", self.side, idx);
+        }
+        for (idx, price, level) in dense.chain(overflow) {
+            let Some(level) = level.filter(|level| !level.is_empty()) else { continue };
+            
+            let mut queued = Quantity::ZERO;
+            for handle in level.iter(pool.links()) {
+                let order = &pool[handle];
+                assert_eq!(order.side, self.side, "{:?} order queued on the wrong side", order.order_id);
+                assert_eq!(order.price, price, "{:?} queued at the wrong level", order.order_id);
+                queued = queued.saturating_add(order.displayed_qty());
             }
-            Side::Sell => {
-                // Asks: search upward from best (lowest) price
-                for idx in (start_idx as usize)..MAX_LEVELS {
-                    if result.len() >= N {
-                        break;
-                    }
-                    if let Some(level) = &self.levels[idx] {
-                        if !level.is_empty() {
-                            result.push((self.idx_to_price(idx), level.total_qty));
-                        }
-                    }
+            assert_eq!(level.total_qty, queued, "{:?} level {:?} total_qty diverged from its orders", self.side, price);
+            
+            orders += level.len() as u64;
+            total = total.saturating_add(level.total_qty);
+            notional += price.notional(level.total_qty);
+            match idx {
+                // Bids want the highest index, asks the lowest
+                Some(idx) if best.is_none() || self.side == Side::Buy => best = Some(idx),
+                Some(_) => {}
+                None => {
+                    assert!(self.price_to_idx(price).is_none(), "{:?} overflow level {:?} inside the window", self.side, price);
+                    let best = self.best_price().expect("overflow level without a best level");
+                    assert!(self.is_better(best, price), "{:?} overflow level {:?} ahead of the best", self.side, price);
                 }
             }
         }
         
-        result
+        assert_eq!(self.order_count, orders, "{:?} order_count diverged from levels", self.side);
+        assert_eq!(self.total_qty, total, "{:?} total_qty diverged from levels", self.side);
+        assert_eq!(self.notional, notional, "{:?} notional diverged from levels", self.side);
+        assert_eq!(self.best_idx, best, "{:?} best_idx is not the best level", self.side);
+    }
+    
+    /// Best `N` levels with their quantity and order count.
+    pub fn depth<const N: usize>(&self) -> ArrayVec<DepthLevel, N> {
+        self.iter_levels()
+            .take(N)
+            .map(|(price, level)| DepthLevel { price, qty: level.total_qty, orders: level.len() as u32 })
+            .collect()
+    }
+    
+    /// Resting quantity on the best `n` levels.
+    pub fn depth_qty(&self, n: usize) -> Quantity {
+        self.iter_levels()
+            .take(n)
+            .fold(Quantity::ZERO, |total, (_, level)| total.saturating_add(level.total_qty))
+    }
+    
+    /// Liquidity on the levels within `ticks` ticks of the best price,
+    /// the best level included.
+    pub fn qty_within(&self, ticks: u64) -> Liquidity {
+        match self.best_price() {
+            Some(best) => self.liquidity_to(self.behind(best, ticks)),
+            None => Liquidity::default(),
+        }
+    }
+    
+    /// Liquidity on the levels at `limit` or better. Stops at the first
+    /// level past it.
+    fn liquidity_to(&self, limit: Price) -> Liquidity {
+        self.iter_levels()
+            .take_while(|&(price, _)| !self.is_better(limit, price))
+            .fold(Liquidity::default(), |total, (_, level)| Liquidity {
+                qty: total.qty.saturating_add(level.total_qty),
+                orders: total.orders + level.len() as u32,
+            })
+    }
+    
+    /// Price `ticks` ticks behind `price` (lower for bids, higher for asks).
+    fn behind(&self, price: Price, ticks: u64) -> Price {
+        let offset = ticks.saturating_mul(self.tick_size);
+        match self.side {
+            Side::Buy => Price(price.0.saturating_sub(offset)),
+            Side::Sell => Price(price.0.saturating_add(offset)),
+        }
+    }
+    
+    /// Volume-weighted average price of the best `n` levels.
+    ///
+    /// Walks out from the best level and stops after `n` non-empty levels.
+    pub fn vwap(&self, n: usize) -> Option<Price> {
+        let (notional, qty) = self.iter_levels()
+            .take(n)
+            .fold((0u128, 0u128), |(notional, qty), (price, level)| {
+                (notional + price.notional(level.total_qty), qty + level.total_qty.0 as u128)
+            });
+        (qty != 0).then(|| Price((notional / qty) as u64))
+    }
+    
+    /// Get top N price levels for L2 depth metrics.
+    /// Returns (Price, Quantity) pairs for the best N levels.
+    /// For bids: highest prices first. For asks: lowest prices first.
+    pub fn top_n_levels<const N: usize>(&self) -> arrayvec::ArrayVec<(Price, Quantity), N> {
+        self.iter_levels()
+            .take(N)
+            .map(|(price, level)| (price, level.total_qty))
+            .collect()
     }
 }
 
@@ -292,6 +782,8 @@ pub struct OrderBook {
     pub asks: BookSide,
     /// Sequence number for determinism.
     sequence: u64,
+    /// Last checksum with the side revisions it was computed at.
+    checksum: Cell<Option<(u64, u64, u32)>>,
 }
 
 impl OrderBook {
@@ -300,10 +792,16 @@ impl OrderBook {
     /// `base_price` is the minimum price for indexing.
     /// Typically set to 0 or a reasonable floor price.
     pub fn new(base_price: Price) -> Self {
+        Self::with_tick_size(base_price, Price::TICK_SIZE)
+    }
+    
+    /// Create an order book on a `tick_size` price grid.
+    pub fn with_tick_size(base_price: Price, tick_size: u64) -> Self {
         Self {
-            bids: BookSide::new(Side::Buy, base_price),
-            asks: BookSide::new(Side::Sell, base_price),
+            bids: BookSide::with_tick_size(Side::Buy, base_price, tick_size),
+            asks: BookSide::with_tick_size(Side::Sell, base_price, tick_size),
             sequence: 0,
+            checksum: Cell::new(None),
         }
     }
     
@@ -313,6 +811,12 @@ impl OrderBook {
         self.sequence
     }
     
+    /// Resume numbering after `sequence` (snapshot restore).
+    #[inline(always)]
+    pub(crate) fn set_sequence(&mut self, sequence: u64) {
+        self.sequence = sequence;
+    }
+    
     /// Increment and return sequence number.
     #[inline(always)]
     pub fn next_sequence(&mut self) -> u64 {
@@ -320,6 +824,16 @@ impl OrderBook {
         self.sequence
     }
     
+    /// Best `N` bid and ask levels (price, quantity, order count).
+    ///
+    /// Does not allocate; `N` fixes the snapshot size at compile time.
+    pub fn depth<const N: usize>(&self) -> DepthSnapshot<N> {
+        DepthSnapshot {
+            bids: self.bids.depth(),
+            asks: self.asks.depth(),
+        }
+    }
+    
     /// Get best bid price.
     #[inline(always)]
     pub fn best_bid(&self) -> Option<Price> {
@@ -350,12 +864,97 @@ impl OrderBook {
         }
     }
     
+    /// Order book imbalance over the best `n` levels of each side, in
+    /// basis points: +10000 is all bids, -10000 all asks.
+    ///
+    /// `None` if both sides are empty.
+    pub fn imbalance_bps(&self, n: usize) -> Option<i32> {
+        let bid = self.bids.depth_qty(n).0 as i128;
+        let ask = self.asks.depth_qty(n).0 as i128;
+        let total = bid + ask;
+        (total != 0).then(|| ((bid - ask) * 10_000 / total) as i32)
+    }
+    
+    /// Bid and ask liquidity within `n` ticks of the midpoint.
+    ///
+    /// Both are empty if the book is.
+    pub fn liquidity_around_mid(&self, n: u64) -> (Liquidity, Liquidity) {
+        match self.midpoint() {
+            Some(mid) => (
+                self.bids.liquidity_to(self.bids.behind(mid, n)),
+                self.asks.liquidity_to(self.asks.behind(mid, n)),
+            ),
+            None => (Liquidity::default(), Liquidity::default()),
+        }
+    }
+    
+    /// Touch prices weighted by their own queue sizes: the average price
+    /// of the displayed top-of-book quantity. Unlike `microprice` it
+    /// leans towards the deeper side.
+    ///
+    /// Reads only the two best levels. `None` unless both sides have one.
+    pub fn weighted_mid(&self) -> Option<Price> {
+        let (bid_px, ask_px) = (self.best_bid()?, self.best_ask()?);
+        let bid_qty = self.bids.best_level()?.total_qty;
+        let ask_qty = self.asks.best_level()?.total_qty;
+        let qty = bid_qty.0 as u128 + ask_qty.0 as u128;
+        if qty == 0 {
+            return self.midpoint();
+        }
+        Some(Price(((bid_px.notional(bid_qty) + ask_px.notional(ask_qty)) / qty) as u64))
+    }
+    
+    /// Microprice: the touch prices weighted by the opposite side's
+    /// queue, which leans towards the side about to be depleted.
+    ///
+    /// `None` unless both sides have a best level.
+    pub fn microprice(&self) -> Option<Price> {
+        let (bid_px, bid) = self.bids.iter_levels().next()?;
+        let (ask_px, ask) = self.asks.iter_levels().next()?;
+        let (bid_qty, ask_qty) = (bid.total_qty.0 as u128, ask.total_qty.0 as u128);
+        if bid_qty + ask_qty == 0 {
+            return self.midpoint();
+        }
+        let weighted = bid_px.0 as u128 * ask_qty + ask_px.0 as u128 * bid_qty;
+        Some(Price((weighted / (bid_qty + ask_qty)) as u64))
+    }
+    
+    /// CRC-32 of the best `CHECKSUM_DEPTH` levels of each side (see
+    /// `checksum` for the exact layout).
+    ///
+    /// Cached until either side changes, so polling it between updates is
+    /// free; a recompute walks only the top levels.
+    pub fn checksum(&self) -> u32 {
+        let revisions = (self.bids.revision(), self.asks.revision());
+        if let Some((bids, asks, crc)) = self.checksum.get() {
+            if (bids, asks) == revisions {
+                return crc;
+            }
+        }
+        
+        let top = |side: &BookSide| {
+            side.iter_levels()
+                .take(CHECKSUM_DEPTH)
+                .map(|(price, level)| (price, level.total_qty))
+                .collect::<ArrayVec<_, CHECKSUM_DEPTH>>()
+        };
+        let crc = checksum::levels_checksum(top(&self.asks), top(&self.bids));
+        self.checksum.set(Some((revisions.0, revisions.1, crc)));
+        crc
+    }
+    
     /// Check if book is empty.
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
         self.bids.is_empty() && self.asks.is_empty()
     }
     
+    /// Place both level windows per `policy`.
+    pub fn set_memory_policy(&mut self, policy: MemoryPolicy) -> bool {
+        let bids = self.bids.set_memory_policy(policy);
+        self.asks.set_memory_policy(policy) && bids
+    }
+    
     /// Get mutable reference to appropriate side.
     #[inline(always)]
     pub fn side_mut(&mut self, side: Side) -> &mut BookSide {
@@ -438,19 +1037,7 @@ impl OrderBook {
     
     /// Collect all non-empty price levels from a book side.
     fn collect_active_levels(&self, side: &BookSide) -> alloc::vec::Vec<(Price, Quantity)> {
-        let mut levels = alloc::vec::Vec::new();
-        
-        for level_opt in side.levels.iter() {
-            if let Some(level) = level_opt {
-                if !level.is_empty() {
-                    // We need to get the price from the level index
-                    // For now, store total_qty (price would need index tracking)
-                    levels.push((Price(0), level.total_qty)); // Placeholder
-                }
-            }
-        }
-        
-        levels
+        side.iter_levels().map(|(price, level)| (price, level.total_qty)).collect()
     }
     
     /// Estimate buffer size needed for snapshot.
@@ -470,6 +1057,7 @@ mod tests {
     
     #[test]
     fn test_book_side_add_order() {
+        let mut links = QueueLinks::new(128);
         let mut side = BookSide::new(Side::Buy, Price::ZERO);
         
         let order = Order::new(
@@ -483,7 +1071,7 @@ mod tests {
         );
         
         let handle = OrderHandle(0);
-        assert!(side.add_order(handle, &order));
+        assert!(side.add_order(handle, &order, &mut links));
         
         assert_eq!(side.order_count(), 1);
         assert_eq!(side.best_price(), Some(Price::from_ticks(100)));
@@ -491,6 +1079,7 @@ mod tests {
     
     #[test]
     fn test_book_side_best_update() {
+        let mut links = QueueLinks::new(128);
         let mut side = BookSide::new(Side::Buy, Price::ZERO);
         
         // Add order at price 100
@@ -498,7 +1087,7 @@ mod tests {
             OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit,
             Price::from_ticks(100), Quantity(100), 0,
         );
-        side.add_order(OrderHandle(0), &order1);
+        side.add_order(OrderHandle(0), &order1, &mut links);
         assert_eq!(side.best_price(), Some(Price::from_ticks(100)));
         
         // Add better order at price 110 (higher is better for bids)
@@ -506,7 +1095,7 @@ mod tests {
             OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit,
             Price::from_ticks(110), Quantity(100), 0,
         );
-        side.add_order(OrderHandle(1), &order2);
+        side.add_order(OrderHandle(1), &order2, &mut links);
         assert_eq!(side.best_price(), Some(Price::from_ticks(110)));
         
         // Add worse order at price 90
@@ -514,13 +1103,14 @@ mod tests {
             OrderId(3), SymbolId(1), Side::Buy, OrderType::Limit,
             Price::from_ticks(90), Quantity(100), 0,
         );
-        side.add_order(OrderHandle(2), &order3);
+        side.add_order(OrderHandle(2), &order3, &mut links);
         // Best should still be 110
         assert_eq!(side.best_price(), Some(Price::from_ticks(110)));
     }
     
     #[test]
     fn test_book_spread() {
+        let mut links = QueueLinks::new(128);
         let mut book = OrderBook::new(Price::ZERO);
         
         // Add bid at 100
@@ -528,17 +1118,313 @@ mod tests {
             OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit,
             Price::from_ticks(100), Quantity(100), 0,
         );
-        book.bids.add_order(OrderHandle(0), &bid);
+        book.bids.add_order(OrderHandle(0), &bid, &mut links);
         
         // Add ask at 101
         let ask = Order::new(
             OrderId(2), SymbolId(1), Side::Sell, OrderType::Limit,
             Price::from_ticks(101), Quantity(100), 0,
         );
-        book.asks.add_order(OrderHandle(1), &ask);
+        book.asks.add_order(OrderHandle(1), &ask, &mut links);
         
         assert_eq!(book.best_bid(), Some(Price::from_ticks(100)));
         assert_eq!(book.best_ask(), Some(Price::from_ticks(101)));
         assert_eq!(book.spread(), Some(Price::from_ticks(1)));
     }
+    
+    #[test]
+    fn test_depth_snapshot() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        book.bids.add_order(OrderHandle(0), &order(Side::Buy, 99, 10), &mut links);
+        book.bids.add_order(OrderHandle(1), &order(Side::Buy, 100, 5), &mut links);
+        book.bids.add_order(OrderHandle(2), &order(Side::Buy, 100, 7), &mut links);
+        book.bids.add_order(OrderHandle(3), &order(Side::Buy, 98, 1), &mut links);
+        book.asks.add_order(OrderHandle(4), &order(Side::Sell, 101, 3), &mut links);
+        
+        let depth = book.depth::<2>();
+        assert_eq!(depth.bids.as_slice(), &[
+            DepthLevel { price: Price::from_ticks(100), qty: Quantity(12), orders: 2 },
+            DepthLevel { price: Price::from_ticks(99), qty: Quantity(10), orders: 1 },
+        ]);
+        assert_eq!(depth.asks.as_slice(), &[DepthLevel { price: Price::from_ticks(101), qty: Quantity(3), orders: 1 }]);
+    }
+    
+    #[test]
+    fn test_book_analytics() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        assert_eq!(book.imbalance_bps(5), None);
+        assert_eq!(book.microprice(), None);
+        
+        book.bids.add_order(OrderHandle(0), &order(Side::Buy, 100, 30), &mut links);
+        book.bids.add_order(OrderHandle(1), &order(Side::Buy, 97, 10), &mut links);
+        book.bids.add_order(OrderHandle(2), &order(Side::Buy, 90, 1_000), &mut links);
+        book.asks.add_order(OrderHandle(3), &order(Side::Sell, 102, 10), &mut links);
+        
+        assert_eq!(book.bids.vwap(2), Some(Price::from_raw(Price::from_ticks(100).0 * 3 / 4 + Price::from_ticks(97).0 / 4)));
+        assert_eq!(book.asks.vwap(5), Some(Price::from_ticks(102)));
+        assert_eq!(book.imbalance_bps(2), Some(6_000));
+        assert_eq!(book.imbalance_bps(1), Some(5_000));
+        // Heavy bid queue pulls the microprice towards the ask
+        assert_eq!(book.microprice(), Some(Price::from_raw((Price::from_ticks(100).0 + Price::from_ticks(102).0 * 3) / 4)));
+    }
+    
+    #[test]
+    fn test_book_checksum() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        assert_eq!(book.checksum(), 0);
+        book.bids.add_order(OrderHandle(0), &order(Side::Buy, 99, 10), &mut links);
+        book.asks.add_order(OrderHandle(1), &order(Side::Sell, 101, 5), &mut links);
+        
+        let expected = checksum::levels_checksum(
+            [(Price::from_ticks(101), Quantity(5))],
+            [(Price::from_ticks(99), Quantity(10))],
+        );
+        assert_eq!(book.checksum(), expected);
+        
+        // A fill on the best bid changes the checksum
+        book.bids.best_level_mut().unwrap().reduce_qty(Quantity(4));
+        book.bids.reduce_qty(Price::from_ticks(99), Quantity(4));
+        assert_ne!(book.checksum(), expected);
+        assert_eq!(book.checksum(), checksum::levels_checksum(
+            [(Price::from_ticks(101), Quantity(5))],
+            [(Price::from_ticks(99), Quantity(6))],
+        ));
+    }
+    
+    #[test]
+    fn test_reanchor_follows_price() {
+        let mut links = QueueLinks::new(128);
+        let mut side = BookSide::new(Side::Sell, Price::ZERO);
+        let order = |id, ticks| Order::new(
+            OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0,
+        );
+        assert!(side.add_order(OrderHandle(0), &order(1, 100), &mut links));
+        assert!(side.add_order(OrderHandle(1), &order(2, 150), &mut links));
+        
+        // Far above the initial window: the live levels move with it
+        let far = MAX_LEVELS as u64 + 50;
+        assert!(side.add_order(OrderHandle(2), &order(3, far), &mut links));
+        assert!(side.base_price() > Price::ZERO);
+        assert_eq!(side.best_price(), Some(Price::from_ticks(100)));
+        assert_eq!(side.order_count(), 3);
+        let prices: alloc::vec::Vec<_> = side.iter_levels().map(|(price, _)| price).collect();
+        assert_eq!(prices, [Price::from_ticks(100), Price::from_ticks(150), Price::from_ticks(far)]);
+        
+        // Below the window: everything slides back down
+        let base = side.base_price().to_ticks();
+        assert!(side.add_order(OrderHandle(3), &order(4, base - 10), &mut links));
+        assert!(side.base_price().to_ticks() < base - 10);
+        assert_eq!(side.best_price(), Some(Price::from_ticks(base - 10)));
+        let prices: alloc::vec::Vec<_> = side.iter_levels().map(|(price, _)| price.to_ticks()).collect();
+        assert_eq!(prices, [base - 10, 100, 150, far]);
+    }
+    
+    #[test]
+    fn test_sparse_overflow_levels() {
+        let mut pool = OrderPool::new(4);
+        let mut side = BookSide::new(Side::Sell, Price::ZERO);
+        let add = |pool: &mut OrderPool, side: &mut BookSide, id, ticks| {
+            let handle = pool.allocate().unwrap();
+            pool.insert(handle, Order::new(
+                OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0,
+            ));
+            let order = pool[handle];
+            assert!(side.add_order(handle, &order, pool.links_mut()));
+            handle
+        };
+        let near = add(&mut pool, &mut side, 1, 100);
+        add(&mut pool, &mut side, 2, 5_000_000);
+        add(&mut pool, &mut side, 3, 3_000_000);
+        
+        // Far levels sit in the overflow but still show up in order
+        let prices: alloc::vec::Vec<_> = side.iter_levels().map(|(price, _)| price.to_ticks()).collect();
+        assert_eq!(prices, [100, 3_000_000, 5_000_000]);
+        assert_eq!(side.total_qty(), Quantity(30));
+        
+        // Clearing the window brings the next overflow level in as best
+        let level = side.level_at_price_mut(Price::from_ticks(100)).unwrap();
+        level.remove(near, pool.links_mut());
+        level.reduce_qty(Quantity(10));
+        side.reduce_qty(Price::from_ticks(100), Quantity(10));
+        side.decrement_order_count();
+        side.find_next_best();
+        assert_eq!(side.best_price(), Some(Price::from_ticks(3_000_000)));
+        assert!(side.base_price() > Price::ZERO);
+        side.verify_invariants(&pool);
+        
+        // A new best far ahead of the window moves it back
+        add(&mut pool, &mut side, 4, 50);
+        assert_eq!(side.best_price(), Some(Price::from_ticks(50)));
+        side.verify_invariants(&pool);
+    }
+    
+    #[test]
+    fn test_find_next_best_skips_cleared_region() {
+        let mut links = QueueLinks::new(128);
+        let mut side = BookSide::new(Side::Buy, Price::ZERO);
+        let order = |ticks| Order::new(
+            OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0,
+        );
+        side.add_order(OrderHandle(0), &order(7), &mut links);
+        for (handle, ticks) in (1..).zip(40_000..40_100) {
+            side.add_order(OrderHandle(handle), &order(ticks), &mut links);
+        }
+        
+        // Empty every level above 7 except the best, then walk down
+        for ticks in 40_000..40_100 {
+            let level = side.level_at_price_mut(Price::from_ticks(ticks)).unwrap();
+            level.reduce_qty(Quantity(10));
+            let handle = level.front().unwrap();
+            level.remove(handle, &mut links);
+        }
+        side.find_next_best();
+        assert_eq!(side.best_price(), Some(Price::from_ticks(7)));
+        assert!(!side.occupied.contains(40_050));
+    }
+    
+    #[test]
+    fn test_liquidity_near_touch() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        assert_eq!(book.bids.qty_within(5), Liquidity::default());
+        
+        book.bids.add_order(OrderHandle(0), &order(Side::Buy, 100, 10), &mut links);
+        book.bids.add_order(OrderHandle(1), &order(Side::Buy, 100, 5), &mut links);
+        book.bids.add_order(OrderHandle(2), &order(Side::Buy, 97, 20), &mut links);
+        book.bids.add_order(OrderHandle(3), &order(Side::Buy, 90, 1_000), &mut links);
+        book.asks.add_order(OrderHandle(4), &order(Side::Sell, 104, 7), &mut links);
+        book.asks.add_order(OrderHandle(5), &order(Side::Sell, 110, 3), &mut links);
+        
+        assert_eq!(book.bids.qty_within(0), Liquidity { qty: Quantity(15), orders: 2 });
+        assert_eq!(book.bids.qty_within(3), Liquidity { qty: Quantity(35), orders: 3 });
+        assert_eq!(book.asks.qty_within(6), Liquidity { qty: Quantity(10), orders: 2 });
+        
+        // Mid is 102: bids down to 97, asks up to 107
+        assert_eq!(book.liquidity_around_mid(5), (
+            Liquidity { qty: Quantity(35), orders: 3 },
+            Liquidity { qty: Quantity(7), orders: 1 },
+        ));
+    }
+    
+    #[test]
+    fn test_iter_levels_in_priority_order() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0,
+        );
+        for (handle, ticks) in (0..).zip([98, 100, 95, 99]) {
+            book.bids.add_order(OrderHandle(handle), &order(Side::Buy, ticks), &mut links);
+            book.asks.add_order(OrderHandle(handle + 10), &order(Side::Sell, ticks + 10), &mut links);
+        }
+        
+        // An emptied level is skipped
+        let level = book.bids.level_at_price_mut(Price::from_ticks(99)).unwrap();
+        level.remove(OrderHandle(3), &mut links);
+        
+        let ticks = |side: &BookSide| side.iter_levels().map(|(price, _)| price.to_ticks()).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(ticks(&book.bids), [100, 98, 95]);
+        assert_eq!(ticks(&book.asks), [105, 108, 109, 110]);
+    }
+    
+    #[test]
+    fn test_notional_and_weighted_mid() {
+        let mut links = QueueLinks::new(128);
+        let mut book = OrderBook::new(Price::ZERO);
+        let order = |side, ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        assert_eq!(book.weighted_mid(), None);
+        
+        book.bids.add_order(OrderHandle(0), &order(Side::Buy, 100, 30), &mut links);
+        book.bids.add_order(OrderHandle(1), &order(Side::Buy, 98, 10), &mut links);
+        book.asks.add_order(OrderHandle(2), &order(Side::Sell, 104, 10), &mut links);
+        assert_eq!(book.bids.notional(), Price::from_ticks(100).notional(Quantity(30)) + Price::from_ticks(98).notional(Quantity(10)));
+        
+        // A fill comes off the notional at its level's price
+        book.bids.reduce_qty(Price::from_ticks(100), Quantity(20));
+        assert_eq!(book.bids.notional(), Price::from_ticks(100).notional(Quantity(10)) + Price::from_ticks(98).notional(Quantity(10)));
+        book.bids.best_level_mut().unwrap().reduce_qty(Quantity(20));
+        
+        // 10 @ 100 and 10 @ 104
+        assert_eq!(book.weighted_mid(), Some(Price::from_ticks(102)));
+        book.asks.best_level_mut().unwrap().add_qty(Quantity(20));
+        assert_eq!(book.weighted_mid(), Some(Price::from_ticks(103)));
+    }
+    
+    #[test]
+    fn test_remove_order_repairs_best() {
+        let mut pool = OrderPool::new(4);
+        let mut side = BookSide::new(Side::Sell, Price::ZERO);
+        let mut add = |side: &mut BookSide, id, ticks| {
+            let handle = pool.allocate().unwrap();
+            pool.insert(handle, Order::new(
+                OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0,
+            ));
+            let order = pool[handle];
+            side.add_order(handle, &order, pool.links_mut());
+            handle
+        };
+        let best = add(&mut side, 1, 100);
+        let next = add(&mut side, 2, 102);
+        let far = add(&mut side, 3, 5_000_000);
+        
+        // Not queued there
+        assert!(!side.remove_order(best, Price::from_ticks(102), Quantity(10), pool.links_mut()));
+        
+        assert!(side.remove_order(best, Price::from_ticks(100), Quantity(10), pool.links_mut()));
+        assert_eq!(side.best_price(), Some(Price::from_ticks(102)));
+        assert!(side.remove_order(far, Price::from_ticks(5_000_000), Quantity(10), pool.links_mut()));
+        assert_eq!(side.iter_levels().count(), 1);
+        assert!(side.remove_order(next, Price::from_ticks(102), Quantity(10), pool.links_mut()));
+        assert!(side.is_empty());
+        assert_eq!((side.order_count(), side.total_qty(), side.notional()), (0, Quantity::ZERO, 0));
+    }
+    
+    #[test]
+    fn test_level_info_and_changes() {
+        let mut links = QueueLinks::new(128);
+        let mut side = BookSide::new(Side::Buy, Price::ZERO);
+        let order = |ticks, qty| Order::new(
+            OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        side.add_order(OrderHandle(0), &order(100, 10), &mut links);
+        side.track_changes(true);
+        side.add_order(OrderHandle(1), &order(100, 5), &mut links);
+        side.add_order(OrderHandle(2), &order(99, 7), &mut links);
+        side.add_order(OrderHandle(3), &order(100, 1), &mut links);
+        assert_eq!(side.level_info(Price::from_ticks(100)), Some(LevelInfo { qty: Quantity(16), order_count: 3 }));
+        assert_eq!(side.level_info(Price::from_ticks(98)), None);
+        
+        let mut changes = alloc::vec::Vec::new();
+        side.flush_changes(&mut changes);
+        assert_eq!(changes, [
+            (Price::from_ticks(99), LevelInfo { qty: Quantity(7), order_count: 1 }),
+            (Price::from_ticks(100), LevelInfo { qty: Quantity(16), order_count: 3 }),
+        ]);
+        
+        // A removed level reports zero; nothing else changed
+        changes.clear();
+        side.remove_order(OrderHandle(2), Price::from_ticks(99), Quantity(7), &mut links);
+        side.flush_changes(&mut changes);
+        assert_eq!(changes, [(Price::from_ticks(99), LevelInfo::default())]);
+        changes.clear();
+        side.flush_changes(&mut changes);
+        assert!(changes.is_empty());
+    }
 }
@@ -0,0 +1,89 @@
+//! Book checksums for feed consumers.
+//!
+//! A subscriber rebuilding the book from incremental updates can compare
+//! its copy against `OrderBook::checksum`. The checksum is CRC-32 (IEEE)
+//! over the best `CHECKSUM_DEPTH` ask levels, best first, followed by the
+//! best `CHECKSUM_DEPTH` bid levels, best first; each level contributes its
+//! raw price and quantity as little-endian `u64`s. `levels_checksum`
+//! computes the same value from any level source.
+
+use crate::fixed::{Price, Quantity};
+
+/// Levels per side covered by the checksum.
+pub const CHECKSUM_DEPTH: usize = 10;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Running CRC-32 (IEEE).
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    /// Start a new checksum.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self(!0)
+    }
+
+    /// Feed `bytes`.
+    #[inline]
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ byte as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    /// Final value.
+    #[inline(always)]
+    pub const fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checksum of a book given its levels, each side best first.
+///
+/// Only the first `CHECKSUM_DEPTH` levels of each side are used.
+pub fn levels_checksum(
+    asks: impl IntoIterator<Item = (Price, Quantity)>,
+    bids: impl IntoIterator<Item = (Price, Quantity)>,
+) -> u32 {
+    let mut crc = Crc32::new();
+    for (price, qty) in asks.into_iter().take(CHECKSUM_DEPTH).chain(bids.into_iter().take(CHECKSUM_DEPTH)) {
+        crc.update(&price.0.to_le_bytes());
+        crc.update(&qty.0.to_le_bytes());
+    }
+    crc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finish(), 0xCBF4_3926);
+        assert_eq!(levels_checksum([], []), 0);
+    }
+}
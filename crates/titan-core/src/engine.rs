@@ -6,9 +6,28 @@
 use core::sync::atomic::{AtomicU64, Ordering};
 use arrayvec::ArrayVec;
 use crate::fixed::{Price, Quantity};
-use crate::order::{Order, OrderId, Side, OrderType, SymbolId};
+use crate::order::{Order, OrderId, ParticipantId, SessionId, Side, OrderType, SymbolId, FLAG_OCO};
 use crate::pool::{OrderPool, OrderHandle};
-use crate::book::OrderBook;
+use crate::memory::MemoryPolicy;
+use crate::book::{BookSide, L3Order, OrderBook};
+use crate::index::OrderIndex;
+use crate::owner::OwnerIndex;
+use crate::wheel::TimingWheel;
+use crate::trigger::TriggerBook;
+use crate::oco::OcoBook;
+use crate::mmp::MmpBook;
+use crate::nocross::{CrossPolicy, NoCrossGroups};
+use crate::quotes::{QuoteBook, QuoteSide};
+use crate::risk::RiskChecker;
+use crate::fees::FeeSchedule;
+use crate::stats::SessionStats;
+use crate::events::{BboChanged, MatchEvents};
+use crate::auction::{self, TradingPhase, Uncross};
+use crate::band::PriceBand;
+use crate::instrument::InstrumentSpec;
+use crate::journal::{InputCommand, InputLog};
+use crate::snapshot::{self, Reader, SnapshotError, Writer, SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+use alloc::vec::Vec;
 
 // === HOT-PATH METRICS (Atomic, lock-free) ===
 // These are read by the metrics thread every 1s. Cost: ~5-10ns per increment.
@@ -25,6 +44,15 @@ pub static ORDERS_REJECTED: AtomicU64 = AtomicU64::new(0);
 /// Maximum fills per order (limits stack usage).
 pub const MAX_FILLS_PER_ORDER: usize = 64;
 
+/// Default number of price levels a market order may sweep.
+pub const DEFAULT_MAX_SWEEP_LEVELS: u32 = 10;
+
+/// Slots in the GTD expiry wheel.
+pub const EXPIRY_WHEEL_SLOTS: usize = 1024;
+
+/// Expiry wheel resolution (1ms per slot, ~1s per rotation).
+pub const EXPIRY_TICK_NS: u64 = 1_000_000;
+
 /// Execution report for a single fill.
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
@@ -43,6 +71,10 @@ pub struct Fill {
     pub symbol: SymbolId,
     /// Timestamp.
     pub timestamp: u64,
+    /// Fee charged to the maker (negative = rebate), raw price units.
+    pub maker_fee: i64,
+    /// Fee charged to the taker (negative = rebate), raw price units.
+    pub taker_fee: i64,
 }
 
 /// Result of order submission.
@@ -90,6 +122,38 @@ pub enum RejectReason {
     SymbolNotFound,
     /// FOK order cannot be fully filled.
     InsufficientLiquidity,
+    /// Market order arrived with an empty opposite side.
+    NoLiquidity,
+    /// Referenced order is not resting on the book.
+    UnknownOrder,
+    /// An order with the same ID is already resting or waiting as a
+    /// trailing stop.
+    DuplicateOrderId,
+    /// GTD order without an expiry in the future.
+    InvalidExpiry,
+    /// Order is already part of an OCO link.
+    AlreadyLinked,
+    /// Order type cannot be entered during the call phase.
+    AuctionInProgress,
+    /// Price is outside the band around the reference price.
+    PriceOutOfBand,
+    /// Participant tripped market maker protection.
+    MmpTripped,
+    /// Order belongs to another participant.
+    NotOwner,
+    /// Participant is blocked from entering orders.
+    ParticipantDisabled,
+    /// Quantity above the pre-trade limit.
+    OrderTooLarge,
+    /// Notional above the pre-trade limit.
+    NotionalTooLarge,
+    /// Participant already holds the maximum number of resting orders.
+    TooManyOpenOrders,
+    /// Price deviates too far from the reference price (fat finger).
+    PriceDeviation,
+    /// Short sale not priced above the last trade while the uptick rule
+    /// is armed.
+    ShortSaleRestricted,
 }
 
 /// The matching engine.
@@ -102,6 +166,46 @@ pub struct MatchingEngine {
     pub pool: OrderPool,
     /// Symbol for this engine.
     pub symbol: SymbolId,
+    /// Tick and lot sizes orders are validated against.
+    spec: InstrumentSpec,
+    /// Resting orders by ID.
+    index: OrderIndex,
+    /// Resting orders by participant.
+    owners: OwnerIndex,
+    /// Resting orders by client session.
+    sessions: OwnerIndex<SessionId>,
+    /// Pending GTD expiries.
+    expiries: TimingWheel,
+    /// Trailing stops waiting to fire.
+    pub stops: TriggerBook,
+    /// OCO links between resting orders.
+    pub oco: OcoBook,
+    /// Market maker protection per participant.
+    pub mmp: MmpBook,
+    /// Groups whose members must not trade with each other.
+    pub no_cross: NoCrossGroups,
+    /// Live mass quote orders per participant.
+    pub quotes: QuoteBook,
+    /// Pre-trade risk limits.
+    pub risk: RiskChecker,
+    /// Last trade and session OHLC/volume.
+    pub stats: SessionStats,
+    /// Price levels a market order may consume before the rest is cancelled.
+    max_sweep_levels: u32,
+    /// Continuous trading or call auction.
+    phase: TradingPhase,
+    /// Collar applied to priced orders (`None` = unchecked).
+    band: Option<PriceBand>,
+    /// Uptick rule for short sales.
+    short_sale_restriction: bool,
+    /// Last trade or externally supplied mark.
+    reference_price: Option<Price>,
+    /// Maker/taker fees applied to every fill.
+    fees: FeeSchedule,
+    /// Commands received while recording.
+    recorder: Option<InputLog>,
+    /// Top of book last reported through `MatchEvents::on_bbo_change`.
+    last_bbo: BboChanged,
 }
 
 impl MatchingEngine {
@@ -110,35 +214,251 @@ impl MatchingEngine {
     /// `pool_bits`: log2 of pool capacity (e.g., 20 = 1M orders)
     /// `base_price`: minimum price for book indexing
     pub fn new(symbol: SymbolId, pool_bits: u32, base_price: Price) -> Self {
+        Self::with_spec(symbol, pool_bits, base_price, InstrumentSpec::default())
+    }
+    
+    /// Create a matching engine for an instrument with its own tick and
+    /// lot sizes. The book is indexed on `spec.tick_size`.
+    pub fn with_spec(symbol: SymbolId, pool_bits: u32, base_price: Price, spec: InstrumentSpec) -> Self {
         Self {
-            book: OrderBook::new(base_price),
+            book: OrderBook::with_tick_size(base_price, spec.tick_size),
             pool: OrderPool::with_capacity(1 << pool_bits),
             symbol,
+            spec,
+            index: OrderIndex::with_capacity(1 << pool_bits),
+            owners: OwnerIndex::with_capacity(1 << pool_bits),
+            sessions: OwnerIndex::with_capacity(1 << pool_bits),
+            expiries: TimingWheel::new(EXPIRY_WHEEL_SLOTS, EXPIRY_TICK_NS, 1 << pool_bits),
+            stops: TriggerBook::new(),
+            oco: OcoBook::new(),
+            mmp: MmpBook::new(),
+            no_cross: NoCrossGroups::new(),
+            quotes: QuoteBook::new(),
+            risk: RiskChecker::default(),
+            stats: SessionStats::new(),
+            max_sweep_levels: DEFAULT_MAX_SWEEP_LEVELS,
+            phase: TradingPhase::Continuous,
+            band: None,
+            short_sale_restriction: false,
+            reference_price: None,
+            fees: FeeSchedule::ZERO,
+            recorder: None,
+            last_bbo: BboChanged::default(),
         }
     }
     
+    /// Tick and lot sizes in force.
+    pub fn spec(&self) -> &InstrumentSpec {
+        &self.spec
+    }
+    
+    /// Limit how many price levels a market order may sweep (minimum 1).
+    pub fn set_max_sweep_levels(&mut self, levels: u32) {
+        self.max_sweep_levels = levels.max(1);
+    }
+    
+    /// Let the order pool grow in chunks to 2^max_bits orders when it
+    /// fills up, rather than rejecting with `PoolExhausted`.
+    pub fn set_pool_limit(&mut self, max_bits: u32) {
+        self.pool.set_limit(max_bits);
+    }
+    
+    /// Back the order pool and book levels with huge pages and/or place
+    /// them on a NUMA node (see `memory`). Call before trading starts;
+    /// returns `false` if the hints could not all be applied.
+    pub fn set_memory_policy(&mut self, policy: MemoryPolicy) -> bool {
+        let pool = self.pool.set_memory_policy(policy);
+        self.book.set_memory_policy(policy) && pool
+    }
+    
+    /// Price levels a market order may sweep.
+    pub fn max_sweep_levels(&self) -> u32 {
+        self.max_sweep_levels
+    }
+    
+    /// Reject priced orders outside `band` around the reference price.
+    ///
+    /// Orders are accepted while there is no reference yet.
+    pub fn set_price_band(&mut self, band: Option<PriceBand>) {
+        self.band = band;
+    }
+    
+    /// Price band in force.
+    pub fn price_band(&self) -> Option<PriceBand> {
+        self.band
+    }
+    
+    /// Arm or disarm the uptick rule.
+    ///
+    /// While armed, short sales must be priced strictly above the last
+    /// trade; market short sales are rejected. Short sales are accepted
+    /// while the session has no trade yet.
+    pub fn set_short_sale_restriction(&mut self, armed: bool) {
+        self.short_sale_restriction = armed;
+    }
+    
+    /// Check if the uptick rule is armed.
+    pub fn short_sale_restriction(&self) -> bool {
+        self.short_sale_restriction
+    }
+    
+    /// Override the reference price with an external mark.
+    ///
+    /// The next trade replaces it.
+    pub fn set_reference_price(&mut self, price: Price) {
+        self.reference_price = Some(price);
+    }
+    
+    /// Price the band is centred on.
+    pub fn reference_price(&self) -> Option<Price> {
+        self.reference_price
+    }
+    
+    /// Charge `fees` on every subsequent fill.
+    pub fn set_fee_schedule(&mut self, fees: FeeSchedule) {
+        self.fees = fees;
+    }
+    
+    /// Fee schedule in force.
+    pub fn fee_schedule(&self) -> &FeeSchedule {
+        &self.fees
+    }
+    
     /// Submit an order to the matching engine.
     ///
     /// This is THE hot path - every nanosecond matters.
     #[inline]
-    pub fn submit_order(&mut self, mut order: Order, timestamp: u64) -> OrderResult {
+    pub fn submit_order(&mut self, order: Order, timestamp: u64) -> OrderResult {
+        self.submit_order_as(ParticipantId::NONE, order, timestamp)
+    }
+    
+    /// Submit an order owned by `participant`.
+    ///
+    /// Any remainder that rests is attributed to the participant, so it can
+    /// be pulled by `cancel_all`.
+    #[inline]
+    pub fn submit_order_as(&mut self, participant: ParticipantId, order: Order, timestamp: u64) -> OrderResult {
+        self.accept(InputCommand::Submit { participant, order, timestamp });
+        self.process(participant, order, timestamp, &mut ())
+    }
+    
+    /// Submit an order owned by `participant`, reporting to `events` as it
+    /// is processed.
+    ///
+    /// Also reports the resulting top-of-book change and any OCO, MMP or
+    /// no-cross cancels the order caused (they are not left for
+    /// `next_oco_cancel` / `next_mmp_cancel` / `next_cross_cancel`).
+    #[inline]
+    pub fn submit_order_with<E: MatchEvents>(
+        &mut self,
+        participant: ParticipantId,
+        order: Order,
+        timestamp: u64,
+        events: &mut E,
+    ) -> OrderResult {
+        self.accept(InputCommand::Submit { participant, order, timestamp });
+        let result = self.process(participant, order, timestamp, events);
+        
+        match result {
+            OrderResult::Rejected { reason } => events.on_reject(&order, reason),
+            OrderResult::Cancelled { filled_qty, .. } => {
+                let mut cancelled = order;
+                cancelled.remaining_qty = order.remaining_qty.saturating_sub(filled_qty);
+                events.on_cancel(&cancelled);
+            }
+            _ => {}
+        }
+        while let Some(cancelled) = self.oco.pop_cancelled() {
+            events.on_cancel(&cancelled);
+        }
+        while let Some(cancelled) = self.mmp.pop_cancelled() {
+            events.on_cancel(&cancelled);
+        }
+        while let Some(cancelled) = self.no_cross.pop_cancelled() {
+            events.on_cancel(&cancelled);
+        }
+        
+        self.report_bbo(events);
+        result
+    }
+    
+    /// Cancel an order by handle, reporting the cancel and the resulting
+    /// top-of-book change to `events`.
+    #[inline]
+    pub fn cancel_order_with<E: MatchEvents>(&mut self, handle: OrderHandle, events: &mut E) -> Option<Order> {
+        let cancelled = self.cancel_order(handle);
+        if let Some(order) = &cancelled {
+            events.on_cancel(order);
+        }
+        self.report_bbo(events);
+        cancelled
+    }
+    
+    /// Current best price and displayed quantity on each side.
+    pub fn bbo(&self) -> BboChanged {
+        let top = |side: &BookSide| match side.best_level() {
+            Some(level) => (side.best_price(), level.total_qty),
+            None => (None, Quantity::ZERO),
+        };
+        let (bid, bid_qty) = top(&self.book.bids);
+        let (ask, ask_qty) = top(&self.book.asks);
+        BboChanged { bid, bid_qty, ask, ask_qty }
+    }
+    
+    /// Emit `on_bbo_change` if the top of book differs from the last one
+    /// reported. Changes made by calls without an event sink are picked up
+    /// by the next call that has one.
+    #[inline]
+    fn report_bbo<E: MatchEvents>(&mut self, events: &mut E) {
+        let bbo = self.bbo();
+        if bbo != self.last_bbo {
+            self.last_bbo = bbo;
+            events.on_bbo_change(&bbo);
+        }
+    }
+    
+    /// Validate, match and rest an order, reporting accepts and fills.
+    #[inline(always)]
+    fn process<E: MatchEvents>(
+        &mut self,
+        participant: ParticipantId,
+        mut order: Order,
+        timestamp: u64,
+        events: &mut E,
+    ) -> OrderResult {
         // === METRICS: Track order submission ===
         ORDERS_PROCESSED.fetch_add(1, Ordering::Relaxed);
         
         // === VALIDATION (minimal, fast-fail) ===
-        if order.remaining_qty.is_zero() {
+        let open_orders = self.owners.count(participant);
+        if let Err(reason) = self.validate(participant, &order, timestamp, open_orders) {
             ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
-            return OrderResult::Rejected { reason: RejectReason::InvalidQuantity };
+            return OrderResult::Rejected { reason };
         }
         
-        if order.price.is_zero() && order.order_type != OrderType::IOC {
+        if self.index.contains(order.order_id) || self.stops.contains(order.order_id) {
             ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
-            return OrderResult::Rejected { reason: RejectReason::InvalidPrice };
+            return OrderResult::Rejected { reason: RejectReason::DuplicateOrderId };
         }
         
         // Assign timestamp
         order.timestamp = timestamp;
         
+        // === CALL PHASE: rest without matching ===
+        if self.phase == TradingPhase::Auction {
+            if !matches!(order.order_type, OrderType::Limit | OrderType::GTD | OrderType::Day) {
+                ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
+                return OrderResult::Rejected { reason: RejectReason::AuctionInProgress };
+            }
+            return match self.add_to_book(order, participant) {
+                Some(handle) => {
+                    events.on_accept(&order);
+                    OrderResult::Resting { handle }
+                }
+                None => OrderResult::Rejected { reason: RejectReason::PoolExhausted },
+            };
+        }
+        
         // === POST-ONLY CHECK ===
         if order.order_type == OrderType::PostOnly {
             let opposite_side = self.book.opposite_side_mut(order.side);
@@ -148,17 +468,46 @@ impl MatchingEngine {
             }
         }
         
+        // === MARKET PRE-CHECK ===
+        if order.order_type == OrderType::Market && self.book.opposite_side_mut(order.side).is_empty() {
+            ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
+            return OrderResult::Rejected { reason: RejectReason::NoLiquidity };
+        }
+        
         // === FOK PRE-CHECK ===
-        if order.order_type == OrderType::FOK {
-            if !self.can_fill_completely(&order) {
-                ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
-                return OrderResult::Rejected { reason: RejectReason::InsufficientLiquidity };
-            }
+        if order.order_type == OrderType::FOK && !self.can_fill(participant, &order, order.remaining_qty) {
+            ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
+            return OrderResult::Rejected { reason: RejectReason::InsufficientLiquidity };
+        }
+        
+        events.on_accept(&order);
+        
+        // === MINQTY PRE-CHECK ===
+        let min_qty = order.min_qty();
+        if !min_qty.is_zero() && !self.can_fill(participant, &order, min_qty) {
+            return OrderResult::Cancelled {
+                filled_qty: Quantity::ZERO,
+                fills: ArrayVec::new(),
+            };
         }
         
         // === MATCHING ===
         let mut fills = ArrayVec::new();
-        self.match_order(&mut order, &mut fills);
+        let stopped = self.match_order(participant, &mut order, &mut fills, events);
+        
+        // A linked order re-entering through modify_order may trade as taker
+        if order.flags & FLAG_OCO != 0 && !fills.is_empty() {
+            order.flags &= !FLAG_OCO;
+            self.cancel_oco_partner(order.order_id);
+        }
+        
+        // Cancelled (or used up by a decrement) on meeting its own group
+        if stopped {
+            return OrderResult::Cancelled {
+                filled_qty: order.filled_qty(),
+                fills,
+            };
+        }
         
         // === POST-MATCH HANDLING ===
         if order.remaining_qty.is_zero() {
@@ -181,9 +530,16 @@ impl MatchingEngine {
                     fills,
                 }
             }
-            OrderType::Limit | OrderType::PostOnly => {
+            OrderType::Market => {
+                // Book exhausted or sweep depth reached: cancel remaining
+                OrderResult::Cancelled {
+                    filled_qty: order.filled_qty(),
+                    fills,
+                }
+            }
+            OrderType::Limit | OrderType::PostOnly | OrderType::GTD | OrderType::Day => {
                 // Add remaining to book
-                match self.add_to_book(order) {
+                match self.add_to_book(order, participant) {
                     Some(handle) => {
                         if fills.is_empty() {
                             OrderResult::Resting { handle }
@@ -201,27 +557,107 @@ impl MatchingEngine {
         }
     }
     
-    /// Check if order can be completely filled (for FOK).
+    /// Order checks that do not depend on the book: MMP, quantity, price,
+    /// expiry, band, uptick rule and pre-trade risk. `open_orders` is the
+    /// participant's resting count the order would join.
+    ///
+    /// Has no side effects, so `modify_order` can check a replacement
+    /// before it pulls the original.
+    #[inline(always)]
+    fn validate(
+        &self,
+        participant: ParticipantId,
+        order: &Order,
+        timestamp: u64,
+        open_orders: u32,
+    ) -> Result<(), RejectReason> {
+        if self.mmp.is_tripped(participant) {
+            return Err(RejectReason::MmpTripped);
+        }
+        
+        if order.remaining_qty.is_zero() || !self.spec.is_valid_qty(order.remaining_qty) {
+            return Err(RejectReason::InvalidQuantity);
+        }
+        
+        if order.price.is_zero() && !matches!(order.order_type, OrderType::IOC | OrderType::Market) {
+            return Err(RejectReason::InvalidPrice);
+        }
+        
+        if !self.spec.is_on_tick(order.price) {
+            return Err(RejectReason::InvalidPrice);
+        }
+        
+        if order.order_type == OrderType::GTD && order.expire_at <= timestamp {
+            return Err(RejectReason::InvalidExpiry);
+        }
+        
+        if let (Some(band), Some(reference)) = (self.band, self.reference_price) {
+            if !order.price.is_zero() && !band.contains(reference, order.price, self.spec.tick_size) {
+                return Err(RejectReason::PriceOutOfBand);
+            }
+        }
+        
+        // === UPTICK RULE ===
+        if self.short_sale_restriction && order.side == Side::Sell && order.is_short_sell() {
+            if let Some(last) = self.stats.last_price {
+                if order.price.is_zero() || order.price <= last {
+                    return Err(RejectReason::ShortSaleRestricted);
+                }
+            }
+        }
+        
+        // === PRE-TRADE RISK ===
+        self.risk.check(participant, order, self.reference_price, open_orders)
+    }
+    
+    /// Check if at least `qty` of the order would fill (for FOK and MinQty).
+    ///
+    /// Walks crossing levels in the same FIFO order the match will take,
+    /// counting displayed quantity only. Market orders stop at the sweep
+    /// depth limit. Fails once more than `MAX_FILLS_PER_ORDER` makers would
+    /// be needed. Orders of the participant's no-cross group are skipped,
+    /// or end the walk unless the policy only cancels resting orders.
     #[inline]
-    fn can_fill_completely(&self, order: &Order) -> bool {
+    fn can_fill(&self, participant: ParticipantId, order: &Order, qty: Quantity) -> bool {
         let opposite_side = match order.side {
             Side::Buy => &self.book.asks,
             Side::Sell => &self.book.bids,
         };
         
-        // Simple check: just verify there's enough total quantity at crossing prices
-        if let Some(best_price) = opposite_side.best_price() {
-            let crosses = match order.side {
-                Side::Buy => order.price.0 >= best_price.0,
-                Side::Sell => order.price.0 <= best_price.0,
+        let is_market = order.order_type == OrderType::Market;
+        let mut needed = qty.0;
+        let mut makers = 0;
+        
+        for (levels, (price, level)) in opposite_side.iter_levels().enumerate() {
+            let crosses = if is_market {
+                levels < self.max_sweep_levels as usize
+            } else {
+                match order.side {
+                    Side::Buy => order.price.0 >= price.0,
+                    Side::Sell => order.price.0 <= price.0,
+                }
             };
+            if !crosses {
+                break;
+            }
             
-            if crosses {
-                // For simplicity, just check if best level has enough
-                // In production, would walk the book
-                if let Some(level) = opposite_side.best_level() {
-                    return level.total_qty.0 >= order.remaining_qty.0;
+            for handle in level.iter(self.pool.links()) {
+                if self.no_cross.same_group(participant, self.owners.owner(handle)) {
+                    if self.no_cross.policy() == CrossPolicy::CancelResting {
+                        continue;
+                    }
+                    return false;
+                }
+                if makers == MAX_FILLS_PER_ORDER {
+                    return false;
+                }
+                makers += 1;
+                
+                let available = self.pool[handle].displayed_qty().0;
+                if available >= needed {
+                    return true;
                 }
+                needed -= available;
             }
         }
         
@@ -230,10 +666,24 @@ impl MatchingEngine {
     
     /// Core matching loop.
     /// Refactored to avoid borrow checker issues by not holding mutable reference across operations.
+    ///
+    /// Returns `true` if the no-cross policy stopped the order.
     #[inline(always)]
-    fn match_order(&mut self, order: &mut Order, fills: &mut ArrayVec<Fill, MAX_FILLS_PER_ORDER>) {
+    fn match_order<E: MatchEvents>(
+        &mut self,
+        participant: ParticipantId,
+        order: &mut Order,
+        fills: &mut ArrayVec<Fill, MAX_FILLS_PER_ORDER>,
+        events: &mut E,
+    ) -> bool {
+        let is_market = order.order_type == OrderType::Market;
+        let mut levels_swept = 0u32;
+        let mut current_level = None;
+        
         loop {
             if order.remaining_qty.is_zero() {
+                // The last fill may have emptied the best level
+                self.book.opposite_side_mut(order.side).find_next_best();
                 break;
             }
             
@@ -246,7 +696,7 @@ impl MatchingEngine {
                 
                 match opposite_side.best_price() {
                     Some(bp) => {
-                        let c = match order.side {
+                        let c = is_market || match order.side {
                             Side::Buy => order.price.0 >= bp.0,
                             Side::Sell => order.price.0 <= bp.0,
                         };
@@ -260,11 +710,34 @@ impl MatchingEngine {
                 break;
             }
             
+            // Market orders stop after `max_sweep_levels` distinct prices
+            if current_level != Some(best_price) {
+                if is_market && levels_swept == self.max_sweep_levels {
+                    break;
+                }
+                levels_swept += 1;
+                current_level = Some(best_price);
+            }
+            
+            // === NO-CROSS GROUPS ===
+            let maker = self.book.side(order.side.opposite()).best_level().and_then(|level| level.front());
+            if let Some(maker_handle) = maker {
+                if self.no_cross.same_group(participant, self.owners.owner(maker_handle)) {
+                    if self.prevent_cross(order, maker_handle) {
+                        return true;
+                    }
+                    continue;
+                }
+            }
+            
             // Match one order at a time at the best level
             let fill_result = self.match_one_at_best(order.side.opposite(), order, best_price);
             
             match fill_result {
                 Some(fill) => {
+                    self.on_trade(fill.price);
+                    self.stats.record(fill.price, fill.quantity, fill.timestamp);
+                    events.on_fill(&fill);
                     if !fills.is_full() {
                         fills.push(fill);
                     }
@@ -279,6 +752,45 @@ impl MatchingEngine {
                 }
             }
         }
+        false
+    }
+    
+    /// Apply the no-cross policy to an order meeting a resting order of
+    /// its own group. Returns `true` if the incoming order must stop.
+    fn prevent_cross(&mut self, taker: &mut Order, maker_handle: OrderHandle) -> bool {
+        let maker = self.pool[maker_handle];
+        match self.no_cross.policy() {
+            CrossPolicy::CancelResting => {
+                self.cancel_crossed(maker_handle, &maker);
+                false
+            }
+            CrossPolicy::CancelAggressor => true,
+            CrossPolicy::CancelBoth => {
+                self.cancel_crossed(maker_handle, &maker);
+                true
+            }
+            CrossPolicy::Decrement => {
+                let qty = taker.remaining_qty.min(maker.remaining_qty);
+                if qty == maker.remaining_qty {
+                    self.cancel_crossed(maker_handle, &maker);
+                } else {
+                    self.shrink_resting(maker_handle, &maker, maker.remaining_qty.saturating_sub(qty));
+                }
+                taker.remaining_qty = taker.remaining_qty.saturating_sub(qty);
+                taker.original_qty = taker.original_qty.saturating_sub(qty);
+                taker.remaining_qty.is_zero()
+            }
+        }
+    }
+    
+    /// Pull a resting order the no-cross policy cancelled.
+    fn cancel_crossed(&mut self, handle: OrderHandle, order: &Order) {
+        if self.remove_resting(handle, order) {
+            if order.flags & FLAG_OCO != 0 {
+                self.dissolve_oco(order.order_id);
+            }
+            self.no_cross.push_cancelled(*order);
+        }
     }
     
     /// Match against one maker order at the best level.
@@ -297,12 +809,14 @@ impl MatchingEngine {
         }
         
         let maker_handle = best_level.front()?;
-        let maker = self.pool.get_mut(maker_handle);
+        let maker = &self.pool[maker_handle];
+        let maker_owner = self.owners.owner(maker_handle);
         
-        // Calculate fill quantity
-        let fill_qty = taker.remaining_qty.min(maker.remaining_qty);
+        // Calculate fill quantity (only the displayed clip is available)
+        let fill_qty = taker.remaining_qty.min(maker.displayed_qty());
         
         // Create fill record
+        let (maker_fee, taker_fee) = self.fees.fees(exec_price, fill_qty);
         let fill = Fill {
             maker_order_id: maker.order_id,
             taker_order_id: taker.order_id,
@@ -311,47 +825,106 @@ impl MatchingEngine {
             maker_side: maker.side,
             symbol: taker.symbol,
             timestamp: taker.timestamp,
+            maker_fee,
+            taker_fee,
         };
         
         // Execute fill
         taker.fill(fill_qty);
-        maker.fill(fill_qty);
+        if let Some(linked) = self.fill_front(maker_side, maker_handle, fill_qty, taker.timestamp) {
+            self.cancel_oco_partner(linked);
+        }
+        if self.mmp.on_fill(maker_owner, maker_side, fill_qty, taker.timestamp) {
+            self.pull_quotes(maker_owner);
+        }
         
-        // Update level
-        let opposite_book = match maker_side {
-            Side::Buy => &mut self.book.bids,
-            Side::Sell => &mut self.book.asks,
+        // === METRICS: Track fill execution ===
+        FILLS_EXECUTED.fetch_add(1, Ordering::Relaxed);
+        
+        Some(fill)
+    }
+    
+    /// Follow a trade with the band reference and trailing stops.
+    #[inline(always)]
+    fn on_trade(&mut self, price: Price) {
+        self.reference_price = Some(price);
+        self.stops.on_trade(price);
+    }
+    
+    /// Fill the front order of `side`'s best level by `qty`.
+    ///
+    /// Releases the order once filled. Returns its ID if it was an OCO
+    /// leg, whose partner the caller must then cancel.
+    #[inline(always)]
+    fn fill_front(&mut self, side: Side, handle: OrderHandle, qty: Quantity, timestamp: u64) -> Option<OrderId> {
+        let maker = &mut self.pool[handle];
+        maker.fill(qty);
+        let (order_id, price) = (maker.order_id, maker.price);
+        let linked = maker.flags & FLAG_OCO != 0;
+        maker.flags &= !FLAG_OCO;
+        
+        // Iceberg clip exhausted: show the next one at the back of the queue
+        let replenished = if maker.is_iceberg() && !maker.is_filled() && maker.displayed_qty().is_zero() {
+            maker.replenish();
+            maker.timestamp = timestamp;
+            Some(maker.displayed_qty())
+        } else {
+            None
         };
+        let filled = maker.is_filled();
         
-        if let Some(level) = opposite_book.best_level_mut() {
-            level.reduce_qty(fill_qty);
+        // Update level
+        let book_side = self.book.side_mut(side);
+        if let Some(level) = book_side.best_level_mut() {
+            level.reduce_qty(qty);
             
             // Remove maker if fully filled
-            if self.pool.get(maker_handle).is_filled() {
-                level.pop_front();
-                self.pool.deallocate(maker_handle);
-                opposite_book.decrement_order_count();
+            if filled {
+                level.pop_front(self.pool.links_mut());
+                self.pool.deallocate(handle);
+                self.index.remove(order_id);
+                self.owners.remove(handle);
+                self.sessions.remove(handle);
+                self.expiries.cancel(handle);
+                book_side.decrement_order_count();
+            } else if let Some(clip) = replenished {
+                level.requeue_front(clip, self.pool.links_mut());
+                book_side.add_qty(price, clip);
             }
         }
         
-        opposite_book.reduce_qty(fill_qty);
-        
-        // === METRICS: Track fill execution ===
-        FILLS_EXECUTED.fetch_add(1, Ordering::Relaxed);
-        
-        Some(fill)
+        book_side.reduce_qty(price, qty);
+        linked.then_some(order_id)
     }
     
     /// Add order to the book.
     #[inline]
-    fn add_to_book(&mut self, order: Order) -> Option<OrderHandle> {
+    fn add_to_book(&mut self, mut order: Order, participant: ParticipantId) -> Option<OrderHandle> {
+        if order.is_iceberg() {
+            order.replenish();
+        } else {
+            order.visible_qty = 0;
+        }
+        self.place_resting(order, participant)
+    }
+    
+    /// Queue an order as-is at the back of its level.
+    fn place_resting(&mut self, order: Order, participant: ParticipantId) -> Option<OrderHandle> {
+        let capacity = self.pool.capacity();
         let handle = self.pool.allocate()?;
+        if self.pool.capacity() != capacity {
+            self.grow_tables();
+        }
         self.pool.insert(handle, order);
         
         let book_side = self.book.side_mut(order.side);
-        let order_ref = self.pool.get(handle);
         
-        if book_side.add_order(handle, order_ref) {
+        if book_side.add_order(handle, &order, self.pool.links_mut()) {
+            self.index.insert(order.order_id, handle);
+            self.owners.insert(handle, participant);
+            if order.order_type == OrderType::GTD {
+                self.expiries.schedule(handle, order.expire_at);
+            }
             Some(handle)
         } else {
             self.pool.deallocate(handle);
@@ -359,181 +932,2118 @@ impl MatchingEngine {
         }
     }
     
+    /// Size the per-handle tables for a pool that just grew.
+    #[cold]
+    fn grow_tables(&mut self) {
+        let capacity = self.pool.capacity();
+        self.index.grow(capacity);
+        self.owners.grow(capacity);
+        self.sessions.grow(capacity);
+        self.expiries.grow(capacity);
+    }
+    
     /// Cancel an order by handle.
     #[inline]
     pub fn cancel_order(&mut self, handle: OrderHandle) -> Option<Order> {
-        if !handle.is_valid() {
+        let order = *self.pool.try_get(handle)?;
+        self.accept(InputCommand::Cancel { order_id: order.order_id });
+        
+        // Unlink from its level and release
+        if !self.remove_resting(handle, &order) {
             return None;
         }
-        
-        let order = *self.pool.get(handle);
-        
-        // Remove from book
-        let book_side = self.book.side_mut(order.side);
-        if let Some(level) = book_side.level_at_price_mut(order.price) {
-            level.reduce_qty(order.remaining_qty);
+        if order.flags & FLAG_OCO != 0 {
+            self.dissolve_oco(order.order_id);
         }
         
-        book_side.reduce_qty(order.remaining_qty);
-        book_side.decrement_order_count();
-        
-        self.pool.deallocate(handle);
-        
         Some(order)
     }
     
-    /// Get order by handle.
-    #[inline(always)]
-    pub fn get_order(&self, handle: OrderHandle) -> Option<&Order> {
-        if handle.is_valid() {
-            Some(self.pool.get(handle))
-        } else {
-            None
-        }
+    /// Cancel a resting order by its client order ID.
+    ///
+    /// Returns `None` if no order with that ID is resting.
+    #[inline]
+    pub fn cancel_by_id(&mut self, order_id: OrderId) -> Option<Order> {
+        let handle = self.index.get(order_id)?;
+        self.cancel_order(handle)
     }
     
-    /// Get pool statistics.
-    pub fn pool_stats(&self) -> (usize, usize) {
-        (self.pool.active(), self.pool.capacity())
+    /// Cancel a resting order on behalf of `participant`.
+    ///
+    /// Rejects with `NotOwner` if the order belongs to anyone else.
+    pub fn cancel_by_id_as(&mut self, participant: ParticipantId, order_id: OrderId) -> Result<Order, RejectReason> {
+        let handle = self.owned_handle(participant, order_id)?;
+        self.cancel_order(handle).ok_or(RejectReason::UnknownOrder)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
     
-    fn create_engine() -> MatchingEngine {
-        MatchingEngine::new(SymbolId(1), 10, Price::ZERO) // 1024 orders
+    /// Amend a resting order on behalf of `participant` (see `modify_order`).
+    ///
+    /// Rejects with `NotOwner` if the order belongs to anyone else.
+    pub fn modify_by_id_as(
+        &mut self,
+        participant: ParticipantId,
+        order_id: OrderId,
+        new_price: Price,
+        new_qty: Quantity,
+    ) -> OrderResult {
+        match self.owned_handle(participant, order_id) {
+            Ok(handle) => self.modify_order(handle, new_price, new_qty),
+            Err(reason) => OrderResult::Rejected { reason },
+        }
     }
     
-    #[test]
-    fn test_simple_match() {
-        let mut engine = create_engine();
-        
-        // Place sell order
-        let sell = Order::new(
-            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
-            Price::from_ticks(100), Quantity(100), 0,
-        );
-        let result = engine.submit_order(sell, 1);
-        assert!(matches!(result, OrderResult::Resting { .. }));
-        
-        // Place matching buy order
-        let buy = Order::new(
-            OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit,
-            Price::from_ticks(100), Quantity(100), 2,
-        );
-        let result = engine.submit_order(buy, 2);
-        
-        match result {
-            OrderResult::Filled { fills } => {
-                assert_eq!(fills.len(), 1);
-                assert_eq!(fills[0].quantity.0, 100);
-                assert_eq!(fills[0].price, Price::from_ticks(100));
-                assert_eq!(fills[0].maker_order_id.0, 1);
-                assert_eq!(fills[0].taker_order_id.0, 2);
-            }
-            _ => panic!("Expected Filled, got {:?}", result),
+    /// Handle of a resting order, provided `participant` owns it.
+    fn owned_handle(&self, participant: ParticipantId, order_id: OrderId) -> Result<OrderHandle, RejectReason> {
+        let handle = self.index.get(order_id).ok_or(RejectReason::UnknownOrder)?;
+        if self.owners.owner(handle) != participant {
+            return Err(RejectReason::NotOwner);
         }
+        Ok(handle)
     }
     
-    #[test]
-    fn test_partial_fill() {
-        let mut engine = create_engine();
-        
-        // Place sell order for 50
-        let sell = Order::new(
-            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
-            Price::from_ticks(100), Quantity(50), 0,
-        );
-        engine.submit_order(sell, 1);
+    /// Park a trailing stop `trail` away from the last trade price.
+    ///
+    /// When a trade retraces through the stop, the order is released as
+    /// its own type (typically Market, or Limit at `order.price`) and is
+    /// submitted by `next_triggered`.
+    pub fn submit_trailing_stop(&mut self, order: Order, trail: Price) -> Result<(), RejectReason> {
+        if order.remaining_qty.is_zero() {
+            return Err(RejectReason::InvalidQuantity);
+        }
+        if trail.is_zero() {
+            return Err(RejectReason::InvalidPrice);
+        }
+        if self.index.contains(order.order_id) || self.stops.contains(order.order_id) {
+            return Err(RejectReason::DuplicateOrderId);
+        }
         
-        // Place buy order for 100
-        let buy = Order::new(
-            OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit,
-            Price::from_ticks(100), Quantity(100), 2,
+        self.stops.add(order, trail);
+        Ok(())
+    }
+    
+    /// Submit the next fired stop, if any.
+    ///
+    /// Call after every submission until it returns `None`; fills from a
+    /// triggered stop can fire further stops.
+    pub fn next_triggered(&mut self, timestamp: u64) -> Option<(Order, OrderResult)> {
+        let order = self.stops.pop_fired()?;
+        let result = self.submit_order(order, timestamp);
+        Some((order, result))
+    }
+    
+    /// Link two resting orders so that a fill on either cancels the other.
+    ///
+    /// Partners cancelled this way are reported by `next_oco_cancel`.
+    /// Cancelling or expiring one leg dissolves the link and leaves the
+    /// other resting.
+    pub fn link_oco(&mut self, link_id: u64, first: OrderId, second: OrderId) -> Result<(), RejectReason> {
+        if first == second {
+            return Err(RejectReason::UnknownOrder);
+        }
+        let (Some(first_handle), Some(second_handle)) = (self.index.get(first), self.index.get(second)) else {
+            return Err(RejectReason::UnknownOrder);
+        };
+        let first_flags = self.pool[first_handle].flags;
+        let second_flags = self.pool[second_handle].flags;
+        if (first_flags | second_flags) & FLAG_OCO != 0 {
+            return Err(RejectReason::AlreadyLinked);
+        }
+        
+        self.pool[first_handle].flags |= FLAG_OCO;
+        self.pool[second_handle].flags |= FLAG_OCO;
+        self.oco.link(link_id, first, second);
+        Ok(())
+    }
+    
+    /// Next order cancelled because its OCO partner traded, oldest first.
+    ///
+    /// Drain after every submission; the cancels belong to that event.
+    #[inline]
+    pub fn next_oco_cancel(&mut self) -> Option<Order> {
+        self.oco.pop_cancelled()
+    }
+    
+    /// Pull the partner of a traded OCO leg off the book.
+    fn cancel_oco_partner(&mut self, order_id: OrderId) {
+        let Some(partner) = self.oco.unlink(order_id) else { return };
+        let Some(handle) = self.index.get(partner) else { return };
+        let order = self.pool[handle];
+        if self.remove_resting(handle, &order) {
+            self.oco.push_cancelled(order);
+        }
+    }
+    
+    /// Drop the link of a leg leaving the book, freeing its partner.
+    fn dissolve_oco(&mut self, order_id: OrderId) {
+        let Some(partner) = self.oco.unlink(order_id) else { return };
+        if let Some(handle) = self.index.get(partner) {
+            self.pool[handle].flags &= !FLAG_OCO;
+        }
+    }
+    
+    /// Next quote pulled because its owner tripped market maker
+    /// protection, oldest first.
+    ///
+    /// Drain after every submission; the cancels belong to that event.
+    #[inline]
+    pub fn next_mmp_cancel(&mut self) -> Option<Order> {
+        self.mmp.pop_cancelled()
+    }
+    
+    /// Next resting order cancelled because an order of its no-cross
+    /// group met it, oldest first.
+    ///
+    /// Drain after every submission; the cancels belong to that event.
+    #[inline]
+    pub fn next_cross_cancel(&mut self) -> Option<Order> {
+        self.no_cross.pop_cancelled()
+    }
+    
+    /// Pull every resting order of a participant that just tripped MMP.
+    fn pull_quotes(&mut self, participant: ParticipantId) {
+        let mut pulled = Vec::new();
+        self.cancel_owned(participant, |order| pulled.push(*order));
+        for order in pulled {
+            self.mmp.push_cancelled(order);
+        }
+    }
+    
+    /// Current trading phase.
+    #[inline(always)]
+    pub fn phase(&self) -> TradingPhase {
+        self.phase
+    }
+    
+    /// Enter the call phase.
+    ///
+    /// Limit and GTD orders rest without matching, so the book may cross;
+    /// other order types are rejected. Cancels, modifies and expiries work
+    /// as usual. Ends with `uncross`.
+    pub fn start_auction(&mut self) {
+        self.accept(InputCommand::StartAuction);
+        self.phase = TradingPhase::Auction;
+    }
+    
+    /// Price and volume the auction would uncross at now.
+    ///
+    /// Counts each order's full remaining quantity, hidden iceberg
+    /// reserve included. Ties are broken towards the last trade price.
+    pub fn indicative_uncross(&self) -> Option<Uncross> {
+        let (best_bid, best_ask) = (self.book.best_bid()?, self.book.best_ask()?);
+        if best_bid.0 < best_ask.0 {
+            return None;
+        }
+        
+        let crossing = |side: Side, limit: Price| -> Vec<(Price, Quantity)> {
+            self.book.side(side)
+                .iter_levels()
+                .take_while(|(price, _)| match side {
+                    Side::Buy => price.0 >= limit.0,
+                    Side::Sell => price.0 <= limit.0,
+                })
+                .map(|(price, level)| {
+                    let qty = level.iter(self.pool.links()).map(|handle| self.pool[handle].remaining_qty.0).sum();
+                    (price, Quantity(qty))
+                })
+                .collect()
+        };
+        auction::equilibrium(
+            &crossing(Side::Buy, best_ask),
+            &crossing(Side::Sell, best_bid),
+            self.stops.last_trade(),
+        )
+    }
+    
+    /// Execute the auction and return to continuous trading.
+    ///
+    /// Every fill prints at the equilibrium price; within that, bids and
+    /// asks execute in price-time priority, the older of each pair being
+    /// reported as maker. Calls `on_fill` with each fill and returns the
+    /// uncross executed, or `None` if the book did not cross.
+    pub fn uncross<F: FnMut(&Fill)>(&mut self, timestamp: u64, mut on_fill: F) -> Option<Uncross> {
+        self.accept(InputCommand::Uncross { timestamp });
+        self.phase = TradingPhase::Continuous;
+        let uncross = self.indicative_uncross()?;
+        let price = uncross.price;
+        
+        let mut remaining = uncross.volume.0;
+        while remaining > 0 {
+            let (Some(bid_handle), Some(ask_handle)) = (self.front_at_best(Side::Buy), self.front_at_best(Side::Sell)) else {
+                break;
+            };
+            let bid = self.pool[bid_handle];
+            let ask = self.pool[ask_handle];
+            // OCO cancels can pull liquidity counted in the uncross
+            if bid.price.0 < price.0 || ask.price.0 > price.0 {
+                break;
+            }
+            
+            let qty = Quantity(remaining.min(bid.displayed_qty().0).min(ask.displayed_qty().0));
+            let (maker, taker) = if bid.timestamp <= ask.timestamp { (&bid, &ask) } else { (&ask, &bid) };
+            let (maker_fee, taker_fee) = self.fees.fees(price, qty);
+            let fill = Fill {
+                maker_order_id: maker.order_id,
+                taker_order_id: taker.order_id,
+                price,
+                quantity: qty,
+                maker_side: maker.side,
+                symbol: self.symbol,
+                timestamp,
+                maker_fee,
+                taker_fee,
+            };
+            
+            let bid_linked = self.fill_front(Side::Buy, bid_handle, qty, timestamp);
+            let ask_linked = self.fill_front(Side::Sell, ask_handle, qty, timestamp);
+            for linked in [bid_linked, ask_linked].into_iter().flatten() {
+                self.cancel_oco_partner(linked);
+            }
+            
+            FILLS_EXECUTED.fetch_add(1, Ordering::Relaxed);
+            self.stats.record(price, qty, timestamp);
+            on_fill(&fill);
+            remaining -= qty.0;
+        }
+        
+        // Drop levels the uncross emptied
+        self.front_at_best(Side::Buy);
+        self.front_at_best(Side::Sell);
+        
+        let executed = Quantity(uncross.volume.0 - remaining);
+        if !executed.is_zero() {
+            self.on_trade(price);
+        }
+        Some(Uncross { volume: executed, ..uncross })
+    }
+    
+    /// Front order of `side`, dropping exhausted levels on the way.
+    fn front_at_best(&mut self, side: Side) -> Option<OrderHandle> {
+        let book_side = self.book.side_mut(side);
+        loop {
+            book_side.best_price()?;
+            if let Some(handle) = book_side.best_level().and_then(|level| level.front()) {
+                return Some(handle);
+            }
+            book_side.find_next_best();
+        }
+    }
+    
+    /// Handle of the resting order with this ID.
+    #[inline]
+    pub fn handle_of(&self, order_id: OrderId) -> Option<OrderHandle> {
+        self.index.get(order_id)
+    }
+    
+    /// Amend a resting order (cancel/replace).
+    ///
+    /// `new_qty` is the new open quantity. A quantity decrease at the same
+    /// price is applied in place and keeps time priority. A price change or
+    /// quantity increase removes the order and re-submits it: it loses
+    /// priority, may match immediately, and the returned result carries the
+    /// replacement's handle. The replacement is checked (band, uptick rule,
+    /// pre-trade risk, post-only) before the original is pulled, so a
+    /// rejected amend leaves the order resting with its priority.
+    pub fn modify_order(&mut self, handle: OrderHandle, new_price: Price, new_qty: Quantity) -> OrderResult {
+        let Some(&order) = self.pool.try_get(handle) else {
+            return OrderResult::Rejected { reason: RejectReason::UnknownOrder };
+        };
+        let order_id = order.order_id;
+        self.accept(InputCommand::Modify { order_id, new_price, new_qty });
+        if new_qty.is_zero() || !self.spec.is_valid_qty(new_qty) {
+            return OrderResult::Rejected { reason: RejectReason::InvalidQuantity };
+        }
+        if new_price.is_zero() || !self.spec.is_on_tick(new_price) {
+            return OrderResult::Rejected { reason: RejectReason::InvalidPrice };
+        }
+        
+        if order.is_filled() {
+            return OrderResult::Rejected { reason: RejectReason::UnknownOrder };
+        }
+        
+        // === IN PLACE: size down, same price ===
+        if new_price == order.price && new_qty.0 <= order.remaining_qty.0 {
+            self.shrink_resting(handle, &order, new_qty);
+            return OrderResult::Resting { handle };
+        }
+        
+        // === CANCEL/REPLACE: loses priority ===
+        if order.order_type == OrderType::PostOnly
+            && self.book.opposite_side_mut(order.side).would_match(new_price, order.side)
+        {
+            return OrderResult::Rejected { reason: RejectReason::PostOnlyWouldMatch };
+        }
+        
+        let owner = self.owners.owner(handle);
+        let mut replacement = Order::new(
+            order.order_id, order.symbol, order.side, order.order_type,
+            new_price, new_qty, order.timestamp,
+        )
+        .with_display_qty(Quantity(order.display_qty as u64))
+        .with_expiry(order.expire_at);
+        replacement.flags = order.flags;
+        // The original still counts towards the owner's open orders
+        let open_orders = self.owners.count(owner).saturating_sub(1);
+        if let Err(reason) = self.validate(owner, &replacement, order.timestamp, open_orders) {
+            ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
+            return OrderResult::Rejected { reason };
+        }
+        
+        let session = self.sessions.owner(handle);
+        if !self.remove_resting(handle, &order) {
+            return OrderResult::Rejected { reason: RejectReason::UnknownOrder };
+        }
+        
+        let result = self.process(owner, replacement, order.timestamp, &mut ());
+        if let OrderResult::Resting { handle } | OrderResult::PartialFill { handle, .. } = result {
+            self.sessions.insert(handle, session);
+        }
+        result
+    }
+    
+    /// Tag the resting order in `handle` with the client `session` that
+    /// entered it, replacing any previous tag.
+    ///
+    /// The gateway tags each order it gets back as resting so that
+    /// `cancel_all_for_session` can purge the session when it drops. Tags
+    /// follow an order through `modify_order`; they are not part of
+    /// snapshots or the input log, since sessions do not outlive the
+    /// process.
+    pub fn tag_session(&mut self, handle: OrderHandle, session: SessionId) -> bool {
+        if !self.pool.try_get(handle).is_some_and(|order| self.index.get(order.order_id) == Some(handle)) {
+            return false;
+        }
+        self.sessions.remove(handle);
+        self.sessions.insert(handle, session);
+        true
+    }
+    
+    /// Client session a resting order was tagged with.
+    #[inline]
+    pub fn session_of(&self, order_id: OrderId) -> Option<SessionId> {
+        self.index.get(order_id).map(|handle| self.sessions.owner(handle))
+    }
+    
+    /// Cancel every resting order tagged with `session` (cancel on
+    /// disconnect). Returns the IDs of the cancelled orders, most recent
+    /// first.
+    ///
+    /// Runs as one call, so no other command interleaves with the purge.
+    /// Walks only that session's orders. Recorded as a cancel per order,
+    /// since session tags are not.
+    pub fn cancel_all_for_session(&mut self, session: SessionId) -> Vec<OrderId> {
+        if session == SessionId::NONE {
+            return Vec::new();
+        }
+        
+        let handles: Vec<OrderHandle> = self.sessions.handles(session).collect();
+        let mut cancelled = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let order = self.pool[handle];
+            self.accept(InputCommand::Cancel { order_id: order.order_id });
+            if self.remove_resting(handle, &order) {
+                if order.flags & FLAG_OCO != 0 {
+                    self.dissolve_oco(order.order_id);
+                }
+                cancelled.push(order.order_id);
+            }
+        }
+        cancelled
+    }
+    
+    /// Set both sides of `participant`'s quote in one call. Returns the
+    /// outcome of the bid and the ask update.
+    ///
+    /// A side with a live quote is amended like `modify_order` (in place
+    /// when it only shrinks at the same price); a side without one is
+    /// placed as a new limit order under `QuoteSide::order_id`. A zero
+    /// quantity pulls the side, reported as `Cancelled`. Each update is
+    /// recorded as the submit, modify or cancel it amounts to.
+    pub fn mass_quote(
+        &mut self,
+        participant: ParticipantId,
+        bid: QuoteSide,
+        ask: QuoteSide,
+        timestamp: u64,
+    ) -> [OrderResult; 2] {
+        [
+            self.update_quote(participant, Side::Buy, bid, timestamp),
+            self.update_quote(participant, Side::Sell, ask, timestamp),
+        ]
+    }
+    
+    /// Apply one side of a mass quote.
+    fn update_quote(&mut self, participant: ParticipantId, side: Side, quote: QuoteSide, timestamp: u64) -> OrderResult {
+        let live = self.quotes.slot(participant, side);
+        let handle = self.index.get(live).filter(|&handle| self.owners.owner(handle) == participant);
+        
+        let (order_id, result) = match handle {
+            Some(handle) if quote.qty.is_zero() => {
+                let order = self.pool[handle];
+                self.accept(InputCommand::Cancel { order_id: live });
+                if self.remove_resting(handle, &order) && order.flags & FLAG_OCO != 0 {
+                    self.dissolve_oco(order.order_id);
+                }
+                (live, OrderResult::Cancelled { filled_qty: order.filled_qty(), fills: ArrayVec::new() })
+            }
+            None if quote.qty.is_zero() => {
+                (live, OrderResult::Cancelled { filled_qty: Quantity::ZERO, fills: ArrayVec::new() })
+            }
+            Some(handle) => (live, self.modify_order(handle, quote.price, quote.qty)),
+            None => {
+                let order = Order::new(
+                    quote.order_id, self.symbol, side, OrderType::Limit, quote.price, quote.qty, timestamp,
+                );
+                (quote.order_id, self.submit_order_as(participant, order, timestamp))
+            }
+        };
+        
+        let resting = match result {
+            OrderResult::Resting { .. } | OrderResult::PartialFill { .. } => order_id,
+            // A rejected amend leaves the live quote untouched
+            OrderResult::Rejected { .. } if handle.is_some() => live,
+            _ => OrderId::INVALID,
+        };
+        self.quotes.set(participant, side, resting);
+        result
+    }
+    
+    /// Reduce the open quantity of a resting order to `new_qty` without
+    /// losing its place in the queue.
+    ///
+    /// Level and side totals shrink by the quantity no longer displayed.
+    /// Increases are rejected (use `modify_order`, which re-queues). Equal
+    /// to `modify_order` at the order's own price, and recorded as such;
+    /// rejected reductions are not recorded, since replaying them as a
+    /// modify would re-queue the order.
+    pub fn reduce_qty(&mut self, handle: OrderHandle, new_qty: Quantity) -> OrderResult {
+        let Some(&order) = self.pool.try_get(handle) else {
+            return OrderResult::Rejected { reason: RejectReason::UnknownOrder };
+        };
+        if new_qty.is_zero() || new_qty.0 > order.remaining_qty.0 || !self.spec.is_valid_qty(new_qty) {
+            return OrderResult::Rejected { reason: RejectReason::InvalidQuantity };
+        }
+        
+        self.accept(InputCommand::Modify { order_id: order.order_id, new_price: order.price, new_qty });
+        self.shrink_resting(handle, &order, new_qty);
+        OrderResult::Resting { handle }
+    }
+    
+    /// Cut a resting order down to `new_qty` in place.
+    fn shrink_resting(&mut self, handle: OrderHandle, order: &Order, new_qty: Quantity) {
+        let resting = &mut self.pool[handle];
+        let reduction = Quantity(resting.remaining_qty.0 - new_qty.0);
+        resting.remaining_qty = new_qty;
+        resting.original_qty = resting.original_qty.saturating_sub(reduction);
+        if new_qty.0 < resting.visible_qty as u64 {
+            resting.visible_qty = new_qty.0 as u32;
+        }
+        let hidden = Quantity(order.displayed_qty().0 - resting.displayed_qty().0);
+        
+        let book_side = self.book.side_mut(order.side);
+        if let Some(level) = book_side.level_at_price_mut(order.price) {
+            level.reduce_qty(hidden);
+        }
+        book_side.reduce_qty(order.price, hidden);
+    }
+    
+    /// Cancel every resting order of `participant`, calling `on_cancel`
+    /// with each. Returns the number cancelled.
+    ///
+    /// Walks only that participant's orders. Trailing stops that have not
+    /// fired are not affected.
+    pub fn cancel_all<F: FnMut(&Order)>(&mut self, participant: ParticipantId, on_cancel: F) -> usize {
+        if participant == ParticipantId::NONE {
+            return 0;
+        }
+        self.accept(InputCommand::CancelAll { participant });
+        self.cancel_owned(participant, on_cancel)
+    }
+    
+    /// Pull every resting order of `participant` without recording it.
+    fn cancel_owned<F: FnMut(&Order)>(&mut self, participant: ParticipantId, mut on_cancel: F) -> usize {
+        let handles: Vec<OrderHandle> = self.owners.handles(participant).collect();
+        let mut cancelled = 0;
+        for handle in handles {
+            let order = self.pool[handle];
+            if self.remove_resting(handle, &order) {
+                if order.flags & FLAG_OCO != 0 {
+                    self.dissolve_oco(order.order_id);
+                }
+                on_cancel(&order);
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+    
+    /// Cancel every resting order in the book, calling `on_cancel` with
+    /// each (bids then asks, in priority order). Returns the number
+    /// cancelled.
+    ///
+    /// Clears both sides wholesale rather than unlinking order by order.
+    pub fn cancel_all_symbol<F: FnMut(&Order)>(&mut self, mut on_cancel: F) -> usize {
+        self.accept(InputCommand::CancelAllSymbol);
+        let mut handles = Vec::with_capacity(self.pool.active());
+        for side in [Side::Buy, Side::Sell] {
+            for (_, level) in self.book.side(side).iter_levels() {
+                handles.extend(level.iter(self.pool.links()));
+            }
+        }
+        
+        for &handle in &handles {
+            let order = self.pool[handle];
+            self.pool.deallocate(handle);
+            self.index.remove(order.order_id);
+            self.owners.remove(handle);
+            self.sessions.remove(handle);
+            self.expiries.cancel(handle);
+            if order.flags & FLAG_OCO != 0 {
+                self.dissolve_oco(order.order_id);
+            }
+            on_cancel(&order);
+        }
+        self.book.bids.clear();
+        self.book.asks.clear();
+        handles.len()
+    }
+    
+    /// Owner of a resting order.
+    #[inline]
+    pub fn owner_of(&self, order_id: OrderId) -> Option<ParticipantId> {
+        self.index.get(order_id).map(|handle| self.owners.owner(handle))
+    }
+    
+    /// Cancel GTD orders whose expiry is at or before `now`, calling
+    /// `on_expire` with each. Returns the number expired.
+    ///
+    /// O(1) per expiry plus one pass over the wheel slots `now` advanced
+    /// past; the book is never scanned. Recorded only if something
+    /// expired.
+    pub fn expire_orders<F: FnMut(&Order)>(&mut self, now: u64, mut on_expire: F) -> usize {
+        let mut expired = 0;
+        while let Some(handle) = self.expiries.pop_expired(now) {
+            let order = self.pool[handle];
+            if self.remove_resting(handle, &order) {
+                if order.flags & FLAG_OCO != 0 {
+                    self.dissolve_oco(order.order_id);
+                }
+                on_expire(&order);
+                expired += 1;
+            }
+        }
+        if expired != 0 {
+            self.accept(InputCommand::Expire { now });
+        }
+        expired
+    }
+    
+    /// Close the trading session: cancel every resting Day order, calling
+    /// `on_expire` with each, and close the session statistics. Returns
+    /// the number expired. Other orders keep resting.
+    pub fn end_of_session<F: FnMut(&Order)>(&mut self, mut on_expire: F) -> usize {
+        self.accept(InputCommand::EndOfSession);
+        let handles: Vec<OrderHandle> = self
+            .pool
+            .iter_active()
+            .filter(|(_, order)| order.order_type == OrderType::Day)
+            .map(|(handle, _)| handle)
+            .collect();
+        
+        let mut expired = 0;
+        for handle in handles {
+            let order = self.pool[handle];
+            if self.remove_resting(handle, &order) {
+                if order.flags & FLAG_OCO != 0 {
+                    self.dissolve_oco(order.order_id);
+                }
+                on_expire(&order);
+                expired += 1;
+            }
+        }
+        self.stats.close();
+        expired
+    }
+    
+    /// Unlink a resting order from its level and release it.
+    ///
+    /// Returns `false` if the handle is not queued at `order.price`.
+    fn remove_resting(&mut self, handle: OrderHandle, order: &Order) -> bool {
+        let book_side = self.book.side_mut(order.side);
+        if !book_side.remove_order(handle, order.price, order.displayed_qty(), self.pool.links_mut()) {
+            return false;
+        }
+        self.pool.deallocate(handle);
+        self.index.remove(order.order_id);
+        self.owners.remove(handle);
+        self.sessions.remove(handle);
+        self.expiries.cancel(handle);
+        true
+    }
+    
+    /// Resting orders of `side` in price-time priority (L3 export).
+    pub fn resting_orders(&self, side: Side) -> impl Iterator<Item = L3Order> + '_ {
+        self.book.side(side).orders(&self.pool)
+    }
+    
+    /// Get order by handle.
+    #[inline(always)]
+    pub fn get_order(&self, handle: OrderHandle) -> Option<&Order> {
+        self.pool.try_get(handle)
+    }
+    
+    /// Panic if the book, pool and order index disagree, or the book is
+    /// crossed outside an auction.
+    ///
+    /// Walks every price level; enable the `invariants` feature to call
+    /// it from fuzz targets and soak tests.
+    #[cfg(any(test, feature = "invariants"))]
+    pub fn verify_invariants(&self) {
+        self.book.bids.verify_invariants(&self.pool);
+        self.book.asks.verify_invariants(&self.pool);
+        
+        if self.phase == TradingPhase::Continuous {
+            if let (Some(bid), Some(ask)) = (self.book.best_bid(), self.book.best_ask()) {
+                assert!(bid < ask, "crossed book: bid {:?} >= ask {:?}", bid, ask);
+            }
+        }
+        
+        let resting = (self.book.bids.order_count() + self.book.asks.order_count()) as usize;
+        assert_eq!(resting, self.pool.active(), "side order counts diverged from pool");
+        assert_eq!(self.index.len(), self.pool.active(), "order index diverged from pool");
+        assert_eq!(self.pool.invalid_frees(), 0, "pool refused a free");
+        for (handle, order) in self.pool.iter_active() {
+            assert_eq!(self.index.get(order.order_id), Some(handle), "live order {:?} not indexed", order.order_id);
+        }
+    }
+    
+    /// Start appending every recorded command to an input log (see
+    /// `journal`). Commands already recorded are kept.
+    pub fn start_recording(&mut self) {
+        let next = self.book.sequence() + 1;
+        self.recorder.get_or_insert_with(|| InputLog::starting_at(next));
+    }
+    
+    /// Stop recording, handing back the log.
+    pub fn stop_recording(&mut self) -> Option<InputLog> {
+        self.recorder.take()
+    }
+    
+    /// Log being recorded.
+    pub fn input_log(&self) -> Option<&InputLog> {
+        self.recorder.as_ref()
+    }
+    
+    /// Number an incoming command and record it if recording.
+    #[inline(always)]
+    fn accept(&mut self, command: InputCommand) {
+        self.book.next_sequence();
+        if let Some(log) = &mut self.recorder {
+            log.push(command);
+        }
+    }
+    
+    /// Re-feed recorded commands in order.
+    ///
+    /// On an engine configured like the recording one and starting from
+    /// the same state, the book and input sequence end up identical. OCO
+    /// and MMP cancels are left for `next_oco_cancel` / `next_mmp_cancel`
+    /// as in live use.
+    pub fn replay<'a>(&mut self, commands: impl IntoIterator<Item = &'a InputCommand>) {
+        for command in commands {
+            match (*command, self.command_target(command)) {
+                (InputCommand::Submit { participant, order, timestamp }, _) => {
+                    self.submit_order_as(participant, order, timestamp);
+                }
+                (InputCommand::Cancel { .. }, Some(handle)) => {
+                    self.cancel_order(handle);
+                }
+                (InputCommand::Modify { new_price, new_qty, .. }, Some(handle)) => {
+                    self.modify_order(handle, new_price, new_qty);
+                }
+                (InputCommand::Expire { now }, _) => {
+                    self.expire_orders(now, |_| {});
+                }
+                (InputCommand::EndOfSession, _) => {
+                    self.end_of_session(|_| {});
+                }
+                (InputCommand::CancelAll { participant }, _) => {
+                    self.cancel_all(participant, |_| {});
+                }
+                (InputCommand::CancelAllSymbol, _) => {
+                    self.cancel_all_symbol(|_| {});
+                }
+                (InputCommand::StartAuction, _) => self.start_auction(),
+                (InputCommand::Uncross { timestamp }, _) => {
+                    self.uncross(timestamp, |_| {});
+                }
+                // Recorded against an order that had already gone: no effect
+                (_, None) => self.accept(*command),
+            }
+        }
+    }
+    
+    /// Resting order a cancel or modify refers to.
+    fn command_target(&self, command: &InputCommand) -> Option<OrderHandle> {
+        match *command {
+            InputCommand::Cancel { order_id } | InputCommand::Modify { order_id, .. } => self.index.get(order_id),
+            _ => None,
+        }
+    }
+    
+    /// Checkpoint the resting book into `out`.
+    ///
+    /// Captures every resting order with its owner and queue position,
+    /// OCO links, the book sequence, reference price and trading phase
+    /// (see `snapshot` for the layout).
+    pub fn snapshot(&self, out: &mut Writer) {
+        out.put(&SNAPSHOT_MAGIC);
+        out.put_u16(SNAPSHOT_VERSION);
+        out.put_u8(match self.phase {
+            TradingPhase::Continuous => 0,
+            TradingPhase::Auction => 1,
+        });
+        out.put_u8(0);
+        out.put_u32(self.symbol.0);
+        out.put_u32(self.pool.capacity() as u32);
+        out.put_u64(self.book.sequence());
+        out.put_u64(self.reference_price.map_or(0, |price| price.0));
+        
+        let sides = [Side::Buy, Side::Sell];
+        let count: usize = sides.iter()
+            .flat_map(|&side| self.book.side(side).iter_levels())
+            .map(|(_, level)| level.len())
+            .sum();
+        out.put_u32(count as u32);
+        for side in sides {
+            for (_, level) in self.book.side(side).iter_levels() {
+                for handle in level.iter(self.pool.links()) {
+                    snapshot::write_order(out, &self.pool[handle], self.owners.owner(handle));
+                }
+            }
+        }
+        
+        out.put_u32(self.oco.len() as u32);
+        for pair in self.oco.iter() {
+            out.put_u64(pair.link_id);
+            out.put_u64(pair.first.0);
+            out.put_u64(pair.second.0);
+        }
+    }
+    
+    /// Load a checkpoint written by `snapshot` into this engine, which
+    /// must be empty and trade the same symbol.
+    ///
+    /// Orders keep their IDs, owners and queue positions but land in new
+    /// pool slots. Returns the number of bytes read. On error the engine
+    /// may hold part of the image and should be discarded.
+    pub fn restore(&mut self, data: &[u8]) -> Result<usize, SnapshotError> {
+        let mut reader = Reader::new(data);
+        if reader.take::<4>()? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let phase = match reader.u8()? {
+            0 => TradingPhase::Continuous,
+            1 => TradingPhase::Auction,
+            _ => return Err(SnapshotError::Corrupt),
+        };
+        reader.u8()?;
+        if reader.u32()? != self.symbol.0 {
+            return Err(SnapshotError::SymbolMismatch);
+        }
+        // Pool capacity of the writer; only the order count matters here
+        reader.u32()?;
+        let sequence = reader.u64()?;
+        let reference = reader.u64()?;
+        
+        if !self.pool.is_empty() {
+            return Err(SnapshotError::NotEmpty);
+        }
+        let count = reader.u32()? as usize;
+        if count > self.pool.available() {
+            return Err(SnapshotError::PoolExhausted);
+        }
+        
+        for _ in 0..count {
+            let (order, participant) = snapshot::read_order(&mut reader, self.symbol)?;
+            if !order.order_id.is_valid()
+                || order.is_filled()
+                || !order.order_type.should_rest()
+                || self.index.contains(order.order_id)
+            {
+                return Err(SnapshotError::Corrupt);
+            }
+            if self.place_resting(order, participant).is_none() {
+                return Err(SnapshotError::Corrupt);
+            }
+        }
+        
+        let links = reader.u32()?;
+        for _ in 0..links {
+            let link_id = reader.u64()?;
+            let first = OrderId(reader.u64()?);
+            let second = OrderId(reader.u64()?);
+            if !self.index.contains(first) || !self.index.contains(second) {
+                return Err(SnapshotError::Corrupt);
+            }
+            self.oco.link(link_id, first, second);
+        }
+        
+        self.book.set_sequence(sequence);
+        self.reference_price = (reference != 0).then_some(Price(reference));
+        self.phase = phase;
+        Ok(reader.consumed(data.len()))
+    }
+    
+    /// Get pool statistics.
+    pub fn pool_stats(&self) -> (usize, usize) {
+        (self.pool.active(), self.pool.capacity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use alloc::vec;
+    use super::*;
+    use crate::mmp::MmpConfig;
+    use crate::risk::RiskConfig;
+    use crate::fees::{FeeRate, FeeSchedule};
+    use crate::events::MatchEvents;
+    
+    fn create_engine() -> MatchingEngine {
+        MatchingEngine::new(SymbolId(1), 10, Price::ZERO) // 1024 orders
+    }
+    
+    #[test]
+    fn test_simple_match() {
+        let mut engine = create_engine();
+        
+        // Place sell order
+        let sell = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 0,
+        );
+        let result = engine.submit_order(sell, 1);
+        assert!(matches!(result, OrderResult::Resting { .. }));
+        
+        // Place matching buy order
+        let buy = Order::new(
+            OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 2,
+        );
+        let result = engine.submit_order(buy, 2);
+        
+        match result {
+            OrderResult::Filled { fills } => {
+                assert_eq!(fills.len(), 1);
+                assert_eq!(fills[0].quantity.0, 100);
+                assert_eq!(fills[0].price, Price::from_ticks(100));
+                assert_eq!(fills[0].maker_order_id.0, 1);
+                assert_eq!(fills[0].taker_order_id.0, 2);
+            }
+            _ => panic!("Expected Filled, got {:?}", result),
+        }
+    }
+    
+    #[test]
+    fn test_partial_fill() {
+        let mut engine = create_engine();
+        
+        // Place sell order for 50
+        let sell = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(50), 0,
+        );
+        engine.submit_order(sell, 1);
+        
+        // Place buy order for 100
+        let buy = Order::new(
+            OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 2,
+        );
+        let result = engine.submit_order(buy, 2);
+        
+        match result {
+            OrderResult::PartialFill { fills, resting_qty, .. } => {
+                assert_eq!(fills.len(), 1);
+                assert_eq!(fills[0].quantity.0, 50);
+                assert_eq!(resting_qty.0, 50);
+            }
+            _ => panic!("Expected PartialFill, got {:?}", result),
+        }
+    }
+    
+    #[test]
+    fn test_price_time_priority() {
+        let mut engine = create_engine();
+        
+        // Place two sell orders at same price
+        let sell1 = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(50), 0,
+        );
+        engine.submit_order(sell1, 1);
+        
+        let sell2 = Order::new(
+            OrderId(2), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(50), 0,
+        );
+        engine.submit_order(sell2, 2);
+        
+        // Buy should match with first sell (time priority)
+        let buy = Order::new(
+            OrderId(3), SymbolId(1), Side::Buy, OrderType::Limit,
+            Price::from_ticks(100), Quantity(50), 3,
+        );
+        let result = engine.submit_order(buy, 3);
+        
+        match result {
+            OrderResult::Filled { fills } => {
+                assert_eq!(fills[0].maker_order_id.0, 1); // First order matched
+            }
+            _ => panic!("Expected Filled"),
+        }
+    }
+    
+    #[test]
+    fn test_ioc_no_match() {
+        let mut engine = create_engine();
+        
+        // IOC order with no matching liquidity
+        let order = Order::new(
+            OrderId(1), SymbolId(1), Side::Buy, OrderType::IOC,
+            Price::from_ticks(100), Quantity(100), 0,
+        );
+        let result = engine.submit_order(order, 1);
+        
+        match result {
+            OrderResult::Cancelled { filled_qty, .. } => {
+                assert_eq!(filled_qty.0, 0);
+            }
+            _ => panic!("Expected Cancelled"),
+        }
+    }
+    
+    #[test]
+    fn test_post_only_reject() {
+        let mut engine = create_engine();
+        
+        // Place sell at 100
+        let sell = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 0,
+        );
+        engine.submit_order(sell, 1);
+        
+        // Post-only buy at 100 should be rejected (would match)
+        let buy = Order::new(
+            OrderId(2), SymbolId(1), Side::Buy, OrderType::PostOnly,
+            Price::from_ticks(100), Quantity(100), 2,
+        );
+        let result = engine.submit_order(buy, 2);
+        
+        assert!(matches!(result, OrderResult::Rejected { reason: RejectReason::PostOnlyWouldMatch }));
+    }
+    
+    #[test]
+    fn test_market_sweeps_levels() {
+        let mut engine = create_engine();
+        engine.set_max_sweep_levels(2);
+        
+        for (id, ticks) in [(1, 100), (2, 101), (3, 102)] {
+            let sell = Order::new(
+                OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit,
+                Price::from_ticks(ticks), Quantity(10), 0,
+            );
+            engine.submit_order(sell, id);
+        }
+        
+        // Price is ignored; only two levels may be consumed
+        let buy = Order::new(
+            OrderId(4), SymbolId(1), Side::Buy, OrderType::Market,
+            Price::ZERO, Quantity(25), 0,
+        );
+        match engine.submit_order(buy, 4) {
+            OrderResult::Cancelled { filled_qty, fills } => {
+                assert_eq!(filled_qty.0, 20);
+                assert_eq!(fills[0].price, Price::from_ticks(100));
+                assert_eq!(fills[1].price, Price::from_ticks(101));
+            }
+            result => panic!("Expected Cancelled, got {:?}", result),
+        }
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(102)));
+        
+        let sell = Order::new(
+            OrderId(5), SymbolId(1), Side::Sell, OrderType::Market,
+            Price::ZERO, Quantity(5), 0,
+        );
+        assert!(matches!(
+            engine.submit_order(sell, 5),
+            OrderResult::Rejected { reason: RejectReason::NoLiquidity }
+        ));
+    }
+    
+    #[test]
+    fn test_iceberg_replenish() {
+        let mut engine = create_engine();
+        
+        // Iceberg: 100 total, 30 shown
+        let iceberg = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 0,
+        ).with_display_qty(Quantity(30));
+        engine.submit_order(iceberg, 1);
+        let plain = Order::new(
+            OrderId(2), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(20), 0,
+        );
+        engine.submit_order(plain, 2);
+        assert_eq!(engine.book.asks.best_level().unwrap().total_qty.0, 50);
+        
+        // Takes the first clip, then the plain order now ahead of the next clip
+        let buy = Order::new(
+            OrderId(3), SymbolId(1), Side::Buy, OrderType::IOC,
+            Price::from_ticks(100), Quantity(60), 0,
+        );
+        match engine.submit_order(buy, 3) {
+            OrderResult::Filled { fills } => {
+                let makers: Vec<(u64, u64)> = fills.iter().map(|f| (f.maker_order_id.0, f.quantity.0)).collect();
+                assert_eq!(makers, vec![(1, 30), (2, 20), (1, 10)]);
+            }
+            result => panic!("Expected Filled, got {:?}", result),
+        }
+        
+        // 60 left on the iceberg, only the current 20-lot clip displayed
+        assert_eq!(engine.book.asks.best_level().unwrap().total_qty.0, 20);
+        assert_eq!(engine.book.asks.total_qty().0, 20);
+    }
+    
+    fn rest(engine: &mut MatchingEngine, id: u64, side: Side, ticks: u64, qty: u64) -> OrderHandle {
+        let order = Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(qty), 0,
+        );
+        match engine.submit_order(order, id) {
+            OrderResult::Resting { handle } => handle,
+            result => panic!("Expected Resting, got {:?}", result),
+        }
+    }
+    
+    fn maker_ids(result: OrderResult) -> Vec<(u64, u64)> {
+        match result {
+            OrderResult::Filled { fills } | OrderResult::PartialFill { fills, .. } => {
+                fills.iter().map(|f| (f.maker_order_id.0, f.quantity.0)).collect()
+            }
+            result => panic!("Expected fills, got {:?}", result),
+        }
+    }
+    
+    #[test]
+    fn test_modify_priority() {
+        let mut engine = create_engine();
+        let first = rest(&mut engine, 1, Side::Sell, 100, 50);
+        rest(&mut engine, 2, Side::Sell, 100, 50);
+        
+        // Size down in place: keeps its place in the queue
+        assert!(matches!(
+            engine.modify_order(first, Price::from_ticks(100), Quantity(20)),
+            OrderResult::Resting { handle } if handle == first
+        ));
+        assert_eq!(engine.book.asks.best_level().unwrap().total_qty.0, 70);
+        
+        let buy = Order::new(OrderId(3), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(30), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 3)), vec![(1, 20), (2, 10)]);
+        
+        // Size up: goes behind order 4
+        let handle = rest(&mut engine, 5, Side::Buy, 90, 10);
+        rest(&mut engine, 4, Side::Buy, 90, 10);
+        let handle = match engine.modify_order(handle, Price::from_ticks(90), Quantity(15)) {
+            OrderResult::Resting { handle } => handle,
+            result => panic!("Expected Resting, got {:?}", result),
+        };
+        assert_eq!(engine.book.bids.best_level().unwrap().total_qty.0, 25);
+        assert_eq!(engine.get_order(handle).unwrap().order_id, OrderId(5));
+        
+        let sell = Order::new(OrderId(6), SymbolId(1), Side::Sell, OrderType::IOC, Price::from_ticks(90), Quantity(25), 0);
+        assert_eq!(maker_ids(engine.submit_order(sell, 6)), vec![(4, 10), (5, 15)]);
+    }
+    
+    #[test]
+    fn test_modify_reprice_crosses() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Buy, 99, 10);
+        let ask = rest(&mut engine, 2, Side::Sell, 101, 10);
+        
+        assert_eq!(maker_ids(engine.modify_order(ask, Price::from_ticks(99), Quantity(10))), vec![(1, 10)]);
+        assert_eq!(engine.book.bids.order_count() + engine.book.asks.order_count(), 0);
+        assert!(engine.book.asks.is_empty());
+        assert_eq!(engine.pool.active(), 0);
+        
+        assert!(matches!(
+            engine.modify_order(OrderHandle::INVALID, Price::from_ticks(99), Quantity(1)),
+            OrderResult::Rejected { reason: RejectReason::UnknownOrder }
+        ));
+    }
+    
+    #[test]
+    fn test_rejected_modify_keeps_order() {
+        let mut engine = create_engine();
+        let alice = ParticipantId(1);
+        engine.set_price_band(Some(PriceBand::Ticks(5)));
+        engine.set_reference_price(Price::from_ticks(100));
+        engine.risk.set_defaults(RiskConfig { max_open_orders: 2, max_order_qty: Quantity(50), ..RiskConfig::UNLIMITED });
+        let sell = |id| Order::new(OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        let first = match engine.submit_order_as(alice, sell(1), 1) {
+            OrderResult::Resting { handle } => handle,
+            result => panic!("Expected Resting, got {:?}", result),
+        };
+        engine.submit_order_as(alice, sell(2), 2);
+        
+        // Out of band, then over the size limit: the original stays queued
+        assert!(matches!(
+            engine.modify_order(first, Price::from_ticks(110), Quantity(10)),
+            OrderResult::Rejected { reason: RejectReason::PriceOutOfBand }
+        ));
+        assert!(matches!(
+            engine.modify_order(first, Price::from_ticks(100), Quantity(60)),
+            OrderResult::Rejected { reason: RejectReason::OrderTooLarge }
+        ));
+        assert_eq!(engine.handle_of(OrderId(1)), Some(first));
+        assert_eq!(engine.pool.active(), 2);
+        engine.verify_invariants();
+        
+        // Short sale repriced at the last trade
+        engine.set_short_sale_restriction(true);
+        let short = Order::new(OrderId(3), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(102), Quantity(10), 0)
+            .with_short_sell();
+        let short = match engine.submit_order(short, 3) {
+            OrderResult::Resting { handle } => handle,
+            result => panic!("Expected Resting, got {:?}", result),
+        };
+        let buy = Order::new(OrderId(4), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(5), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 4)), vec![(1, 5)]);
+        assert!(matches!(
+            engine.modify_order(short, Price::from_ticks(100), Quantity(10)),
+            OrderResult::Rejected { reason: RejectReason::ShortSaleRestricted }
+        ));
+        assert_eq!(engine.handle_of(OrderId(3)), Some(short));
+        
+        // The owner's open-order limit counts the original only once
+        assert!(matches!(engine.modify_order(first, Price::from_ticks(101), Quantity(5)), OrderResult::Resting { .. }));
+        
+        // Order 2 at 100 trades ahead of the repriced order
+        let buy = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(15), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 5)), vec![(2, 10), (1, 5)]);
+        engine.verify_invariants();
+    }
+    
+    #[test]
+    fn test_cancel_by_id() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Sell, 100, 10);
+        let handle = rest(&mut engine, 2, Side::Sell, 101, 10);
+        assert_eq!(engine.handle_of(OrderId(2)), Some(handle));
+        
+        // Live ids cannot be reused
+        let dup = Order::new(OrderId(2), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(90), Quantity(1), 0);
+        assert!(matches!(
+            engine.submit_order(dup, 3),
+            OrderResult::Rejected { reason: RejectReason::DuplicateOrderId }
+        ));
+        
+        assert_eq!(engine.cancel_by_id(OrderId(2)).map(|o| o.order_id), Some(OrderId(2)));
+        assert!(engine.cancel_by_id(OrderId(2)).is_none());
+        assert!(engine.cancel_by_id(OrderId(99)).is_none());
+        
+        // Fully filled makers leave the index
+        let buy = Order::new(OrderId(3), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(10), 0);
+        engine.submit_order(buy, 4);
+        assert!(engine.handle_of(OrderId(1)).is_none());
+        assert!(engine.cancel_by_id(OrderId(1)).is_none());
+    }
+    
+    #[test]
+    fn test_gtd_expiry() {
+        let mut engine = create_engine();
+        let gtd = |id, ticks, expire_at| Order::new(
+            OrderId(id), SymbolId(1), Side::Buy, OrderType::GTD,
+            Price::from_ticks(ticks), Quantity(10), 0,
+        ).with_expiry(expire_at);
+        
+        assert!(matches!(
+            engine.submit_order(gtd(1, 100, 1_000), 1_000),
+            OrderResult::Rejected { reason: RejectReason::InvalidExpiry }
+        ));
+        assert!(matches!(engine.submit_order(gtd(2, 101, 5_000_000), 1_000), OrderResult::Resting { .. }));
+        assert!(matches!(engine.submit_order(gtd(3, 100, 9_000_000), 1_000), OrderResult::Resting { .. }));
+        rest(&mut engine, 4, Side::Buy, 99, 10);
+        
+        assert_eq!(engine.expire_orders(4_999_999, |_| {}), 0);
+        let mut expired = Vec::new();
+        assert_eq!(engine.expire_orders(5_000_000, |o| expired.push(o.order_id.0)), 1);
+        assert_eq!(expired, vec![2]);
+        assert_eq!(engine.book.best_bid(), Some(Price::from_ticks(100)));
+        assert!(engine.handle_of(OrderId(2)).is_none());
+        
+        // Cancelled before expiry: nothing fires
+        engine.cancel_by_id(OrderId(3));
+        assert_eq!(engine.expire_orders(u64::MAX, |_| {}), 0);
+        assert_eq!(engine.book.bids.order_count(), 1);
+    }
+    
+    #[test]
+    fn test_fok_walks_depth() {
+        let mut engine = create_engine();
+        for (id, ticks) in [(1, 100), (2, 101), (3, 102)] {
+            rest(&mut engine, id, Side::Sell, ticks, 10);
+        }
+        let fok = |id, ticks, qty| Order::new(
+            OrderId(id), SymbolId(1), Side::Buy, OrderType::FOK,
+            Price::from_ticks(ticks), Quantity(qty), 0,
         );
-        let result = engine.submit_order(buy, 2);
         
-        match result {
-            OrderResult::PartialFill { fills, resting_qty, .. } => {
-                assert_eq!(fills.len(), 1);
-                assert_eq!(fills[0].quantity.0, 50);
-                assert_eq!(resting_qty.0, 50);
+        // Only two levels cross
+        assert!(matches!(
+            engine.submit_order(fok(4, 101, 25), 4),
+            OrderResult::Rejected { reason: RejectReason::InsufficientLiquidity }
+        ));
+        assert_eq!(maker_ids(engine.submit_order(fok(5, 102, 25), 5)), vec![(1, 10), (2, 10), (3, 5)]);
+        
+        // More makers than one order can report
+        let mut engine = create_engine();
+        for id in 1..=MAX_FILLS_PER_ORDER as u64 + 1 {
+            rest(&mut engine, id, Side::Sell, 100, 1);
+        }
+        let too_many = MAX_FILLS_PER_ORDER as u64 + 1;
+        assert!(matches!(engine.submit_order(fok(100, 100, too_many), 100), OrderResult::Rejected { .. }));
+        assert!(matches!(
+            engine.submit_order(fok(101, 100, too_many - 1), 101),
+            OrderResult::Filled { fills } if fills.len() == MAX_FILLS_PER_ORDER
+        ));
+    }
+    
+    #[test]
+    fn test_trailing_stop_triggers() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Buy, 95, 10);
+        rest(&mut engine, 2, Side::Buy, 99, 10);
+        rest(&mut engine, 3, Side::Buy, 100, 2);
+        rest(&mut engine, 4, Side::Sell, 104, 10);
+        
+        let trade = |engine: &mut MatchingEngine, id, side, ticks, qty| {
+            let order = Order::new(OrderId(id), SymbolId(1), side, OrderType::IOC, Price::from_ticks(ticks), Quantity(qty), 0);
+            engine.submit_order(order, id);
+        };
+        
+        // Sell 10 at market if price falls 5 ticks from its high
+        let stop = Order::new(OrderId(10), SymbolId(1), Side::Sell, OrderType::Market, Price::ZERO, Quantity(10), 0);
+        engine.submit_trailing_stop(stop, Price::from_ticks(5)).unwrap();
+        
+        trade(&mut engine, 5, Side::Buy, 104, 1);
+        trade(&mut engine, 6, Side::Sell, 100, 1);
+        assert!(engine.next_triggered(7).is_none());
+        assert_eq!(engine.stops.get(OrderId(10)).unwrap().stop_price, Some(Price::from_ticks(99)));
+        
+        // Trades at 100 then 99: fires on the second
+        trade(&mut engine, 8, Side::Sell, 99, 2);
+        let (order, result) = engine.next_triggered(9).unwrap();
+        assert_eq!(order.order_id, OrderId(10));
+        assert_eq!(maker_ids(result), vec![(2, 9), (1, 1)]);
+        assert!(engine.next_triggered(10).is_none());
+        assert!(engine.stops.is_empty());
+    }
+    
+    #[test]
+    fn test_oco_fill_cancels_partner() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Sell, 105, 10);
+        rest(&mut engine, 2, Side::Sell, 101, 10);
+        rest(&mut engine, 3, Side::Sell, 102, 10);
+        engine.link_oco(7, OrderId(1), OrderId(2)).unwrap();
+        assert_eq!(engine.link_oco(8, OrderId(2), OrderId(3)), Err(RejectReason::AlreadyLinked));
+        assert_eq!(engine.link_oco(8, OrderId(3), OrderId(99)), Err(RejectReason::UnknownOrder));
+        
+        // Partial fill on 2 is enough to pull 1
+        let buy = Order::new(OrderId(4), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(4), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 4)), vec![(2, 4)]);
+        assert_eq!(engine.next_oco_cancel().map(|o| o.order_id), Some(OrderId(1)));
+        assert!(engine.next_oco_cancel().is_none());
+        assert!(engine.handle_of(OrderId(1)).is_none());
+        assert!(engine.oco.is_empty());
+        assert_eq!(engine.book.asks.order_count(), 2);
+        assert_eq!(engine.book.asks.total_qty().0, 16);
+        
+        // Cancelling a leg only dissolves the link
+        engine.link_oco(9, OrderId(2), OrderId(3)).unwrap();
+        engine.cancel_by_id(OrderId(3));
+        assert!(engine.oco.is_empty());
+        let buy = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(6), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 5)), vec![(2, 6)]);
+        assert!(engine.next_oco_cancel().is_none());
+    }
+    
+    #[test]
+    fn test_min_qty() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Sell, 100, 10);
+        rest(&mut engine, 2, Side::Sell, 101, 10);
+        let buy = |id, ticks, min_qty| Order::new(
+            OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(50), 0,
+        ).with_min_qty(Quantity(min_qty));
+        
+        // Only 10 crosses at 100: nothing executes, nothing rests
+        assert!(matches!(
+            engine.submit_order(buy(3, 100, 15), 3),
+            OrderResult::Cancelled { filled_qty, .. } if filled_qty.is_zero()
+        ));
+        assert_eq!(engine.book.asks.total_qty().0, 20);
+        assert!(engine.book.bids.is_empty());
+        
+        // Met across two levels; the remainder rests as usual
+        match engine.submit_order(buy(4, 101, 15), 4) {
+            OrderResult::PartialFill { resting_qty, handle, .. } => {
+                assert_eq!(resting_qty.0, 30);
+                assert!(engine.get_order(handle).unwrap().min_qty().is_zero());
             }
-            _ => panic!("Expected PartialFill, got {:?}", result),
+            result => panic!("Expected PartialFill, got {:?}", result),
         }
     }
     
     #[test]
-    fn test_price_time_priority() {
+    fn test_call_auction_uncross() {
         let mut engine = create_engine();
+        engine.start_auction();
+        rest(&mut engine, 1, Side::Buy, 102, 20);
+        rest(&mut engine, 2, Side::Sell, 100, 15);
+        rest(&mut engine, 3, Side::Buy, 101, 10);
+        rest(&mut engine, 4, Side::Sell, 101, 25);
+        rest(&mut engine, 5, Side::Sell, 103, 10);
+        assert_eq!(engine.book.best_bid(), Some(Price::from_ticks(102)));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(100)));
         
-        // Place two sell orders at same price
-        let sell1 = Order::new(
-            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
-            Price::from_ticks(100), Quantity(50), 0,
+        let ioc = Order::new(OrderId(6), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(103), Quantity(1), 0);
+        assert!(matches!(
+            engine.submit_order(ioc, 6),
+            OrderResult::Rejected { reason: RejectReason::AuctionInProgress }
+        ));
+        
+        // 101 trades 30 (bids 30, asks 40); 102 trades only 20
+        let indicative = engine.indicative_uncross().unwrap();
+        assert_eq!((indicative.price, indicative.volume.0), (Price::from_ticks(101), 30));
+        
+        let mut fills = Vec::new();
+        let uncross = engine.uncross(7, |f| fills.push((f.maker_order_id.0, f.taker_order_id.0, f.quantity.0))).unwrap();
+        assert_eq!(uncross.volume.0, 30);
+        assert_eq!(fills, vec![(1, 2, 15), (1, 4, 5), (3, 4, 10)]);
+        assert_eq!(engine.phase(), TradingPhase::Continuous);
+        
+        // Left: 10 @ 101 and 10 @ 103 offered, no bids
+        assert!(engine.book.bids.is_empty());
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(101)));
+        assert_eq!(engine.book.asks.total_qty().0, 20);
+        assert_eq!(engine.pool.active(), 2);
+        assert!(engine.uncross(8, |_| {}).is_none());
+    }
+    
+    #[test]
+    fn test_price_band() {
+        let mut engine = create_engine();
+        engine.set_price_band(Some(PriceBand::Ticks(5)));
+        let limit = |id, side, ticks| Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(10), 0,
         );
-        engine.submit_order(sell1, 1);
         
-        let sell2 = Order::new(
-            OrderId(2), SymbolId(1), Side::Sell, OrderType::Limit,
-            Price::from_ticks(100), Quantity(50), 0,
+        // No reference yet: anything goes
+        rest(&mut engine, 1, Side::Sell, 120, 10);
+        engine.set_reference_price(Price::from_ticks(100));
+        assert!(matches!(
+            engine.submit_order(limit(2, Side::Buy, 94), 2),
+            OrderResult::Rejected { reason: RejectReason::PriceOutOfBand }
+        ));
+        rest(&mut engine, 3, Side::Sell, 105, 10);
+        
+        // A trade re-centres the band
+        let buy = Order::new(OrderId(4), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(105), Quantity(1), 0);
+        engine.submit_order(buy, 4);
+        assert_eq!(engine.reference_price(), Some(Price::from_ticks(105)));
+        assert!(matches!(engine.submit_order(limit(5, Side::Sell, 110), 5), OrderResult::Resting { .. }));
+        assert!(matches!(
+            engine.submit_order(limit(6, Side::Buy, 99), 6),
+            OrderResult::Rejected { reason: RejectReason::PriceOutOfBand }
+        ));
+    }
+    
+    #[test]
+    fn test_instrument_spec() {
+        // Crypto-style grid: 1 raw unit ticks, lots of 10
+        let spec = InstrumentSpec { lot_size: Quantity(10), ..InstrumentSpec::with_tick_size(1) };
+        let mut engine = MatchingEngine::with_spec(SymbolId(2), 10, Price(50_000), spec);
+        let order = |id, side, price, qty| Order::new(
+            OrderId(id), SymbolId(2), side, OrderType::Limit, Price(price), Quantity(qty), 0,
         );
-        engine.submit_order(sell2, 2);
         
-        // Buy should match with first sell (time priority)
-        let buy = Order::new(
-            OrderId(3), SymbolId(1), Side::Buy, OrderType::Limit,
-            Price::from_ticks(100), Quantity(50), 3,
+        assert!(matches!(
+            engine.submit_order(order(1, Side::Sell, 50_001, 15), 1),
+            OrderResult::Rejected { reason: RejectReason::InvalidQuantity }
+        ));
+        engine.submit_order(order(2, Side::Sell, 50_001, 20), 2);
+        engine.submit_order(order(3, Side::Sell, 50_002, 20), 3);
+        assert_eq!(engine.book.best_ask(), Some(Price(50_001)));
+        assert_eq!(maker_ids(engine.submit_order(order(4, Side::Buy, 50_002, 30), 4)), vec![(2, 20), (3, 10)]);
+        
+        // Default spec keeps the global cent grid
+        let mut engine = create_engine();
+        let off_tick = Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price(10_050), Quantity(1), 0);
+        assert!(matches!(
+            engine.submit_order(off_tick, 1),
+            OrderResult::Rejected { reason: RejectReason::InvalidPrice }
+        ));
+    }
+    
+    #[test]
+    fn test_mass_cancel() {
+        let mut engine = create_engine();
+        let limit = |id, side, ticks| Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(10), 0,
         );
-        let result = engine.submit_order(buy, 3);
+        let (alice, bob) = (ParticipantId(1), ParticipantId(2));
+        engine.submit_order_as(alice, limit(1, Side::Buy, 99), 1);
+        engine.submit_order_as(bob, limit(2, Side::Buy, 99), 2);
+        engine.submit_order_as(alice, limit(3, Side::Sell, 101), 3);
+        engine.submit_order(limit(4, Side::Sell, 102), 4);
         
-        match result {
-            OrderResult::Filled { fills } => {
-                assert_eq!(fills[0].maker_order_id.0, 1); // First order matched
-            }
-            _ => panic!("Expected Filled"),
+        // Modify keeps the owner
+        let handle = engine.handle_of(OrderId(1)).unwrap();
+        engine.modify_order(handle, Price::from_ticks(100), Quantity(10));
+        assert_eq!(engine.owner_of(OrderId(1)), Some(alice));
+        
+        let mut cancelled = Vec::new();
+        assert_eq!(engine.cancel_all(alice, |o| cancelled.push(o.order_id.0)), 2);
+        cancelled.sort();
+        assert_eq!(cancelled, vec![1, 3]);
+        assert_eq!(engine.book.best_bid(), Some(Price::from_ticks(99)));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(102)));
+        assert_eq!(engine.cancel_all(alice, |_| {}), 0);
+        
+        let mut cancelled = Vec::new();
+        assert_eq!(engine.cancel_all_symbol(|o| cancelled.push(o.order_id.0)), 2);
+        assert_eq!(cancelled, vec![2, 4]);
+        assert!(engine.book.is_empty());
+        assert_eq!(engine.pool.active(), 0);
+        assert!(engine.handle_of(OrderId(2)).is_none());
+    }
+    
+    #[test]
+    fn test_mmp_trip_pulls_quotes() {
+        let mut engine = create_engine();
+        let maker = ParticipantId(1);
+        engine.mmp.set(maker, MmpConfig { window_ns: 1_000, max_fills: 2, ..MmpConfig::default() });
+        let limit = |id, side, ticks| Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(10), 0,
+        );
+        engine.submit_order_as(maker, limit(1, Side::Sell, 100), 1);
+        engine.submit_order_as(maker, limit(2, Side::Sell, 101), 2);
+        engine.submit_order_as(maker, limit(3, Side::Buy, 95), 3);
+        
+        // Second fill trips: the rest of order 2 and order 3 are pulled
+        let taker = Order::new(OrderId(10), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(15), 0);
+        let result = engine.submit_order(taker, 4);
+        assert_eq!(maker_ids(result), vec![(1, 10), (2, 5)]);
+        assert!(engine.mmp.is_tripped(maker));
+        let mut pulled = Vec::new();
+        while let Some(order) = engine.next_mmp_cancel() {
+            pulled.push((order.order_id.0, order.remaining_qty.0));
         }
+        pulled.sort();
+        assert_eq!(pulled, vec![(2, 5), (3, 10)]);
+        assert!(engine.book.is_empty());
+        
+        assert!(matches!(
+            engine.submit_order_as(maker, limit(4, Side::Sell, 100), 5),
+            OrderResult::Rejected { reason: RejectReason::MmpTripped }
+        ));
+        assert!(engine.mmp.reset(maker));
+        assert!(matches!(engine.submit_order_as(maker, limit(4, Side::Sell, 100), 6), OrderResult::Resting { .. }));
     }
     
     #[test]
-    fn test_ioc_no_match() {
+    fn test_ownership_checks() {
         let mut engine = create_engine();
+        let (alice, bob) = (ParticipantId(1), ParticipantId(2));
+        let order = Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        engine.submit_order_as(alice, order, 1);
         
-        // IOC order with no matching liquidity
-        let order = Order::new(
-            OrderId(1), SymbolId(1), Side::Buy, OrderType::IOC,
-            Price::from_ticks(100), Quantity(100), 0,
-        );
-        let result = engine.submit_order(order, 1);
+        assert_eq!(engine.cancel_by_id_as(bob, OrderId(1)).unwrap_err(), RejectReason::NotOwner);
+        assert!(matches!(
+            engine.modify_by_id_as(bob, OrderId(1), Price::from_ticks(101), Quantity(10)),
+            OrderResult::Rejected { reason: RejectReason::NotOwner }
+        ));
+        assert!(matches!(
+            engine.modify_by_id_as(alice, OrderId(1), Price::from_ticks(101), Quantity(10)),
+            OrderResult::Resting { .. }
+        ));
+        assert_eq!(engine.cancel_by_id_as(alice, OrderId(1)).unwrap().order_id, OrderId(1));
+        assert_eq!(engine.cancel_by_id_as(alice, OrderId(1)).unwrap_err(), RejectReason::UnknownOrder);
+    }
+    
+    #[test]
+    fn test_pre_trade_risk() {
+        let mut engine = create_engine();
+        let alice = ParticipantId(1);
+        engine.risk.set_defaults(RiskConfig { max_open_orders: 1, ..RiskConfig::UNLIMITED });
+        let limit = |id| Order::new(OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
         
-        match result {
-            OrderResult::Cancelled { filled_qty, .. } => {
-                assert_eq!(filled_qty.0, 0);
-            }
-            _ => panic!("Expected Cancelled"),
+        assert!(matches!(engine.submit_order_as(alice, limit(1), 1), OrderResult::Resting { .. }));
+        assert!(matches!(
+            engine.submit_order_as(alice, limit(2), 2),
+            OrderResult::Rejected { reason: RejectReason::TooManyOpenOrders }
+        ));
+        engine.cancel_by_id(OrderId(1));
+        assert!(matches!(engine.submit_order_as(alice, limit(2), 3), OrderResult::Resting { .. }));
+        
+        engine.risk.set_enabled(alice, false);
+        engine.cancel_by_id(OrderId(2));
+        assert!(matches!(
+            engine.submit_order_as(alice, limit(3), 4),
+            OrderResult::Rejected { reason: RejectReason::ParticipantDisabled }
+        ));
+    }
+    
+    #[test]
+    fn test_fill_fees() {
+        let mut engine = create_engine();
+        engine.set_fee_schedule(FeeSchedule { maker: FeeRate::Bps(-1), taker: FeeRate::Bps(3) });
+        rest(&mut engine, 1, Side::Sell, 1_000, 10);
+        
+        let taker = Order::new(OrderId(2), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(1_000), Quantity(10), 0);
+        let OrderResult::Filled { fills } = engine.submit_order(taker, 1) else {
+            panic!("expected fill");
+        };
+        // Notional 1_000_000 raw units
+        assert_eq!((fills[0].maker_fee, fills[0].taker_fee), (-100, 300));
+    }
+    
+    #[derive(Default)]
+    struct Recorder {
+        log: Vec<(&'static str, u64, u64)>,
+    }
+    
+    impl MatchEvents for Recorder {
+        fn on_accept(&mut self, order: &Order) {
+            self.log.push(("accept", order.order_id.0, order.remaining_qty.0));
+        }
+        fn on_fill(&mut self, fill: &Fill) {
+            self.log.push(("fill", fill.maker_order_id.0, fill.quantity.0));
+        }
+        fn on_cancel(&mut self, order: &Order) {
+            self.log.push(("cancel", order.order_id.0, order.remaining_qty.0));
+        }
+        fn on_reject(&mut self, order: &Order, _reason: RejectReason) {
+            self.log.push(("reject", order.order_id.0, 0));
+        }
+        fn on_bbo_change(&mut self, bbo: &BboChanged) {
+            self.log.push(("bbo", bbo.bid.map_or(0, Price::to_ticks), bbo.ask.map_or(0, Price::to_ticks)));
+            self.log.push(("bbo_qty", bbo.bid_qty.0, bbo.ask_qty.0));
         }
     }
     
     #[test]
-    fn test_post_only_reject() {
+    fn test_event_callbacks() {
         let mut engine = create_engine();
+        let mut events = Recorder::default();
+        let order = |id, side, order_type, ticks, qty| Order::new(
+            OrderId(id), SymbolId(1), side, order_type, Price::from_ticks(ticks), Quantity(qty), 0,
+        );
         
-        // Place sell at 100
-        let sell = Order::new(
-            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
-            Price::from_ticks(100), Quantity(100), 0,
+        engine.submit_order_with(ParticipantId::NONE, order(1, Side::Sell, OrderType::Limit, 100, 10), 1, &mut events);
+        engine.submit_order_with(ParticipantId::NONE, order(2, Side::Buy, OrderType::IOC, 100, 15), 2, &mut events);
+        engine.submit_order_with(ParticipantId::NONE, order(2, Side::Buy, OrderType::Limit, 0, 15), 3, &mut events);
+        assert_eq!(events.log, vec![
+            ("accept", 1, 10),
+            ("bbo", 0, 100),
+            ("bbo_qty", 0, 10),
+            ("accept", 2, 15),
+            ("fill", 1, 10),
+            ("cancel", 2, 5),
+            ("bbo", 0, 0),
+            ("bbo_qty", 0, 0),
+            ("reject", 2, 0),
+        ]);
+    }
+    
+    #[test]
+    fn test_bbo_change_events() {
+        let mut engine = create_engine();
+        let mut events = Recorder::default();
+        let order = |id, side, ticks, qty| Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0,
         );
-        engine.submit_order(sell, 1);
         
-        // Post-only buy at 100 should be rejected (would match)
-        let buy = Order::new(
-            OrderId(2), SymbolId(1), Side::Buy, OrderType::PostOnly,
-            Price::from_ticks(100), Quantity(100), 2,
+        engine.submit_order_with(ParticipantId::NONE, order(1, Side::Buy, 99, 10), 1, &mut events);
+        // Behind the best: no change
+        engine.submit_order_with(ParticipantId::NONE, order(2, Side::Buy, 98, 10), 2, &mut events);
+        // Joins the best: quantity change only
+        engine.submit_order_with(ParticipantId::NONE, order(3, Side::Buy, 99, 5), 3, &mut events);
+        // Changes made without a sink are reported by the next call with one
+        rest(&mut engine, 4, Side::Sell, 101, 7);
+        let handle = engine.handle_of(OrderId(1)).unwrap();
+        engine.cancel_order_with(handle, &mut events);
+        
+        let bbo: Vec<_> = events.log.iter().filter(|e| e.0.starts_with("bbo")).copied().collect();
+        assert_eq!(bbo, vec![
+            ("bbo", 99, 0),
+            ("bbo_qty", 10, 0),
+            ("bbo", 99, 0),
+            ("bbo_qty", 15, 0),
+            ("bbo", 99, 101),
+            ("bbo_qty", 5, 7),
+        ]);
+        assert_eq!(engine.bbo(), BboChanged {
+            bid: Some(Price::from_ticks(99)),
+            bid_qty: Quantity(5),
+            ask: Some(Price::from_ticks(101)),
+            ask_qty: Quantity(7),
+        });
+    }
+    
+    #[test]
+    fn test_l3_export() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Buy, 99, 10);
+        rest(&mut engine, 2, Side::Buy, 100, 20);
+        rest(&mut engine, 3, Side::Buy, 100, 30);
+        rest(&mut engine, 4, Side::Sell, 101, 5);
+        
+        let bids: Vec<(u64, u64, u64)> = engine
+            .resting_orders(Side::Buy)
+            .map(|o| (o.price.to_ticks(), o.order_id.0, o.remaining_qty.0))
+            .collect();
+        assert_eq!(bids, vec![(100, 2, 20), (100, 3, 30), (99, 1, 10)]);
+        assert_eq!(engine.resting_orders(Side::Sell).count(), 1);
+    }
+    
+    #[test]
+    fn test_verify_invariants() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Buy, 99, 10);
+        rest(&mut engine, 2, Side::Buy, 100, 20);
+        rest(&mut engine, 3, Side::Sell, 101, 5);
+        rest(&mut engine, 4, Side::Sell, 103, 5);
+        engine.verify_invariants();
+        
+        // Sweep the best ask and part of the next level
+        let order = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(103), Quantity(7), 0);
+        assert!(matches!(engine.submit_order(order, 0), OrderResult::Filled { .. }));
+        engine.verify_invariants();
+    }
+    
+    #[test]
+    fn test_snapshot_restore() {
+        let mut engine = create_engine();
+        let alice = ParticipantId(7);
+        let iceberg = Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(50), 1)
+            .with_display_qty(Quantity(10));
+        assert!(matches!(engine.submit_order_as(alice, iceberg, 1), OrderResult::Resting { .. }));
+        rest(&mut engine, 2, Side::Buy, 100, 20);
+        rest(&mut engine, 3, Side::Sell, 105, 30);
+        rest(&mut engine, 4, Side::Sell, 106, 30);
+        engine.link_oco(9, OrderId(3), OrderId(4)).unwrap();
+        // Take part of the iceberg clip
+        let order = Order::new(OrderId(5), SymbolId(1), Side::Sell, OrderType::IOC, Price::from_ticks(100), Quantity(4), 2);
+        assert!(matches!(engine.submit_order(order, 2), OrderResult::Filled { .. }));
+        
+        let mut out = Writer::new();
+        engine.snapshot(&mut out);
+        let mut restored = create_engine();
+        assert_eq!(restored.restore(out.as_bytes()), Ok(out.len()));
+        restored.verify_invariants();
+        
+        let l3 = |engine: &MatchingEngine, side| engine.resting_orders(side).collect::<Vec<_>>();
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(l3(&restored, side), l3(&engine, side));
+        }
+        assert_eq!(restored.owner_of(OrderId(1)), Some(alice));
+        assert_eq!(restored.book.sequence(), engine.book.sequence());
+        assert_eq!(restored.reference_price(), engine.reference_price());
+        assert_eq!(restored.oco.get(OrderId(4)).map(|pair| pair.link_id), Some(9));
+        
+        assert_eq!(restored.restore(out.as_bytes()), Err(SnapshotError::NotEmpty));
+        assert_eq!(create_engine().restore(&out.as_bytes()[..40]), Err(SnapshotError::Truncated));
+        assert_eq!(MatchingEngine::new(SymbolId(2), 10, Price::ZERO).restore(out.as_bytes()), Err(SnapshotError::SymbolMismatch));
+    }
+    
+    #[test]
+    fn test_record_and_replay() {
+        let mut engine = create_engine();
+        engine.start_recording();
+        let alice = ParticipantId(3);
+        let order = |id, side, ticks, qty| Order::new(OrderId(id), SymbolId(1), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), id);
+        engine.submit_order_as(alice, order(1, Side::Buy, 100, 10), 1);
+        engine.submit_order(order(2, Side::Buy, 99, 10), 2);
+        engine.submit_order(order(3, Side::Sell, 102, 10), 3);
+        let handle = engine.handle_of(OrderId(1)).unwrap();
+        engine.modify_order(handle, Price::from_ticks(101), Quantity(8));
+        engine.submit_order(order(4, Side::Sell, 101, 5), 4);
+        engine.cancel_by_id(OrderId(2));
+        let log = engine.stop_recording().unwrap();
+        assert_eq!((log.len(), log.last_sequence()), (6, engine.book.sequence()));
+        
+        let mut out = Writer::new();
+        log.encode(&mut out);
+        let decoded = InputLog::decode(out.as_bytes()).unwrap();
+        let mut replayed = create_engine();
+        replayed.replay(decoded.iter());
+        
+        let (mut expected, mut actual) = (Writer::new(), Writer::new());
+        engine.snapshot(&mut expected);
+        replayed.snapshot(&mut actual);
+        assert_eq!(actual.as_bytes(), expected.as_bytes());
+        assert!(engine.input_log().is_none());
+    }
+    
+    #[test]
+    fn test_session_stats_on_fills() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Sell, 101, 5);
+        rest(&mut engine, 2, Side::Sell, 103, 5);
+        let order = Order::new(OrderId(3), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(103), Quantity(8), 7);
+        engine.submit_order(order, 7);
+        
+        let stats = engine.stats;
+        assert_eq!((stats.open, stats.high, stats.low), (Some(Price::from_ticks(101)), Some(Price::from_ticks(103)), Some(Price::from_ticks(101))));
+        assert_eq!((stats.last_price, stats.last_qty, stats.last_time), (Some(Price::from_ticks(103)), Quantity(3), 7));
+        assert_eq!((stats.volume, stats.trade_count), (Quantity(8), 2));
+    }
+    
+    #[test]
+    fn test_short_sale_restriction() {
+        let mut engine = create_engine();
+        engine.set_short_sale_restriction(true);
+        let short = |id, ticks| Order::new(
+            OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(ticks), Quantity(10), 0,
+        ).with_short_sell();
+        
+        // No trade yet: nothing to compare against
+        assert!(matches!(engine.submit_order(short(1, 100), 1), OrderResult::Resting { .. }));
+        let buy = Order::new(OrderId(2), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(5), 0);
+        engine.submit_order(buy, 2);
+        assert_eq!(engine.stats.last_price, Some(Price::from_ticks(100)));
+        
+        assert!(matches!(
+            engine.submit_order(short(3, 100), 3),
+            OrderResult::Rejected { reason: RejectReason::ShortSaleRestricted }
+        ));
+        assert!(matches!(engine.submit_order(short(4, 101), 4), OrderResult::Resting { .. }));
+        
+        // Long sales and a disarmed rule are unaffected
+        rest(&mut engine, 5, Side::Sell, 99, 10);
+        engine.set_short_sale_restriction(false);
+        assert!(matches!(engine.submit_order(short(6, 98), 6), OrderResult::Resting { .. }));
+    }
+    
+    #[test]
+    fn test_end_of_session() {
+        let mut engine = create_engine();
+        let day = |id, side, ticks| Order::new(
+            OrderId(id), SymbolId(1), side, OrderType::Day,
+            Price::from_ticks(ticks), Quantity(10), 0,
         );
-        let result = engine.submit_order(buy, 2);
         
-        assert!(matches!(result, OrderResult::Rejected { reason: RejectReason::PostOnlyWouldMatch }));
+        assert!(matches!(engine.submit_order(day(1, Side::Buy, 99), 1), OrderResult::Resting { .. }));
+        rest(&mut engine, 2, Side::Buy, 98, 10);
+        assert!(matches!(engine.submit_order(day(3, Side::Sell, 101), 3), OrderResult::Resting { .. }));
+        rest(&mut engine, 4, Side::Sell, 102, 10);
+        let buy = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(4), 0);
+        engine.submit_order(buy, 5);
+        
+        let mut expired = Vec::new();
+        assert_eq!(engine.end_of_session(|order| expired.push((order.order_id, order.remaining_qty))), 2);
+        assert_eq!(expired, [(OrderId(1), Quantity(10)), (OrderId(3), Quantity(6))]);
+        assert_eq!(engine.book.best_bid(), Some(Price::from_ticks(98)));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(102)));
+        assert_eq!(engine.pool.active(), 2);
+        assert_eq!(engine.stats.close, Some(Price::from_ticks(101)));
+        assert_eq!(engine.end_of_session(|_| {}), 0);
+    }
+    
+    #[test]
+    fn test_cancel_all_for_session() {
+        let mut engine = create_engine();
+        let (desk, other) = (SessionId(7), SessionId(8));
+        let mut handles = Vec::new();
+        for (id, side, ticks, session) in [
+            (1, Side::Buy, 99, desk),
+            (2, Side::Buy, 98, other),
+            (3, Side::Sell, 101, desk),
+            (4, Side::Sell, 102, desk),
+        ] {
+            let handle = rest(&mut engine, id, side, ticks, 10);
+            assert!(engine.tag_session(handle, session));
+            handles.push(handle);
+        }
+        
+        // Tags survive a cancel/replace and drop with a full fill
+        engine.modify_order(handles[0], Price::from_ticks(97), Quantity(10));
+        assert_eq!(engine.session_of(OrderId(1)), Some(desk));
+        let buy = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(101), Quantity(10), 0);
+        engine.submit_order(buy, 5);
+        
+        assert_eq!(engine.cancel_all_for_session(desk), [OrderId(1), OrderId(4)]);
+        assert_eq!(engine.book.best_bid(), Some(Price::from_ticks(98)));
+        assert!(engine.book.asks.is_empty());
+        assert_eq!(engine.pool.active(), 1);
+        assert!(engine.cancel_all_for_session(desk).is_empty());
+    }
+    
+    #[test]
+    fn test_reduce_qty_keeps_priority() {
+        let mut engine = create_engine();
+        let first = rest(&mut engine, 1, Side::Sell, 100, 10);
+        rest(&mut engine, 2, Side::Sell, 100, 10);
+        
+        assert!(matches!(engine.reduce_qty(first, Quantity(4)), OrderResult::Resting { handle } if handle == first));
+        assert_eq!(engine.book.asks.total_qty(), Quantity(14));
+        assert_eq!(engine.book.asks.best_level().unwrap().total_qty, Quantity(14));
+        assert!(matches!(
+            engine.reduce_qty(first, Quantity(5)),
+            OrderResult::Rejected { reason: RejectReason::InvalidQuantity }
+        ));
+        
+        // Still first in the queue
+        let buy = Order::new(OrderId(3), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(4), 0);
+        assert_eq!(maker_ids(engine.submit_order(buy, 3)), [(1, 4)]);
+    }
+    
+    #[test]
+    fn test_reduce_qty_after_cancel() {
+        let mut engine = create_engine();
+        let order = rest(&mut engine, 1, Side::Sell, 100, 10);
+        engine.cancel_order(order);
+        
+        // A late reduce is refused without touching the journal
+        engine.start_recording();
+        assert!(matches!(
+            engine.reduce_qty(order, Quantity(4)),
+            OrderResult::Rejected { reason: RejectReason::UnknownOrder }
+        ));
+        assert!(engine.input_log().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_reduce_qty_replays() {
+        let mut engine = create_engine();
+        engine.start_recording();
+        let first = rest(&mut engine, 1, Side::Sell, 100, 10);
+        let second = rest(&mut engine, 2, Side::Sell, 100, 10);
+        
+        // Refused increases and zero sizes are not journaled
+        assert!(matches!(engine.reduce_qty(first, Quantity(6)), OrderResult::Resting { .. }));
+        assert!(matches!(
+            engine.reduce_qty(first, Quantity(50)),
+            OrderResult::Rejected { reason: RejectReason::InvalidQuantity }
+        ));
+        assert!(matches!(
+            engine.reduce_qty(second, Quantity::ZERO),
+            OrderResult::Rejected { reason: RejectReason::InvalidQuantity }
+        ));
+        let log = engine.stop_recording().unwrap();
+        assert_eq!((log.len(), log.last_sequence()), (3, engine.book.sequence()));
+        
+        let mut replayed = create_engine();
+        replayed.replay(log.iter());
+        assert_eq!(replayed.get_order(replayed.handle_of(OrderId(1)).unwrap()).unwrap().remaining_qty, Quantity(6));
+        let (mut expected, mut actual) = (Writer::new(), Writer::new());
+        engine.snapshot(&mut expected);
+        replayed.snapshot(&mut actual);
+        assert_eq!(actual.as_bytes(), expected.as_bytes());
+    }
+    
+    #[test]
+    fn test_no_cross_groups() {
+        use crate::nocross::CrossGroupId;
+        
+        let (desk_a, desk_b, outsider) = (ParticipantId(1), ParticipantId(2), ParticipantId(3));
+        let sell = |id, ticks| Order::new(OrderId(id), SymbolId(1), Side::Sell, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0);
+        let buy = |id, qty| Order::new(OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(101), Quantity(qty), 0);
+        let setup = |policy| {
+            let mut engine = create_engine();
+            engine.no_cross.assign(desk_a, CrossGroupId(7));
+            engine.no_cross.assign(desk_b, CrossGroupId(7));
+            engine.no_cross.set_policy(policy);
+            engine.submit_order_as(desk_a, sell(1, 100), 1);
+            engine.submit_order_as(outsider, sell(2, 101), 2);
+            engine
+        };
+        
+        // Cancel resting: the desk's ask goes, the buy trades with the outsider
+        let mut engine = setup(CrossPolicy::CancelResting);
+        assert_eq!(maker_ids(engine.submit_order_as(desk_b, buy(3, 10), 3)), [(2, 10)]);
+        assert_eq!(engine.next_cross_cancel().map(|o| o.order_id), Some(OrderId(1)));
+        assert!(engine.book.asks.is_empty());
+        
+        // Cancel aggressor: nothing trades, the ask stays
+        let mut engine = setup(CrossPolicy::CancelAggressor);
+        assert!(matches!(
+            engine.submit_order_as(desk_b, buy(3, 10), 3),
+            OrderResult::Cancelled { filled_qty: Quantity::ZERO, .. }
+        ));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(100)));
+        
+        // Decrement: both shrink by the smaller size
+        let mut engine = setup(CrossPolicy::Decrement);
+        assert!(matches!(
+            engine.submit_order_as(desk_b, buy(3, 4), 3),
+            OrderResult::Cancelled { filled_qty: Quantity::ZERO, .. }
+        ));
+        assert_eq!(engine.book.asks.best_level().unwrap().total_qty, Quantity(6));
+        assert!(engine.next_cross_cancel().is_none());
+        
+        // Other participants trade as usual
+        assert_eq!(maker_ids(engine.submit_order_as(outsider, buy(4, 6), 4)), [(1, 6)]);
+    }
+    
+    #[test]
+    fn test_mass_quote() {
+        let mut engine = create_engine();
+        let mm = ParticipantId(9);
+        let quote = |id, ticks, qty| QuoteSide::new(OrderId(id), Price::from_ticks(ticks), Quantity(qty));
+        
+        let [bid, ask] = engine.mass_quote(mm, quote(1, 99, 10), quote(2, 101, 10), 1);
+        assert!(matches!((bid, ask), (OrderResult::Resting { .. }, OrderResult::Resting { .. })));
+        let bid_handle = engine.handle_of(OrderId(1)).unwrap();
+        rest(&mut engine, 3, Side::Buy, 99, 10);
+        
+        // Shrinking at the same price keeps the slot order and its priority;
+        // moving the ask re-quotes it under the same ID
+        engine.mass_quote(mm, quote(4, 99, 6), quote(5, 102, 10), 2);
+        assert_eq!(engine.handle_of(OrderId(1)), Some(bid_handle));
+        assert_eq!(engine.owner_of(OrderId(2)), Some(mm));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(102)));
+        assert!(engine.handle_of(OrderId(4)).is_none());
+        let sell = Order::new(OrderId(6), SymbolId(1), Side::Sell, OrderType::IOC, Price::from_ticks(99), Quantity(6), 0);
+        assert_eq!(maker_ids(engine.submit_order(sell, 6)), [(1, 6)]);
+        
+        // The filled bid left its slot; pulling the ask empties the book side
+        let [bid, ask] = engine.mass_quote(mm, quote(7, 98, 5), QuoteSide::pull(), 3);
+        assert!(matches!(bid, OrderResult::Resting { .. }));
+        assert!(matches!(ask, OrderResult::Cancelled { .. }));
+        assert_eq!(engine.owner_of(OrderId(7)), Some(mm));
+        assert!(engine.book.asks.is_empty());
+        assert_eq!(engine.quotes.slot(mm, Side::Sell), OrderId::INVALID);
+    }
+    
+    #[test]
+    fn test_rejected_quote_amend_keeps_quote() {
+        let mut engine = create_engine();
+        let mm = ParticipantId(9);
+        let quote = |id, ticks, qty| QuoteSide::new(OrderId(id), Price::from_ticks(ticks), Quantity(qty));
+        engine.set_price_band(Some(PriceBand::Ticks(5)));
+        engine.set_reference_price(Price::from_ticks(100));
+        engine.mass_quote(mm, quote(1, 99, 10), quote(2, 101, 10), 1);
+        let ask_handle = engine.handle_of(OrderId(2)).unwrap();
+        
+        // Ask moved out of band: the old ask stays live in its slot
+        let [bid, ask] = engine.mass_quote(mm, quote(3, 98, 10), quote(4, 110, 10), 2);
+        assert!(matches!(bid, OrderResult::Resting { .. }));
+        assert!(matches!(ask, OrderResult::Rejected { reason: RejectReason::PriceOutOfBand }));
+        assert_eq!(engine.quotes.slot(mm, Side::Sell), OrderId(2));
+        assert_eq!(engine.handle_of(OrderId(2)), Some(ask_handle));
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(101)));
+        engine.verify_invariants();
+        
+        // The next quote still amends it in place
+        engine.mass_quote(mm, quote(5, 98, 10), quote(6, 101, 4), 3);
+        assert_eq!(engine.handle_of(OrderId(2)), Some(ask_handle));
+        assert_eq!(engine.book.asks.total_qty(), Quantity(4));
+        assert!(engine.handle_of(OrderId(6)).is_none());
+    }
+    
+    #[test]
+    fn test_duplicate_order_id_with_stop() {
+        let mut engine = create_engine();
+        let stop = Order::new(OrderId(1), SymbolId(1), Side::Sell, OrderType::Market, Price::ZERO, Quantity(5), 0);
+        engine.submit_trailing_stop(stop, Price::from_ticks(5)).unwrap();
+        
+        // A waiting stop's ID is live
+        let limit = Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(90), Quantity(1), 0);
+        assert!(matches!(
+            engine.submit_order(limit, 1),
+            OrderResult::Rejected { reason: RejectReason::DuplicateOrderId }
+        ));
+        assert!(engine.index.is_empty());
+        
+        // Free again once the stop is gone
+        assert!(engine.stops.cancel(OrderId(1)).is_some());
+        assert!(matches!(engine.submit_order(limit, 2), OrderResult::Resting { .. }));
+    }
+    
+    #[test]
+    fn test_cancel_unlinks_from_level() {
+        let mut engine = create_engine();
+        rest(&mut engine, 1, Side::Sell, 100, 10);
+        let middle = rest(&mut engine, 2, Side::Sell, 100, 10);
+        rest(&mut engine, 3, Side::Sell, 100, 10);
+        let only = rest(&mut engine, 4, Side::Sell, 99, 10);
+        
+        assert!(engine.cancel_order(middle).is_some());
+        assert!(engine.cancel_order(only).is_some());
+        assert_eq!(engine.book.asks.best_level().unwrap().len(), 2);
+        assert_eq!(engine.book.best_ask(), Some(Price::from_ticks(100)));
+        engine.verify_invariants();
+        
+        // The cancelled order is never matched
+        let buy = Order::new(OrderId(5), SymbolId(1), Side::Buy, OrderType::IOC, Price::from_ticks(100), Quantity(20), 5);
+        assert_eq!(maker_ids(engine.submit_order(buy, 5)), [(1, 10), (3, 10)]);
+        engine.verify_invariants();
+    }
+    
+    #[test]
+    fn test_stale_handle_ignored_after_reuse() {
+        let mut engine = create_engine();
+        let old = rest(&mut engine, 1, Side::Buy, 100, 10);
+        assert!(engine.cancel_order(old).is_some());
+        
+        // The next order lands in the same slot
+        let new = rest(&mut engine, 2, Side::Buy, 100, 20);
+        assert_eq!(new.index(), old.index());
+        
+        assert!(engine.get_order(old).is_none());
+        assert!(engine.cancel_order(old).is_none());
+        assert!(matches!(
+            engine.modify_order(old, Price::from_ticks(100), Quantity(5)),
+            OrderResult::Rejected { reason: RejectReason::UnknownOrder }
+        ));
+        assert!(!engine.tag_session(old, SessionId(3)));
+        assert_eq!(engine.get_order(new).unwrap().remaining_qty, Quantity(20));
+        engine.verify_invariants();
+    }
+    
+    #[test]
+    fn test_pool_grows_past_initial_capacity() {
+        let mut engine = MatchingEngine::new(SymbolId(1), 2, Price::ZERO); // 4 orders
+        for id in 0..4 {
+            rest(&mut engine, id, Side::Buy, 100 - id, 10);
+        }
+        let order = Order::new(OrderId(9), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(90), Quantity(10), 0);
+        assert!(matches!(
+            engine.submit_order(order, 9),
+            OrderResult::Rejected { reason: RejectReason::PoolExhausted }
+        ));
+        
+        engine.set_pool_limit(4);
+        let handles: Vec<OrderHandle> = (4..16).map(|id| rest(&mut engine, id, Side::Buy, 100 - id, 10)).collect();
+        assert_eq!(engine.cancel_by_id(OrderId(12)).unwrap().order_id, OrderId(12));
+        assert!(engine.tag_session(handles[9], SessionId(1)));
+        assert_eq!(engine.cancel_all_for_session(SessionId(1)), [OrderId(13)]);
+        engine.verify_invariants();
     }
 }
@@ -0,0 +1,52 @@
+//! Engine event callbacks.
+//!
+//! `MatchingEngine::submit_order_with` reports what happens to an order
+//! through a caller-supplied `MatchEvents` as it happens, so gateways and
+//! feeds do not have to re-derive events from `OrderResult`. The sink is a
+//! generic parameter: calls are monomorphized and inlined, and `()` is a
+//! sink that compiles away.
+
+use crate::engine::{Fill, RejectReason};
+use crate::fixed::{Price, Quantity};
+use crate::order::Order;
+
+/// Top of book: best price and displayed quantity on each side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BboChanged {
+    /// Best bid (`None` = empty side).
+    pub bid: Option<Price>,
+    /// Quantity displayed at the best bid.
+    pub bid_qty: Quantity,
+    /// Best ask (`None` = empty side).
+    pub ask: Option<Price>,
+    /// Quantity displayed at the best ask.
+    pub ask_qty: Quantity,
+}
+
+/// Receiver of engine events. Every method defaults to doing nothing.
+pub trait MatchEvents {
+    /// An order passed validation and is about to match or rest.
+    #[inline(always)]
+    fn on_accept(&mut self, _order: &Order) {}
+
+    /// A trade, emitted as each maker is hit.
+    #[inline(always)]
+    fn on_fill(&mut self, _fill: &Fill) {}
+
+    /// An order left the book or its remainder was cancelled without
+    /// resting; `remaining_qty` is the quantity cancelled.
+    #[inline(always)]
+    fn on_cancel(&mut self, _order: &Order) {}
+
+    /// An order was rejected.
+    #[inline(always)]
+    fn on_reject(&mut self, _order: &Order, _reason: RejectReason) {}
+
+    /// Best price or the quantity at it changed on either side. Reported
+    /// once per change, after the command that caused it.
+    #[inline(always)]
+    fn on_bbo_change(&mut self, _bbo: &BboChanged) {}
+}
+
+/// Discards every event.
+impl MatchEvents for () {}
@@ -0,0 +1,72 @@
+//! Trading fees and rebates.
+//!
+//! Fees are charged per fill from the exchange's point of view: a positive
+//! amount is paid by the participant, a negative one is a rebate paid to
+//! them. Amounts are in raw price units, so no floating point is involved.
+
+use crate::fixed::{bps_of, Price, Quantity};
+
+/// How a fee is levied on one side of a fill.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeRate {
+    /// Basis points of the fill notional (negative for a rebate).
+    Bps(i32),
+    /// Fixed amount per unit of quantity, in raw price units (negative for
+    /// a rebate).
+    PerContract(i64),
+}
+
+impl FeeRate {
+    /// No fee.
+    pub const ZERO: Self = FeeRate::Bps(0);
+
+    /// Fee on `qty` filled at `price`.
+    #[inline(always)]
+    pub fn fee(self, price: Price, qty: Quantity) -> i64 {
+        match self {
+            FeeRate::Bps(bps) => bps_of(price.notional(qty), bps),
+            FeeRate::PerContract(amount) => amount.saturating_mul(qty.0.min(i64::MAX as u64) as i64),
+        }
+    }
+}
+
+impl Default for FeeRate {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+/// Maker and taker rates of an instrument.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    /// Rate for the resting side.
+    pub maker: FeeRate,
+    /// Rate for the aggressing side.
+    pub taker: FeeRate,
+}
+
+impl FeeSchedule {
+    /// Schedule charging nothing.
+    pub const ZERO: Self = Self { maker: FeeRate::ZERO, taker: FeeRate::ZERO };
+
+    /// `(maker_fee, taker_fee)` of a fill.
+    #[inline(always)]
+    pub fn fees(&self, price: Price, qty: Quantity) -> (i64, i64) {
+        (self.maker.fee(price, qty), self.taker.fee(price, qty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maker_rebate() {
+        let schedule = FeeSchedule { maker: FeeRate::Bps(-2), taker: FeeRate::PerContract(5) };
+        // 1_000 ticks × 10 = 1_000_000 raw units
+        let (maker, taker) = schedule.fees(Price::from_ticks(1_000), Quantity(10));
+        assert_eq!(maker, -200);
+        assert_eq!(taker, 50);
+        assert_eq!(FeeSchedule::ZERO.fees(Price::from_ticks(1_000), Quantity(10)), (0, 0));
+    }
+}
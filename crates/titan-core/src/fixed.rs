@@ -69,6 +69,32 @@ impl Price {
     pub const fn saturating_sub(self, other: Self) -> Self {
         Self(self.0.saturating_sub(other.0))
     }
+    
+    /// Value of `qty` at this price, in raw price units.
+    ///
+    /// Widened to 128 bits so it cannot overflow.
+    #[inline(always)]
+    pub const fn notional(self, qty: Quantity) -> u128 {
+        self.0 as u128 * qty.0 as u128
+    }
+}
+
+/// `bps` basis points of `amount`, rounded towards positive infinity.
+///
+/// Negative rates (rebates) therefore round towards zero. Saturates at
+/// the `i64` range.
+#[inline(always)]
+pub const fn bps_of(amount: u128, bps: i32) -> i64 {
+    let amount = if amount > i128::MAX as u128 { i128::MAX } else { amount as i128 };
+    let scaled = amount.saturating_mul(bps as i128);
+    let value = scaled.div_euclid(10_000) + (scaled.rem_euclid(10_000) != 0) as i128;
+    if value > i64::MAX as i128 {
+        i64::MAX
+    } else if value < i64::MIN as i128 {
+        i64::MIN
+    } else {
+        value as i64
+    }
 }
 
 impl Add for Price {
@@ -195,6 +221,15 @@ mod tests {
         assert_eq!(q1.min(q2), q2);
     }
     
+    #[test]
+    fn test_notional_bps() {
+        let notional = Price::from_ticks(101).notional(Quantity(3));
+        assert_eq!(notional, 30_300);
+        assert_eq!(bps_of(notional, 10), 31);
+        assert_eq!(bps_of(notional, -10), -30);
+        assert_eq!(bps_of(u128::MAX >> 1, 10_000), i64::MAX);
+    }
+    
     #[test]
     fn test_saturating_ops() {
         let q = Quantity(10);
@@ -0,0 +1,203 @@
+//! Order ID index.
+//!
+//! Open-addressed hash table mapping client `OrderId`s to pool handles.
+//! Sized to twice the pool capacity, so the load factor never exceeds
+//! 50% and insert/remove never allocate; a growing pool grows it too. Linear probing
+//! with backward-shift deletion keeps probe chains free of tombstones.
+
+use alloc::vec;
+use alloc::boxed::Box;
+use crate::order::OrderId;
+use crate::pool::OrderHandle;
+
+/// Fibonacci hashing multiplier (2^64 / golden ratio).
+const HASH_MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// One table slot. Empty when `handle` is invalid.
+#[derive(Clone, Copy)]
+struct Slot {
+    order_id: OrderId,
+    handle: OrderHandle,
+}
+
+impl Slot {
+    const EMPTY: Self = Self { order_id: OrderId(0), handle: OrderHandle::INVALID };
+}
+
+/// OrderId → OrderHandle map for resting orders.
+pub struct OrderIndex {
+    slots: Box<[Slot]>,
+    /// `slots.len() - 1` (length is a power of 2).
+    mask: usize,
+    /// `64 - log2(slots.len())`, for taking the top hash bits.
+    shift: u32,
+    len: usize,
+}
+
+impl OrderIndex {
+    /// Create an index able to hold `capacity` orders.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let size = (capacity.max(1) * 2).next_power_of_two();
+        Self {
+            slots: vec![Slot::EMPTY; size].into_boxed_slice(),
+            mask: size - 1,
+            shift: 64 - size.trailing_zeros(),
+            len: 0,
+        }
+    }
+
+    /// Resize for a pool of `capacity` orders, rehashing every entry.
+    pub fn grow(&mut self, capacity: usize) {
+        let mut grown = Self::with_capacity(capacity);
+        if grown.slots.len() <= self.slots.len() {
+            return;
+        }
+        for slot in self.slots.iter().filter(|slot| slot.handle.is_valid()) {
+            grown.insert(slot.order_id, slot.handle);
+        }
+        *self = grown;
+    }
+
+    /// Number of indexed orders.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if index is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    fn home(&self, order_id: OrderId) -> usize {
+        (order_id.0.wrapping_mul(HASH_MULTIPLIER) >> self.shift) as usize & self.mask
+    }
+
+    /// Slot holding `order_id`, if present.
+    #[inline]
+    fn find(&self, order_id: OrderId) -> Option<usize> {
+        let mut idx = self.home(order_id);
+        loop {
+            let slot = &self.slots[idx];
+            if !slot.handle.is_valid() {
+                return None;
+            }
+            if slot.order_id == order_id {
+                return Some(idx);
+            }
+            idx = (idx + 1) & self.mask;
+        }
+    }
+
+    /// Look up the handle of a resting order.
+    #[inline]
+    pub fn get(&self, order_id: OrderId) -> Option<OrderHandle> {
+        self.find(order_id).map(|idx| self.slots[idx].handle)
+    }
+
+    /// Check if `order_id` is indexed.
+    #[inline]
+    pub fn contains(&self, order_id: OrderId) -> bool {
+        self.find(order_id).is_some()
+    }
+
+    /// Insert or update the handle for `order_id`.
+    ///
+    /// Returns `false` if the table is full.
+    #[inline]
+    pub fn insert(&mut self, order_id: OrderId, handle: OrderHandle) -> bool {
+        debug_assert!(handle.is_valid(), "Cannot index invalid handle");
+
+        let mut idx = self.home(order_id);
+        loop {
+            let slot = &mut self.slots[idx];
+            if !slot.handle.is_valid() {
+                // Keep at least one empty slot so probes terminate
+                if self.len == self.mask {
+                    return false;
+                }
+                *slot = Slot { order_id, handle };
+                self.len += 1;
+                return true;
+            }
+            if slot.order_id == order_id {
+                slot.handle = handle;
+                return true;
+            }
+            idx = (idx + 1) & self.mask;
+        }
+    }
+
+    /// Remove `order_id`, returning its handle.
+    #[inline]
+    pub fn remove(&mut self, order_id: OrderId) -> Option<OrderHandle> {
+        let mut hole = self.find(order_id)?;
+        let handle = self.slots[hole].handle;
+        self.len -= 1;
+
+        // Backward-shift: pull later chain members into the hole
+        let mut idx = hole;
+        loop {
+            idx = (idx + 1) & self.mask;
+            let slot = self.slots[idx];
+            if !slot.handle.is_valid() {
+                break;
+            }
+            // Distance from home; movable if its home is not in (hole, idx]
+            let home = self.home(slot.order_id);
+            if (idx.wrapping_sub(home) & self.mask) >= (idx.wrapping_sub(hole) & self.mask) {
+                self.slots[hole] = slot;
+                hole = idx;
+            }
+        }
+        self.slots[hole] = Slot::EMPTY;
+
+        Some(handle)
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.slots.fill(Slot::EMPTY);
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_insert_remove() {
+        let mut index = OrderIndex::with_capacity(4);
+        assert!(index.insert(OrderId(7), OrderHandle(0)));
+        assert!(index.insert(OrderId(9), OrderHandle(1)));
+        assert_eq!(index.get(OrderId(7)), Some(OrderHandle(0)));
+        assert_eq!(index.get(OrderId(8)), None);
+
+        assert_eq!(index.remove(OrderId(7)), Some(OrderHandle(0)));
+        assert_eq!(index.remove(OrderId(7)), None);
+        assert_eq!(index.get(OrderId(9)), Some(OrderHandle(1)));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_index_collisions() {
+        // Small table, many keys: every probe chain is long and wraps
+        let mut index = OrderIndex::with_capacity(64);
+        for id in 0..64u32 {
+            assert!(index.insert(OrderId(id as u64 * 1024), OrderHandle(id)));
+        }
+
+        // Remove every other key; the rest must stay reachable
+        for id in (0..64u32).step_by(2) {
+            assert_eq!(index.remove(OrderId(id as u64 * 1024)), Some(OrderHandle(id)));
+        }
+        for id in 0..64u32 {
+            let expected = (id % 2 == 1).then_some(OrderHandle(id));
+            assert_eq!(index.get(OrderId(id as u64 * 1024)), expected);
+        }
+        assert_eq!(index.len(), 32);
+    }
+}
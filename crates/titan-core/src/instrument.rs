@@ -0,0 +1,76 @@
+//! Per-instrument trading parameters.
+//!
+//! Tick size drives both price validation and the book's price-to-level
+//! mapping, so instruments with different price grids (cents for
+//! equities, satoshis for crypto) can live side by side.
+
+use crate::fixed::{Price, Quantity};
+
+/// Price grid and size limits for one instrument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InstrumentSpec {
+    /// Price increment (raw price units).
+    pub tick_size: u64,
+    /// Order quantities must be a multiple of this.
+    pub lot_size: Quantity,
+    /// Smallest accepted order quantity.
+    pub min_qty: Quantity,
+    /// Largest accepted order quantity.
+    pub max_qty: Quantity,
+}
+
+impl InstrumentSpec {
+    /// Spec with the given tick size and no size limits beyond a lot of 1.
+    ///
+    /// A zero tick size is treated as 1.
+    pub const fn with_tick_size(tick_size: u64) -> Self {
+        Self {
+            tick_size: if tick_size == 0 { 1 } else { tick_size },
+            lot_size: Quantity(1),
+            min_qty: Quantity(1),
+            max_qty: Quantity::MAX,
+        }
+    }
+
+    /// Check if `price` lies on the tick grid.
+    #[inline(always)]
+    pub const fn is_on_tick(&self, price: Price) -> bool {
+        price.0.is_multiple_of(self.tick_size)
+    }
+
+    /// Check if `qty` is a whole number of lots within the size limits.
+    #[inline(always)]
+    pub const fn is_valid_qty(&self, qty: Quantity) -> bool {
+        qty.0 >= self.min_qty.0
+            && qty.0 <= self.max_qty.0
+            && (self.lot_size.0 <= 1 || qty.0.is_multiple_of(self.lot_size.0))
+    }
+}
+
+impl Default for InstrumentSpec {
+    fn default() -> Self {
+        Self::with_tick_size(Price::TICK_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_validation() {
+        let spec = InstrumentSpec {
+            lot_size: Quantity(10),
+            min_qty: Quantity(20),
+            max_qty: Quantity(1_000),
+            ..InstrumentSpec::with_tick_size(5)
+        };
+
+        assert!(spec.is_on_tick(Price(105)));
+        assert!(!spec.is_on_tick(Price(103)));
+        assert!(spec.is_valid_qty(Quantity(20)));
+        assert!(!spec.is_valid_qty(Quantity(10)));
+        assert!(!spec.is_valid_qty(Quantity(25)));
+        assert!(!spec.is_valid_qty(Quantity(1_010)));
+    }
+}
@@ -0,0 +1,262 @@
+//! Engine input recording.
+//!
+//! Every submission (fired trailing stops included), cancel and modify an
+//! engine receives takes the next input sequence number (`OrderBook::
+//! sequence`), as do the calls that pull orders in bulk or change the
+//! trading phase: expiries that expired something, end of session, mass
+//! cancels, auction start and uncross. While recording, the engine also
+//! appends the command to an `InputLog`, in arrival order. Feeding the log
+//! to `MatchingEngine::replay` on an engine with the same configuration
+//! and starting state reproduces the book exactly. A cancel on disconnect
+//! is recorded as one cancel per order pulled, since session tags are not
+//! recorded. OCO links, trailing stops and settings are not recorded.
+//! Encoded layout (little-endian):
+//!
+//! ```text
+//! [Magic: 4B "TLOG"][Version: 2B][Reserved: 2B][FirstSequence: 8B]
+//! then records until the end of input:
+//! Submit:          [Tag 1: 1B][Reserved: 3B][Symbol: 4B][Timestamp: 8B][Order: 64B]
+//! Cancel:          [Tag 2: 1B][Reserved: 7B][OrderId: 8B]
+//! Modify:          [Tag 3: 1B][Reserved: 7B][OrderId: 8B][Price: 8B][Qty: 8B]
+//! Expire:          [Tag 4: 1B][Reserved: 7B][Now: 8B]
+//! EndOfSession:    [Tag 5: 1B][Reserved: 7B]
+//! CancelAll:       [Tag 6: 1B][Reserved: 3B][Participant: 4B]
+//! CancelAllSymbol: [Tag 7: 1B][Reserved: 7B]
+//! StartAuction:    [Tag 8: 1B][Reserved: 7B]
+//! Uncross:         [Tag 9: 1B][Reserved: 7B][Timestamp: 8B]
+//! ```
+//!
+//! The order record is the one used by snapshots (see `snapshot`).
+
+use alloc::vec::Vec;
+use crate::fixed::{Price, Quantity};
+use crate::order::{Order, OrderId, ParticipantId, SymbolId};
+use crate::snapshot::{self, Reader, SnapshotError, Writer};
+
+/// Leading bytes of every encoded input log.
+pub const INPUT_LOG_MAGIC: [u8; 4] = *b"TLOG";
+
+/// Input log format version written by this build.
+pub const INPUT_LOG_VERSION: u16 = 1;
+
+const TAG_SUBMIT: u8 = 1;
+const TAG_CANCEL: u8 = 2;
+const TAG_MODIFY: u8 = 3;
+const TAG_EXPIRE: u8 = 4;
+const TAG_END_OF_SESSION: u8 = 5;
+const TAG_CANCEL_ALL: u8 = 6;
+const TAG_CANCEL_ALL_SYMBOL: u8 = 7;
+const TAG_START_AUCTION: u8 = 8;
+const TAG_UNCROSS: u8 = 9;
+
+/// One command received by an engine.
+#[derive(Clone, Copy, Debug)]
+pub enum InputCommand {
+    /// New order, as submitted.
+    Submit {
+        participant: ParticipantId,
+        order: Order,
+        timestamp: u64,
+    },
+    /// Cancel of a resting order.
+    Cancel { order_id: OrderId },
+    /// Amend of a resting order.
+    Modify {
+        order_id: OrderId,
+        new_price: Price,
+        new_qty: Quantity,
+    },
+    /// GTD expiry pass at `now`.
+    Expire { now: u64 },
+    /// Close of the trading session.
+    EndOfSession,
+    /// Mass cancel of one participant's orders.
+    CancelAll { participant: ParticipantId },
+    /// Mass cancel of the whole book.
+    CancelAllSymbol,
+    /// Start of the call phase.
+    StartAuction,
+    /// Auction uncross.
+    Uncross { timestamp: u64 },
+}
+
+impl InputCommand {
+    /// Append the encoded record to `out`.
+    pub fn encode(&self, out: &mut Writer) {
+        match *self {
+            InputCommand::Submit { participant, order, timestamp } => {
+                out.put(&[TAG_SUBMIT, 0, 0, 0]);
+                out.put_u32(order.symbol.0);
+                out.put_u64(timestamp);
+                snapshot::write_order(out, &order, participant);
+            }
+            InputCommand::Cancel { order_id } => {
+                out.put(&[TAG_CANCEL, 0, 0, 0, 0, 0, 0, 0]);
+                out.put_u64(order_id.0);
+            }
+            InputCommand::Modify { order_id, new_price, new_qty } => {
+                out.put(&[TAG_MODIFY, 0, 0, 0, 0, 0, 0, 0]);
+                out.put_u64(order_id.0);
+                out.put_u64(new_price.0);
+                out.put_u64(new_qty.0);
+            }
+            InputCommand::Expire { now } => {
+                out.put(&[TAG_EXPIRE, 0, 0, 0, 0, 0, 0, 0]);
+                out.put_u64(now);
+            }
+            InputCommand::EndOfSession => out.put(&[TAG_END_OF_SESSION, 0, 0, 0, 0, 0, 0, 0]),
+            InputCommand::CancelAll { participant } => {
+                out.put(&[TAG_CANCEL_ALL, 0, 0, 0]);
+                out.put_u32(participant.0);
+            }
+            InputCommand::CancelAllSymbol => out.put(&[TAG_CANCEL_ALL_SYMBOL, 0, 0, 0, 0, 0, 0, 0]),
+            InputCommand::StartAuction => out.put(&[TAG_START_AUCTION, 0, 0, 0, 0, 0, 0, 0]),
+            InputCommand::Uncross { timestamp } => {
+                out.put(&[TAG_UNCROSS, 0, 0, 0, 0, 0, 0, 0]);
+                out.put_u64(timestamp);
+            }
+        }
+    }
+
+    /// Read one record.
+    pub(crate) fn decode(reader: &mut Reader<'_>) -> Result<Self, SnapshotError> {
+        let header = reader.take::<8>()?;
+        match header[0] {
+            TAG_SUBMIT => {
+                let symbol = SymbolId(u32::from_le_bytes([header[4], header[5], header[6], header[7]]));
+                let timestamp = reader.u64()?;
+                let (order, participant) = snapshot::read_order(reader, symbol)?;
+                Ok(InputCommand::Submit { participant, order, timestamp })
+            }
+            TAG_CANCEL => Ok(InputCommand::Cancel { order_id: OrderId(reader.u64()?) }),
+            TAG_MODIFY => Ok(InputCommand::Modify {
+                order_id: OrderId(reader.u64()?),
+                new_price: Price(reader.u64()?),
+                new_qty: Quantity(reader.u64()?),
+            }),
+            TAG_EXPIRE => Ok(InputCommand::Expire { now: reader.u64()? }),
+            TAG_END_OF_SESSION => Ok(InputCommand::EndOfSession),
+            TAG_CANCEL_ALL => {
+                let participant = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+                Ok(InputCommand::CancelAll { participant: ParticipantId(participant) })
+            }
+            TAG_CANCEL_ALL_SYMBOL => Ok(InputCommand::CancelAllSymbol),
+            TAG_START_AUCTION => Ok(InputCommand::StartAuction),
+            TAG_UNCROSS => Ok(InputCommand::Uncross { timestamp: reader.u64()? }),
+            _ => Err(SnapshotError::Corrupt),
+        }
+    }
+}
+
+/// Commands in arrival order, numbered from `first_sequence`.
+#[derive(Clone, Debug)]
+pub struct InputLog {
+    first_sequence: u64,
+    commands: Vec<InputCommand>,
+}
+
+impl InputLog {
+    /// Create an empty log starting at input sequence 1.
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Create an empty log whose first command is `first_sequence`.
+    pub fn starting_at(first_sequence: u64) -> Self {
+        Self { first_sequence, commands: Vec::new() }
+    }
+
+    /// Input sequence number of the first command.
+    #[inline(always)]
+    pub fn first_sequence(&self) -> u64 {
+        self.first_sequence
+    }
+
+    /// Input sequence number of the last command (one before
+    /// `first_sequence` if empty).
+    #[inline(always)]
+    pub fn last_sequence(&self) -> u64 {
+        (self.first_sequence + self.commands.len() as u64).saturating_sub(1)
+    }
+
+    /// Number of recorded commands.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check if nothing was recorded.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Append a command.
+    #[inline]
+    pub fn push(&mut self, command: InputCommand) {
+        self.commands.push(command);
+    }
+
+    /// Recorded commands, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &InputCommand> + '_ {
+        self.commands.iter()
+    }
+
+    /// Commands numbered after `sequence`.
+    pub fn after(&self, sequence: u64) -> impl Iterator<Item = &InputCommand> + '_ {
+        let skip = sequence.saturating_add(1).saturating_sub(self.first_sequence);
+        self.commands.iter().skip(skip.min(self.commands.len() as u64) as usize)
+    }
+
+    /// Write the header and every command to `out`.
+    pub fn encode(&self, out: &mut Writer) {
+        out.put(&INPUT_LOG_MAGIC);
+        out.put_u16(INPUT_LOG_VERSION);
+        out.put_u16(0);
+        out.put_u64(self.first_sequence);
+        for command in &self.commands {
+            command.encode(out);
+        }
+    }
+
+    /// Decode a log written by `encode`.
+    pub fn decode(data: &[u8]) -> Result<Self, SnapshotError> {
+        let (log, used) = Self::decode_prefix(data)?;
+        if used != data.len() {
+            return Err(SnapshotError::Truncated);
+        }
+        Ok(log)
+    }
+
+    /// Decode every complete command of a journal whose tail may have
+    /// been cut short by a crash. Returns the log and the bytes it spans.
+    pub fn decode_prefix(data: &[u8]) -> Result<(Self, usize), SnapshotError> {
+        let mut reader = Reader::new(data);
+        if reader.take::<4>()? != INPUT_LOG_MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if version != INPUT_LOG_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        reader.u16()?;
+        let mut log = Self::starting_at(reader.u64()?);
+
+        let mut used = reader.consumed(data.len());
+        while !reader.is_empty() {
+            match InputCommand::decode(&mut reader) {
+                Ok(command) => log.push(command),
+                Err(SnapshotError::Truncated) => break,
+                Err(e) => return Err(e),
+            }
+            used = reader.consumed(data.len());
+        }
+        Ok((log, used))
+    }
+}
+
+impl Default for InputLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,32 +2,76 @@
 //!
 //! A price level contains all orders at a specific price,
 //! organized as a FIFO queue (price-time priority).
+//!
+//! The queue is an intrusive doubly-linked list: the level keeps its head
+//! and tail handles and each order's neighbours live in a `QueueLinks`
+//! table indexed by pool slot, so an order anywhere in the queue is
+//! unlinked in O(1). A level holds as many orders as the pool does.
 
+use alloc::vec;
+use alloc::boxed::Box;
 use crate::fixed::Quantity;
 use crate::pool::OrderHandle;
 
-/// Maximum orders per price level.
-/// Tune based on expected market depth.
-pub const MAX_ORDERS_PER_LEVEL: usize = 1024;
+/// Neighbours of one queued order.
+#[derive(Clone, Copy, Debug)]
+struct Link {
+    prev: OrderHandle,
+    next: OrderHandle,
+}
+
+impl Link {
+    const UNLINKED: Self = Self { prev: OrderHandle::INVALID, next: OrderHandle::INVALID };
+}
+
+/// Queue links of every pool slot.
+///
+/// Kept beside the order slots rather than in `Order`, which has to stay
+/// one cache line.
+pub struct QueueLinks {
+    links: Box<[Link]>,
+}
+
+impl QueueLinks {
+    /// Create links for handles below `capacity`.
+    pub fn new(capacity: usize) -> Self {
+        Self { links: vec![Link::UNLINKED; capacity].into_boxed_slice() }
+    }
+    
+    /// Extend to handles below `capacity`.
+    pub fn grow(&mut self, capacity: usize) {
+        let mut links = core::mem::take(&mut self.links).into_vec();
+        links.resize(capacity, Link::UNLINKED);
+        self.links = links.into_boxed_slice();
+    }
+    
+    /// Order queued behind `handle`.
+    #[inline(always)]
+    pub fn next(&self, handle: OrderHandle) -> Option<OrderHandle> {
+        Some(self.links[handle.index()].next).filter(|next| next.is_valid())
+    }
+    
+    /// Order queued ahead of `handle`.
+    #[inline(always)]
+    pub fn prev(&self, handle: OrderHandle) -> Option<OrderHandle> {
+        Some(self.links[handle.index()].prev).filter(|prev| prev.is_valid())
+    }
+}
 
 /// A single price level in the order book.
 ///
-/// Uses a circular buffer for FIFO order queue, which is cache-friendly
-/// and provides O(1) push/pop operations.
+/// Only the ends of the queue live here; the rest is threaded through
+/// `QueueLinks`, so every queue operation is O(1).
 #[repr(C)]
 pub struct PriceLevel {
     /// Total quantity at this level.
     pub total_qty: Quantity,
     /// Number of orders at this level.
-    order_count: u16,
-    /// Head of circular buffer (next to dequeue).
-    head: u16,
-    /// Tail of circular buffer (next insert position).
-    tail: u16,
-    /// Padding for alignment.
-    _padding: u16,
-    /// Circular buffer of order handles.
-    orders: [OrderHandle; MAX_ORDERS_PER_LEVEL],
+    order_count: u32,
+    /// Oldest order (next to match).
+    head: OrderHandle,
+    /// Newest order.
+    tail: OrderHandle,
 }
 
 impl PriceLevel {
@@ -36,10 +80,8 @@ impl PriceLevel {
         Self {
             total_qty: Quantity::ZERO,
             order_count: 0,
-            head: 0,
-            tail: 0,
-            _padding: 0,
-            orders: [OrderHandle::INVALID; MAX_ORDERS_PER_LEVEL],
+            head: OrderHandle::INVALID,
+            tail: OrderHandle::INVALID,
         }
     }
     
@@ -55,23 +97,28 @@ impl PriceLevel {
         self.order_count as usize
     }
     
-    /// Check if level is full.
+    /// Check if level is full (its order count would overflow).
     #[inline(always)]
     pub const fn is_full(&self) -> bool {
-        self.order_count as usize >= MAX_ORDERS_PER_LEVEL
+        self.order_count == u32::MAX
     }
     
     /// Add order to back of queue.
     ///
     /// Returns `false` if level is full.
     #[inline(always)]
-    pub fn push_back(&mut self, handle: OrderHandle, qty: Quantity) -> bool {
+    pub fn push_back(&mut self, handle: OrderHandle, qty: Quantity, links: &mut QueueLinks) -> bool {
         if self.is_full() {
             return false;
         }
         
-        self.orders[self.tail as usize] = handle;
-        self.tail = ((self.tail as usize + 1) % MAX_ORDERS_PER_LEVEL) as u16;
+        links.links[handle.index()] = Link { prev: self.tail, next: OrderHandle::INVALID };
+        if self.tail.is_valid() {
+            links.links[self.tail.index()].next = handle;
+        } else {
+            self.head = handle;
+        }
+        self.tail = handle;
         self.order_count += 1;
         self.total_qty = self.total_qty.saturating_add(qty);
         true
@@ -80,11 +127,7 @@ impl PriceLevel {
     /// Get front order handle (for matching).
     #[inline(always)]
     pub fn front(&self) -> Option<OrderHandle> {
-        if self.is_empty() {
-            None
-        } else {
-            Some(self.orders[self.head as usize])
-        }
+        Some(self.head).filter(|head| head.is_valid())
     }
     
     /// Peek at front order handle without removing.
@@ -98,16 +141,52 @@ impl PriceLevel {
     /// Note: Does NOT update total_qty. Caller must call reduce_qty separately
     /// if the order was partially or fully filled.
     #[inline(always)]
-    pub fn pop_front(&mut self) -> Option<OrderHandle> {
-        if self.is_empty() {
-            return None;
+    pub fn pop_front(&mut self, links: &mut QueueLinks) -> Option<OrderHandle> {
+        let handle = self.front()?;
+        self.unlink(handle, links);
+        Some(handle)
+    }
+    
+    /// Remove an order from anywhere in the queue, preserving the order
+    /// of the rest. O(1).
+    ///
+    /// Returns `false` if `handle` is not queued; it must not be queued
+    /// at another level. Like `pop_front`, does NOT update total_qty.
+    pub fn remove(&mut self, handle: OrderHandle, links: &mut QueueLinks) -> bool {
+        if self.head != handle && links.prev(handle).is_none() {
+            return false;
         }
-        
-        let handle = self.orders[self.head as usize];
-        self.orders[self.head as usize] = OrderHandle::INVALID;
-        self.head = ((self.head as usize + 1) % MAX_ORDERS_PER_LEVEL) as u16;
+        self.unlink(handle, links);
+        true
+    }
+    
+    /// Splice a queued order out of the list.
+    #[inline(always)]
+    fn unlink(&mut self, handle: OrderHandle, links: &mut QueueLinks) {
+        let Link { prev, next } = links.links[handle.index()];
+        if prev.is_valid() {
+            links.links[prev.index()].next = next;
+        } else {
+            self.head = next;
+        }
+        if next.is_valid() {
+            links.links[next.index()].prev = prev;
+        } else {
+            self.tail = prev;
+        }
+        links.links[handle.index()] = Link::UNLINKED;
         self.order_count -= 1;
-        Some(handle)
+    }
+    
+    /// Move the front order to the back of the queue with `qty` displayed.
+    ///
+    /// Used when an iceberg clip is exhausted and the next one is shown;
+    /// the new clip loses time priority.
+    #[inline(always)]
+    pub fn requeue_front(&mut self, qty: Quantity, links: &mut QueueLinks) {
+        if let Some(handle) = self.pop_front(links) {
+            self.push_back(handle, qty, links);
+        }
     }
     
     /// Update total quantity (after partial or full fill).
@@ -123,20 +202,23 @@ impl PriceLevel {
     }
     
     /// Reset the level to empty state.
+    ///
+    /// The orders' own links are left as they were; only for levels whose
+    /// orders are being discarded.
     #[inline(always)]
     pub fn clear(&mut self) {
         self.order_count = 0;
-        self.head = 0;
-        self.tail = 0;
+        self.head = OrderHandle::INVALID;
+        self.tail = OrderHandle::INVALID;
         self.total_qty = Quantity::ZERO;
-        // Note: We don't clear the orders array for performance
     }
     
-    /// Iterator over order handles (for debugging/testing).
-    pub fn iter(&self) -> PriceLevelIter<'_> {
+    /// Iterator over order handles, front first.
+    pub fn iter<'a>(&self, links: &'a QueueLinks) -> PriceLevelIter<'a> {
         PriceLevelIter {
-            level: self,
-            pos: 0,
+            links,
+            next: self.head,
+            remaining: self.order_count as usize,
         }
     }
 }
@@ -149,26 +231,27 @@ impl Default for PriceLevel {
 
 /// Iterator over order handles in a price level.
 pub struct PriceLevelIter<'a> {
-    level: &'a PriceLevel,
-    pos: usize,
+    links: &'a QueueLinks,
+    next: OrderHandle,
+    remaining: usize,
 }
 
 impl<'a> Iterator for PriceLevelIter<'a> {
     type Item = OrderHandle;
     
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.level.order_count as usize {
+        if self.remaining == 0 {
             return None;
         }
         
-        let idx = ((self.level.head as usize + self.pos) % MAX_ORDERS_PER_LEVEL) as usize;
-        self.pos += 1;
-        Some(self.level.orders[idx])
+        let handle = self.next;
+        self.next = self.links.links[handle.index()].next;
+        self.remaining -= 1;
+        Some(handle)
     }
     
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.level.order_count as usize - self.pos;
-        (remaining, Some(remaining))
+        (self.remaining, Some(self.remaining))
     }
 }
 
@@ -183,21 +266,22 @@ mod tests {
     #[test]
     fn test_level_push_pop() {
         let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(1024);
         assert!(level.is_empty());
         
         // Push some orders
-        assert!(level.push_back(OrderHandle(1), Quantity(100)));
-        assert!(level.push_back(OrderHandle(2), Quantity(200)));
-        assert!(level.push_back(OrderHandle(3), Quantity(300)));
+        assert!(level.push_back(OrderHandle(1), Quantity(100), &mut links));
+        assert!(level.push_back(OrderHandle(2), Quantity(200), &mut links));
+        assert!(level.push_back(OrderHandle(3), Quantity(300), &mut links));
         
         assert_eq!(level.len(), 3);
         assert_eq!(level.total_qty.0, 600);
         
         // Pop in FIFO order
-        assert_eq!(level.pop_front(), Some(OrderHandle(1)));
-        assert_eq!(level.pop_front(), Some(OrderHandle(2)));
-        assert_eq!(level.pop_front(), Some(OrderHandle(3)));
-        assert_eq!(level.pop_front(), None);
+        assert_eq!(level.pop_front(&mut links), Some(OrderHandle(1)));
+        assert_eq!(level.pop_front(&mut links), Some(OrderHandle(2)));
+        assert_eq!(level.pop_front(&mut links), Some(OrderHandle(3)));
+        assert_eq!(level.pop_front(&mut links), None);
         
         assert!(level.is_empty());
     }
@@ -205,25 +289,26 @@ mod tests {
     #[test]
     fn test_level_wrap_around() {
         let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(1024);
         
         // Fill half
         for i in 0..512 {
-            assert!(level.push_back(OrderHandle(i), Quantity(1)));
+            assert!(level.push_back(OrderHandle(i), Quantity(1), &mut links));
         }
         
         // Pop half
         for i in 0..256 {
-            assert_eq!(level.pop_front().map(|h| h.0), Some(i));
+            assert_eq!(level.pop_front(&mut links).map(|h| h.0), Some(i));
         }
         
         // Push more (should wrap around)
         for i in 512..768 {
-            assert!(level.push_back(OrderHandle(i), Quantity(1)));
+            assert!(level.push_back(OrderHandle(i), Quantity(1), &mut links));
         }
         
         // Pop remaining
         for i in 256..768 {
-            assert_eq!(level.pop_front().map(|h| h.0), Some(i));
+            assert_eq!(level.pop_front(&mut links).map(|h| h.0), Some(i));
         }
         
         assert!(level.is_empty());
@@ -232,9 +317,10 @@ mod tests {
     #[test]
     fn test_level_front() {
         let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(1024);
         assert!(level.front().is_none());
         
-        level.push_back(OrderHandle(42), Quantity(100));
+        level.push_back(OrderHandle(42), Quantity(100), &mut links);
         assert_eq!(level.front(), Some(OrderHandle(42)));
         
         // Front doesn't remove
@@ -245,12 +331,59 @@ mod tests {
     #[test]
     fn test_level_iterator() {
         let mut level = PriceLevel::new();
-        level.push_back(OrderHandle(1), Quantity(1));
-        level.push_back(OrderHandle(2), Quantity(1));
-        level.push_back(OrderHandle(3), Quantity(1));
+        let mut links = QueueLinks::new(1024);
+        level.push_back(OrderHandle(1), Quantity(1), &mut links);
+        level.push_back(OrderHandle(2), Quantity(1), &mut links);
+        level.push_back(OrderHandle(3), Quantity(1), &mut links);
         
-        let handles: Vec<u32> = level.iter().map(|h| h.0).collect();
+        let handles: Vec<u32> = level.iter(&links).map(|h| h.0).collect();
         assert_eq!(handles, vec![1, 2, 3]);
     }
+    
+    #[test]
+    fn test_level_remove() {
+        let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(1024);
+        for i in 1..=4 {
+            level.push_back(OrderHandle(i), Quantity(1), &mut links);
+        }
+        
+        assert!(level.remove(OrderHandle(2), &mut links));
+        assert!(!level.remove(OrderHandle(2), &mut links));
+        assert!(level.remove(OrderHandle(4), &mut links));
+        level.push_back(OrderHandle(5), Quantity(1), &mut links);
+        
+        let handles: Vec<u32> = level.iter(&links).map(|h| h.0).collect();
+        assert_eq!(handles, vec![1, 3, 5]);
+    }
+    
+    #[test]
+    fn test_level_requeue_front() {
+        let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(1024);
+        level.push_back(OrderHandle(1), Quantity(10), &mut links);
+        level.push_back(OrderHandle(2), Quantity(5), &mut links);
+        level.reduce_qty(Quantity(10));
+        
+        level.requeue_front(Quantity(10), &mut links);
+        let handles: Vec<u32> = level.iter(&links).map(|h| h.0).collect();
+        assert_eq!(handles, vec![2, 1]);
+        assert_eq!(level.total_qty.0, 15);
+    }
+    
+    #[test]
+    fn test_level_holds_deep_queue() {
+        let mut level = PriceLevel::new();
+        let mut links = QueueLinks::new(4096);
+        for i in 0..4096 {
+            assert!(level.push_back(OrderHandle(i), Quantity(1), &mut links));
+        }
+        assert_eq!(level.len(), 4096);
+        assert!(level.remove(OrderHandle(2000), &mut links));
+        
+        let handles: Vec<u32> = level.iter(&links).map(|h| h.0).collect();
+        assert_eq!(handles.len(), 4095);
+        assert!(handles.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(level.pop_front(&mut links), Some(OrderHandle(0)));
+    }
 }
-
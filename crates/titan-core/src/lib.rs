@@ -17,15 +17,58 @@ pub mod fixed;
 pub mod order;
 pub mod pool;
 pub mod level;
+pub mod checksum;
+pub mod bitmap;
+pub mod memory;
 pub mod book;
+pub mod index;
+pub mod owner;
+pub mod wheel;
+pub mod trigger;
+pub mod oco;
+pub mod mmp;
+pub mod nocross;
+pub mod quotes;
+pub mod risk;
+pub mod fees;
+pub mod stats;
+pub mod events;
+pub mod auction;
+pub mod band;
+pub mod instrument;
+pub mod snapshot;
+pub mod journal;
 pub mod engine;
+pub mod recovery;
+pub mod manager;
 
 pub use fixed::{Price, Quantity};
-pub use order::{Order, OrderId, SymbolId, Side, OrderType};
+pub use order::{Order, OrderId, ParticipantId, SessionId, SymbolId, Side, OrderType};
 pub use pool::{OrderPool, OrderHandle};
 pub use level::PriceLevel;
-pub use book::{OrderBook, BookSide};
+pub use bitmap::LevelBitmap;
+pub use memory::MemoryPolicy;
+pub use book::{OrderBook, BookSide, DepthLevel, DepthSnapshot, L3Order, LevelInfo, Liquidity};
+pub use index::OrderIndex;
+pub use owner::OwnerIndex;
+pub use wheel::TimingWheel;
+pub use trigger::{TrailingStop, TriggerBook};
+pub use oco::{OcoBook, OcoPair};
+pub use mmp::{MmpBook, MmpConfig};
+pub use nocross::{CrossGroupId, CrossPolicy, NoCrossGroups};
+pub use quotes::{QuoteBook, QuoteSide};
+pub use risk::{RiskChecker, RiskConfig};
+pub use fees::{FeeRate, FeeSchedule};
+pub use stats::SessionStats;
+pub use events::{BboChanged, MatchEvents};
+pub use auction::{TradingPhase, Uncross};
+pub use band::PriceBand;
+pub use instrument::InstrumentSpec;
+pub use snapshot::{SnapshotError, Writer};
+pub use journal::{InputCommand, InputLog};
 pub use engine::{Fill, OrderResult, RejectReason, MatchingEngine};
+pub use recovery::{recover, RecoveryError, RecoveryReport};
+pub use manager::{EngineManager, ManagerStats};
 
 // Re-export atomic metrics for external observability
 pub use engine::{ORDERS_PROCESSED, FILLS_EXECUTED, ORDERS_REJECTED};
@@ -0,0 +1,209 @@
+//! Multi-symbol engine manager.
+//!
+//! Owns one `MatchingEngine` per symbol in a dense table indexed by
+//! `SymbolId`, so routing an order is a bounds check and a load. Each
+//! engine keeps its own order pool; pools are partitioned, not shared.
+
+use core::sync::atomic::Ordering;
+use alloc::vec::Vec;
+use crate::fixed::{Price, Quantity};
+use crate::instrument::InstrumentSpec;
+use crate::order::{Order, OrderId, ParticipantId, SymbolId};
+use crate::quotes::QuoteSide;
+use crate::engine::{MatchingEngine, OrderResult, RejectReason, ORDERS_PROCESSED, ORDERS_REJECTED};
+
+/// Largest symbol ID the manager accepts (bounds the dense table).
+pub const MAX_SYMBOL_ID: u32 = 1 << 16;
+
+/// Totals across all engines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ManagerStats {
+    /// Symbols with an engine.
+    pub symbols: usize,
+    /// Orders allocated across all pools.
+    pub active_orders: usize,
+    /// Combined pool capacity.
+    pub pool_capacity: usize,
+    /// Resting buy orders.
+    pub bid_orders: u64,
+    /// Resting sell orders.
+    pub ask_orders: u64,
+}
+
+/// Per-symbol matching engines.
+#[derive(Default)]
+pub struct EngineManager {
+    engines: Vec<Option<MatchingEngine>>,
+    symbols: usize,
+}
+
+impl EngineManager {
+    /// Create a manager with no symbols.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of symbols with an engine.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.symbols
+    }
+
+    /// Check if no symbols are configured.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.symbols == 0
+    }
+
+    /// Create the engine for `symbol` with a pool of `1 << pool_bits` orders.
+    ///
+    /// Returns `false` if the symbol already has an engine or its ID is
+    /// above `MAX_SYMBOL_ID`.
+    pub fn add_symbol(&mut self, symbol: SymbolId, pool_bits: u32, base_price: Price, spec: InstrumentSpec) -> bool {
+        if symbol.0 > MAX_SYMBOL_ID {
+            return false;
+        }
+        let idx = symbol.0 as usize;
+        if idx >= self.engines.len() {
+            self.engines.resize_with(idx + 1, || None);
+        }
+        if self.engines[idx].is_some() {
+            return false;
+        }
+
+        self.engines[idx] = Some(MatchingEngine::with_spec(symbol, pool_bits, base_price, spec));
+        self.symbols += 1;
+        true
+    }
+
+    /// Engine for `symbol`.
+    #[inline(always)]
+    pub fn get(&self, symbol: SymbolId) -> Option<&MatchingEngine> {
+        self.engines.get(symbol.0 as usize)?.as_ref()
+    }
+
+    /// Engine for `symbol` (mutable).
+    #[inline(always)]
+    pub fn get_mut(&mut self, symbol: SymbolId) -> Option<&mut MatchingEngine> {
+        self.engines.get_mut(symbol.0 as usize)?.as_mut()
+    }
+
+    /// Engines in symbol ID order.
+    pub fn iter(&self) -> impl Iterator<Item = &MatchingEngine> + '_ {
+        self.engines.iter().flatten()
+    }
+
+    /// Route an order to its symbol's engine.
+    ///
+    /// Rejects with `SymbolNotFound` if the symbol has no engine.
+    #[inline]
+    pub fn submit_order(&mut self, order: Order, timestamp: u64) -> OrderResult {
+        self.submit_order_as(ParticipantId::NONE, order, timestamp)
+    }
+    
+    /// Route an order owned by `participant` to its symbol's engine.
+    #[inline]
+    pub fn submit_order_as(&mut self, participant: ParticipantId, order: Order, timestamp: u64) -> OrderResult {
+        match self.get_mut(order.symbol) {
+            Some(engine) => engine.submit_order_as(participant, order, timestamp),
+            None => {
+                ORDERS_PROCESSED.fetch_add(1, Ordering::Relaxed);
+                ORDERS_REJECTED.fetch_add(1, Ordering::Relaxed);
+                OrderResult::Rejected { reason: RejectReason::SymbolNotFound }
+            }
+        }
+    }
+
+    /// Cancel a resting order on `symbol` by its client order ID.
+    #[inline]
+    pub fn cancel_by_id(&mut self, symbol: SymbolId, order_id: OrderId) -> Option<Order> {
+        self.get_mut(symbol)?.cancel_by_id(order_id)
+    }
+    
+    /// Cancel a resting order on `symbol` on behalf of `participant`.
+    pub fn cancel_as(&mut self, symbol: SymbolId, participant: ParticipantId, order_id: OrderId) -> Result<Order, RejectReason> {
+        self.get_mut(symbol)
+            .ok_or(RejectReason::SymbolNotFound)?
+            .cancel_by_id_as(participant, order_id)
+    }
+    
+    /// Amend a resting order on `symbol` on behalf of `participant`.
+    pub fn modify_as(
+        &mut self,
+        symbol: SymbolId,
+        participant: ParticipantId,
+        order_id: OrderId,
+        new_price: Price,
+        new_qty: Quantity,
+    ) -> OrderResult {
+        match self.get_mut(symbol) {
+            Some(engine) => engine.modify_by_id_as(participant, order_id, new_price, new_qty),
+            None => OrderResult::Rejected { reason: RejectReason::SymbolNotFound },
+        }
+    }
+
+    /// Set both sides of `participant`'s quote on `symbol`.
+    pub fn mass_quote(
+        &mut self,
+        symbol: SymbolId,
+        participant: ParticipantId,
+        bid: QuoteSide,
+        ask: QuoteSide,
+        timestamp: u64,
+    ) -> [OrderResult; 2] {
+        match self.get_mut(symbol) {
+            Some(engine) => engine.mass_quote(participant, bid, ask, timestamp),
+            None => [
+                OrderResult::Rejected { reason: RejectReason::SymbolNotFound },
+                OrderResult::Rejected { reason: RejectReason::SymbolNotFound },
+            ],
+        }
+    }
+    
+    /// Totals across all engines.
+    pub fn stats(&self) -> ManagerStats {
+        self.iter().fold(ManagerStats::default(), |mut stats, engine| {
+            let (active, capacity) = engine.pool_stats();
+            stats.symbols += 1;
+            stats.active_orders += active;
+            stats.pool_capacity += capacity;
+            stats.bid_orders += engine.book.bids.order_count();
+            stats.ask_orders += engine.book.asks.order_count();
+            stats
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{OrderType, Side};
+
+    fn limit(id: u64, symbol: u32, side: Side, ticks: u64) -> Order {
+        Order::new(OrderId(id), SymbolId(symbol), side, OrderType::Limit, Price::from_ticks(ticks), Quantity(10), 0)
+    }
+
+    #[test]
+    fn test_routes_by_symbol() {
+        let mut manager = EngineManager::new();
+        assert!(manager.add_symbol(SymbolId(1), 8, Price::ZERO, InstrumentSpec::default()));
+        assert!(manager.add_symbol(SymbolId(2), 8, Price::ZERO, InstrumentSpec::default()));
+        assert!(!manager.add_symbol(SymbolId(2), 8, Price::ZERO, InstrumentSpec::default()));
+
+        // Same order ID on two symbols lives in two books
+        assert!(matches!(manager.submit_order(limit(1, 1, Side::Sell, 100), 1), OrderResult::Resting { .. }));
+        assert!(matches!(manager.submit_order(limit(1, 2, Side::Buy, 100), 2), OrderResult::Resting { .. }));
+        assert!(matches!(
+            manager.submit_order(limit(2, 3, Side::Buy, 100), 3),
+            OrderResult::Rejected { reason: RejectReason::SymbolNotFound }
+        ));
+
+        let stats = manager.stats();
+        assert_eq!((stats.symbols, stats.active_orders, stats.bid_orders, stats.ask_orders), (2, 2, 1, 1));
+        assert_eq!(stats.pool_capacity, 512);
+
+        assert!(manager.cancel_by_id(SymbolId(2), OrderId(1)).is_some());
+        assert!(manager.cancel_by_id(SymbolId(3), OrderId(1)).is_none());
+        assert_eq!(manager.get(SymbolId(1)).unwrap().book.asks.order_count(), 1);
+    }
+}
@@ -0,0 +1,113 @@
+//! Placement hints for the large per-engine arrays.
+//!
+//! A `MemoryPolicy` asks the kernel to back an already allocated region
+//! with transparent huge pages (`madvise(MADV_HUGEPAGE)`) and to place it
+//! on a preferred NUMA node (`mbind(MPOL_PREFERRED)`, moving pages already
+//! touched). Only whole pages inside the region are affected. The hints
+//! need Linux and the `hugepages` feature; elsewhere they do nothing.
+
+/// Huge page and NUMA placement of a memory region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryPolicy {
+    /// Back the region with 2MB transparent huge pages.
+    pub huge_pages: bool,
+    /// NUMA node to place the region on.
+    pub numa_node: Option<u32>,
+}
+
+impl MemoryPolicy {
+    /// Huge pages on the default node.
+    pub const fn huge_pages() -> Self {
+        Self { huge_pages: true, numa_node: None }
+    }
+
+    /// Also prefer NUMA node `node`.
+    pub const fn on_node(mut self, node: u32) -> Self {
+        self.numa_node = Some(node);
+        self
+    }
+
+    /// Check if the policy asks for nothing.
+    #[inline]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply to the pages of `region`.
+    ///
+    /// Returns `false` if a requested hint could not be applied (kernel
+    /// refused, or unsupported build).
+    pub fn apply<T>(&self, region: &[T]) -> bool {
+        if self.is_default() {
+            return true;
+        }
+        sys::apply(self, region.as_ptr() as usize, core::mem::size_of_val(region))
+    }
+}
+
+#[cfg(all(feature = "hugepages", target_os = "linux"))]
+mod sys {
+    use super::MemoryPolicy;
+
+    /// `mbind` mode: allocate on the node, fall back if it is full.
+    const MPOL_PREFERRED: libc::c_long = 1;
+    /// `mbind` flag: migrate pages already allocated elsewhere.
+    const MPOL_MF_MOVE: libc::c_long = 1 << 1;
+    /// Nodes representable in the mask passed to `mbind`.
+    const MAX_NODES: usize = 1024;
+
+    pub(super) fn apply(policy: &MemoryPolicy, addr: usize, len: usize) -> bool {
+        // SAFETY: sysconf has no preconditions
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as usize;
+        let start = addr.next_multiple_of(page);
+        let end = (addr + len) / page * page;
+        if end <= start {
+            return true;
+        }
+        let (ptr, len) = (start as *mut libc::c_void, end - start);
+
+        let mut applied = true;
+        if policy.huge_pages {
+            // SAFETY: the range lies inside a live allocation owned by the caller
+            applied &= unsafe { libc::madvise(ptr, len, libc::MADV_HUGEPAGE) } == 0;
+        }
+        if let Some(node) = policy.numa_node {
+            let node = node as usize;
+            if node >= MAX_NODES {
+                return false;
+            }
+            let mut mask = [0 as libc::c_ulong; MAX_NODES / libc::c_ulong::BITS as usize];
+            mask[node / libc::c_ulong::BITS as usize] |= 1 << (node % libc::c_ulong::BITS as usize);
+            // SAFETY: as above; the mask outlives the call. The kernel reads
+            // one bit fewer than `maxnode`.
+            applied &= unsafe {
+                libc::syscall(libc::SYS_mbind, ptr, len, MPOL_PREFERRED, mask.as_ptr(), MAX_NODES + 1, MPOL_MF_MOVE)
+            } == 0;
+        }
+        applied
+    }
+}
+
+#[cfg(not(all(feature = "hugepages", target_os = "linux")))]
+mod sys {
+    use super::MemoryPolicy;
+
+    pub(super) fn apply(_policy: &MemoryPolicy, _addr: usize, _len: usize) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use super::*;
+
+    #[test]
+    fn test_policy_leaves_contents() {
+        let buffer = vec![7u64; 1 << 20];
+        assert!(MemoryPolicy::default().apply(&buffer));
+        // Huge pages may be disabled on the host; only the data must survive
+        let _ = MemoryPolicy::huge_pages().on_node(0).apply(&buffer);
+        assert!(buffer.iter().all(|&word| word == 7));
+    }
+}
@@ -0,0 +1,203 @@
+//! Market maker protection (MMP).
+//!
+//! Counts fills against each protected participant's resting quotes over
+//! a rolling time window. Once a threshold is breached the participant is
+//! tripped: the engine pulls their quotes and rejects new orders until
+//! the protection is reset. Quotes pulled this way are queued here until
+//! the caller drains them.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use crate::fixed::Quantity;
+use crate::order::{Order, ParticipantId, Side};
+
+/// Thresholds for one participant. A zero limit is not checked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MmpConfig {
+    /// Length of the rolling window (nanoseconds).
+    pub window_ns: u64,
+    /// Fills allowed within the window.
+    pub max_fills: u32,
+    /// Quantity allowed to fill within the window.
+    pub max_volume: Quantity,
+    /// Net quantity bought minus sold allowed within the window, either way.
+    pub max_delta: u64,
+}
+
+/// A fill inside the window.
+#[derive(Clone, Copy, Debug)]
+struct MmpFill {
+    timestamp: u64,
+    side: Side,
+    qty: Quantity,
+}
+
+/// Window counters of one participant.
+#[derive(Debug, Default)]
+struct MmpState {
+    config: MmpConfig,
+    fills: VecDeque<MmpFill>,
+    volume: u64,
+    delta: i64,
+    tripped: bool,
+}
+
+impl MmpState {
+    #[inline(always)]
+    fn signed(side: Side, qty: Quantity) -> i64 {
+        match side {
+            Side::Buy => qty.0 as i64,
+            Side::Sell => -(qty.0 as i64),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.fills.clear();
+        self.volume = 0;
+        self.delta = 0;
+    }
+
+    fn breached(&self) -> bool {
+        let config = &self.config;
+        (config.max_fills != 0 && self.fills.len() as u64 >= config.max_fills as u64)
+            || (!config.max_volume.is_zero() && self.volume >= config.max_volume.0)
+            || (config.max_delta != 0 && self.delta.unsigned_abs() >= config.max_delta)
+    }
+}
+
+/// Protected participants and quotes pulled on a trip.
+#[derive(Default)]
+pub struct MmpBook {
+    participants: BTreeMap<ParticipantId, MmpState>,
+    cancelled: VecDeque<Order>,
+}
+
+impl MmpBook {
+    /// Create a book with no protected participants.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of protected participants.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.participants.len()
+    }
+
+    /// Check if no participant is protected.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.participants.is_empty()
+    }
+
+    /// Protect `participant` with `config`, clearing its counters.
+    ///
+    /// A tripped participant stays tripped.
+    pub fn set(&mut self, participant: ParticipantId, config: MmpConfig) {
+        let state = self.participants.entry(participant).or_default();
+        state.config = config;
+        state.clear();
+    }
+
+    /// Stop protecting `participant`, returning its thresholds.
+    pub fn remove(&mut self, participant: ParticipantId) -> Option<MmpConfig> {
+        self.participants.remove(&participant).map(|state| state.config)
+    }
+
+    /// Thresholds of `participant`.
+    pub fn config(&self, participant: ParticipantId) -> Option<MmpConfig> {
+        self.participants.get(&participant).map(|state| state.config)
+    }
+
+    /// Check if `participant` is tripped.
+    #[inline(always)]
+    pub fn is_tripped(&self, participant: ParticipantId) -> bool {
+        self.participants.get(&participant).is_some_and(|state| state.tripped)
+    }
+
+    /// Re-arm a tripped participant with empty counters.
+    ///
+    /// Returns `false` if the participant is not protected.
+    pub fn reset(&mut self, participant: ParticipantId) -> bool {
+        let Some(state) = self.participants.get_mut(&participant) else {
+            return false;
+        };
+        state.tripped = false;
+        state.clear();
+        true
+    }
+
+    /// Count a fill of `qty` against a `side` quote of `participant`.
+    ///
+    /// Returns `true` if this fill trips the participant.
+    #[inline]
+    pub fn on_fill(&mut self, participant: ParticipantId, side: Side, qty: Quantity, timestamp: u64) -> bool {
+        let Some(state) = self.participants.get_mut(&participant) else {
+            return false;
+        };
+        if state.tripped {
+            return false;
+        }
+
+        // Age out fills that left the window
+        let horizon = timestamp.saturating_sub(state.config.window_ns);
+        while let Some(old) = state.fills.front().copied() {
+            if old.timestamp >= horizon {
+                break;
+            }
+            state.fills.pop_front();
+            state.volume -= old.qty.0;
+            state.delta -= MmpState::signed(old.side, old.qty);
+        }
+
+        state.fills.push_back(MmpFill { timestamp, side, qty });
+        state.volume += qty.0;
+        state.delta += MmpState::signed(side, qty);
+
+        if state.breached() {
+            state.tripped = true;
+            state.clear();
+            return true;
+        }
+        false
+    }
+
+    /// Queue a quote pulled by the engine.
+    #[inline]
+    pub(crate) fn push_cancelled(&mut self, order: Order) {
+        self.cancelled.push_back(order);
+    }
+
+    /// Next quote pulled by the engine, oldest first.
+    #[inline]
+    pub fn pop_cancelled(&mut self) -> Option<Order> {
+        self.cancelled.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_window() {
+        let mut mmp = MmpBook::new();
+        let maker = ParticipantId(1);
+        mmp.set(maker, MmpConfig { window_ns: 100, max_fills: 3, ..MmpConfig::default() });
+
+        assert!(!mmp.on_fill(ParticipantId(2), Side::Buy, Quantity(1), 0));
+        assert!(!mmp.on_fill(maker, Side::Buy, Quantity(1), 0));
+        assert!(!mmp.on_fill(maker, Side::Buy, Quantity(1), 50));
+        // The first fill ages out
+        assert!(!mmp.on_fill(maker, Side::Sell, Quantity(1), 101));
+        assert!(mmp.on_fill(maker, Side::Sell, Quantity(1), 120));
+        assert!(mmp.is_tripped(maker));
+        assert!(!mmp.on_fill(maker, Side::Sell, Quantity(1), 130));
+
+        assert!(mmp.reset(maker));
+        assert!(!mmp.is_tripped(maker));
+        mmp.set(maker, MmpConfig { window_ns: 100, max_delta: 5, ..MmpConfig::default() });
+        assert!(!mmp.on_fill(maker, Side::Buy, Quantity(4), 200));
+        assert!(!mmp.on_fill(maker, Side::Sell, Quantity(3), 210));
+        assert!(mmp.on_fill(maker, Side::Buy, Quantity(4), 220));
+    }
+}
@@ -0,0 +1,100 @@
+//! No-cross groups (anti-internalization).
+//!
+//! Participants assigned to the same group (desks of one firm, say) never
+//! trade with each other. When an incoming order would match a resting
+//! order of its own group the engine applies the group policy instead of
+//! trading. A participant in a group of its own gets plain self-trade
+//! prevention; participants without a group are never restricted.
+//! Resting orders cancelled by the policy are queued here until the
+//! caller drains them.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use crate::order::{Order, ParticipantId};
+
+/// No-cross group identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct CrossGroupId(pub u32);
+
+/// What happens when an order meets a resting order of its own group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossPolicy {
+    /// Cancel the resting order and keep matching.
+    #[default]
+    CancelResting,
+    /// Cancel the rest of the incoming order.
+    CancelAggressor,
+    /// Cancel the resting order and the rest of the incoming order.
+    CancelBoth,
+    /// Reduce both by the smaller quantity without trading; whichever is
+    /// used up is cancelled.
+    Decrement,
+}
+
+/// Group membership, the policy in force and resting orders it cancelled.
+#[derive(Default)]
+pub struct NoCrossGroups {
+    groups: BTreeMap<ParticipantId, CrossGroupId>,
+    policy: CrossPolicy,
+    cancelled: VecDeque<Order>,
+}
+
+impl NoCrossGroups {
+    /// Create an empty table (nothing restricted).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put `participant` in `group`, leaving any previous one.
+    pub fn assign(&mut self, participant: ParticipantId, group: CrossGroupId) {
+        if participant != ParticipantId::NONE {
+            self.groups.insert(participant, group);
+        }
+    }
+
+    /// Take `participant` out of its group.
+    pub fn remove(&mut self, participant: ParticipantId) {
+        self.groups.remove(&participant);
+    }
+
+    /// Group of `participant`.
+    #[inline]
+    pub fn group_of(&self, participant: ParticipantId) -> Option<CrossGroupId> {
+        self.groups.get(&participant).copied()
+    }
+
+    /// Check if orders of `a` and `b` must not trade with each other.
+    #[inline]
+    pub fn same_group(&self, a: ParticipantId, b: ParticipantId) -> bool {
+        if self.groups.is_empty() {
+            return false;
+        }
+        match (self.group_of(a), self.group_of(b)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// Policy applied to every group.
+    #[inline(always)]
+    pub fn policy(&self) -> CrossPolicy {
+        self.policy
+    }
+
+    /// Change the policy applied to every group.
+    pub fn set_policy(&mut self, policy: CrossPolicy) {
+        self.policy = policy;
+    }
+
+    /// Queue a resting order cancelled by the policy.
+    #[inline]
+    pub fn push_cancelled(&mut self, order: Order) {
+        self.cancelled.push_back(order);
+    }
+
+    /// Oldest queued cancel.
+    #[inline]
+    pub fn pop_cancelled(&mut self) -> Option<Order> {
+        self.cancelled.pop_front()
+    }
+}
@@ -0,0 +1,109 @@
+//! One-Cancels-Other links.
+//!
+//! Linked orders carry `FLAG_OCO`, so the matching path only consults the
+//! link table when a flagged maker trades. Partners cancelled because of
+//! a fill are queued here until the caller drains them.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use crate::order::{Order, OrderId};
+
+/// A linked pair of resting orders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OcoPair {
+    /// Client-chosen link identifier.
+    pub link_id: u64,
+    pub first: OrderId,
+    pub second: OrderId,
+}
+
+impl OcoPair {
+    /// The other order of the pair.
+    #[inline(always)]
+    fn partner(&self, order_id: OrderId) -> Option<OrderId> {
+        if self.first == order_id {
+            Some(self.second)
+        } else if self.second == order_id {
+            Some(self.first)
+        } else {
+            None
+        }
+    }
+}
+
+/// Active OCO links and cancels waiting to be reported.
+#[derive(Default)]
+pub struct OcoBook {
+    pairs: Vec<OcoPair>,
+    cancelled: VecDeque<Order>,
+}
+
+impl OcoBook {
+    /// Create an empty link table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of active links.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Check if there are no active links.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Link two orders.
+    pub fn link(&mut self, link_id: u64, first: OrderId, second: OrderId) {
+        self.pairs.push(OcoPair { link_id, first, second });
+    }
+
+    /// Link containing `order_id`.
+    pub fn get(&self, order_id: OrderId) -> Option<&OcoPair> {
+        self.pairs.iter().find(|p| p.partner(order_id).is_some())
+    }
+
+    /// Dissolve the link containing `order_id`, returning the partner.
+    pub fn unlink(&mut self, order_id: OrderId) -> Option<OrderId> {
+        let pos = self.pairs.iter().position(|p| p.partner(order_id).is_some())?;
+        self.pairs.swap_remove(pos).partner(order_id)
+    }
+
+    /// Active links, oldest first until one is dissolved.
+    pub fn iter(&self) -> impl Iterator<Item = &OcoPair> + '_ {
+        self.pairs.iter()
+    }
+
+    /// Queue a partner cancelled by the engine.
+    #[inline]
+    pub(crate) fn push_cancelled(&mut self, order: Order) {
+        self.cancelled.push_back(order);
+    }
+
+    /// Next partner cancelled by the engine, oldest first.
+    #[inline]
+    pub fn pop_cancelled(&mut self) -> Option<Order> {
+        self.cancelled.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_unlink() {
+        let mut links = OcoBook::new();
+        links.link(7, OrderId(1), OrderId(2));
+        links.link(8, OrderId(3), OrderId(4));
+
+        assert_eq!(links.get(OrderId(2)).map(|p| p.link_id), Some(7));
+        assert_eq!(links.unlink(OrderId(4)), Some(OrderId(3)));
+        assert_eq!(links.unlink(OrderId(3)), None);
+        assert_eq!(links.unlink(OrderId(1)), Some(OrderId(2)));
+        assert!(links.is_empty());
+    }
+}
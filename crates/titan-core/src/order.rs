@@ -50,16 +50,28 @@ pub enum OrderType {
     FOK = 2,
     /// Post-Only: reject if would immediately match (maker-only).
     PostOnly = 3,
+    /// Market: sweep the opposite side at any price, cancel the rest.
+    Market = 4,
+    /// Good-Til-Date: rests like Limit until its expiry timestamp.
+    GTD = 5,
+    /// Day: rests like Limit until the session ends.
+    Day = 6,
 }
 
 impl OrderType {
     /// Check if order should rest on book after partial fill.
     #[inline(always)]
     pub const fn should_rest(self) -> bool {
-        matches!(self, OrderType::Limit | OrderType::PostOnly)
+        matches!(self, OrderType::Limit | OrderType::PostOnly | OrderType::GTD | OrderType::Day)
     }
 }
 
+/// Order is one leg of an OCO link (see `OcoBook`).
+pub const FLAG_OCO: u8 = 1 << 0;
+
+/// Sell order is a short sale (subject to the uptick rule when armed).
+pub const FLAG_SHORT_SELL: u8 = 1 << 1;
+
 /// Symbol identifier.
 ///
 /// Pre-hashed at order entry. Maps "AAPL" → SymbolId(42) at startup.
@@ -72,6 +84,26 @@ impl SymbolId {
     pub const INVALID: Self = Self(u32::MAX);
 }
 
+/// Participant (member / trading session) owning an order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct ParticipantId(pub u32);
+
+impl ParticipantId {
+    /// No owner (orders are not attributed).
+    pub const NONE: Self = Self(0);
+}
+
+/// Client connection (gateway session) an order was entered on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct SessionId(pub u32);
+
+impl SessionId {
+    /// No session (orders are not tagged).
+    pub const NONE: Self = Self(0);
+}
+
 /// Unique order identifier.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 #[repr(transparent)]
@@ -104,20 +136,26 @@ pub struct Order {
     /// Timestamp (RDTSC or monotonic nanos).
     pub timestamp: u64,             // 8 bytes
     
-    // === WARM FIELDS (accessed occasionally) === 15 bytes
+    // === WARM FIELDS (accessed occasionally) === 16 bytes
     /// Original quantity when order was placed.
     pub original_qty: Quantity,     // 8 bytes
     /// Symbol identifier.
     pub symbol: SymbolId,           // 4 bytes
     /// Order side (buy/sell).
     pub side: Side,                 // 1 byte
-    /// Order type (limit, IOC, FOK, post-only).
+    /// Order type (limit, IOC, FOK, post-only, market).
     pub order_type: OrderType,      // 1 byte
     /// Bitflags for special handling.
     pub flags: u8,                  // 1 byte
+    _padding: [u8; 1],              // 1 byte
     
-    // === PADDING to 64 bytes ===
-    _padding: [u8; 17],             // 17 bytes
+    // === ICEBERG / GTD FIELDS === 16 bytes
+    /// Clip size shown on the book (zero = fully displayed).
+    pub display_qty: u32,           // 4 bytes
+    /// Quantity of the current clip still displayed (MinQty until the order rests).
+    pub visible_qty: u32,           // 4 bytes
+    /// Expiry timestamp for GTD orders (zero = none).
+    pub expire_at: u64,             // 8 bytes
 }
 
 // Compile-time assertion that Order is exactly 64 bytes.
@@ -145,10 +183,82 @@ impl Order {
             remaining_qty: qty,
             timestamp,
             flags: 0,
-            _padding: [0; 17],
+            _padding: [0; 1],
+            display_qty: 0,
+            visible_qty: 0,
+            expire_at: 0,
         }
     }
     
+    /// Make this an iceberg order showing `display_qty` at a time.
+    ///
+    /// A display quantity of zero, or one covering the whole order,
+    /// leaves the order fully displayed. Clips are capped at `u32::MAX`.
+    #[inline(always)]
+    pub fn with_display_qty(mut self, display_qty: Quantity) -> Self {
+        self.display_qty = if display_qty.0 < self.original_qty.0 {
+            display_qty.0.min(u32::MAX as u64) as u32
+        } else {
+            0
+        };
+        self
+    }
+    
+    /// Only execute if at least `min_qty` can fill on entry.
+    ///
+    /// Shares storage with the iceberg clip, which is only set once the
+    /// order rests, so the constraint covers the aggressive sweep alone.
+    /// Capped at the order quantity and at `u32::MAX`.
+    #[inline(always)]
+    pub fn with_min_qty(mut self, min_qty: Quantity) -> Self {
+        self.visible_qty = min_qty.0.min(self.original_qty.0).min(u32::MAX as u64) as u32;
+        self
+    }
+    
+    /// Minimum quantity that must fill on entry (zero = none).
+    #[inline(always)]
+    pub const fn min_qty(&self) -> Quantity {
+        Quantity(self.visible_qty as u64)
+    }
+    
+    /// Expire the order at `expire_at` (only honoured for GTD orders).
+    #[inline(always)]
+    pub fn with_expiry(mut self, expire_at: u64) -> Self {
+        self.expire_at = expire_at;
+        self
+    }
+    
+    /// Mark a sell order as a short sale.
+    #[inline(always)]
+    pub fn with_short_sell(mut self) -> Self {
+        self.flags |= FLAG_SHORT_SELL;
+        self
+    }
+    
+    /// Check if the order is marked as a short sale.
+    #[inline(always)]
+    pub const fn is_short_sell(&self) -> bool {
+        self.flags & FLAG_SHORT_SELL != 0
+    }
+    
+    /// Check if only part of the order is displayed.
+    #[inline(always)]
+    pub const fn is_iceberg(&self) -> bool {
+        self.display_qty != 0
+    }
+    
+    /// Quantity visible on the book.
+    #[inline(always)]
+    pub const fn displayed_qty(&self) -> Quantity {
+        if self.is_iceberg() { Quantity(self.visible_qty as u64) } else { self.remaining_qty }
+    }
+    
+    /// Show the next clip (or the whole remainder if smaller).
+    #[inline(always)]
+    pub fn replenish(&mut self) {
+        self.visible_qty = self.remaining_qty.0.min(self.display_qty as u64) as u32;
+    }
+    
     /// Check if order is completely filled.
     #[inline(always)]
     pub const fn is_filled(&self) -> bool {
@@ -163,6 +273,7 @@ impl Order {
     pub fn fill(&mut self, qty: Quantity) {
         debug_assert!(qty.0 <= self.remaining_qty.0, "Fill quantity exceeds remaining");
         self.remaining_qty = self.remaining_qty.saturating_sub(qty);
+        self.visible_qty = (self.visible_qty as u64).saturating_sub(qty.0) as u32;
     }
     
     /// Get filled quantity.
@@ -196,7 +307,10 @@ impl Default for Order {
             side: Side::Buy,
             order_type: OrderType::Limit,
             flags: 0,
-            _padding: [0; 17],
+            _padding: [0; 1],
+            display_qty: 0,
+            visible_qty: 0,
+            expire_at: 0,
         }
     }
 }
@@ -232,6 +346,25 @@ mod tests {
         assert_eq!(order.filled_qty().0, 100);
     }
     
+    #[test]
+    fn test_iceberg_clips() {
+        let order = Order::new(
+            OrderId(1), SymbolId(1), Side::Sell, OrderType::Limit,
+            Price::from_ticks(100), Quantity(100), 0,
+        );
+        assert!(!order.with_display_qty(Quantity(100)).is_iceberg());
+        
+        let mut order = order.with_display_qty(Quantity(30));
+        order.replenish();
+        assert_eq!(order.displayed_qty().0, 30);
+        
+        order.fill(Quantity(30));
+        assert_eq!(order.displayed_qty().0, 0);
+        order.fill(Quantity(60));
+        order.replenish();
+        assert_eq!(order.displayed_qty().0, 10);
+    }
+    
     #[test]
     fn test_side_opposite() {
         assert_eq!(Side::Buy.opposite(), Side::Sell);
@@ -0,0 +1,157 @@
+//! Order ownership index.
+//!
+//! Records which participant owns each resting order, and threads every
+//! participant's orders onto an intrusive doubly linked list through
+//! per-handle `next`/`prev` arrays. Insert and remove are O(1) and only
+//! allocate the first time a participant is seen; walking one
+//! participant's orders never touches anyone else's.
+//!
+//! The index is generic over the owner key so the engine can also group
+//! orders by client session (`OwnerIndex<SessionId>`). The key's default
+//! value means "no owner".
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use crate::order::ParticipantId;
+use crate::pool::OrderHandle;
+
+/// Head and length of one participant's list.
+#[derive(Clone, Copy, Debug)]
+struct OwnerList {
+    head: OrderHandle,
+    len: u32,
+}
+
+/// Owner of every pool slot plus per-participant order lists.
+pub struct OwnerIndex<K = ParticipantId> {
+    owners: Box<[K]>,
+    next: Box<[OrderHandle]>,
+    prev: Box<[OrderHandle]>,
+    /// List of each participant with resting orders.
+    lists: BTreeMap<K, OwnerList>,
+}
+
+impl<K: Copy + Ord + Default> OwnerIndex<K> {
+    /// Create an index for a pool of `capacity` orders.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            owners: vec![K::default(); capacity].into_boxed_slice(),
+            next: vec![OrderHandle::INVALID; capacity].into_boxed_slice(),
+            prev: vec![OrderHandle::INVALID; capacity].into_boxed_slice(),
+            lists: BTreeMap::new(),
+        }
+    }
+
+    /// Extend to a pool of `capacity` orders.
+    pub fn grow(&mut self, capacity: usize) {
+        let mut owners = core::mem::take(&mut self.owners).into_vec();
+        owners.resize(capacity, K::default());
+        self.owners = owners.into_boxed_slice();
+        for links in [&mut self.next, &mut self.prev] {
+            let mut grown = core::mem::take(links).into_vec();
+            grown.resize(capacity, OrderHandle::INVALID);
+            *links = grown.into_boxed_slice();
+        }
+    }
+
+    /// Owner of the order in `handle`.
+    #[inline(always)]
+    pub fn owner(&self, handle: OrderHandle) -> K {
+        self.owners[handle.index()]
+    }
+
+    /// Record `participant` as the owner of `handle`.
+    ///
+    /// Orders without an owner are not linked anywhere.
+    #[inline]
+    pub fn insert(&mut self, handle: OrderHandle, participant: K) {
+        self.owners[handle.index()] = participant;
+        if participant == K::default() {
+            return;
+        }
+
+        let list = self.lists.entry(participant).or_insert(OwnerList { head: OrderHandle::INVALID, len: 0 });
+        self.next[handle.index()] = list.head;
+        self.prev[handle.index()] = OrderHandle::INVALID;
+        if list.head.is_valid() {
+            self.prev[list.head.index()] = handle;
+        }
+        list.head = handle;
+        list.len += 1;
+    }
+
+    /// Forget the owner of `handle`.
+    #[inline]
+    pub fn remove(&mut self, handle: OrderHandle) {
+        let participant = core::mem::take(&mut self.owners[handle.index()]);
+        if participant == K::default() {
+            return;
+        }
+
+        let next = self.next[handle.index()];
+        let prev = self.prev[handle.index()];
+        if next.is_valid() {
+            self.prev[next.index()] = prev;
+        }
+        if prev.is_valid() {
+            self.next[prev.index()] = next;
+        }
+
+        let Some(list) = self.lists.get_mut(&participant) else { return };
+        list.len -= 1;
+        if list.len == 0 {
+            self.lists.remove(&participant);
+        } else if !prev.is_valid() {
+            list.head = next;
+        }
+    }
+
+    /// Number of resting orders of `participant`.
+    #[inline]
+    pub fn count(&self, participant: K) -> u32 {
+        self.lists.get(&participant).map_or(0, |list| list.len)
+    }
+
+    /// Resting orders of `participant`, most recent first.
+    pub fn handles(&self, participant: K) -> impl Iterator<Item = OrderHandle> + '_ {
+        let head = self.lists.get(&participant).map_or(OrderHandle::INVALID, |list| list.head);
+        core::iter::successors(Some(head).filter(|h| h.is_valid()), move |h| {
+            Some(self.next[h.index()]).filter(|n| n.is_valid())
+        })
+    }
+
+    /// Participants with at least one resting order.
+    pub fn participants(&self) -> impl Iterator<Item = K> + '_ {
+        self.lists.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_owner_lists() {
+        let mut owners = OwnerIndex::with_capacity(8);
+        let alice = ParticipantId(1);
+        let bob = ParticipantId(2);
+        for h in 0..4 {
+            owners.insert(OrderHandle(h), if h % 2 == 0 { alice } else { bob });
+        }
+        owners.insert(OrderHandle(4), ParticipantId::NONE);
+
+        let of = |owners: &OwnerIndex, p: ParticipantId| owners.handles(p).map(|h| h.0).collect::<Vec<_>>();
+        assert_eq!(of(&owners, alice), vec![2, 0]);
+        assert_eq!(owners.owner(OrderHandle(3)), bob);
+        assert_eq!(owners.count(bob), 2);
+
+        owners.remove(OrderHandle(2));
+        owners.remove(OrderHandle(4));
+        assert_eq!(of(&owners, alice), vec![0]);
+        owners.remove(OrderHandle(0));
+        assert!(of(&owners, alice).is_empty());
+        assert_eq!(owners.participants().collect::<Vec<_>>(), vec![bob]);
+    }
+}
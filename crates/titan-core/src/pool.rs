@@ -1,16 +1,43 @@
 //! Object pool for zero-allocation order management.
 //!
-//! Pre-allocates all order slots at startup. Uses LIFO free list
+//! Pre-allocates order slots at startup. Uses LIFO free list
 //! for better cache locality on recently deallocated slots.
+//!
+//! Slots live in fixed-size chunks. A pool given a limit above its
+//! starting capacity adds a chunk when it runs out instead of failing;
+//! existing chunks never move, so orders keep their address.
+//!
+//! Each slot carries a generation that is bumped when it is freed and
+//! stamped into the handles it hands out, so a handle kept past its
+//! order's release no longer resolves once the slot has been recycled.
+//! A live bit per slot (set once an order is written, cleared on free)
+//! keeps `try_get` off slots that hold no order, and an allocated bit
+//! keeps a slot from being freed twice; refused frees are counted.
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use crate::bitmap::LevelBitmap;
+use crate::level::QueueLinks;
+use crate::memory::MemoryPolicy;
 use crate::order::Order;
 
+/// Bits of a handle holding the slot index.
+pub const HANDLE_INDEX_BITS: u32 = 24;
+
+/// log2 of the largest chunk (64K orders); smaller pools use one chunk
+/// of their own size.
+const MAX_CHUNK_BITS: u32 = 16;
+
+/// Generations a slot cycles through (the all-ones value is skipped so
+/// no live handle equals `OrderHandle::INVALID`).
+const GENERATIONS: u8 = u8::MAX;
+
 /// Index into the order pool.
 ///
-/// Uses u32 to save space (supports up to 4 billion orders).
+/// Uses u32 to save space: the low `HANDLE_INDEX_BITS` hold the slot
+/// index (up to 16M orders), the high 8 the slot generation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct OrderHandle(pub u32);
@@ -19,6 +46,12 @@ impl OrderHandle {
     /// Invalid handle constant.
     pub const INVALID: Self = Self(u32::MAX);
     
+    /// Handle to slot `index` at `generation`.
+    #[inline(always)]
+    pub const fn new(index: u32, generation: u8) -> Self {
+        Self((generation as u32) << HANDLE_INDEX_BITS | index)
+    }
+    
     /// Check if handle is valid.
     #[inline(always)]
     pub const fn is_valid(self) -> bool {
@@ -28,7 +61,13 @@ impl OrderHandle {
     /// Get raw index.
     #[inline(always)]
     pub const fn index(self) -> usize {
-        self.0 as usize
+        (self.0 & ((1 << HANDLE_INDEX_BITS) - 1)) as usize
+    }
+    
+    /// Generation of the slot this handle was issued for.
+    #[inline(always)]
+    pub const fn generation(self) -> u8 {
+        (self.0 >> HANDLE_INDEX_BITS) as u8
     }
 }
 
@@ -42,12 +81,28 @@ impl Default for OrderHandle {
 ///
 /// Capacity should be power of 2 for efficient operations.
 pub struct OrderPool {
-    /// Storage for orders.
-    orders: Box<[MaybeUninit<Order>]>,
+    /// Storage for orders, `1 << chunk_bits` per chunk.
+    chunks: Vec<Box<[MaybeUninit<Order>]>>,
+    /// log2 of the chunk size.
+    chunk_bits: u32,
+    /// Price level queue links of each slot.
+    links: QueueLinks,
+    /// Current generation of each slot.
+    generations: Box<[u8]>,
+    /// Slots holding an order.
+    live: LevelBitmap,
+    /// Slots handed out and not yet freed.
+    allocated: LevelBitmap,
+    /// Frees refused as double or stale.
+    invalid_frees: u64,
     /// LIFO free list for O(1) alloc/dealloc.
     free_list: Vec<u32>,
     /// Total capacity.
     capacity: u32,
+    /// Capacity the pool may grow to.
+    limit: u32,
+    /// Placement of order chunks.
+    policy: MemoryPolicy,
     /// Number of active orders.
     active_count: u32,
 }
@@ -60,31 +115,109 @@ impl OrderPool {
     /// - `order_bits = 24` → 16,777,216 orders
     ///
     /// # Panics
-    /// Panics if order_bits > 28 (256M orders max).
+    /// Panics if order_bits > 24 (16M orders max).
     pub fn new(order_bits: u32) -> Self {
-        assert!(order_bits <= 28, "Pool too large (max 2^28)");
+        assert!(order_bits <= HANDLE_INDEX_BITS, "Pool too large (max 2^24)");
         let capacity = 1u32 << order_bits;
+        let chunk_bits = order_bits.min(MAX_CHUNK_BITS);
+        
+        let mut pool = Self {
+            chunks: Vec::new(),
+            chunk_bits,
+            links: QueueLinks::new(0),
+            generations: Box::default(),
+            live: LevelBitmap::new(0),
+            allocated: LevelBitmap::new(0),
+            invalid_frees: 0,
+            free_list: Vec::with_capacity(capacity as usize),
+            capacity: 0,
+            limit: capacity,
+            policy: MemoryPolicy::default(),
+            active_count: 0,
+        };
+        for _ in 0..capacity >> chunk_bits {
+            pool.add_chunk();
+        }
+        pool
+    }
+    
+    /// Create a pool of 2^order_bits orders that grows by chunks up to
+    /// 2^max_bits when it runs out.
+    pub fn growable(order_bits: u32, max_bits: u32) -> Self {
+        let mut pool = Self::new(order_bits);
+        pool.set_limit(max_bits);
+        pool
+    }
+    
+    /// Let the pool grow to 2^max_bits orders (never below its current
+    /// capacity).
+    ///
+    /// # Panics
+    /// Panics if max_bits > 24.
+    pub fn set_limit(&mut self, max_bits: u32) {
+        assert!(max_bits <= HANDLE_INDEX_BITS, "Pool too large (max 2^24)");
+        self.limit = (1u32 << max_bits).max(self.capacity);
+    }
+    
+    /// Capacity the pool may grow to.
+    #[inline(always)]
+    pub fn limit(&self) -> usize {
+        self.limit as usize
+    }
+    
+    /// Place order storage (existing chunks and any added later) per
+    /// `policy`. Returns `false` if the hints could not all be applied.
+    pub fn set_memory_policy(&mut self, policy: MemoryPolicy) -> bool {
+        self.policy = policy;
+        let mut applied = true;
+        for chunk in &self.chunks {
+            applied &= policy.apply(chunk);
+        }
+        applied
+    }
+    
+    /// Append one chunk of free slots.
+    #[cold]
+    fn add_chunk(&mut self) {
+        let chunk_size = 1usize << self.chunk_bits;
         
         // Allocate uninitialized storage
-        let mut orders: Vec<MaybeUninit<Order>> = Vec::with_capacity(capacity as usize);
+        let mut orders: Vec<MaybeUninit<Order>> = Vec::with_capacity(chunk_size);
         // SAFETY: MaybeUninit doesn't require initialization
-        unsafe { orders.set_len(capacity as usize); }
+        unsafe { orders.set_len(chunk_size); }
+        self.policy.apply(&orders);
+        self.chunks.push(orders.into_boxed_slice());
         
-        // Pre-populate free list in reverse (LIFO gives better cache locality)
-        let free_list: Vec<u32> = (0..capacity).rev().collect();
+        let start = self.capacity;
+        self.capacity += chunk_size as u32;
+        self.links.grow(self.capacity as usize);
+        let mut generations = core::mem::take(&mut self.generations).into_vec();
+        generations.resize(self.capacity as usize, 0);
+        self.generations = generations.into_boxed_slice();
+        self.live.grow(self.capacity as usize);
+        self.allocated.grow(self.capacity as usize);
         
-        Self {
-            orders: orders.into_boxed_slice(),
-            free_list,
-            capacity,
-            active_count: 0,
-        }
+        // Free list in reverse (LIFO gives better cache locality)
+        self.free_list.extend((start..self.capacity).rev());
+    }
+    
+    /// Storage slot of `handle` (already checked to be current).
+    #[inline(always)]
+    fn slot(&self, handle: OrderHandle) -> &MaybeUninit<Order> {
+        let idx = handle.index();
+        &self.chunks[idx >> self.chunk_bits][idx & ((1 << self.chunk_bits) - 1)]
+    }
+    
+    #[inline(always)]
+    fn slot_mut(&mut self, handle: OrderHandle) -> &mut MaybeUninit<Order> {
+        let idx = handle.index();
+        &mut self.chunks[idx >> self.chunk_bits][idx & ((1 << self.chunk_bits) - 1)]
     }
     
     /// Create a pool with specified capacity (must be power of 2).
     pub fn with_capacity(capacity: usize) -> Self {
         assert!(capacity.is_power_of_two(), "Capacity must be power of 2");
-        assert!(capacity <= (1 << 28), "Capacity too large");
+        assert!(capacity <= (1 << HANDLE_INDEX_BITS), "Capacity too large");
         
         let bits = capacity.trailing_zeros();
         Self::new(bits)
@@ -92,55 +225,106 @@ impl OrderPool {
     
     /// Allocate an order slot.
     ///
+    /// Grows the pool if it is out of slots and below its limit.
     /// Returns `None` if pool is exhausted.
     #[inline(always)]
     pub fn allocate(&mut self) -> Option<OrderHandle> {
+        if self.free_list.is_empty() && self.capacity < self.limit {
+            self.add_chunk();
+        }
         self.free_list.pop().map(|idx| {
             self.active_count += 1;
-            OrderHandle(idx)
+            self.allocated.set(idx as usize);
+            OrderHandle::new(idx, self.generations[idx as usize])
         })
     }
     
+    /// Check if `handle` is the current handle of its slot.
+    #[inline(always)]
+    fn is_current(&self, handle: OrderHandle) -> bool {
+        self.generations.get(handle.index()) == Some(&handle.generation())
+    }
+    
+    /// Check if `handle` is current and its slot holds an order.
+    #[inline(always)]
+    pub fn is_live(&self, handle: OrderHandle) -> bool {
+        self.is_current(handle) && self.live.contains(handle.index())
+    }
+    
+    /// Every live order with its handle, in slot order.
+    pub fn iter_active(&self) -> impl Iterator<Item = (OrderHandle, &Order)> + '_ {
+        core::iter::successors(self.live.first_from(0), move |&idx| self.live.first_from(idx + 1))
+            .map(move |idx| {
+                let handle = OrderHandle::new(idx as u32, self.generations[idx]);
+                (handle, &self[handle])
+            })
+    }
+    
     /// Return an order slot to the pool.
     ///
-    /// # Safety
-    /// The handle must have been previously allocated and not yet deallocated.
+    /// Returns `false` and frees nothing if the handle is stale (its slot
+    /// was already freed since it was issued) or its slot is not
+    /// allocated; the refusal is counted in `invalid_frees`.
     #[inline(always)]
-    pub fn deallocate(&mut self, handle: OrderHandle) {
-        debug_assert!(handle.0 < self.capacity, "Invalid handle");
-        debug_assert!(self.active_count > 0, "Double deallocation");
+    pub fn deallocate(&mut self, handle: OrderHandle) -> bool {
+        if !self.is_current(handle) || !self.allocated.contains(handle.index()) {
+            self.invalid_frees += 1;
+            return false;
+        }
         
-        self.free_list.push(handle.0);
+        let idx = handle.index();
+        self.generations[idx] = (self.generations[idx] + 1) % GENERATIONS;
+        self.live.clear(idx);
+        self.allocated.clear(idx);
+        self.free_list.push(idx as u32);
         self.active_count -= 1;
+        true
     }
     
     /// Get immutable reference to order.
     ///
-    /// # Safety
-    /// Handle must point to an initialized order.
+    /// Returns `None` if the handle is out of range or stale, or its slot
+    /// holds no order.
     #[inline(always)]
-    pub fn get(&self, handle: OrderHandle) -> &Order {
-        debug_assert!(handle.0 < self.capacity, "Handle out of bounds");
-        // SAFETY: Caller ensures handle points to initialized order
-        unsafe { self.orders[handle.index()].assume_init_ref() }
+    pub fn try_get(&self, handle: OrderHandle) -> Option<&Order> {
+        if !self.is_live(handle) {
+            return None;
+        }
+        // SAFETY: Live slots hold initialized orders
+        Some(unsafe { self.slot(handle).assume_init_ref() })
     }
     
     /// Get mutable reference to order.
     ///
-    /// # Safety
-    /// Handle must point to an initialized order.
+    /// Returns `None` if the handle is out of range or stale, or its slot
+    /// holds no order.
+    #[inline(always)]
+    pub fn try_get_mut(&mut self, handle: OrderHandle) -> Option<&mut Order> {
+        if !self.is_live(handle) {
+            return None;
+        }
+        // SAFETY: Live slots hold initialized orders
+        Some(unsafe { self.slot_mut(handle).assume_init_mut() })
+    }
+    
+    /// Price level queue links of every slot.
+    #[inline(always)]
+    pub fn links(&self) -> &QueueLinks {
+        &self.links
+    }
+    
+    /// Mutable queue links, for queueing and unlinking orders.
     #[inline(always)]
-    pub fn get_mut(&mut self, handle: OrderHandle) -> &mut Order {
-        debug_assert!(handle.0 < self.capacity, "Handle out of bounds");
-        // SAFETY: Caller ensures handle points to initialized order
-        unsafe { self.orders[handle.index()].assume_init_mut() }
+    pub fn links_mut(&mut self) -> &mut QueueLinks {
+        &mut self.links
     }
     
     /// Write a new order into the slot.
     #[inline(always)]
     pub fn insert(&mut self, handle: OrderHandle, order: Order) {
-        debug_assert!(handle.0 < self.capacity, "Handle out of bounds");
-        self.orders[handle.index()].write(order);
+        debug_assert!(self.is_current(handle), "Stale handle");
+        self.slot_mut(handle).write(order);
+        self.live.set(handle.index());
     }
     
     /// Allocate and insert an order in one operation.
@@ -151,19 +335,26 @@ impl OrderPool {
         Some(handle)
     }
     
-    /// Number of available slots.
+    /// Number of free slots (not counting room to grow).
     #[inline(always)]
     pub fn available(&self) -> usize {
         self.free_list.len()
     }
     
+    /// Number of frees refused (double frees, stale or foreign handles).
+    /// Non-zero means a caller's bookkeeping is corrupt.
+    #[inline(always)]
+    pub fn invalid_frees(&self) -> u64 {
+        self.invalid_frees
+    }
+    
     /// Number of active orders.
     #[inline(always)]
     pub fn active(&self) -> usize {
         self.active_count as usize
     }
     
-    /// Total capacity.
+    /// Total capacity (slots allocated so far).
     #[inline(always)]
     pub fn capacity(&self) -> usize {
         self.capacity as usize
@@ -172,7 +363,7 @@ impl OrderPool {
     /// Check if pool is exhausted.
     #[inline(always)]
     pub fn is_full(&self) -> bool {
-        self.free_list.is_empty()
+        self.free_list.is_empty() && self.capacity == self.limit
     }
     
     /// Check if pool is empty.
@@ -182,6 +373,27 @@ impl OrderPool {
     }
 }
 
+/// Order behind a handle the caller knows is live (one taken from the
+/// book or the order index).
+///
+/// # Panics
+/// Panics if the handle is stale or its slot holds no order.
+impl Index<OrderHandle> for OrderPool {
+    type Output = Order;
+    
+    #[inline(always)]
+    fn index(&self, handle: OrderHandle) -> &Order {
+        self.try_get(handle).expect("stale order handle")
+    }
+}
+
+impl IndexMut<OrderHandle> for OrderPool {
+    #[inline(always)]
+    fn index_mut(&mut self, handle: OrderHandle) -> &mut Order {
+        self.try_get_mut(handle).expect("stale order handle")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,13 +414,20 @@ mod tests {
         assert_eq!(pool.available(), 14);
         assert_eq!(pool.active(), 2);
         
-        pool.deallocate(h1);
+        assert!(pool.deallocate(h1));
         assert_eq!(pool.available(), 15);
         assert_eq!(pool.active(), 1);
         
-        // LIFO: next alloc should return h1's slot
+        // LIFO: next alloc should return h1's slot, under a new generation
         let h3 = pool.allocate().unwrap();
-        assert_eq!(h3.0, h1.0);
+        assert_eq!(h3.index(), h1.index());
+        assert_ne!(h3.generation(), h1.generation());
+        assert_ne!(h3.index(), h2.index());
+        
+        // The stale handle cannot free the reused slot; h2 is unaffected
+        assert!(!pool.deallocate(h1));
+        assert!(pool.deallocate(h2));
+        assert_eq!(pool.active(), 1);
     }
     
     #[test]
@@ -228,7 +447,7 @@ mod tests {
         
         pool.insert(handle, order);
         
-        let retrieved = pool.get(handle);
+        let retrieved = pool.try_get(handle).unwrap();
         assert_eq!(retrieved.order_id.0, 42);
         assert_eq!(retrieved.remaining_qty.0, 1000);
     }
@@ -245,4 +464,107 @@ mod tests {
         assert!(pool.is_full());
         assert!(pool.allocate().is_none());
     }
+    
+    #[test]
+    fn test_stale_handle_rejected() {
+        let mut pool = OrderPool::new(4);
+        let order = |id| Order::new(OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        let old = pool.allocate_and_insert(order(1)).unwrap();
+        assert!(pool.deallocate(old));
+        
+        // Same slot, next generation
+        let new = pool.allocate_and_insert(order(2)).unwrap();
+        assert_eq!(new.index(), old.index());
+        assert_ne!(new, old);
+        assert!(pool.try_get(old).is_none());
+        assert!(pool.try_get_mut(old).is_none());
+        assert!(!pool.deallocate(old));
+        assert_eq!(pool.active(), 1);
+        assert_eq!(pool[new].order_id, OrderId(2));
+        assert!(pool.try_get(OrderHandle::INVALID).is_none());
+        
+        // Generations wrap without ever producing the invalid handle
+        for _ in 0..600 {
+            let handle = pool.allocate().unwrap();
+            assert!(handle.is_valid());
+            assert!(pool.deallocate(handle));
+        }
+    }
+    
+    #[test]
+    fn test_pool_grows_by_chunks() {
+        let mut pool = OrderPool::growable(2, 4); // 4 slots, up to 16
+        let order = |id| Order::new(OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        let handles: Vec<OrderHandle> = (0..4).map(|id| pool.allocate_and_insert(order(id)).unwrap()).collect();
+        let first = pool.try_get(handles[0]).unwrap() as *const Order;
+        assert_eq!(pool.capacity(), 4);
+        assert!(!pool.is_full());
+        
+        // Out of slots: a chunk is added and earlier orders stay put
+        let grown: Vec<OrderHandle> = (4..16).map(|id| pool.allocate_and_insert(order(id)).unwrap()).collect();
+        assert_eq!(pool.capacity(), 16);
+        assert_eq!(grown[0].index(), 4);
+        assert!(core::ptr::eq(pool.try_get(handles[0]).unwrap(), first));
+        assert!(handles.iter().chain(&grown).enumerate().all(|(id, &h)| pool[h].order_id == OrderId(id as u64)));
+        
+        // Hard cap
+        assert!(pool.is_full());
+        assert!(pool.allocate().is_none());
+        assert_eq!(pool.limit(), 16);
+    }
+    
+    #[test]
+    fn test_try_get_checks_liveness() {
+        let mut pool = OrderPool::new(4);
+        let order = Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        
+        // Never allocated, allocated but not written, out of range
+        assert!(pool.try_get(OrderHandle::new(3, 0)).is_none());
+        let handle = pool.allocate().unwrap();
+        assert!(pool.try_get(handle).is_none());
+        assert!(pool.try_get(OrderHandle::new(1 << 10, 0)).is_none());
+        
+        pool.insert(handle, order);
+        assert!(pool.is_live(handle));
+        pool.try_get_mut(handle).unwrap().remaining_qty = Quantity(4);
+        assert_eq!(pool[handle].remaining_qty, Quantity(4));
+        
+        assert!(pool.deallocate(handle));
+        assert!(!pool.is_live(handle));
+        assert!(pool.try_get_mut(handle).is_none());
+    }
+    
+    #[test]
+    fn test_iter_active_skips_free_slots() {
+        let mut pool = OrderPool::growable(2, 3);
+        let order = |id| Order::new(OrderId(id), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(100), Quantity(10), 0);
+        let handles: Vec<OrderHandle> = (0..6).map(|id| pool.allocate_and_insert(order(id)).unwrap()).collect();
+        assert!(pool.deallocate(handles[1]));
+        assert!(pool.deallocate(handles[4]));
+        let _unwritten = pool.allocate().unwrap();
+        
+        let active: Vec<(OrderHandle, u64)> = pool.iter_active().map(|(h, o)| (h, o.order_id.0)).collect();
+        assert_eq!(active, [(handles[0], 0), (handles[2], 2), (handles[3], 3), (handles[5], 5)]);
+        assert_eq!(OrderPool::new(4).iter_active().count(), 0);
+    }
+    
+    #[test]
+    fn test_double_free_refused() {
+        let mut pool = OrderPool::new(2);
+        
+        // Never handed out, though its generation is current
+        assert!(!pool.deallocate(OrderHandle::new(3, 0)));
+        assert_eq!(pool.available(), 4);
+        
+        let handle = pool.allocate().unwrap();
+        assert!(pool.deallocate(handle));
+        assert!(!pool.deallocate(handle));
+        assert_eq!(pool.invalid_frees(), 2);
+        assert_eq!(pool.active(), 0);
+        
+        // Every slot is handed out exactly once
+        let mut slots: Vec<usize> = core::iter::from_fn(|| pool.allocate()).map(|h| h.index()).collect();
+        slots.sort_unstable();
+        assert_eq!(slots, [0, 1, 2, 3]);
+    }
 }
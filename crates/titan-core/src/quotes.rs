@@ -0,0 +1,76 @@
+//! Mass quote slots.
+//!
+//! A market maker keeps at most one bid and one ask quote per symbol.
+//! `MatchingEngine::mass_quote` sets both in one call: each side updates
+//! the order already in its slot (in place when it only shrinks at the
+//! same price, cancel/replace otherwise) or places a new one if the slot
+//! is empty. The slots only remember which order is live; an order that
+//! traded away or was cancelled some other way simply leaves its slot
+//! empty for the next quote.
+
+use alloc::collections::BTreeMap;
+use crate::fixed::{Price, Quantity};
+use crate::order::{OrderId, ParticipantId, Side};
+
+/// One side of a two-sided quote.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuoteSide {
+    /// ID of the order placed if the slot is empty (a live quote keeps
+    /// its own ID).
+    pub order_id: OrderId,
+    /// Quote price.
+    pub price: Price,
+    /// Quote size (zero = pull this side).
+    pub qty: Quantity,
+}
+
+impl QuoteSide {
+    /// Quote `qty` at `price`.
+    pub const fn new(order_id: OrderId, price: Price, qty: Quantity) -> Self {
+        Self { order_id, price, qty }
+    }
+
+    /// Pull this side.
+    pub const fn pull() -> Self {
+        Self { order_id: OrderId::INVALID, price: Price::ZERO, qty: Quantity::ZERO }
+    }
+}
+
+/// Live quote order of each quoting participant, per side.
+#[derive(Default)]
+pub struct QuoteBook {
+    slots: BTreeMap<ParticipantId, [OrderId; 2]>,
+}
+
+impl QuoteBook {
+    /// Create a book with no quotes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Order in the `side` slot of `participant` (`OrderId::INVALID` if
+    /// empty).
+    #[inline]
+    pub fn slot(&self, participant: ParticipantId, side: Side) -> OrderId {
+        self.slots.get(&participant).map_or(OrderId::INVALID, |slots| slots[side as usize])
+    }
+
+    /// Put `order_id` in the `side` slot of `participant`.
+    pub fn set(&mut self, participant: ParticipantId, side: Side, order_id: OrderId) {
+        let slots = self.slots.entry(participant).or_insert([OrderId::INVALID; 2]);
+        slots[side as usize] = order_id;
+        if slots.iter().all(|id| !id.is_valid()) {
+            self.slots.remove(&participant);
+        }
+    }
+
+    /// Number of participants with a quote slot filled.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check if nobody is quoting.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
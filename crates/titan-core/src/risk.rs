@@ -0,0 +1,166 @@
+//! In-engine pre-trade risk checks.
+//!
+//! Run on every submission after validation and before matching. Limits
+//! come from a default set, optionally overridden per participant; a
+//! participant can also be switched off entirely. With nothing configured
+//! the check is a couple of branches.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use crate::fixed::{Price, Quantity};
+use crate::order::{Order, ParticipantId};
+use crate::engine::RejectReason;
+
+/// Pre-trade limits. A limit of zero is not checked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RiskConfig {
+    /// Largest quantity of a single order.
+    pub max_order_qty: Quantity,
+    /// Largest notional of a single priced order (raw price units ×
+    /// quantity).
+    pub max_notional: u128,
+    /// Resting orders a participant may hold on this book.
+    pub max_open_orders: u32,
+    /// Furthest a priced order may stray from the reference price, in
+    /// basis points (fat-finger check).
+    pub max_deviation_bps: u32,
+}
+
+impl RiskConfig {
+    /// Limits with every check disabled.
+    pub const UNLIMITED: Self = Self {
+        max_order_qty: Quantity::ZERO,
+        max_notional: 0,
+        max_open_orders: 0,
+        max_deviation_bps: 0,
+    };
+}
+
+/// Pre-trade risk limits and participant switches for one engine.
+#[derive(Default)]
+pub struct RiskChecker {
+    defaults: RiskConfig,
+    overrides: BTreeMap<ParticipantId, RiskConfig>,
+    disabled: BTreeSet<ParticipantId>,
+}
+
+impl RiskChecker {
+    /// Create a checker applying `defaults` to every participant.
+    pub fn new(defaults: RiskConfig) -> Self {
+        Self {
+            defaults,
+            ..Self::default()
+        }
+    }
+
+    /// Replace the limits of participants without an override.
+    pub fn set_defaults(&mut self, defaults: RiskConfig) {
+        self.defaults = defaults;
+    }
+
+    /// Give `participant` its own limits (`None` reverts to the defaults).
+    pub fn set_limits(&mut self, participant: ParticipantId, limits: Option<RiskConfig>) {
+        match limits {
+            Some(limits) => self.overrides.insert(participant, limits),
+            None => self.overrides.remove(&participant),
+        };
+    }
+
+    /// Limits in force for `participant`.
+    #[inline(always)]
+    pub fn limits(&self, participant: ParticipantId) -> &RiskConfig {
+        self.overrides.get(&participant).unwrap_or(&self.defaults)
+    }
+
+    /// Allow or block all new orders of `participant`.
+    pub fn set_enabled(&mut self, participant: ParticipantId, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&participant);
+        } else {
+            self.disabled.insert(participant);
+        }
+    }
+
+    /// Check if `participant` may enter orders.
+    #[inline(always)]
+    pub fn is_enabled(&self, participant: ParticipantId) -> bool {
+        !self.disabled.contains(&participant)
+    }
+
+    /// Check an incoming order of `participant`, who already has
+    /// `open_orders` resting.
+    #[inline]
+    pub fn check(
+        &self,
+        participant: ParticipantId,
+        order: &Order,
+        reference: Option<Price>,
+        open_orders: u32,
+    ) -> Result<(), RejectReason> {
+        if !self.disabled.is_empty() && !self.is_enabled(participant) {
+            return Err(RejectReason::ParticipantDisabled);
+        }
+
+        let limits = self.limits(participant);
+        let qty = order.remaining_qty;
+        if !limits.max_order_qty.is_zero() && qty.0 > limits.max_order_qty.0 {
+            return Err(RejectReason::OrderTooLarge);
+        }
+
+        let price = order.price;
+        if limits.max_notional != 0 && price.0 as u128 * qty.0 as u128 > limits.max_notional {
+            return Err(RejectReason::NotionalTooLarge);
+        }
+
+        if limits.max_open_orders != 0 && open_orders >= limits.max_open_orders {
+            return Err(RejectReason::TooManyOpenOrders);
+        }
+
+        match reference {
+            Some(reference) if limits.max_deviation_bps != 0 && !price.is_zero() => {
+                let deviation = price.0.abs_diff(reference.0) as u128 * 10_000;
+                if deviation > reference.0 as u128 * limits.max_deviation_bps as u128 {
+                    return Err(RejectReason::PriceDeviation);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{OrderId, OrderType, Side, SymbolId};
+
+    fn order(ticks: u64, qty: u64) -> Order {
+        Order::new(OrderId(1), SymbolId(1), Side::Buy, OrderType::Limit, Price::from_ticks(ticks), Quantity(qty), 0)
+    }
+
+    #[test]
+    fn test_risk_limits() {
+        let alice = ParticipantId(1);
+        let reference = Some(Price::from_ticks(100));
+        let mut risk = RiskChecker::new(RiskConfig {
+            max_order_qty: Quantity(100),
+            max_open_orders: 2,
+            max_deviation_bps: 1_000,
+            ..RiskConfig::UNLIMITED
+        });
+
+        assert_eq!(risk.check(alice, &order(100, 100), reference, 1), Ok(()));
+        assert_eq!(risk.check(alice, &order(100, 101), reference, 1), Err(RejectReason::OrderTooLarge));
+        assert_eq!(risk.check(alice, &order(100, 10), reference, 2), Err(RejectReason::TooManyOpenOrders));
+        assert_eq!(risk.check(alice, &order(111, 10), reference, 0), Err(RejectReason::PriceDeviation));
+        assert_eq!(risk.check(alice, &order(111, 10), None, 0), Ok(()));
+
+        risk.set_limits(alice, Some(RiskConfig { max_notional: 50 * Price::TICK_SIZE as u128 * 10, ..RiskConfig::UNLIMITED }));
+        assert_eq!(risk.check(alice, &order(100, 10), reference, 5), Err(RejectReason::NotionalTooLarge));
+        assert_eq!(risk.check(alice, &order(50, 10), reference, 5), Ok(()));
+
+        risk.set_enabled(alice, false);
+        assert_eq!(risk.check(alice, &order(50, 10), reference, 0), Err(RejectReason::ParticipantDisabled));
+        risk.set_enabled(alice, true);
+        assert!(risk.is_enabled(alice));
+    }
+}
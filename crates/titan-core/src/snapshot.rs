@@ -0,0 +1,248 @@
+//! Engine checkpoints and the binary records they share with the input
+//! journal.
+//!
+//! `MatchingEngine::snapshot` writes the open interest of one engine into
+//! a compact binary image and `MatchingEngine::restore` loads it back into
+//! an empty engine for the same symbol. Layout (little-endian):
+//!
+//! ```text
+//! [Magic: 4B "TSNP"][Version: 2B][Phase: 1B][Reserved: 1B]
+//! [Symbol: 4B][PoolCapacity: 4B][BookSequence: 8B][ReferencePrice: 8B, 0 = none]
+//! [OrderCount: 4B][Orders: OrderCount * 64B, bids then asks, price-time order]
+//! [LinkCount: 4B][OCO links: LinkCount * 24B (link_id, first, second)]
+//! ```
+//!
+//! Each order record is:
+//!
+//! ```text
+//! [OrderId: 8B][Price: 8B][Remaining: 8B][Original: 8B][Timestamp: 8B]
+//! [ExpireAt: 8B][DisplayQty: 4B][VisibleQty: 4B][Participant: 4B]
+//! [Side: 1B][OrderType: 1B][Flags: 1B][Reserved: 1B]
+//! ```
+//!
+//! Orders come back in the same queue positions but in new pool slots, so
+//! handles are not preserved. Settings (risk, MMP, fees, band) and pending
+//! trailing stops are not part of the image.
+
+use alloc::vec::Vec;
+use crate::fixed::{Price, Quantity};
+use crate::order::{Order, OrderId, OrderType, ParticipantId, Side, SymbolId};
+
+/// Leading bytes of every snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"TSNP";
+
+/// Format version written by this build.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Size of the fixed header.
+pub const HEADER_SIZE: usize = 32;
+
+/// Size of one order record.
+pub const ORDER_RECORD_SIZE: usize = 64;
+
+/// Size of one OCO link record.
+pub const LINK_RECORD_SIZE: usize = 24;
+
+/// Why a snapshot or input log could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// Input ended mid-record.
+    Truncated,
+    /// Input does not start with `SNAPSHOT_MAGIC`.
+    BadMagic,
+    /// Written by an incompatible format version.
+    UnsupportedVersion(u16),
+    /// Snapshot belongs to another symbol.
+    SymbolMismatch,
+    /// Target engine already has resting orders.
+    NotEmpty,
+    /// Target pool cannot hold every order.
+    PoolExhausted,
+    /// A record holds an invalid value or an order the book cannot place.
+    Corrupt,
+}
+
+/// Growable output buffer for snapshots and input logs.
+#[derive(Clone, Debug, Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Create an empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a writer that can hold `capacity` bytes before growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { buf: Vec::with_capacity(capacity) }
+    }
+
+    /// Bytes written so far.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Number of bytes written.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Check if nothing has been written.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Forget the contents, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Take the written bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    #[inline(always)]
+    pub(crate) fn put(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    #[inline(always)]
+    pub(crate) fn put_u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    #[inline(always)]
+    pub(crate) fn put_u16(&mut self, value: u16) {
+        self.put(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub(crate) fn put_u32(&mut self, value: u32) {
+        self.put(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    pub(crate) fn put_u64(&mut self, value: u64) {
+        self.put(&value.to_le_bytes());
+    }
+}
+
+/// Cursor over a snapshot or input log being decoded.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub(crate) fn take<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let (head, rest) = self.data.split_first_chunk::<N>().ok_or(SnapshotError::Truncated)?;
+        self.data = rest;
+        Ok(*head)
+    }
+
+    #[inline]
+    pub(crate) fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    #[inline]
+    pub(crate) fn u16(&mut self) -> Result<u16, SnapshotError> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    #[inline]
+    pub(crate) fn u32(&mut self) -> Result<u32, SnapshotError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    #[inline]
+    pub(crate) fn u64(&mut self) -> Result<u64, SnapshotError> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    /// Bytes consumed once `total` was the input length.
+    #[inline]
+    pub(crate) fn consumed(&self, total: usize) -> usize {
+        total - self.data.len()
+    }
+
+    /// Check if the input is exhausted.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// Write one order record.
+pub(crate) fn write_order(out: &mut Writer, order: &Order, participant: ParticipantId) {
+    out.put_u64(order.order_id.0);
+    out.put_u64(order.price.0);
+    out.put_u64(order.remaining_qty.0);
+    out.put_u64(order.original_qty.0);
+    out.put_u64(order.timestamp);
+    out.put_u64(order.expire_at);
+    out.put_u32(order.display_qty);
+    out.put_u32(order.visible_qty);
+    out.put_u32(participant.0);
+    out.put_u8(order.side as u8);
+    out.put_u8(order.order_type as u8);
+    out.put_u8(order.flags);
+    out.put_u8(0);
+}
+
+/// Read one order record for `symbol`.
+pub(crate) fn read_order(reader: &mut Reader<'_>, symbol: SymbolId) -> Result<(Order, ParticipantId), SnapshotError> {
+    let order_id = OrderId(reader.u64()?);
+    let price = Price(reader.u64()?);
+    let remaining = Quantity(reader.u64()?);
+    let original = Quantity(reader.u64()?);
+    let timestamp = reader.u64()?;
+    let expire_at = reader.u64()?;
+    let display_qty = reader.u32()?;
+    let visible_qty = reader.u32()?;
+    let participant = ParticipantId(reader.u32()?);
+    let side = side_from_u8(reader.u8()?)?;
+    let order_type = order_type_from_u8(reader.u8()?)?;
+    let flags = reader.u8()?;
+    reader.u8()?;
+
+    let mut order = Order::new(order_id, symbol, side, order_type, price, original, timestamp).with_expiry(expire_at);
+    order.remaining_qty = remaining;
+    order.display_qty = display_qty;
+    order.visible_qty = visible_qty;
+    order.flags = flags;
+    Ok((order, participant))
+}
+
+/// Decode a side byte.
+pub(crate) fn side_from_u8(value: u8) -> Result<Side, SnapshotError> {
+    match value {
+        0 => Ok(Side::Buy),
+        1 => Ok(Side::Sell),
+        _ => Err(SnapshotError::Corrupt),
+    }
+}
+
+/// Decode an order type byte.
+pub(crate) fn order_type_from_u8(value: u8) -> Result<OrderType, SnapshotError> {
+    match value {
+        0 => Ok(OrderType::Limit),
+        1 => Ok(OrderType::IOC),
+        2 => Ok(OrderType::FOK),
+        3 => Ok(OrderType::PostOnly),
+        4 => Ok(OrderType::Market),
+        5 => Ok(OrderType::GTD),
+        6 => Ok(OrderType::Day),
+        _ => Err(SnapshotError::Corrupt),
+    }
+}
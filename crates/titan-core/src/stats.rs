@@ -0,0 +1,102 @@
+//! Session trading statistics.
+//!
+//! Updated by the engine on every fill (continuous and auction). A
+//! session runs from `reset` to `close`; the close price is only set by
+//! `close`, while `last_price` always tracks the latest trade.
+
+use crate::fixed::{Price, Quantity};
+
+/// Last trade and open/high/low/close of the current session.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Price of the latest trade.
+    pub last_price: Option<Price>,
+    /// Quantity of the latest trade.
+    pub last_qty: Quantity,
+    /// Time of the latest trade.
+    pub last_time: u64,
+    /// First trade price of the session.
+    pub open: Option<Price>,
+    /// Highest trade price of the session.
+    pub high: Option<Price>,
+    /// Lowest trade price of the session.
+    pub low: Option<Price>,
+    /// Last trade price when the session was closed.
+    pub close: Option<Price>,
+    /// Quantity traded this session.
+    pub volume: Quantity,
+    /// Trades this session.
+    pub trade_count: u64,
+}
+
+impl SessionStats {
+    /// Statistics of a session without trades.
+    pub const fn new() -> Self {
+        Self {
+            last_price: None,
+            last_qty: Quantity::ZERO,
+            last_time: 0,
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+            volume: Quantity::ZERO,
+            trade_count: 0,
+        }
+    }
+
+    /// Count a trade of `qty` at `price`.
+    #[inline(always)]
+    pub fn record(&mut self, price: Price, qty: Quantity, timestamp: u64) {
+        self.last_price = Some(price);
+        self.last_qty = qty;
+        self.last_time = timestamp;
+        self.open.get_or_insert(price);
+        self.high = Some(self.high.map_or(price, |high| high.max(price)));
+        self.low = Some(self.low.map_or(price, |low| low.min(price)));
+        self.volume = self.volume.saturating_add(qty);
+        self.trade_count += 1;
+    }
+
+    /// End the session, fixing the close at the last trade price.
+    pub fn close(&mut self) -> Option<Price> {
+        self.close = self.last_price;
+        self.close
+    }
+
+    /// Start a new session. The last trade is kept as a reference.
+    pub fn reset(&mut self) {
+        *self = Self {
+            last_price: self.last_price,
+            last_qty: self.last_qty,
+            last_time: self.last_time,
+            ..Self::new()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_stats() {
+        let mut stats = SessionStats::new();
+        stats.record(Price::from_ticks(100), Quantity(5), 1);
+        stats.record(Price::from_ticks(103), Quantity(2), 2);
+        stats.record(Price::from_ticks(98), Quantity(1), 3);
+        stats.record(Price::from_ticks(101), Quantity(4), 4);
+
+        assert_eq!(stats.open, Some(Price::from_ticks(100)));
+        assert_eq!(stats.high, Some(Price::from_ticks(103)));
+        assert_eq!(stats.low, Some(Price::from_ticks(98)));
+        assert_eq!((stats.last_price, stats.last_qty), (Some(Price::from_ticks(101)), Quantity(4)));
+        assert_eq!((stats.volume, stats.trade_count), (Quantity(12), 4));
+        assert_eq!(stats.close, None);
+        assert_eq!(stats.close(), Some(Price::from_ticks(101)));
+
+        stats.reset();
+        assert_eq!((stats.open, stats.close, stats.trade_count), (None, None, 0));
+        assert_eq!(stats.last_price, Some(Price::from_ticks(101)));
+    }
+}
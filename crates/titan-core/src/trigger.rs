@@ -0,0 +1,165 @@
+//! Stop order triggers.
+//!
+//! Trailing stops wait off-book and follow the last trade price at a fixed
+//! offset: a sell stop ratchets up behind rising trades, a buy stop down
+//! behind falling ones. Once a trade retraces through the stop price the
+//! order fires and is released for submission as its own order type.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use crate::fixed::Price;
+use crate::order::{Order, OrderId, Side};
+
+/// A trailing stop waiting for its trigger.
+#[derive(Clone, Copy, Debug)]
+pub struct TrailingStop {
+    /// Order submitted when the stop fires.
+    pub order: Order,
+    /// Distance kept from the best trade price since entry.
+    pub trail: Price,
+    /// Current trigger price (`None` until the first trade).
+    pub stop_price: Option<Price>,
+}
+
+impl TrailingStop {
+    /// Follow a trade; returns `true` if the stop fires.
+    #[inline]
+    fn on_trade(&mut self, price: Price) -> bool {
+        match self.order.side {
+            // Protects a long: trails below the high
+            Side::Sell => {
+                let candidate = Price(price.0.saturating_sub(self.trail.0));
+                let fired = self.stop_price.is_some_and(|stop| price.0 <= stop.0);
+                if self.stop_price.is_none_or(|stop| candidate.0 > stop.0) {
+                    self.stop_price = Some(candidate);
+                }
+                fired
+            }
+            // Protects a short: trails above the low
+            Side::Buy => {
+                let candidate = Price(price.0.saturating_add(self.trail.0));
+                let fired = self.stop_price.is_some_and(|stop| price.0 >= stop.0);
+                if self.stop_price.is_none_or(|stop| candidate.0 < stop.0) {
+                    self.stop_price = Some(candidate);
+                }
+                fired
+            }
+        }
+    }
+}
+
+/// Pending and fired stops for one engine.
+#[derive(Default)]
+pub struct TriggerBook {
+    stops: Vec<TrailingStop>,
+    fired: VecDeque<Order>,
+    last_trade: Option<Price>,
+}
+
+impl TriggerBook {
+    /// Create an empty trigger book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stops waiting to fire.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Check if no stops are waiting.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Last trade price seen.
+    #[inline(always)]
+    pub fn last_trade(&self) -> Option<Price> {
+        self.last_trade
+    }
+
+    /// Add a trailing stop, anchored on the last trade if there was one.
+    pub fn add(&mut self, order: Order, trail: Price) {
+        let mut stop = TrailingStop { order, trail, stop_price: None };
+        if let Some(last) = self.last_trade {
+            stop.on_trade(last);
+        }
+        self.stops.push(stop);
+    }
+
+    /// Get a waiting stop.
+    pub fn get(&self, order_id: OrderId) -> Option<&TrailingStop> {
+        self.stops.iter().find(|s| s.order.order_id == order_id)
+    }
+
+    /// Check if `order_id` belongs to a stop that is waiting or has fired
+    /// but not been submitted yet.
+    #[inline]
+    pub fn contains(&self, order_id: OrderId) -> bool {
+        self.stops.iter().any(|s| s.order.order_id == order_id)
+            || self.fired.iter().any(|order| order.order_id == order_id)
+    }
+
+    /// Remove a waiting stop.
+    pub fn cancel(&mut self, order_id: OrderId) -> Option<Order> {
+        let pos = self.stops.iter().position(|s| s.order.order_id == order_id)?;
+        Some(self.stops.remove(pos).order)
+    }
+
+    /// Update every trail with a trade, queueing stops that fire.
+    #[inline]
+    pub fn on_trade(&mut self, price: Price) {
+        self.last_trade = Some(price);
+        if self.stops.is_empty() {
+            return;
+        }
+
+        let fired = &mut self.fired;
+        self.stops.retain_mut(|stop| {
+            if stop.on_trade(price) {
+                fired.push_back(stop.order);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Next fired stop, in firing order.
+    #[inline]
+    pub fn pop_fired(&mut self) -> Option<Order> {
+        self.fired.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed::Quantity;
+    use crate::order::{OrderType, SymbolId};
+
+    fn stop(id: u64, side: Side) -> Order {
+        Order::new(OrderId(id), SymbolId(1), side, OrderType::Market, Price::ZERO, Quantity(1), 0)
+    }
+
+    #[test]
+    fn test_trailing_ratchet() {
+        let mut triggers = TriggerBook::new();
+        triggers.on_trade(Price(100));
+        triggers.add(stop(1, Side::Sell), Price(5));
+        triggers.add(stop(2, Side::Buy), Price(5));
+        assert_eq!(triggers.get(OrderId(1)).unwrap().stop_price, Some(Price(95)));
+
+        // Sell stop follows the high, never back down
+        triggers.on_trade(Price(110));
+        triggers.on_trade(Price(106));
+        assert_eq!(triggers.get(OrderId(1)).unwrap().stop_price, Some(Price(105)));
+        assert_eq!(triggers.pop_fired().map(|o| o.order_id), Some(OrderId(2)));
+
+        triggers.on_trade(Price(105));
+        assert_eq!(triggers.pop_fired().map(|o| o.order_id), Some(OrderId(1)));
+        assert!(triggers.is_empty());
+    }
+}
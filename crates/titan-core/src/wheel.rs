@@ -0,0 +1,209 @@
+//! Hashed timing wheel for order expiry.
+//!
+//! Each slot covers one tick; a deadline lands in slot
+//! `(deadline / tick) % slots`, so deadlines further out than one rotation
+//! share slots with nearer ones and are skipped until due. Entries are
+//! intrusive doubly-linked lists indexed by pool handle: schedule and
+//! cancel are O(1) and nothing allocates after construction.
+
+use alloc::vec;
+use alloc::boxed::Box;
+use crate::pool::OrderHandle;
+
+/// End-of-list / unscheduled marker.
+const NIL: u32 = u32::MAX;
+
+/// Per-handle array index of a raw handle stored in a list.
+#[inline(always)]
+fn slot_of(raw: u32) -> usize {
+    OrderHandle(raw).index()
+}
+
+/// Timing wheel over order pool handles.
+pub struct TimingWheel {
+    /// Nanoseconds per slot.
+    tick_ns: u64,
+    /// `slots - 1` (slot count is a power of 2).
+    mask: u64,
+    /// First handle in each slot.
+    heads: Box<[u32]>,
+    /// Per-handle links (raw handles, generation included).
+    next: Box<[u32]>,
+    prev: Box<[u32]>,
+    /// Per-handle slot, NIL when not scheduled.
+    slot: Box<[u32]>,
+    /// Per-handle deadline.
+    deadline: Box<[u64]>,
+    /// Tick the wheel has advanced to (its slot is rescanned each call).
+    current_tick: u64,
+    len: usize,
+}
+
+impl TimingWheel {
+    /// Create a wheel of `slots` slots (power of 2) of `tick_ns` each,
+    /// for handles below `capacity`.
+    pub fn new(slots: usize, tick_ns: u64, capacity: usize) -> Self {
+        assert!(slots.is_power_of_two(), "Slot count must be power of 2");
+        assert!(tick_ns > 0, "Tick must be non-zero");
+
+        Self {
+            tick_ns,
+            mask: slots as u64 - 1,
+            heads: vec![NIL; slots].into_boxed_slice(),
+            next: vec![NIL; capacity].into_boxed_slice(),
+            prev: vec![NIL; capacity].into_boxed_slice(),
+            slot: vec![NIL; capacity].into_boxed_slice(),
+            deadline: vec![0; capacity].into_boxed_slice(),
+            current_tick: 0,
+            len: 0,
+        }
+    }
+
+    /// Extend to handles below `capacity`.
+    pub fn grow(&mut self, capacity: usize) {
+        for links in [&mut self.next, &mut self.prev, &mut self.slot] {
+            let mut grown = core::mem::take(links).into_vec();
+            grown.resize(capacity, NIL);
+            *links = grown.into_boxed_slice();
+        }
+        let mut deadline = core::mem::take(&mut self.deadline).into_vec();
+        deadline.resize(capacity, 0);
+        self.deadline = deadline.into_boxed_slice();
+    }
+
+    /// Number of scheduled handles.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if nothing is scheduled.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check if `handle` is scheduled.
+    #[inline(always)]
+    pub fn is_scheduled(&self, handle: OrderHandle) -> bool {
+        self.slot[handle.index()] != NIL
+    }
+
+    /// Schedule `handle` to expire at `deadline`, replacing any earlier
+    /// schedule. Deadlines already behind the wheel fire on the next advance.
+    #[inline]
+    pub fn schedule(&mut self, handle: OrderHandle, deadline: u64) {
+        self.cancel(handle);
+
+        let tick = (deadline / self.tick_ns).max(self.current_tick);
+        let slot = (tick & self.mask) as u32;
+        let idx = handle.index();
+        let head = self.heads[slot as usize];
+
+        self.next[idx] = head;
+        self.prev[idx] = NIL;
+        if head != NIL {
+            self.prev[slot_of(head)] = handle.0;
+        }
+        self.heads[slot as usize] = handle.0;
+        self.slot[idx] = slot;
+        self.deadline[idx] = deadline;
+        self.len += 1;
+    }
+
+    /// Unschedule `handle`. Returns `false` if it was not scheduled.
+    #[inline]
+    pub fn cancel(&mut self, handle: OrderHandle) -> bool {
+        let idx = handle.index();
+        let slot = self.slot[idx];
+        if slot == NIL {
+            return false;
+        }
+
+        let (prev, next) = (self.prev[idx], self.next[idx]);
+        if prev == NIL {
+            self.heads[slot as usize] = next;
+        } else {
+            self.next[slot_of(prev)] = next;
+        }
+        if next != NIL {
+            self.prev[slot_of(next)] = prev;
+        }
+        self.slot[idx] = NIL;
+        self.len -= 1;
+        true
+    }
+
+    /// Unschedule and return one handle whose deadline is at or before
+    /// `now`, advancing the wheel as needed. Call until `None`.
+    pub fn pop_expired(&mut self, now: u64) -> Option<OrderHandle> {
+        let target = now / self.tick_ns;
+
+        // After a long gap every slot is visited once rather than every tick
+        if target > self.current_tick + self.mask {
+            self.current_tick = target - self.mask;
+        }
+
+        loop {
+            let mut cursor = self.heads[(self.current_tick & self.mask) as usize];
+            while cursor != NIL {
+                let handle = OrderHandle(cursor);
+                if self.deadline[slot_of(cursor)] <= now {
+                    self.cancel(handle);
+                    return Some(handle);
+                }
+                cursor = self.next[slot_of(cursor)];
+            }
+
+            if self.current_tick >= target {
+                return None;
+            }
+            self.current_tick += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use alloc::vec;
+    use super::*;
+
+    fn drain(wheel: &mut TimingWheel, now: u64) -> Vec<u32> {
+        let mut expired: Vec<u32> = core::iter::from_fn(|| wheel.pop_expired(now)).map(|h| h.0).collect();
+        expired.sort_unstable();
+        expired
+    }
+
+    #[test]
+    fn test_wheel_expiry() {
+        let mut wheel = TimingWheel::new(8, 10, 16);
+        wheel.schedule(OrderHandle(1), 25);
+        wheel.schedule(OrderHandle(2), 25);
+        wheel.schedule(OrderHandle(3), 30);
+        // Same slot as 25, one rotation later
+        wheel.schedule(OrderHandle(4), 105);
+        assert!(wheel.cancel(OrderHandle(2)));
+        assert!(!wheel.cancel(OrderHandle(2)));
+
+        assert!(drain(&mut wheel, 24).is_empty());
+        assert_eq!(drain(&mut wheel, 29), vec![1]);
+        assert_eq!(drain(&mut wheel, 100), vec![3]);
+        assert_eq!(drain(&mut wheel, 105), vec![4]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_wheel_long_gap() {
+        let mut wheel = TimingWheel::new(4, 10, 16);
+        wheel.schedule(OrderHandle(1), 15);
+        wheel.schedule(OrderHandle(2), 1_000);
+        wheel.schedule(OrderHandle(3), 1_000_000);
+
+        assert_eq!(drain(&mut wheel, 500_000), vec![1, 2]);
+
+        // Past deadlines scheduled late still fire
+        wheel.schedule(OrderHandle(4), 5);
+        assert_eq!(drain(&mut wheel, 1_000_000), vec![3, 4]);
+    }
+}
@@ -0,0 +1,22 @@
+//! Titan Exchange - thread-per-core sharded deployment.
+//!
+//! Symbols are partitioned across N engine shards. Each shard runs on its
+//! own pinned core with its own pools and books, so no state is shared on
+//! the matching path:
+//!
+//! ```text
+//!              ┌──SPSC──▶ shard 0 ──SPSC──┐
+//! gateway ─────┼──SPSC──▶ shard 1 ──SPSC──┼──▶ merger ──▶ feed (UDP)
+//!    ▲         └──SPSC──▶ shard N ──SPSC──┘       │
+//!    └───────────────────SPSC─────────────────────┘ execution reports
+//! ```
+
+pub mod router;
+pub mod shard;
+pub mod merger;
+pub mod sequencer;
+
+pub use router::ShardRouter;
+pub use shard::{Shard, ShardCommand, ShardOutput};
+pub use merger::Merger;
+pub use sequencer::Sequencer;
@@ -0,0 +1,224 @@
+//! Titan Exchange - sharded multi-symbol matching engine.
+//!
+//! Threads:
+//! - Gateway: TCP order entry, sequences and routes commands to shards,
+//!   writes reports back
+//! - Shard 0..N: CPU-pinned matching engines, one SPSC ring in and out each
+//! - Merger: merges shard outputs into the session and market data streams
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use clap::Parser;
+use titan_core::MemoryPolicy;
+use titan_exchange::{Merger, Sequencer, Shard, ShardCommand, ShardOutput, ShardRouter};
+use titan_feed::Publisher;
+use titan_metrics::{ClockSource, UtcClock};
+use titan_net::Gateway;
+use titan_net::gateway::GatewayEvent;
+use titan_proto::ExecutionReport;
+use titan_ring::SpscRing;
+use titan_symbols::SymbolRegistry;
+
+/// Entries per shard ring.
+const SHARD_RING_SIZE: usize = 16 * 1024;
+
+/// Entries in the merger → gateway report ring.
+const REPORT_RING_SIZE: usize = 16 * 1024;
+
+#[derive(Parser, Debug)]
+#[command(name = "titan-exchange")]
+#[command(about = "Thread-per-core sharded Titan exchange")]
+struct Args {
+    /// Number of engine shards
+    #[arg(short, long, default_value = "2")]
+    shards: usize,
+
+    /// Order entry listen address
+    #[arg(long, default_value = "0.0.0.0:8080")]
+    gateway: String,
+
+    /// Market data destination (multicast group or unicast address)
+    #[arg(long, default_value = "239.255.0.1:12345")]
+    feed: String,
+
+    /// Symbol reference data file (all symbols accepted if missing)
+    #[arg(long, default_value = "config/symbols.conf")]
+    symbols: String,
+
+    /// log2 of the order pool capacity per symbol
+    #[arg(long, default_value = "16")]
+    pool_bits: u32,
+
+    /// log2 of the capacity pools may grow to (default: --pool-bits, no growth)
+    #[arg(long)]
+    max_pool_bits: Option<u32>,
+
+    /// Back order pools and books with transparent huge pages
+    #[arg(long)]
+    huge_pages: bool,
+
+    /// NUMA node to place order pools and books on
+    #[arg(long)]
+    numa_node: Option<u32>,
+
+    /// First CPU core for shard threads (shard i is pinned to core + i)
+    #[arg(long, default_value = "1")]
+    first_core: usize,
+}
+
+fn main() {
+    let args = Args::parse();
+    assert!(args.shards > 0, "--shards must be at least 1");
+
+    println!("╔══════════════════════════════════════════════════════════════╗");
+    println!("║                    TITAN EXCHANGE v0.1.0                     ║");
+    println!("║               Thread-per-core Sharded Matching               ║");
+    println!("╚══════════════════════════════════════════════════════════════╝");
+    println!();
+
+    let registry = match titan_symbols::load_file(&args.symbols) {
+        Ok(registry) => {
+            println!("📚 Loaded {} symbols from {}", registry.len(), args.symbols);
+            registry
+        }
+        Err(e) => {
+            eprintln!("⚠️  Failed to load {}: {}; accepting all symbols", args.symbols, e);
+            SymbolRegistry::new()
+        }
+    };
+    let router = ShardRouter::from_registry(&registry, args.shards);
+
+    let clock = UtcClock::new(ClockSource::Realtime, Duration::from_millis(10))
+        .expect("Failed to calibrate UTC clock");
+
+    let mut gateway = Gateway::bind(&args.gateway).expect("Failed to bind gateway");
+    let mut publisher = Publisher::new(&args.feed).expect("Failed to create feed publisher");
+    println!("🌐 Gateway listening on tcp://{}", args.gateway);
+    println!("📡 Market data to udp://{}", args.feed);
+
+    // Rings hold their entries inline; keep them off the stack
+    let mut inbound: Vec<Box<SpscRing<ShardCommand, SHARD_RING_SIZE>>> =
+        (0..args.shards).map(|_| Box::new(SpscRing::new())).collect();
+    let mut outbound: Vec<Box<SpscRing<ShardOutput, SHARD_RING_SIZE>>> =
+        (0..args.shards).map(|_| Box::new(SpscRing::new())).collect();
+    let mut reports: Box<SpscRing<(usize, ExecutionReport), REPORT_RING_SIZE>> = Box::new(SpscRing::new());
+
+    let shutdown = AtomicBool::new(false);
+    let core_ids = core_affinity::get_core_ids().unwrap_or_default();
+
+    thread::scope(|scope| {
+        let (mut shard_tx, shard_rx): (Vec<_>, Vec<_>) = inbound.iter_mut().map(|r| r.split()).unzip();
+        let (out_tx, mut out_rx): (Vec<_>, Vec<_>) = outbound.iter_mut().map(|r| r.split()).unzip();
+        let (mut report_tx, mut report_rx) = reports.split();
+
+        // === Engine shards ===
+        for (id, (mut rx, mut tx)) in shard_rx.into_iter().zip(out_tx).enumerate() {
+            let assigned = router.symbols_for(id);
+            let symbols = (!registry.is_empty()).then_some(assigned);
+            let core = core_ids.get(args.first_core + id).copied();
+            let shutdown = &shutdown;
+            let pool_bits = args.pool_bits;
+            let max_pool_bits = args.max_pool_bits.unwrap_or(pool_bits);
+            let memory = MemoryPolicy { huge_pages: args.huge_pages, numa_node: args.numa_node };
+
+            thread::Builder::new()
+                .name(format!("titan-shard-{}", id))
+                .spawn_scoped(scope, move || {
+                    if let Some(core) = core {
+                        if core_affinity::set_for_current(core) {
+                            println!("📍 Shard {} pinned to CPU core {:?}", id, core);
+                        }
+                    }
+
+                    let mut shard = Shard::new(id, pool_bits, symbols)
+                        .with_pool_limit(max_pool_bits)
+                        .with_memory_policy(memory);
+                    let mut emit = |output| tx.publish(output);
+
+                    while !shutdown.load(Ordering::Relaxed) {
+                        match rx.try_consume() {
+                            Some(cmd) => shard.handle(cmd, &mut emit),
+                            None => core::hint::spin_loop(),
+                        }
+                    }
+                })
+                .expect("Failed to spawn shard thread");
+        }
+
+        // === Merger ===
+        let shutdown_ref = &shutdown;
+        thread::Builder::new()
+            .name("titan-merger".to_string())
+            .spawn_scoped(scope, move || {
+                let mut merger = Merger::default();
+                let mut sink = |output| match output {
+                    ShardOutput::Execution { session, report } => report_tx.publish((session, report)),
+                    ShardOutput::Trade { symbol_id, side, price, quantity, timestamp, trade_id } => {
+                        let _ = publisher.publish_trade(symbol_id, side, price, quantity, timestamp, trade_id);
+                    }
+                    ShardOutput::Quote { symbol_id, bid, ask } => {
+                        let _ = publisher.publish_quote(symbol_id, bid, ask);
+                    }
+                    ShardOutput::TradingStatus { symbol_id, state, reason, timestamp } => {
+                        let _ = publisher.publish_trading_status(symbol_id, state, reason, timestamp);
+                    }
+                };
+
+                while !shutdown_ref.load(Ordering::Relaxed) {
+                    if merger.poll(&mut out_rx, &mut sink) == 0 {
+                        thread::yield_now();
+                    }
+                }
+            })
+            .expect("Failed to spawn merger thread");
+
+        println!();
+        println!("🚀 Titan Exchange running with {} shards", args.shards);
+        println!();
+
+        // === Gateway (this thread) ===
+        let mut sequencer = Sequencer::new();
+        let mut directory = Vec::new();
+        while !shutdown.load(Ordering::Relaxed) {
+            match gateway.poll(Some(0)) {
+                Ok(events) => {
+                    for event in events {
+                        // Reference data is answered here, not by a shard
+                        if let GatewayEvent::DirectoryRequest { token, request_id, symbol_id, .. } = *event {
+                            directory.extend(registry.directory(request_id, symbol_id).into_iter().map(|d| (token, d)));
+                            continue;
+                        }
+                        let now = clock.now().as_nanos();
+                        let Some(mut cmd) = ShardCommand::from_gateway(event, now) else {
+                            continue;
+                        };
+                        sequencer.sequence(&mut cmd, now);
+                        if cmd.is_broadcast() {
+                            // e.g. cancel-on-disconnect: the session's orders may rest on any shard
+                            for tx in shard_tx.iter_mut() {
+                                tx.publish(cmd);
+                            }
+                        } else {
+                            shard_tx[router.shard_for(cmd.symbol_id())].publish(cmd);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Gateway poll error: {}", e);
+                    shutdown.store(true, Ordering::Relaxed);
+                }
+            }
+
+            for (token, definition) in directory.drain(..) {
+                gateway.send(token, bytemuck::bytes_of(&definition));
+            }
+            while let Some((session, report)) = report_rx.try_consume() {
+                gateway.send(mio::Token(session), bytemuck::bytes_of(&report));
+            }
+        }
+    });
+
+    println!("\n🛑 Titan Exchange stopped");
+}
@@ -0,0 +1,107 @@
+//! Outbound stream merger.
+//!
+//! Drains every shard's output ring round-robin, stamps a single global
+//! sequence on execution reports and fans them out to the session and
+//! market data paths. Per-shard ordering is preserved; ordering across
+//! shards is arrival order at the merger.
+
+use titan_ring::Consumer;
+
+use crate::shard::ShardOutput;
+
+/// Default number of messages taken from one shard before moving on.
+pub const DEFAULT_BATCH: usize = 64;
+
+/// Round-robin merger over shard output rings.
+pub struct Merger {
+    batch: usize,
+    sequence: u32,
+    merged: u64,
+}
+
+impl Merger {
+    /// Create a merger taking up to `batch` messages per shard per pass.
+    pub fn new(batch: usize) -> Self {
+        Self { batch: batch.max(1), sequence: 0, merged: 0 }
+    }
+
+    /// Messages merged so far.
+    pub fn merged(&self) -> u64 {
+        self.merged
+    }
+
+    /// One round-robin pass over `shards`.
+    ///
+    /// Returns the number of messages forwarded to `sink`.
+    pub fn poll<const N: usize, F: FnMut(ShardOutput)>(
+        &mut self,
+        shards: &mut [Consumer<'_, ShardOutput, N>],
+        sink: &mut F,
+    ) -> usize {
+        let mut count = 0;
+
+        for consumer in shards.iter_mut() {
+            for _ in 0..self.batch {
+                let Some(mut output) = consumer.try_consume() else {
+                    break;
+                };
+                if let ShardOutput::Execution { report, .. } = &mut output {
+                    self.sequence = self.sequence.wrapping_add(1);
+                    report.header.sequence = self.sequence;
+                }
+                sink(output);
+                count += 1;
+            }
+        }
+
+        self.merged += count as u64;
+        count
+    }
+}
+
+impl Default for Merger {
+    fn default() -> Self {
+        Self::new(DEFAULT_BATCH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_proto::{ExecType, ExecutionReport};
+    use titan_ring::SpscRing;
+
+    fn exec(order_id: u64) -> ShardOutput {
+        let report = ExecutionReport::new(0, order_id, 0, 1, 0, ExecType::New, 100, 0, 10, 0);
+        ShardOutput::Execution { session: 1, report }
+    }
+
+    #[test]
+    fn test_round_robin_and_sequence() {
+        let mut a: SpscRing<ShardOutput, 16> = SpscRing::new();
+        let mut b: SpscRing<ShardOutput, 16> = SpscRing::new();
+        let (mut pa, ca) = a.split();
+        let (mut pb, cb) = b.split();
+        let mut consumers = [ca, cb];
+
+        for id in 1..=3 {
+            pa.publish(exec(id));
+        }
+        pb.publish(exec(10));
+        pb.publish(ShardOutput::Quote { symbol_id: 1, bid: 99, ask: 101 });
+
+        let mut merger = Merger::new(2);
+        let mut seen = Vec::new();
+        let mut sink = |o: ShardOutput| seen.push(o);
+
+        assert_eq!(merger.poll(&mut consumers, &mut sink), 4);
+        assert_eq!(merger.poll(&mut consumers, &mut sink), 1);
+        assert_eq!(merger.merged(), 5);
+
+        let ids: Vec<(u64, u32)> = seen.iter().filter_map(|o| match o {
+            ShardOutput::Execution { report, .. } => Some((report.order_id, report.header.sequence)),
+            _ => None,
+        }).collect();
+        assert_eq!(ids, vec![(1, 1), (2, 2), (10, 3), (3, 4)]);
+    }
+}
@@ -0,0 +1,95 @@
+//! Symbol-to-shard routing.
+
+use titan_symbols::SymbolRegistry;
+
+/// Maps symbols to engine shards.
+///
+/// Registered symbols are spread round-robin in id order so shards stay
+/// balanced; unregistered symbols fall back to `symbol_id % shards`.
+#[derive(Clone, Debug)]
+pub struct ShardRouter {
+    /// Dense table indexed by symbol id.
+    table: Vec<u16>,
+    shards: usize,
+}
+
+/// Marker for symbols without an explicit assignment.
+const UNASSIGNED: u16 = u16::MAX;
+
+impl ShardRouter {
+    /// Modulo routing over `shards` shards.
+    pub fn new(shards: usize) -> Self {
+        assert!(shards > 0 && shards < UNASSIGNED as usize, "shard count out of range");
+        Self { table: Vec::new(), shards }
+    }
+
+    /// Balanced routing for every symbol in `registry`.
+    pub fn from_registry(registry: &SymbolRegistry, shards: usize) -> Self {
+        let mut router = Self::new(shards);
+        for (i, spec) in registry.iter().enumerate() {
+            let idx = spec.id.0 as usize;
+            if idx >= router.table.len() {
+                router.table.resize(idx + 1, UNASSIGNED);
+            }
+            router.table[idx] = (i % shards) as u16;
+        }
+        router
+    }
+
+    /// Number of shards.
+    #[inline]
+    pub fn shards(&self) -> usize {
+        self.shards
+    }
+
+    /// Shard owning `symbol_id`.
+    #[inline]
+    pub fn shard_for(&self, symbol_id: u32) -> usize {
+        match self.table.get(symbol_id as usize) {
+            Some(&shard) if shard != UNASSIGNED => shard as usize,
+            _ => symbol_id as usize % self.shards,
+        }
+    }
+
+    /// Symbols explicitly assigned to `shard`.
+    pub fn symbols_for(&self, shard: usize) -> Vec<u32> {
+        self.table
+            .iter()
+            .enumerate()
+            .filter(|&(_, &s)| s as usize == shard)
+            .map(|(id, _)| id as u32)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_core::SymbolId;
+    use titan_symbols::SymbolSpec;
+
+    #[test]
+    fn test_modulo_fallback() {
+        let router = ShardRouter::new(4);
+        assert_eq!(router.shard_for(0), 0);
+        assert_eq!(router.shard_for(7), 3);
+        assert!(router.symbols_for(0).is_empty());
+    }
+
+    #[test]
+    fn test_registry_balanced() {
+        let mut registry = SymbolRegistry::new();
+        for (id, name) in [(10, "A"), (20, "B"), (30, "C")] {
+            registry.insert(SymbolSpec::new(SymbolId(id), name, 1)).unwrap();
+        }
+
+        let router = ShardRouter::from_registry(&registry, 2);
+        assert_eq!(router.shard_for(10), 0);
+        assert_eq!(router.shard_for(20), 1);
+        assert_eq!(router.shard_for(30), 0);
+        assert_eq!(router.symbols_for(0), vec![10, 30]);
+        assert_eq!(router.symbols_for(1), vec![20]);
+        // Unregistered
+        assert_eq!(router.shard_for(5), 1);
+    }
+}
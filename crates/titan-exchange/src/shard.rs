@@ -0,0 +1,808 @@
+//! Engine shard.
+//!
+//! A shard owns the matching engines for its symbols and turns inbound
+//! commands into execution reports and market data. It is driven by a
+//! single pinned thread; nothing here is shared.
+
+use std::collections::HashMap;
+
+use titan_core::{
+    Fill, MatchingEngine, MemoryPolicy, Order, OrderHandle, OrderId, OrderResult, OrderType,
+    ParticipantId, Price, Quantity, RejectReason, Side, SymbolId, TradingPhase,
+};
+use titan_net::gateway::GatewayEvent;
+use titan_proto::{ExecType, ExecutionReport, TradingState};
+
+/// Command routed from the gateway to a shard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardCommand {
+    /// New order. `side` and `order_type` are wire values, validated by
+    /// the shard so rejects flow through the normal report path.
+    NewOrder {
+        session: usize,
+        /// Client order ID, unique per session.
+        order_id: u64,
+        /// Exchange order ID assigned by the `Sequencer` (0 until then).
+        exchange_id: u64,
+        symbol_id: u32,
+        side: u8,
+        order_type: u8,
+        price: u64,
+        quantity: u64,
+        /// Iceberg clip size (0 = fully displayed).
+        display_qty: u64,
+        /// Owning participant, already checked against the session.
+        participant: u32,
+        /// Client order ID text, echoed on every report of the order.
+        client_ref: [u8; 16],
+        timestamp: u64,
+    },
+    /// Cancel a resting order.
+    Cancel {
+        session: usize,
+        order_id: u64,
+        symbol_id: u32,
+        timestamp: u64,
+    },
+    /// Amend price and/or open quantity of a resting order.
+    Modify {
+        session: usize,
+        order_id: u64,
+        symbol_id: u32,
+        price: u64,
+        quantity: u64,
+        timestamp: u64,
+    },
+    /// Put a symbol into the call phase (operator or schedule driven).
+    StartAuction {
+        symbol_id: u32,
+        timestamp: u64,
+    },
+    /// Uncross a symbol's auction and return it to continuous trading.
+    Uncross {
+        symbol_id: u32,
+        timestamp: u64,
+    },
+    /// Cancel every resting order of a session whose connection dropped.
+    /// Goes to every shard.
+    CancelSession {
+        session: usize,
+        timestamp: u64,
+    },
+}
+
+impl ShardCommand {
+    /// Convert an order-entry gateway event; connection events yield `None`.
+    ///
+    /// Mass quotes are not routed to shards yet and also yield `None`.
+    pub fn from_gateway(event: &GatewayEvent, timestamp: u64) -> Option<Self> {
+        match *event {
+            GatewayEvent::NewOrder {
+                token, order_id, symbol_id, side, order_type, price, quantity, display_qty, participant, client_ref, ..
+            } => {
+                Some(ShardCommand::NewOrder {
+                    session: token.0,
+                    order_id,
+                    exchange_id: 0,
+                    symbol_id,
+                    side,
+                    order_type,
+                    price,
+                    quantity,
+                    display_qty,
+                    participant,
+                    client_ref,
+                    timestamp,
+                })
+            }
+            GatewayEvent::CancelOrder { token, order_id, symbol_id, .. } => {
+                Some(ShardCommand::Cancel { session: token.0, order_id, symbol_id, timestamp })
+            }
+            GatewayEvent::ModifyOrder { token, order_id, symbol_id, price, quantity, .. } => {
+                Some(ShardCommand::Modify { session: token.0, order_id, symbol_id, price, quantity, timestamp })
+            }
+            GatewayEvent::SessionLost { token, .. } => Some(ShardCommand::CancelSession { session: token.0, timestamp }),
+            GatewayEvent::MassQuote { .. }
+            | GatewayEvent::MassCancel { .. }
+            | GatewayEvent::OrderStatusRequest { .. }
+            | GatewayEvent::Logon { .. }
+            | GatewayEvent::LogonRejected { .. }
+            | GatewayEvent::LogonRefused { .. }
+            | GatewayEvent::Unauthenticated { .. }
+            | GatewayEvent::Invalid { .. }
+            | GatewayEvent::DirectoryRequest { .. }
+            | GatewayEvent::Connected { .. }
+            | GatewayEvent::Disconnected { .. }
+            | GatewayEvent::Unauthorized { .. } => None,
+        }
+    }
+
+    /// Symbol the command targets (0 = all symbols, see `is_broadcast`).
+    pub fn symbol_id(&self) -> u32 {
+        match *self {
+            ShardCommand::NewOrder { symbol_id, .. }
+            | ShardCommand::Cancel { symbol_id, .. }
+            | ShardCommand::Modify { symbol_id, .. }
+            | ShardCommand::StartAuction { symbol_id, .. }
+            | ShardCommand::Uncross { symbol_id, .. } => symbol_id,
+            ShardCommand::CancelSession { .. } => 0,
+        }
+    }
+
+    /// Whether the command goes to every shard rather than the one owning
+    /// its symbol.
+    pub fn is_broadcast(&self) -> bool {
+        matches!(self, ShardCommand::CancelSession { .. })
+    }
+}
+
+/// Output of a shard, merged across shards downstream.
+#[derive(Clone, Copy, Debug)]
+pub enum ShardOutput {
+    /// Execution report for the session that owns the order.
+    Execution { session: usize, report: ExecutionReport },
+    /// Public trade (prices in ticks).
+    Trade {
+        symbol_id: u32,
+        side: u8,
+        price: u64,
+        quantity: u64,
+        timestamp: u64,
+        trade_id: u64,
+    },
+    /// Top of book changed (prices in ticks, 0 = empty side).
+    Quote { symbol_id: u32, bid: u64, ask: u64 },
+    /// A symbol changed trading state.
+    TradingStatus { symbol_id: u32, state: TradingState, reason: u16, timestamp: u64 },
+}
+
+/// Resting order bookkeeping for cancels and maker reports.
+#[derive(Clone, Copy, Debug)]
+struct RestingOrder {
+    handle: OrderHandle,
+    session: usize,
+    client_order_id: u64,
+    client_ref: [u8; 16],
+    symbol_id: u32,
+    side: Side,
+    price: Price,
+    remaining: u64,
+}
+
+/// One engine shard.
+pub struct Shard {
+    id: usize,
+    pool_bits: u32,
+    /// log2 of the capacity each engine's pool may grow to.
+    max_pool_bits: u32,
+    /// Huge page / NUMA placement of each engine's pool and book.
+    memory: MemoryPolicy,
+    /// When set, only these symbols are accepted; otherwise engines are
+    /// created for any symbol routed here.
+    symbols: Option<Vec<u32>>,
+    engines: HashMap<u32, MatchingEngine>,
+    /// Resting orders by exchange order ID (the engines' `OrderId`).
+    orders: HashMap<u64, RestingOrder>,
+    /// Exchange order ID by session and client order ID.
+    clients: HashMap<(usize, u64), u64>,
+    quotes: HashMap<u32, (u64, u64)>,
+    next_exec_id: u64,
+}
+
+impl Shard {
+    /// Create a shard. `symbols` restricts it to an assigned symbol set.
+    pub fn new(id: usize, pool_bits: u32, symbols: Option<Vec<u32>>) -> Self {
+        Self {
+            id,
+            pool_bits,
+            max_pool_bits: pool_bits,
+            memory: MemoryPolicy::default(),
+            symbols,
+            engines: HashMap::new(),
+            orders: HashMap::new(),
+            clients: HashMap::new(),
+            quotes: HashMap::new(),
+            next_exec_id: 0,
+        }
+    }
+
+    /// Let engine order pools grow past `pool_bits` up to 2^max_bits
+    /// orders.
+    pub fn with_pool_limit(mut self, max_bits: u32) -> Self {
+        self.max_pool_bits = max_bits;
+        self
+    }
+
+    /// Place engine pools and books per `policy`.
+    pub fn with_memory_policy(mut self, policy: MemoryPolicy) -> Self {
+        self.memory = policy;
+        self
+    }
+
+    /// Shard index.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Number of orders resting across all engines.
+    pub fn resting_orders(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Engine for a symbol, if it has seen any orders.
+    pub fn engine(&self, symbol_id: u32) -> Option<&MatchingEngine> {
+        self.engines.get(&symbol_id)
+    }
+
+    /// Globally unique execution id (shard in the top 16 bits).
+    fn exec_id(&mut self) -> u64 {
+        self.next_exec_id += 1;
+        ((self.id as u64) << 48) | self.next_exec_id
+    }
+
+    /// Process one command, emitting outputs in order.
+    pub fn handle<F: FnMut(ShardOutput)>(&mut self, cmd: ShardCommand, out: &mut F) {
+        match cmd {
+            ShardCommand::NewOrder {
+                session, order_id, exchange_id, symbol_id, side, order_type, price, quantity, display_qty, participant,
+                client_ref, timestamp,
+            } => {
+                let participant = ParticipantId(participant);
+                self.new_order(
+                    session, order_id, exchange_id, symbol_id, side, order_type, price, quantity, display_qty, participant,
+                    client_ref, timestamp, out,
+                );
+            }
+            ShardCommand::Cancel { session, order_id, symbol_id, timestamp } => {
+                self.cancel(session, order_id, symbol_id, timestamp, out);
+            }
+            ShardCommand::Modify { session, order_id, symbol_id, price, quantity, timestamp } => {
+                self.modify(session, order_id, symbol_id, price, quantity, timestamp, out);
+            }
+            ShardCommand::StartAuction { symbol_id, timestamp } => self.start_auction(symbol_id, timestamp, out),
+            ShardCommand::Uncross { symbol_id, timestamp } => self.uncross(symbol_id, timestamp, out),
+            ShardCommand::CancelSession { session, timestamp } => self.cancel_session(session, timestamp, out),
+        }
+    }
+
+    /// Whether this shard trades `symbol_id`.
+    fn accepts(&self, symbol_id: u32) -> bool {
+        self.symbols.as_ref().is_none_or(|symbols| symbols.contains(&symbol_id))
+    }
+
+    /// Engine for `symbol_id`, created on first use.
+    fn engine_mut(&mut self, symbol_id: u32) -> &mut MatchingEngine {
+        let (pool_bits, max_pool_bits, memory) = (self.pool_bits, self.max_pool_bits, self.memory);
+        self.engines.entry(symbol_id).or_insert_with(|| {
+            let mut engine = MatchingEngine::new(SymbolId(symbol_id), pool_bits, Price::ZERO);
+            engine.set_pool_limit(max_pool_bits);
+            // Best effort: the kernel may not offer huge pages or the node
+            engine.set_memory_policy(memory);
+            engine
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_order<F: FnMut(ShardOutput)>(
+        &mut self,
+        session: usize,
+        order_id: u64,
+        exchange_id: u64,
+        symbol_id: u32,
+        side: u8,
+        order_type: u8,
+        price: u64,
+        quantity: u64,
+        display_qty: u64,
+        participant: ParticipantId,
+        client_ref: [u8; 16],
+        timestamp: u64,
+        out: &mut F,
+    ) {
+        let reject = |shard: &mut Self, out: &mut F| {
+            let exec_id = shard.exec_id();
+            let report = ExecutionReport::new(
+                0, order_id, exec_id, symbol_id, side, ExecType::Rejected, price, 0, 0, timestamp,
+            ).with_client_order_id(client_ref);
+            out(ShardOutput::Execution { session, report });
+        };
+
+        let parsed_side = match side {
+            0 => Side::Buy,
+            1 => Side::Sell,
+            _ => return reject(self, out),
+        };
+        let parsed_type = match order_type {
+            0 => OrderType::Limit,
+            1 => OrderType::IOC,
+            2 => OrderType::FOK,
+            3 => OrderType::PostOnly,
+            4 => OrderType::Market,
+            _ => return reject(self, out),
+        };
+        if exchange_id == 0 || self.clients.contains_key(&(session, order_id)) {
+            return reject(self, out);
+        }
+        if !self.accepts(symbol_id) {
+            return reject(self, out);
+        }
+
+        let engine = self.engine_mut(symbol_id);
+
+        let order = Order::new(
+            OrderId(exchange_id),
+            SymbolId(symbol_id),
+            parsed_side,
+            parsed_type,
+            Price::from_ticks(price),
+            Quantity(quantity),
+            timestamp,
+        ).with_display_qty(Quantity(display_qty));
+        let result = engine.submit_order_as(participant, order, timestamp);
+        if matches!(result, OrderResult::Rejected { .. }) {
+            return reject(self, out);
+        }
+
+        self.report_result(
+            session, order_id, client_ref, exchange_id, symbol_id, parsed_side, price, quantity, timestamp, &result,
+            ExecType::New, out,
+        );
+        self.publish_quote(symbol_id, out);
+    }
+
+    /// Emit fills and the acknowledgement for an accepted order, and track
+    /// it if it rests. `quantity` is the open quantity the engine was given;
+    /// `ack` is the exec type reported when the order ends up resting.
+    #[allow(clippy::too_many_arguments)]
+    fn report_result<F: FnMut(ShardOutput)>(
+        &mut self,
+        session: usize,
+        order_id: u64,
+        client_ref: [u8; 16],
+        exchange_id: u64,
+        symbol_id: u32,
+        parsed_side: Side,
+        price: u64,
+        quantity: u64,
+        timestamp: u64,
+        result: &OrderResult,
+        ack: ExecType,
+        out: &mut F,
+    ) {
+        let side = parsed_side as u8;
+        let (fills, leaves, handle, exec_type): (&[Fill], u64, Option<OrderHandle>, Option<ExecType>) = match &result {
+            OrderResult::Filled { fills } => (fills, 0, None, None),
+            OrderResult::PartialFill { fills, resting_qty, handle } => (fills, resting_qty.0, Some(*handle), Some(ack)),
+            OrderResult::Resting { handle } => (&[], quantity, Some(*handle), Some(ack)),
+            OrderResult::Cancelled { fills, .. } => (fills, 0, None, Some(ExecType::Canceled)),
+            OrderResult::Rejected { .. } => (&[], 0, None, None),
+        };
+
+        // Fills: taker report, maker report, public trade
+        let mut cum_qty = 0;
+        for fill in fills {
+            cum_qty += fill.quantity.0;
+            let fill_price = fill.price.to_ticks();
+
+            let taker_exec_id = self.exec_id();
+            let report = ExecutionReport::new_fill(
+                0, order_id, taker_exec_id, symbol_id, side, fill_price, fill.quantity.0, quantity - cum_qty, timestamp,
+            ).with_client_order_id(client_ref);
+            out(ShardOutput::Execution { session, report });
+
+            self.fill_resting(fill.maker_order_id.0, fill_price, fill.quantity.0, timestamp, out);
+
+            out(ShardOutput::Trade {
+                symbol_id,
+                side,
+                price: fill_price,
+                quantity: fill.quantity.0,
+                timestamp,
+                trade_id: taker_exec_id,
+            });
+        }
+
+        if let Some(exec_type) = exec_type {
+            let exec_id = self.exec_id();
+            let report = ExecutionReport::new(
+                0, order_id, exec_id, symbol_id, side, exec_type, price, 0, leaves, timestamp,
+            ).with_client_order_id(client_ref);
+            out(ShardOutput::Execution { session, report });
+        }
+
+        if let Some(handle) = handle {
+            self.clients.insert((session, order_id), exchange_id);
+            self.orders.insert(exchange_id, RestingOrder {
+                handle,
+                session,
+                client_order_id: order_id,
+                client_ref,
+                symbol_id,
+                side: parsed_side,
+                price: Price::from_ticks(price),
+                remaining: leaves,
+            });
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn modify<F: FnMut(ShardOutput)>(
+        &mut self,
+        session: usize,
+        order_id: u64,
+        symbol_id: u32,
+        price: u64,
+        quantity: u64,
+        timestamp: u64,
+        out: &mut F,
+    ) {
+        // Only the owning session may modify
+        let resting = self.resting(session, order_id, symbol_id);
+
+        let result = match (resting, self.engines.get_mut(&symbol_id)) {
+            (Some(resting), Some(engine)) => {
+                engine.modify_order(resting.handle, Price::from_ticks(price), Quantity(quantity))
+            }
+            _ => OrderResult::Rejected { reason: RejectReason::UnknownOrder },
+        };
+
+        let (Some(resting), false) = (resting, matches!(result, OrderResult::Rejected { .. })) else {
+            // The original order, if any, is untouched
+            let (side, client_ref) = resting.map_or((0, [0; 16]), |o| (o.side as u8, o.client_ref));
+            let exec_id = self.exec_id();
+            let report = ExecutionReport::new(
+                0, order_id, exec_id, symbol_id, side, ExecType::Rejected, price, 0, 0, timestamp,
+            ).with_client_order_id(client_ref);
+            out(ShardOutput::Execution { session, report });
+            return;
+        };
+
+        let exchange_id = self.forget(session, order_id);
+        self.report_result(
+            session, order_id, resting.client_ref, exchange_id, symbol_id, resting.side, price, quantity, timestamp, &result,
+            ExecType::Replaced, out,
+        );
+        self.publish_quote(symbol_id, out);
+    }
+
+    fn cancel<F: FnMut(ShardOutput)>(&mut self, session: usize, order_id: u64, symbol_id: u32, timestamp: u64, out: &mut F) {
+        // Only the owning session may cancel
+        let resting = self.resting(session, order_id, symbol_id);
+
+        let Some(resting) = resting else {
+            let exec_id = self.exec_id();
+            let report = ExecutionReport::new(
+                0, order_id, exec_id, symbol_id, 0, ExecType::Rejected, 0, 0, 0, timestamp,
+            );
+            out(ShardOutput::Execution { session, report });
+            return;
+        };
+
+        if let Some(engine) = self.engines.get_mut(&symbol_id) {
+            engine.cancel_order(resting.handle);
+        }
+        self.forget(session, order_id);
+
+        let exec_id = self.exec_id();
+        let report = ExecutionReport::new(
+            0, order_id, exec_id, symbol_id, resting.side as u8, ExecType::Canceled,
+            resting.price.to_ticks(), 0, 0, timestamp,
+        ).with_client_order_id(resting.client_ref);
+        out(ShardOutput::Execution { session, report });
+
+        self.publish_quote(symbol_id, out);
+    }
+
+    /// Cancel every resting order of `session`, oldest first.
+    fn cancel_session<F: FnMut(ShardOutput)>(&mut self, session: usize, timestamp: u64, out: &mut F) {
+        let mut orphans: Vec<(u64, RestingOrder)> = self.orders.iter()
+            .filter(|(_, order)| order.session == session)
+            .map(|(&exchange_id, &order)| (exchange_id, order))
+            .collect();
+        orphans.sort_unstable_by_key(|&(exchange_id, _)| exchange_id);
+
+        let mut symbols = Vec::new();
+        for (_, order) in orphans {
+            if let Some(engine) = self.engines.get_mut(&order.symbol_id) {
+                engine.cancel_order(order.handle);
+            }
+            self.forget(session, order.client_order_id);
+
+            let exec_id = self.exec_id();
+            let report = ExecutionReport::new(
+                0, order.client_order_id, exec_id, order.symbol_id, order.side as u8, ExecType::Canceled,
+                order.price.to_ticks(), 0, 0, timestamp,
+            ).with_client_order_id(order.client_ref);
+            out(ShardOutput::Execution { session, report });
+            if !symbols.contains(&order.symbol_id) {
+                symbols.push(order.symbol_id);
+            }
+        }
+
+        for symbol_id in symbols {
+            self.publish_quote(symbol_id, out);
+        }
+    }
+
+    /// Report a fill to the owner of resting order `exchange_id`, and stop
+    /// tracking the order once it is done.
+    fn fill_resting<F: FnMut(ShardOutput)>(&mut self, exchange_id: u64, price: u64, quantity: u64, timestamp: u64, out: &mut F) {
+        let Some(order) = self.orders.get_mut(&exchange_id) else {
+            return;
+        };
+        order.remaining = order.remaining.saturating_sub(quantity);
+        let order = *order;
+        if order.remaining == 0 {
+            self.orders.remove(&exchange_id);
+            self.clients.remove(&(order.session, order.client_order_id));
+        }
+        let exec_id = self.exec_id();
+        let report = ExecutionReport::new_fill(
+            0, order.client_order_id, exec_id, order.symbol_id, order.side as u8, price, quantity, order.remaining, timestamp,
+        ).with_client_order_id(order.client_ref);
+        out(ShardOutput::Execution { session: order.session, report });
+    }
+
+    fn start_auction<F: FnMut(ShardOutput)>(&mut self, symbol_id: u32, timestamp: u64, out: &mut F) {
+        if !self.accepts(symbol_id) {
+            return;
+        }
+        let engine = self.engine_mut(symbol_id);
+        if engine.phase() == TradingPhase::Auction {
+            return;
+        }
+        engine.start_auction();
+        out(ShardOutput::TradingStatus { symbol_id, state: TradingState::PreOpen, reason: 0, timestamp });
+    }
+
+    fn uncross<F: FnMut(ShardOutput)>(&mut self, symbol_id: u32, timestamp: u64, out: &mut F) {
+        let Some(engine) = self.engines.get_mut(&symbol_id) else {
+            return;
+        };
+        if engine.phase() != TradingPhase::Auction {
+            return;
+        }
+        let mut fills = Vec::new();
+        engine.uncross(timestamp, |fill| fills.push(*fill));
+
+        // Both sides were resting; the trade takes the newer one's side
+        for fill in &fills {
+            let price = fill.price.to_ticks();
+            self.fill_resting(fill.taker_order_id.0, price, fill.quantity.0, timestamp, out);
+            self.fill_resting(fill.maker_order_id.0, price, fill.quantity.0, timestamp, out);
+            let trade_id = self.exec_id();
+            out(ShardOutput::Trade {
+                symbol_id,
+                side: fill.maker_side.opposite() as u8,
+                price,
+                quantity: fill.quantity.0,
+                timestamp,
+                trade_id,
+            });
+        }
+
+        out(ShardOutput::TradingStatus { symbol_id, state: TradingState::Open, reason: 0, timestamp });
+        self.publish_quote(symbol_id, out);
+    }
+
+    /// Resting order of `session` with client ID `order_id` on `symbol_id`.
+    fn resting(&self, session: usize, order_id: u64, symbol_id: u32) -> Option<RestingOrder> {
+        let exchange_id = self.clients.get(&(session, order_id))?;
+        self.orders.get(exchange_id).copied().filter(|o| o.symbol_id == symbol_id)
+    }
+
+    /// Stop tracking a resting order, returning its exchange order ID.
+    fn forget(&mut self, session: usize, order_id: u64) -> u64 {
+        let exchange_id = self.clients.remove(&(session, order_id)).unwrap_or(0);
+        self.orders.remove(&exchange_id);
+        exchange_id
+    }
+
+    /// Emit a quote if the top of book changed.
+    fn publish_quote<F: FnMut(ShardOutput)>(&mut self, symbol_id: u32, out: &mut F) {
+        let Some(engine) = self.engines.get(&symbol_id) else {
+            return;
+        };
+        let bid = engine.book.best_bid().map_or(0, Price::to_ticks);
+        let ask = engine.book.best_ask().map_or(0, Price::to_ticks);
+
+        if self.quotes.insert(symbol_id, (bid, ask)) != Some((bid, ask)) {
+            out(ShardOutput::Quote { symbol_id, bid, ask });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mio::Token;
+    use titan_core::SessionId;
+    use titan_proto::ExecType;
+
+    fn new_order(session: usize, order_id: u64, side: u8, order_type: u8, price: u64, quantity: u64) -> ShardCommand {
+        ShardCommand::NewOrder {
+            session, order_id, exchange_id: order_id, symbol_id: 1, side, order_type, price, quantity, display_qty: 0,
+            participant: session as u32, client_ref: [order_id as u8; 16], timestamp: order_id,
+        }
+    }
+
+    fn run(shard: &mut Shard, cmd: ShardCommand) -> Vec<ShardOutput> {
+        let mut outputs = Vec::new();
+        shard.handle(cmd, &mut |o| outputs.push(o));
+        outputs
+    }
+
+    fn reports(outputs: &[ShardOutput]) -> Vec<(usize, u64, u8, u64, u64)> {
+        outputs.iter().filter_map(|o| match o {
+            ShardOutput::Execution { session, report } => {
+                let (id, ty, qty, leaves) = (report.order_id, report.exec_type, report.exec_qty, report.leaves_qty);
+                Some((*session, id, ty, qty, leaves))
+            }
+            _ => None,
+        }).collect()
+    }
+
+    #[test]
+    fn test_rest_and_match() {
+        let mut shard = Shard::new(0, 10, None);
+
+        let out = run(&mut shard, new_order(1, 10, 1, 0, 100, 50));
+        assert_eq!(reports(&out), vec![(1, 10, ExecType::New as u8, 0, 50)]);
+        assert!(matches!(out.last(), Some(ShardOutput::Quote { bid: 0, ask: 100, .. })));
+
+        let out = run(&mut shard, new_order(2, 11, 0, 0, 100, 80));
+        assert_eq!(reports(&out), vec![
+            (2, 11, ExecType::PartialFill as u8, 50, 30),
+            (1, 10, ExecType::Fill as u8, 50, 0),
+            (2, 11, ExecType::New as u8, 0, 30),
+        ]);
+        assert!(out.iter().any(|o| matches!(o, ShardOutput::Trade { price: 100, quantity: 50, .. })));
+        // Each side's reports echo its own client order ID
+        let refs: Vec<_> = out.iter().filter_map(|o| match o {
+            ShardOutput::Execution { report, .. } => Some(report.client_order_id),
+            _ => None,
+        }).collect();
+        assert_eq!(refs, vec![[11; 16], [10; 16], [11; 16]]);
+        assert_eq!(shard.resting_orders(), 1);
+        assert_eq!(shard.engine(1).unwrap().owner_of(OrderId(11)), Some(ParticipantId(2)));
+    }
+
+    #[test]
+    fn test_client_ids_per_session() {
+        let mut shard = Shard::new(0, 10, None);
+        let bid = |session, exchange_id, price| ShardCommand::NewOrder {
+            session, order_id: 10, exchange_id, symbol_id: 1, side: 0, order_type: 0, price, quantity: 50,
+            display_qty: 0, participant: 0, client_ref: [0; 16], timestamp: 0,
+        };
+
+        // Same client ID from two sessions rests twice; a repeat is rejected
+        run(&mut shard, bid(1, 1, 100));
+        let out = run(&mut shard, bid(2, 2, 99));
+        assert_eq!(reports(&out), vec![(2, 10, ExecType::New as u8, 0, 50)]);
+        let out = run(&mut shard, bid(2, 3, 98));
+        assert_eq!(reports(&out), vec![(2, 10, ExecType::Rejected as u8, 0, 0)]);
+        assert_eq!(shard.resting_orders(), 2);
+
+        let out = run(&mut shard, ShardCommand::Cancel { session: 2, order_id: 10, symbol_id: 1, timestamp: 0 });
+        assert_eq!(reports(&out)[0].2, ExecType::Canceled as u8);
+        assert_eq!(shard.engine(1).unwrap().book.best_bid(), Some(Price::from_ticks(100)));
+        assert_eq!(shard.resting_orders(), 1);
+    }
+
+    #[test]
+    fn test_cancel_ownership() {
+        let mut shard = Shard::new(0, 10, None);
+        run(&mut shard, new_order(1, 10, 0, 0, 100, 50));
+
+        // Wrong session
+        let out = run(&mut shard, ShardCommand::Cancel { session: 2, order_id: 10, symbol_id: 1, timestamp: 0 });
+        assert_eq!(reports(&out)[0].2, ExecType::Rejected as u8);
+
+        let out = run(&mut shard, ShardCommand::Cancel { session: 1, order_id: 10, symbol_id: 1, timestamp: 0 });
+        assert_eq!(reports(&out)[0].2, ExecType::Canceled as u8);
+        assert_eq!(shard.resting_orders(), 0);
+    }
+
+    #[test]
+    fn test_modify() {
+        let mut shard = Shard::new(0, 10, None);
+        run(&mut shard, new_order(1, 10, 0, 0, 100, 50));
+        run(&mut shard, new_order(2, 11, 1, 0, 103, 20));
+        let modify = |session, price, quantity| ShardCommand::Modify {
+            session, order_id: 10, symbol_id: 1, price, quantity, timestamp: 0,
+        };
+
+        // Wrong session
+        let out = run(&mut shard, modify(2, 101, 50));
+        assert_eq!(reports(&out), vec![(2, 10, ExecType::Rejected as u8, 0, 0)]);
+
+        let out = run(&mut shard, modify(1, 101, 40));
+        assert_eq!(reports(&out), vec![(1, 10, ExecType::Replaced as u8, 0, 40)]);
+        assert!(matches!(out.last(), Some(ShardOutput::Quote { bid: 101, ask: 103, .. })));
+
+        // Reprice through the ask: trades, rest replaced
+        let out = run(&mut shard, modify(1, 103, 40));
+        assert_eq!(reports(&out), vec![
+            (1, 10, ExecType::PartialFill as u8, 20, 20),
+            (2, 11, ExecType::Fill as u8, 20, 0),
+            (1, 10, ExecType::Replaced as u8, 0, 20),
+        ]);
+        assert_eq!(shard.resting_orders(), 1);
+    }
+
+    #[test]
+    fn test_rejects() {
+        let mut shard = Shard::new(3, 10, Some(vec![2]));
+
+        // Symbol 1 not assigned to this shard
+        let out = run(&mut shard, new_order(1, 10, 0, 0, 100, 50));
+        assert_eq!(reports(&out), vec![(1, 10, ExecType::Rejected as u8, 0, 0)]);
+
+        let mut shard = Shard::new(3, 10, None);
+        let out = run(&mut shard, new_order(1, 10, 7, 0, 100, 50));
+        assert_eq!(reports(&out)[0].2, ExecType::Rejected as u8);
+        let out = run(&mut shard, new_order(1, 11, 0, 0, 100, 0));
+        assert_eq!(reports(&out)[0].2, ExecType::Rejected as u8);
+
+        // Exec ids carry the shard index
+        if let ShardOutput::Execution { report, .. } = out[0] {
+            assert_eq!({ report.exec_id } >> 48, 3);
+        }
+    }
+
+    #[test]
+    fn test_ioc_remainder_cancelled() {
+        let mut shard = Shard::new(0, 10, None);
+        run(&mut shard, new_order(1, 10, 1, 0, 100, 20));
+
+        let out = run(&mut shard, new_order(2, 11, 0, 1, 100, 50));
+        assert_eq!(reports(&out).last(), Some(&(2, 11, ExecType::Canceled as u8, 0, 0)));
+        assert_eq!(shard.resting_orders(), 0);
+    }
+
+    #[test]
+    fn test_auction_trading_status() {
+        let mut shard = Shard::new(0, 10, None);
+        let out = run(&mut shard, ShardCommand::StartAuction { symbol_id: 1, timestamp: 5 });
+        assert!(matches!(out[..], [ShardOutput::TradingStatus { symbol_id: 1, state: TradingState::PreOpen, timestamp: 5, .. }]));
+        assert!(run(&mut shard, ShardCommand::StartAuction { symbol_id: 1, timestamp: 6 }).is_empty());
+
+        // Crossing orders rest during the call
+        run(&mut shard, new_order(1, 10, 0, 0, 102, 50));
+        run(&mut shard, new_order(2, 11, 1, 0, 100, 30));
+        assert_eq!(shard.resting_orders(), 2);
+
+        let out = run(&mut shard, ShardCommand::Uncross { symbol_id: 1, timestamp: 9 });
+        let mut fills = reports(&out);
+        fills.sort();
+        assert_eq!(fills, vec![
+            (1, 10, ExecType::PartialFill as u8, 30, 20),
+            (2, 11, ExecType::Fill as u8, 30, 0),
+        ]);
+        assert!(out.iter().any(|o| matches!(o, ShardOutput::Trade { quantity: 30, .. })));
+        assert!(out.iter().any(|o| matches!(o, ShardOutput::TradingStatus { state: TradingState::Open, timestamp: 9, .. })));
+        assert_eq!(shard.resting_orders(), 1);
+
+        // Already continuous
+        assert!(run(&mut shard, ShardCommand::Uncross { symbol_id: 1, timestamp: 10 }).is_empty());
+    }
+
+    #[test]
+    fn test_cancel_on_disconnect() {
+        let mut shard = Shard::new(0, 10, None);
+        run(&mut shard, new_order(1, 10, 0, 0, 100, 50));
+        run(&mut shard, new_order(1, 11, 1, 0, 105, 20));
+        run(&mut shard, new_order(2, 12, 0, 0, 99, 30));
+
+        let lost = GatewayEvent::SessionLost { token: Token(1), session: SessionId(4) };
+        let cmd = ShardCommand::from_gateway(&lost, 7).unwrap();
+        assert!(cmd.is_broadcast());
+        let out = run(&mut shard, cmd);
+        assert_eq!(reports(&out), vec![
+            (1, 10, ExecType::Canceled as u8, 0, 0),
+            (1, 11, ExecType::Canceled as u8, 0, 0),
+        ]);
+        assert!(matches!(out.last(), Some(ShardOutput::Quote { bid: 99, ask: 0, .. })));
+        assert_eq!(shard.resting_orders(), 1);
+    }
+}
@@ -5,7 +5,7 @@
 use std::net::{UdpSocket, SocketAddr};
 use std::io;
 
-use titan_proto::{MessageBuilder, TradeMessage, QuoteMessage, MessageHeader, MessageType};
+use titan_proto::{BookAction, MessageBuilder, TradeMessage, StatisticsMessage, MessageHeader, MessageType, TradingState};
 
 /// Market data publisher.
 pub struct Publisher {
@@ -41,6 +41,12 @@ impl Publisher {
         })
     }
     
+    /// Append a CRC32C trailer to every message from now on, so
+    /// receivers drop corrupted datagrams.
+    pub fn set_checksums(&mut self, enabled: bool) {
+        self.builder.set_checksums(enabled);
+    }
+    
     /// Publish a trade.
     pub fn publish_trade(
         &mut self,
@@ -70,8 +76,9 @@ impl Publisher {
         
         let bytes = bytemuck::bytes_of(&trade);
         self.buffer[..bytes.len()].copy_from_slice(bytes);
+        let size = self.builder.seal(&mut self.buffer, bytes.len());
         
-        match self.socket.send_to(&self.buffer[..bytes.len()], self.dest_addr) {
+        match self.socket.send_to(&self.buffer[..size], self.dest_addr) {
             Ok(_) => Ok(()),
             Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
             Err(e) => Err(e),
@@ -94,6 +101,55 @@ impl Publisher {
         }
     }
     
+    /// Publish an L2 book update: level `price` on `side` now holds
+    /// `quantity`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn publish_book_update(
+        &mut self,
+        symbol_id: u32,
+        update_seq: u64,
+        side: u8,
+        action: BookAction,
+        price: u64,
+        quantity: u64,
+    ) -> io::Result<()> {
+        let size = self.builder.build_book_update(&mut self.buffer, symbol_id, update_seq, side, action, price, quantity);
+        
+        match self.socket.send_to(&self.buffer[..size], self.dest_addr) {
+            Ok(_) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// Publish a trading state change of a symbol.
+    pub fn publish_trading_status(
+        &mut self,
+        symbol_id: u32,
+        state: TradingState,
+        reason: u16,
+        timestamp: u64,
+    ) -> io::Result<()> {
+        let size = self.builder.build_trading_status(&mut self.buffer, symbol_id, state, reason, timestamp);
+        
+        match self.socket.send_to(&self.buffer[..size], self.dest_addr) {
+            Ok(_) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// Publish session statistics (the header is filled in here).
+    pub fn publish_statistics(&mut self, stats: StatisticsMessage) -> io::Result<()> {
+        let size = self.builder.build_statistics(&mut self.buffer, stats);
+        
+        match self.socket.send_to(&self.buffer[..size], self.dest_addr) {
+            Ok(_) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    
     /// Publish execution report.
     pub fn publish_execution(
         &mut self,
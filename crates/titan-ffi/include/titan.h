@@ -0,0 +1,327 @@
+#ifndef TITAN_FFI_H
+#define TITAN_FFI_H
+
+/* Generated by cbindgen from crates/titan-ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Maximum number of undrained events held by an engine handle.
+ */
+#define TITAN_EVENT_QUEUE_CAPACITY 4096
+
+#define TITAN_REJECT_INVALID_PRICE 1
+
+#define TITAN_REJECT_INVALID_QUANTITY 2
+
+#define TITAN_REJECT_POOL_EXHAUSTED 3
+
+#define TITAN_REJECT_BOOK_FULL 4
+
+#define TITAN_REJECT_POST_ONLY_WOULD_MATCH 5
+
+#define TITAN_REJECT_SYMBOL_NOT_FOUND 6
+
+#define TITAN_REJECT_INSUFFICIENT_LIQUIDITY 7
+
+#define TITAN_REJECT_NO_LIQUIDITY 8
+
+#define TITAN_REJECT_UNKNOWN_ORDER 9
+
+#define TITAN_REJECT_DUPLICATE_ORDER_ID 10
+
+#define TITAN_REJECT_INVALID_EXPIRY 11
+
+#define TITAN_REJECT_ALREADY_LINKED 12
+
+#define TITAN_REJECT_AUCTION_IN_PROGRESS 13
+
+#define TITAN_REJECT_PRICE_OUT_OF_BAND 14
+
+#define TITAN_REJECT_MMP_TRIPPED 15
+
+#define TITAN_REJECT_NOT_OWNER 16
+
+#define TITAN_REJECT_PARTICIPANT_DISABLED 17
+
+#define TITAN_REJECT_ORDER_TOO_LARGE 18
+
+#define TITAN_REJECT_NOTIONAL_TOO_LARGE 19
+
+#define TITAN_REJECT_TOO_MANY_OPEN_ORDERS 20
+
+#define TITAN_REJECT_PRICE_DEVIATION 21
+
+#define TITAN_REJECT_SHORT_SALE_RESTRICTED 22
+
+/**
+ * Kind of an engine event.
+ */
+enum TitanEventKind
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  /**
+   * Order (or its remainder) is resting on the book.
+   */
+  TITAN_EVENT_KIND_ACCEPTED = 0,
+  /**
+   * A trade occurred.
+   */
+  TITAN_EVENT_KIND_FILL = 1,
+  /**
+   * Order was cancelled (explicitly, or IOC/FOK remainder).
+   */
+  TITAN_EVENT_KIND_CANCELLED = 2,
+  /**
+   * Order was rejected; see `reject_reason`.
+   */
+  TITAN_EVENT_KIND_REJECTED = 3,
+};
+#ifndef __cplusplus
+typedef uint8_t TitanEventKind;
+#endif // __cplusplus
+
+/**
+ * Status codes returned by fallible calls.
+ */
+enum TitanStatus
+#ifdef __cplusplus
+  : int32_t
+#endif // __cplusplus
+ {
+  /**
+   * Call succeeded.
+   */
+  TITAN_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null.
+   */
+  TITAN_STATUS_NULL_POINTER = -1,
+  /**
+   * An enum field was out of range.
+   */
+  TITAN_STATUS_INVALID_ARGUMENT = -2,
+  /**
+   * The event queue cannot hold the worst-case output of this call.
+   */
+  TITAN_STATUS_QUEUE_FULL = -3,
+  /**
+   * The referenced order does not exist.
+   */
+  TITAN_STATUS_NOT_FOUND = -4,
+};
+#ifndef __cplusplus
+typedef int32_t TitanStatus;
+#endif // __cplusplus
+
+/**
+ * Opaque engine handle owned by the host.
+ */
+typedef struct TitanEngine TitanEngine;
+
+/**
+ * Order submitted from the host.
+ */
+typedef struct TitanOrder {
+  /**
+   * Client-assigned order identifier (must be non-zero).
+   */
+  uint64_t order_id;
+  /**
+   * Limit price in ticks.
+   */
+  uint64_t price;
+  /**
+   * Order quantity.
+   */
+  uint64_t quantity;
+  /**
+   * Symbol identifier.
+   */
+  uint32_t symbol_id;
+  /**
+   * 0 = Buy, 1 = Sell.
+   */
+  uint8_t side;
+  /**
+   * 0 = Limit, 1 = IOC, 2 = FOK, 3 = PostOnly, 4 = Market.
+   */
+  uint8_t order_type;
+  /**
+   * Reserved, must be zero.
+   */
+  uint8_t reserved[2];
+} TitanOrder;
+
+/**
+ * Event delivered to the host.
+ *
+ * For `Fill` events `order_id` is the aggressor and `maker_order_id`
+ * the resting order; for all other kinds `maker_order_id` is zero.
+ */
+typedef struct TitanEvent {
+  /**
+   * Order the event refers to.
+   */
+  uint64_t order_id;
+  /**
+   * Resting counterparty (fills only).
+   */
+  uint64_t maker_order_id;
+  /**
+   * Execution price in ticks (fills), otherwise the order price.
+   */
+  uint64_t price;
+  /**
+   * Executed quantity (fills) or affected quantity.
+   */
+  uint64_t quantity;
+  /**
+   * Quantity still open on `order_id` after this event.
+   */
+  uint64_t leaves_qty;
+  /**
+   * Engine timestamp of the call that produced the event.
+   */
+  uint64_t timestamp;
+  /**
+   * Book handle of a resting order (`Accepted`), otherwise `u32::MAX`.
+   */
+  uint32_t handle;
+  /**
+   * Symbol identifier.
+   */
+  uint32_t symbol_id;
+  /**
+   * Event kind.
+   */
+  TitanEventKind kind;
+  /**
+   * Side of `order_id` (0 = Buy, 1 = Sell).
+   */
+  uint8_t side;
+  /**
+   * Reject reason code (`Rejected` only, see `TITAN_REJECT_*`).
+   */
+  uint8_t reject_reason;
+  /**
+   * Reserved.
+   */
+  uint8_t reserved[5];
+} TitanEvent;
+
+/**
+ * Callback invoked once per drained event.
+ */
+typedef void (*TitanEventCallback)(const TitanEvent *event, void *user_data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a matching engine for one symbol.
+ *
+ * `pool_bits` is log2 of the order pool capacity (at most 24) and
+ * `base_price` is the lowest representable price, in ticks.
+ * Returns null if `pool_bits` is out of range.
+ */
+TitanEngine *titan_engine_create(uint32_t symbol_id, uint32_t pool_bits, uint64_t base_price);
+
+/**
+ * Destroy an engine created by `titan_engine_create`.
+ *
+ * # Safety
+ * `engine` must be null or a pointer returned by `titan_engine_create`
+ * that has not already been destroyed.
+ */
+void titan_engine_destroy(TitanEngine *engine);
+
+/**
+ * Submit an order. Resulting events are queued for polling.
+ *
+ * Returns `QueueFull` without touching the book if the queue cannot hold
+ * the worst-case output of a submission; drain events and retry.
+ *
+ * # Safety
+ * `engine` must be a live engine handle and `order` must point to a
+ * valid `TitanOrder`.
+ */
+TitanStatus titan_engine_submit(TitanEngine *engine, const TitanOrder *order, uint64_t timestamp);
+
+/**
+ * Cancel a resting order by the handle reported in its `Accepted` event.
+ *
+ * A handle whose order has left the book is `NotFound`, even if its
+ * pool slot has been reused since.
+ *
+ * # Safety
+ * `engine` must be a live engine handle.
+ */
+TitanStatus titan_engine_cancel(TitanEngine *engine, uint32_t handle, uint64_t timestamp);
+
+/**
+ * Drain queued events through `callback`, oldest first.
+ *
+ * Returns the number of events delivered.
+ *
+ * # Safety
+ * `engine` must be a live engine handle. The callback must not call back
+ * into the same engine handle.
+ */
+size_t titan_engine_poll(TitanEngine *engine, TitanEventCallback callback, void *user_data);
+
+/**
+ * Copy up to `capacity` queued events into `out`, oldest first.
+ *
+ * Returns the number of events copied; remaining events stay queued.
+ *
+ * # Safety
+ * `engine` must be a live engine handle and `out` must be valid for
+ * writes of `capacity` events.
+ */
+size_t titan_engine_poll_into(TitanEngine *engine, TitanEvent *out, size_t capacity);
+
+/**
+ * Number of events waiting to be polled.
+ *
+ * # Safety
+ * `engine` must be null or a live engine handle.
+ */
+size_t titan_engine_pending_events(const TitanEngine *engine);
+
+/**
+ * Write the best bid (in ticks) to `out`. Returns false if there are no bids.
+ *
+ * # Safety
+ * `engine` must be a live engine handle and `out` valid for a write.
+ */
+bool titan_engine_best_bid(const TitanEngine *engine, uint64_t *out);
+
+/**
+ * Write the best ask (in ticks) to `out`. Returns false if there are no asks.
+ *
+ * # Safety
+ * `engine` must be a live engine handle and `out` valid for a write.
+ */
+bool titan_engine_best_ask(const TitanEngine *engine, uint64_t *out);
+
+/**
+ * Number of orders currently resting in the engine.
+ *
+ * # Safety
+ * `engine` must be null or a live engine handle.
+ */
+size_t titan_engine_active_orders(const TitanEngine *engine);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TITAN_FFI_H */
@@ -0,0 +1,571 @@
+//! C ABI for embedding the Titan matching engine.
+//!
+//! Exposes an opaque engine handle with create/destroy, submit, cancel
+//! and event polling, so C and C++ hosts can link the matcher as a
+//! static or shared library without a Rust toolchain in their build.
+//!
+//! Events produced by a call are queued inside the handle and drained
+//! either through a callback (`titan_engine_poll`) or by copying into a
+//! caller-owned array (`titan_engine_poll_into`). The queue is
+//! pre-allocated at creation, so the submit path never allocates.
+//!
+//! Prices cross the boundary in ticks, the same unit as the wire protocol.
+//! The checked-in `include/titan.h` is generated by cbindgen from this
+//! file; see `cbindgen.toml` for the regeneration command.
+
+use core::ffi::c_void;
+use core::ptr;
+
+use titan_core::engine::MAX_FILLS_PER_ORDER;
+use titan_core::{
+    Fill, MatchingEngine, Order, OrderHandle, OrderId, OrderResult, OrderType,
+    Price, Quantity, RejectReason, Side, SymbolId,
+};
+
+/// Maximum number of undrained events held by an engine handle.
+pub const TITAN_EVENT_QUEUE_CAPACITY: usize = 4096;
+
+// Reject reason codes carried in `TitanEvent::reject_reason`.
+pub const TITAN_REJECT_INVALID_PRICE: u8 = 1;
+pub const TITAN_REJECT_INVALID_QUANTITY: u8 = 2;
+pub const TITAN_REJECT_POOL_EXHAUSTED: u8 = 3;
+pub const TITAN_REJECT_BOOK_FULL: u8 = 4;
+pub const TITAN_REJECT_POST_ONLY_WOULD_MATCH: u8 = 5;
+pub const TITAN_REJECT_SYMBOL_NOT_FOUND: u8 = 6;
+pub const TITAN_REJECT_INSUFFICIENT_LIQUIDITY: u8 = 7;
+pub const TITAN_REJECT_NO_LIQUIDITY: u8 = 8;
+pub const TITAN_REJECT_UNKNOWN_ORDER: u8 = 9;
+pub const TITAN_REJECT_DUPLICATE_ORDER_ID: u8 = 10;
+pub const TITAN_REJECT_INVALID_EXPIRY: u8 = 11;
+pub const TITAN_REJECT_ALREADY_LINKED: u8 = 12;
+pub const TITAN_REJECT_AUCTION_IN_PROGRESS: u8 = 13;
+pub const TITAN_REJECT_PRICE_OUT_OF_BAND: u8 = 14;
+pub const TITAN_REJECT_MMP_TRIPPED: u8 = 15;
+pub const TITAN_REJECT_NOT_OWNER: u8 = 16;
+pub const TITAN_REJECT_PARTICIPANT_DISABLED: u8 = 17;
+pub const TITAN_REJECT_ORDER_TOO_LARGE: u8 = 18;
+pub const TITAN_REJECT_NOTIONAL_TOO_LARGE: u8 = 19;
+pub const TITAN_REJECT_TOO_MANY_OPEN_ORDERS: u8 = 20;
+pub const TITAN_REJECT_PRICE_DEVIATION: u8 = 21;
+pub const TITAN_REJECT_SHORT_SALE_RESTRICTED: u8 = 22;
+
+/// Status codes returned by fallible calls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum TitanStatus {
+    /// Call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = -1,
+    /// An enum field was out of range.
+    InvalidArgument = -2,
+    /// The event queue cannot hold the worst-case output of this call.
+    QueueFull = -3,
+    /// The referenced order does not exist.
+    NotFound = -4,
+}
+
+/// Order submitted from the host.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct TitanOrder {
+    /// Client-assigned order identifier (must be non-zero).
+    pub order_id: u64,
+    /// Limit price in ticks.
+    pub price: u64,
+    /// Order quantity.
+    pub quantity: u64,
+    /// Symbol identifier.
+    pub symbol_id: u32,
+    /// 0 = Buy, 1 = Sell.
+    pub side: u8,
+    /// 0 = Limit, 1 = IOC, 2 = FOK, 3 = PostOnly, 4 = Market.
+    pub order_type: u8,
+    /// Reserved, must be zero.
+    pub reserved: [u8; 2],
+}
+
+/// Kind of an engine event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TitanEventKind {
+    /// Order (or its remainder) is resting on the book.
+    Accepted = 0,
+    /// A trade occurred.
+    Fill = 1,
+    /// Order was cancelled (explicitly, or IOC/FOK remainder).
+    Cancelled = 2,
+    /// Order was rejected; see `reject_reason`.
+    Rejected = 3,
+}
+
+/// Event delivered to the host.
+///
+/// For `Fill` events `order_id` is the aggressor and `maker_order_id`
+/// the resting order; for all other kinds `maker_order_id` is zero.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct TitanEvent {
+    /// Order the event refers to.
+    pub order_id: u64,
+    /// Resting counterparty (fills only).
+    pub maker_order_id: u64,
+    /// Execution price in ticks (fills), otherwise the order price.
+    pub price: u64,
+    /// Executed quantity (fills) or affected quantity.
+    pub quantity: u64,
+    /// Quantity still open on `order_id` after this event.
+    pub leaves_qty: u64,
+    /// Engine timestamp of the call that produced the event.
+    pub timestamp: u64,
+    /// Book handle of a resting order (`Accepted`), otherwise `u32::MAX`.
+    pub handle: u32,
+    /// Symbol identifier.
+    pub symbol_id: u32,
+    /// Event kind.
+    pub kind: TitanEventKind,
+    /// Side of `order_id` (0 = Buy, 1 = Sell).
+    pub side: u8,
+    /// Reject reason code (`Rejected` only, see `TITAN_REJECT_*`).
+    pub reject_reason: u8,
+    /// Reserved.
+    pub reserved: [u8; 5],
+}
+
+impl TitanEvent {
+    fn new(kind: TitanEventKind, order: &Order, timestamp: u64) -> Self {
+        Self {
+            order_id: order.order_id.0,
+            maker_order_id: 0,
+            price: order.price.to_ticks(),
+            quantity: order.original_qty.0,
+            leaves_qty: order.remaining_qty.0,
+            timestamp,
+            handle: OrderHandle::INVALID.0,
+            symbol_id: order.symbol.0,
+            kind,
+            side: order.side as u8,
+            reject_reason: 0,
+            reserved: [0; 5],
+        }
+    }
+}
+
+/// Callback invoked once per drained event.
+pub type TitanEventCallback =
+    Option<unsafe extern "C" fn(event: *const TitanEvent, user_data: *mut c_void)>;
+
+/// Opaque engine handle owned by the host.
+pub struct TitanEngine {
+    engine: MatchingEngine,
+    events: Vec<TitanEvent>,
+}
+
+impl TitanEngine {
+    /// Check the queue can absorb the worst case of one submission.
+    #[inline]
+    fn has_room_for_submit(&self) -> bool {
+        self.events.len() + MAX_FILLS_PER_ORDER < TITAN_EVENT_QUEUE_CAPACITY
+    }
+
+    /// Translate an `OrderResult` into queued events.
+    fn push_result(&mut self, order: &Order, result: &OrderResult, timestamp: u64) {
+        match result {
+            OrderResult::Filled { fills } => {
+                self.push_fills(order, fills, timestamp);
+            }
+            OrderResult::PartialFill { fills, resting_qty, handle } => {
+                self.push_fills(order, fills, timestamp);
+                let mut event = TitanEvent::new(TitanEventKind::Accepted, order, timestamp);
+                event.leaves_qty = resting_qty.0;
+                event.handle = handle.0;
+                self.events.push(event);
+            }
+            OrderResult::Resting { handle } => {
+                let mut event = TitanEvent::new(TitanEventKind::Accepted, order, timestamp);
+                event.handle = handle.0;
+                self.events.push(event);
+            }
+            OrderResult::Rejected { reason } => {
+                let mut event = TitanEvent::new(TitanEventKind::Rejected, order, timestamp);
+                event.leaves_qty = 0;
+                event.reject_reason = reject_code(*reason);
+                self.events.push(event);
+            }
+            OrderResult::Cancelled { filled_qty, fills } => {
+                self.push_fills(order, fills, timestamp);
+                let mut event = TitanEvent::new(TitanEventKind::Cancelled, order, timestamp);
+                event.quantity = order.original_qty.0 - filled_qty.0;
+                event.leaves_qty = 0;
+                self.events.push(event);
+            }
+        }
+    }
+
+    fn push_fills(&mut self, order: &Order, fills: &[Fill], timestamp: u64) {
+        let mut leaves = order.original_qty.0;
+        for fill in fills {
+            leaves = leaves.saturating_sub(fill.quantity.0);
+            let mut event = TitanEvent::new(TitanEventKind::Fill, order, timestamp);
+            event.maker_order_id = fill.maker_order_id.0;
+            event.price = fill.price.to_ticks();
+            event.quantity = fill.quantity.0;
+            event.leaves_qty = leaves;
+            self.events.push(event);
+        }
+    }
+}
+
+/// Stable wire code for a reject reason.
+fn reject_code(reason: RejectReason) -> u8 {
+    match reason {
+        RejectReason::InvalidPrice => TITAN_REJECT_INVALID_PRICE,
+        RejectReason::InvalidQuantity => TITAN_REJECT_INVALID_QUANTITY,
+        RejectReason::PoolExhausted => TITAN_REJECT_POOL_EXHAUSTED,
+        RejectReason::BookFull => TITAN_REJECT_BOOK_FULL,
+        RejectReason::PostOnlyWouldMatch => TITAN_REJECT_POST_ONLY_WOULD_MATCH,
+        RejectReason::SymbolNotFound => TITAN_REJECT_SYMBOL_NOT_FOUND,
+        RejectReason::InsufficientLiquidity => TITAN_REJECT_INSUFFICIENT_LIQUIDITY,
+        RejectReason::NoLiquidity => TITAN_REJECT_NO_LIQUIDITY,
+        RejectReason::UnknownOrder => TITAN_REJECT_UNKNOWN_ORDER,
+        RejectReason::DuplicateOrderId => TITAN_REJECT_DUPLICATE_ORDER_ID,
+        RejectReason::InvalidExpiry => TITAN_REJECT_INVALID_EXPIRY,
+        RejectReason::AlreadyLinked => TITAN_REJECT_ALREADY_LINKED,
+        RejectReason::AuctionInProgress => TITAN_REJECT_AUCTION_IN_PROGRESS,
+        RejectReason::PriceOutOfBand => TITAN_REJECT_PRICE_OUT_OF_BAND,
+        RejectReason::MmpTripped => TITAN_REJECT_MMP_TRIPPED,
+        RejectReason::NotOwner => TITAN_REJECT_NOT_OWNER,
+        RejectReason::ParticipantDisabled => TITAN_REJECT_PARTICIPANT_DISABLED,
+        RejectReason::OrderTooLarge => TITAN_REJECT_ORDER_TOO_LARGE,
+        RejectReason::NotionalTooLarge => TITAN_REJECT_NOTIONAL_TOO_LARGE,
+        RejectReason::TooManyOpenOrders => TITAN_REJECT_TOO_MANY_OPEN_ORDERS,
+        RejectReason::PriceDeviation => TITAN_REJECT_PRICE_DEVIATION,
+        RejectReason::ShortSaleRestricted => TITAN_REJECT_SHORT_SALE_RESTRICTED,
+    }
+}
+
+fn decode_side(side: u8) -> Option<Side> {
+    match side {
+        0 => Some(Side::Buy),
+        1 => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn decode_order_type(order_type: u8) -> Option<OrderType> {
+    match order_type {
+        0 => Some(OrderType::Limit),
+        1 => Some(OrderType::IOC),
+        2 => Some(OrderType::FOK),
+        3 => Some(OrderType::PostOnly),
+        4 => Some(OrderType::Market),
+        _ => None,
+    }
+}
+
+/// Create a matching engine for one symbol.
+///
+/// `pool_bits` is log2 of the order pool capacity (at most 24) and
+/// `base_price` is the lowest representable price, in ticks.
+/// Returns null if `pool_bits` is out of range.
+#[no_mangle]
+pub extern "C" fn titan_engine_create(symbol_id: u32, pool_bits: u32, base_price: u64) -> *mut TitanEngine {
+    if pool_bits > 24 {
+        return ptr::null_mut();
+    }
+
+    let engine = TitanEngine {
+        engine: MatchingEngine::new(SymbolId(symbol_id), pool_bits, Price::from_ticks(base_price)),
+        events: Vec::with_capacity(TITAN_EVENT_QUEUE_CAPACITY),
+    };
+    Box::into_raw(Box::new(engine))
+}
+
+/// Destroy an engine created by `titan_engine_create`.
+///
+/// # Safety
+/// `engine` must be null or a pointer returned by `titan_engine_create`
+/// that has not already been destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_destroy(engine: *mut TitanEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Submit an order. Resulting events are queued for polling.
+///
+/// Returns `QueueFull` without touching the book if the queue cannot hold
+/// the worst-case output of a submission; drain events and retry.
+///
+/// # Safety
+/// `engine` must be a live engine handle and `order` must point to a
+/// valid `TitanOrder`.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_submit(
+    engine: *mut TitanEngine,
+    order: *const TitanOrder,
+    timestamp: u64,
+) -> TitanStatus {
+    let (Some(engine), Some(order)) = (engine.as_mut(), order.as_ref()) else {
+        return TitanStatus::NullPointer;
+    };
+
+    let (Some(side), Some(order_type)) = (decode_side(order.side), decode_order_type(order.order_type)) else {
+        return TitanStatus::InvalidArgument;
+    };
+
+    if !engine.has_room_for_submit() {
+        return TitanStatus::QueueFull;
+    }
+
+    let core_order = Order::new(
+        OrderId(order.order_id),
+        SymbolId(order.symbol_id),
+        side,
+        order_type,
+        Price::from_ticks(order.price),
+        Quantity(order.quantity),
+        timestamp,
+    );
+
+    let result = engine.engine.submit_order(core_order, timestamp);
+    engine.push_result(&core_order, &result, timestamp);
+    TitanStatus::Ok
+}
+
+/// Cancel a resting order by the handle reported in its `Accepted` event.
+///
+/// A handle whose order has left the book is `NotFound`, even if its
+/// pool slot has been reused since.
+///
+/// # Safety
+/// `engine` must be a live engine handle.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_cancel(
+    engine: *mut TitanEngine,
+    handle: u32,
+    timestamp: u64,
+) -> TitanStatus {
+    let Some(engine) = engine.as_mut() else {
+        return TitanStatus::NullPointer;
+    };
+
+    if engine.events.len() >= TITAN_EVENT_QUEUE_CAPACITY {
+        return TitanStatus::QueueFull;
+    }
+
+    match engine.engine.cancel_order(OrderHandle(handle)) {
+        Some(order) => {
+            let mut event = TitanEvent::new(TitanEventKind::Cancelled, &order, timestamp);
+            event.quantity = order.remaining_qty.0;
+            event.leaves_qty = 0;
+            event.handle = handle;
+            engine.events.push(event);
+            TitanStatus::Ok
+        }
+        None => TitanStatus::NotFound,
+    }
+}
+
+/// Drain queued events through `callback`, oldest first.
+///
+/// Returns the number of events delivered.
+///
+/// # Safety
+/// `engine` must be a live engine handle. The callback must not call back
+/// into the same engine handle.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_poll(
+    engine: *mut TitanEngine,
+    callback: TitanEventCallback,
+    user_data: *mut c_void,
+) -> usize {
+    let (Some(engine), Some(callback)) = (engine.as_mut(), callback) else {
+        return 0;
+    };
+
+    let count = engine.events.len();
+    for event in &engine.events {
+        callback(event, user_data);
+    }
+    engine.events.clear();
+    count
+}
+
+/// Copy up to `capacity` queued events into `out`, oldest first.
+///
+/// Returns the number of events copied; remaining events stay queued.
+///
+/// # Safety
+/// `engine` must be a live engine handle and `out` must be valid for
+/// writes of `capacity` events.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_poll_into(
+    engine: *mut TitanEngine,
+    out: *mut TitanEvent,
+    capacity: usize,
+) -> usize {
+    let Some(engine) = engine.as_mut() else {
+        return 0;
+    };
+    if out.is_null() {
+        return 0;
+    }
+
+    let count = engine.events.len().min(capacity);
+    ptr::copy_nonoverlapping(engine.events.as_ptr(), out, count);
+    engine.events.drain(..count);
+    count
+}
+
+/// Number of events waiting to be polled.
+///
+/// # Safety
+/// `engine` must be null or a live engine handle.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_pending_events(engine: *const TitanEngine) -> usize {
+    engine.as_ref().map_or(0, |e| e.events.len())
+}
+
+/// Write the best bid (in ticks) to `out`. Returns false if there are no bids.
+///
+/// # Safety
+/// `engine` must be a live engine handle and `out` valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_best_bid(engine: *const TitanEngine, out: *mut u64) -> bool {
+    match (engine.as_ref(), out.is_null()) {
+        (Some(e), false) => match e.engine.book.best_bid() {
+            Some(price) => {
+                *out = price.to_ticks();
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Write the best ask (in ticks) to `out`. Returns false if there are no asks.
+///
+/// # Safety
+/// `engine` must be a live engine handle and `out` valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_best_ask(engine: *const TitanEngine, out: *mut u64) -> bool {
+    match (engine.as_ref(), out.is_null()) {
+        (Some(e), false) => match e.engine.book.best_ask() {
+            Some(price) => {
+                *out = price.to_ticks();
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Number of orders currently resting in the engine.
+///
+/// # Safety
+/// `engine` must be null or a live engine handle.
+#[no_mangle]
+pub unsafe extern "C" fn titan_engine_active_orders(engine: *const TitanEngine) -> usize {
+    engine.as_ref().map_or(0, |e| e.engine.pool_stats().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(order_id: u64, side: u8, order_type: u8, price: u64, quantity: u64) -> TitanOrder {
+        TitanOrder {
+            order_id,
+            price,
+            quantity,
+            symbol_id: 1,
+            side,
+            order_type,
+            reserved: [0; 2],
+        }
+    }
+
+    unsafe extern "C" fn count_fills(event: *const TitanEvent, user_data: *mut c_void) {
+        if (*event).kind == TitanEventKind::Fill {
+            *(user_data as *mut u64) += (*event).quantity;
+        }
+    }
+
+    #[test]
+    fn test_submit_and_poll() {
+        unsafe {
+            let engine = titan_engine_create(1, 10, 0);
+            assert!(!engine.is_null());
+
+            let sell = order(1, 1, 0, 100, 100);
+            assert_eq!(titan_engine_submit(engine, &sell, 1), TitanStatus::Ok);
+
+            let buy = order(2, 0, 0, 100, 60);
+            assert_eq!(titan_engine_submit(engine, &buy, 2), TitanStatus::Ok);
+
+            // Accepted(sell) + Fill(buy)
+            assert_eq!(titan_engine_pending_events(engine), 2);
+
+            let mut filled = 0u64;
+            let delivered = titan_engine_poll(engine, Some(count_fills), &mut filled as *mut u64 as *mut c_void);
+            assert_eq!(delivered, 2);
+            assert_eq!(filled, 60);
+            assert_eq!(titan_engine_pending_events(engine), 0);
+
+            let mut best_ask = 0u64;
+            assert!(titan_engine_best_ask(engine, &mut best_ask));
+            assert_eq!(best_ask, 100);
+
+            titan_engine_destroy(engine);
+        }
+    }
+
+    #[test]
+    fn test_poll_into_and_cancel() {
+        unsafe {
+            let engine = titan_engine_create(1, 10, 0);
+
+            let bid = order(7, 0, 0, 95, 10);
+            titan_engine_submit(engine, &bid, 1);
+
+            let mut events = [TitanEvent::new(TitanEventKind::Accepted, &Order::default(), 0); 4];
+            assert_eq!(titan_engine_poll_into(engine, events.as_mut_ptr(), events.len()), 1);
+            assert_eq!(events[0].kind, TitanEventKind::Accepted);
+            assert_eq!(events[0].order_id, 7);
+
+            let handle = events[0].handle;
+            assert_eq!(titan_engine_cancel(engine, handle, 2), TitanStatus::Ok);
+            assert_eq!(titan_engine_poll_into(engine, events.as_mut_ptr(), events.len()), 1);
+            assert_eq!(events[0].kind, TitanEventKind::Cancelled);
+            assert_eq!(events[0].quantity, 10);
+            assert_eq!(titan_engine_active_orders(engine), 0);
+
+            titan_engine_destroy(engine);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        unsafe {
+            let engine = titan_engine_create(1, 10, 0);
+            let bad_side = order(1, 9, 0, 100, 1);
+            assert_eq!(titan_engine_submit(engine, &bad_side, 0), TitanStatus::InvalidArgument);
+            assert_eq!(titan_engine_submit(engine, ptr::null(), 0), TitanStatus::NullPointer);
+            assert_eq!(titan_engine_submit(ptr::null_mut(), &bad_side, 0), TitanStatus::NullPointer);
+
+            let zero_qty = order(2, 0, 0, 100, 0);
+            titan_engine_submit(engine, &zero_qty, 0);
+            let mut events = [TitanEvent::new(TitanEventKind::Accepted, &Order::default(), 0); 1];
+            assert_eq!(titan_engine_poll_into(engine, events.as_mut_ptr(), 1), 1);
+            assert_eq!(events[0].kind, TitanEventKind::Rejected);
+            assert_eq!(events[0].reject_reason, TITAN_REJECT_INVALID_QUANTITY);
+
+            titan_engine_destroy(engine);
+        }
+        assert!(titan_engine_create(1, 40, 0).is_null());
+    }
+}
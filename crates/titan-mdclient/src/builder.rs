@@ -0,0 +1,255 @@
+//! Book reconstruction from snapshot plus incremental updates.
+//!
+//! A `BookBuilder` starts unsynced. A `BookSnapshotMessage` loads the
+//! levels it carries and records the book update it is current as of;
+//! `BookUpdateMessage`s then apply in `update_seq` order. Updates already
+//! covered by the snapshot (or arriving before it) are skipped, and a gap
+//! drops the builder back to unsynced until the next snapshot.
+//!
+//! The rebuilt `OrderBook` is aggregated (L2): each level holds a single
+//! placeholder order carrying the level quantity, so level order counts
+//! are not meaningful.
+
+use std::collections::BTreeMap;
+use titan_core::{Order, OrderBook, OrderHandle, OrderId, OrderPool, OrderType, Price, Quantity, Side, SymbolId};
+use titan_proto::{BookSnapshotMessage, BookUpdateMessage, MessageParser, MessageType, ParseError};
+
+/// Book reconstruction errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// Message could not be parsed.
+    Parse(ParseError),
+    /// Message is for another symbol.
+    WrongSymbol,
+    /// Side byte is neither bid nor ask.
+    InvalidSide,
+    /// An update was missed; a new snapshot is needed.
+    Gap {
+        /// Update sequence the builder was waiting for.
+        expected: u64,
+        /// Update sequence that arrived.
+        received: u64,
+    },
+    /// No room for another level.
+    BookFull,
+}
+
+impl From<ParseError> for BuildError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Rebuilds one symbol's book from its market data.
+pub struct BookBuilder {
+    symbol_id: u32,
+    book: OrderBook,
+    pool: OrderPool,
+    /// Placeholder order of each level, per side.
+    levels: [BTreeMap<u64, OrderHandle>; 2],
+    /// Next update to apply (`None` while unsynced).
+    next_seq: Option<u64>,
+}
+
+impl BookBuilder {
+    /// Builder for `symbol_id` on a `tick_size` price grid, holding up to
+    /// `2^level_bits` levels.
+    pub fn new(symbol_id: u32, tick_size: u64, level_bits: u32) -> Self {
+        Self {
+            symbol_id,
+            book: OrderBook::with_tick_size(Price::ZERO, tick_size),
+            pool: OrderPool::new(level_bits),
+            levels: [BTreeMap::new(), BTreeMap::new()],
+            next_seq: None,
+        }
+    }
+
+    /// Rebuilt book.
+    #[inline]
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Check if the book is current (snapshot loaded, no gap since).
+    #[inline]
+    pub fn is_synced(&self) -> bool {
+        self.next_seq.is_some()
+    }
+
+    /// Update sequence expected next (`None` while unsynced).
+    #[inline]
+    pub fn next_update_seq(&self) -> Option<u64> {
+        self.next_seq
+    }
+
+    /// Apply one raw market data message.
+    ///
+    /// Returns `true` if it changed the book; messages other than book
+    /// snapshots and updates are ignored.
+    pub fn on_message(&mut self, buffer: &[u8]) -> Result<bool, BuildError> {
+        let (msg_type, _) = MessageParser::validate_message(buffer)?;
+        match msg_type {
+            MessageType::BookSnapshot => {
+                self.apply_snapshot(MessageParser::parse_book_snapshot(buffer)?)?;
+                Ok(true)
+            }
+            MessageType::BookUpdate => self.apply_update(MessageParser::parse_book_update(buffer)?),
+            _ => Ok(false),
+        }
+    }
+
+    /// Replace the book with `snapshot` and sync to its update sequence.
+    pub fn apply_snapshot(&mut self, snapshot: &BookSnapshotMessage) -> Result<(), BuildError> {
+        if snapshot.symbol_id != self.symbol_id {
+            return Err(BuildError::WrongSymbol);
+        }
+
+        for side in [Side::Buy, Side::Sell] {
+            for (_, handle) in core::mem::take(&mut self.levels[side as usize]) {
+                self.pool.deallocate(handle);
+            }
+            self.book.side_mut(side).clear();
+        }
+        self.next_seq = None;
+
+        for (side, levels) in [(Side::Buy, snapshot.bid_levels()), (Side::Sell, snapshot.ask_levels())] {
+            for level in levels {
+                let (price, quantity) = (level.price, level.quantity);
+                self.set_level(side, price, quantity)?;
+            }
+        }
+        self.next_seq = Some(snapshot.update_seq + 1);
+        Ok(())
+    }
+
+    /// Apply the next book update.
+    ///
+    /// Returns `false` if it was skipped: already reflected in the
+    /// snapshot, or no snapshot loaded yet.
+    pub fn apply_update(&mut self, update: &BookUpdateMessage) -> Result<bool, BuildError> {
+        if update.symbol_id != self.symbol_id {
+            return Err(BuildError::WrongSymbol);
+        }
+        let Some(expected) = self.next_seq else {
+            return Ok(false);
+        };
+
+        let received = update.update_seq;
+        if received < expected {
+            return Ok(false);
+        }
+        if received > expected {
+            self.next_seq = None;
+            return Err(BuildError::Gap { expected, received });
+        }
+
+        let side = match update.side {
+            0 => Side::Buy,
+            1 => Side::Sell,
+            _ => return Err(BuildError::InvalidSide),
+        };
+        let (price, quantity) = (update.price, update.quantity);
+        self.set_level(side, price, quantity)?;
+        self.next_seq = Some(received + 1);
+        Ok(true)
+    }
+
+    /// Make level `price` on `side` hold `quantity` (zero removes it).
+    fn set_level(&mut self, side: Side, price: u64, quantity: u64) -> Result<(), BuildError> {
+        let book_side = self.book.side_mut(side);
+        let price = Price(price);
+        let quantity = Quantity(quantity);
+
+        match self.levels[side as usize].get(&price.0).copied() {
+            None if quantity.is_zero() => {}
+            None => {
+                let order = Order::new(OrderId(price.0), SymbolId(self.symbol_id), side, OrderType::Limit, price, quantity, 0);
+                let handle = self.pool.allocate_and_insert(order).ok_or(BuildError::BookFull)?;
+                if !book_side.add_order(handle, &order, self.pool.links_mut()) {
+                    self.pool.deallocate(handle);
+                    return Err(BuildError::BookFull);
+                }
+                self.levels[side as usize].insert(price.0, handle);
+            }
+            Some(handle) if quantity.is_zero() => {
+                let old = self.pool[handle].remaining_qty;
+                book_side.remove_order(handle, price, old, self.pool.links_mut());
+                self.pool.deallocate(handle);
+                self.levels[side as usize].remove(&price.0);
+            }
+            Some(handle) => {
+                let order = &mut self.pool[handle];
+                let old = core::mem::replace(&mut order.remaining_qty, quantity);
+                order.original_qty = quantity;
+                if let Some(level) = book_side.level_at_price_mut(price) {
+                    level.reduce_qty(old);
+                    level.add_qty(quantity);
+                }
+                book_side.reduce_qty(price, old);
+                book_side.add_qty(price, quantity);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_proto::HeartbeatMessage;
+
+    fn update(update_seq: u64, side: u8, ticks: u64, qty: u64) -> BookUpdateMessage {
+        BookUpdateMessage::new(0, 7, update_seq, side, Price::from_ticks(ticks).0, qty)
+    }
+
+    #[test]
+    fn test_snapshot_then_updates() {
+        let mut builder = BookBuilder::new(7, Price::TICK_SIZE, 8);
+        let mut snapshot = BookSnapshotMessage::new(1, 7, 10);
+        snapshot.push_level(0, Price::from_ticks(100).0, 50);
+        snapshot.push_level(0, Price::from_ticks(99).0, 20);
+        snapshot.push_level(1, Price::from_ticks(101).0, 30);
+
+        // Updates before the snapshot are dropped
+        assert_eq!(builder.apply_update(&update(9, 0, 100, 1)), Ok(false));
+        assert!(builder.on_message(bytemuck::bytes_of(&snapshot)).unwrap());
+        assert_eq!(builder.next_update_seq(), Some(11));
+        assert_eq!(builder.book().best_bid(), Some(Price::from_ticks(100)));
+
+        // Already in the snapshot
+        assert_eq!(builder.apply_update(&update(10, 0, 100, 1)), Ok(false));
+        // Best bid removed, ask grows, new ask level
+        assert!(builder.on_message(bytemuck::bytes_of(&update(11, 0, 100, 0))).unwrap());
+        assert_eq!(builder.apply_update(&update(12, 1, 101, 45)), Ok(true));
+        assert_eq!(builder.apply_update(&update(13, 1, 103, 5)), Ok(true));
+
+        let book = builder.book();
+        assert_eq!(book.best_bid(), Some(Price::from_ticks(99)));
+        assert_eq!(book.bids.total_qty(), Quantity(20));
+        assert_eq!(book.asks.total_qty(), Quantity(50));
+        let asks: Vec<_> = book.asks.iter_levels().map(|(price, level)| (price.to_ticks(), level.total_qty.0)).collect();
+        assert_eq!(asks, [(101, 45), (103, 5)]);
+
+        // Other feed messages are ignored
+        let heartbeat = HeartbeatMessage::new(2, 0, 0);
+        assert_eq!(builder.on_message(bytemuck::bytes_of(&heartbeat)), Ok(false));
+    }
+
+    #[test]
+    fn test_gap_needs_new_snapshot() {
+        let mut builder = BookBuilder::new(7, Price::TICK_SIZE, 8);
+        builder.apply_snapshot(&BookSnapshotMessage::new(1, 7, 0)).unwrap();
+        assert_eq!(builder.apply_update(&update(1, 1, 101, 10)), Ok(true));
+
+        assert_eq!(builder.apply_update(&update(3, 1, 102, 10)), Err(BuildError::Gap { expected: 2, received: 3 }));
+        assert!(!builder.is_synced());
+        assert_eq!(builder.apply_update(&update(4, 1, 102, 10)), Ok(false));
+
+        let mut snapshot = BookSnapshotMessage::new(2, 7, 4);
+        snapshot.push_level(1, Price::from_ticks(102).0, 10);
+        builder.apply_snapshot(&snapshot).unwrap();
+        assert_eq!(builder.book().best_ask(), Some(Price::from_ticks(102)));
+        assert_eq!(builder.book().asks.total_qty(), Quantity(10));
+        assert_eq!(builder.apply_snapshot(&BookSnapshotMessage::new(3, 8, 0)), Err(BuildError::WrongSymbol));
+    }
+}
@@ -0,0 +1,99 @@
+//! Level-by-level difference between two book snapshots.
+//!
+//! `diff_snapshots` lists the book updates that turn one snapshot into
+//! the other: a level only in the newer snapshot is added, one only in the
+//! older is removed (zero quantity) and one in both with another quantity
+//! is changed. Only the levels the snapshots carry are compared; a level
+//! that merely fell below the snapshot depth shows up as removed.
+
+use std::collections::BTreeMap;
+use titan_proto::{BookAction, BookSnapshotMessage, BookUpdateMessage, SnapshotLevel};
+use crate::builder::BuildError;
+
+/// Price → quantity of one snapshot side.
+fn side_map(levels: &[SnapshotLevel]) -> BTreeMap<u64, u64> {
+    levels.iter().map(|level| (level.price, level.quantity)).collect()
+}
+
+/// Book updates taking `before` to `after`, bids then asks, each side
+/// best price first.
+///
+/// Updates are numbered on from `before.update_seq`, so a `BookBuilder`
+/// synced to `before` can apply them directly; the header sequence is
+/// left zero for the publisher to stamp.
+pub fn diff_snapshots(before: &BookSnapshotMessage, after: &BookSnapshotMessage) -> Result<Vec<BookUpdateMessage>, BuildError> {
+    let symbol_id = before.symbol_id;
+    if after.symbol_id != symbol_id {
+        return Err(BuildError::WrongSymbol);
+    }
+
+    let mut updates = Vec::new();
+    let mut update_seq = before.update_seq;
+    let sides = [
+        (0, before.bid_levels(), after.bid_levels()),
+        (1, before.ask_levels(), after.ask_levels()),
+    ];
+    for (side, old, new) in sides {
+        let (old, new) = (side_map(old), side_map(new));
+        let mut prices: Vec<u64> = old.keys().chain(new.keys()).copied().collect();
+        prices.sort_unstable();
+        prices.dedup();
+        // Bids best (highest) first
+        if side == 0 {
+            prices.reverse();
+        }
+
+        for price in prices {
+            let quantity = new.get(&price).copied().unwrap_or(0);
+            let previous = old.get(&price).copied();
+            if previous.unwrap_or(0) != quantity {
+                update_seq += 1;
+                let mut update = BookUpdateMessage::new(0, symbol_id, update_seq, side, price, quantity);
+                if previous.is_none() {
+                    update = update.with_action(BookAction::New);
+                }
+                updates.push(update);
+            }
+        }
+    }
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_core::Price;
+    use crate::builder::BookBuilder;
+
+    #[test]
+    fn test_diff_replays_to_newer_snapshot() {
+        let px = |ticks| Price::from_ticks(ticks).0;
+        let mut before = BookSnapshotMessage::new(1, 7, 20);
+        before.push_level(0, px(100), 10);
+        before.push_level(0, px(99), 5);
+        before.push_level(1, px(101), 8);
+        let mut after = BookSnapshotMessage::new(2, 7, 31);
+        after.push_level(0, px(100), 12);
+        after.push_level(1, px(101), 8);
+        after.push_level(1, px(102), 4);
+
+        let updates = diff_snapshots(&before, &after).unwrap();
+        let summary: Vec<_> = updates.iter().map(|u| (u.update_seq, u.side, u.price, u.quantity)).collect();
+        assert_eq!(summary, [(21, 0, px(100), 12), (22, 0, px(99), 0), (23, 1, px(102), 4)]);
+        let actions: Vec<_> = updates.iter().filter_map(|u| u.book_action()).collect();
+        assert_eq!(actions, [BookAction::Change, BookAction::Delete, BookAction::New]);
+
+        // Replaying the diff on the older snapshot gives the newer book
+        let mut builder = BookBuilder::new(7, Price::TICK_SIZE, 8);
+        builder.apply_snapshot(&before).unwrap();
+        for update in &updates {
+            assert_eq!(builder.apply_update(update), Ok(true));
+        }
+        let mut expected = BookBuilder::new(7, Price::TICK_SIZE, 8);
+        expected.apply_snapshot(&after).unwrap();
+        assert_eq!(builder.book().checksum(), expected.book().checksum());
+
+        assert!(diff_snapshots(&before, &before).unwrap().is_empty());
+        assert_eq!(diff_snapshots(&before, &BookSnapshotMessage::new(3, 8, 0)).unwrap_err(), BuildError::WrongSymbol);
+    }
+}
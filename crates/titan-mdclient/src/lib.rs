@@ -0,0 +1,11 @@
+//! Market data client.
+//!
+//! Consumer-side counterpart of `titan-feed`: rebuilds a symbol's book
+//! from a snapshot followed by incremental book updates, and diffs
+//! snapshots into the updates between them.
+
+pub mod builder;
+pub mod diff;
+
+pub use builder::{BookBuilder, BuildError};
+pub use diff::diff_snapshots;
@@ -0,0 +1,297 @@
+//! Exchange-grade UTC timestamps.
+//!
+//! Reads a reference clock (PTP hardware clock, `CLOCK_TAI` or
+//! `CLOCK_REALTIME`) and maintains a mapping from the raw RDTSC counter to
+//! UTC nanoseconds, so the hot path can stamp orders, fills and feed
+//! messages without a syscall. Call `resync` periodically (e.g. once a
+//! second from a housekeeping thread) to track the reference clock.
+
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// TAI - UTC offset in seconds (valid since 2017-01-01).
+pub const TAI_UTC_OFFSET_S: i64 = 37;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Fixed-point shift for the counter-to-nanosecond multiplier.
+const MAPPING_SHIFT: u32 = 32;
+
+/// Reference clock samples taken per anchor; the tightest bracket wins.
+const ANCHOR_SAMPLES: usize = 8;
+
+/// UTC timestamp in nanoseconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct UtcTimestamp(pub u64);
+
+impl UtcTimestamp {
+    /// Get nanoseconds since the Unix epoch.
+    #[inline(always)]
+    pub const fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Whole seconds since the Unix epoch.
+    #[inline(always)]
+    pub const fn secs(self) -> u64 {
+        self.0 / NANOS_PER_SEC
+    }
+
+    /// Nanosecond part of the current second.
+    #[inline(always)]
+    pub const fn subsec_nanos(self) -> u32 {
+        (self.0 % NANOS_PER_SEC) as u32
+    }
+}
+
+/// ISO 8601 with nanosecond precision, e.g. `2024-03-01T14:30:00.123456789Z`.
+impl fmt::Display for UtcTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.secs();
+        let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+        let tod = secs % 86_400;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+            year, month, day,
+            tod / 3600, (tod / 60) % 60, tod % 60,
+            self.subsec_nanos(),
+        )
+    }
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Reference clock to derive UTC from.
+#[derive(Debug)]
+pub enum ClockSource {
+    /// `CLOCK_REALTIME` (NTP/PTP-disciplined system clock).
+    Realtime,
+    /// `CLOCK_TAI`, converted to UTC with `utc_offset_s`.
+    Tai { utc_offset_s: i64 },
+    /// PTP hardware clock (`/dev/ptpN`), converted with `utc_offset_s`.
+    ///
+    /// PHCs disciplined by `ptp4l` run on the TAI timescale; use
+    /// `TAI_UTC_OFFSET_S` unless the clock is configured for UTC.
+    Phc { device: File, utc_offset_s: i64 },
+}
+
+impl ClockSource {
+    /// `CLOCK_TAI` with the current leap second offset.
+    pub fn tai() -> Self {
+        ClockSource::Tai { utc_offset_s: TAI_UTC_OFFSET_S }
+    }
+
+    /// Open a PTP hardware clock device running on TAI.
+    pub fn open_phc<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let device = File::open(path)?;
+        Ok(ClockSource::Phc { device, utc_offset_s: TAI_UTC_OFFSET_S })
+    }
+
+    /// Read the reference clock as UTC nanoseconds.
+    pub fn read_utc(&self) -> io::Result<UtcTimestamp> {
+        let (clock_id, offset_s) = match self {
+            ClockSource::Realtime => (libc::CLOCK_REALTIME, 0),
+            ClockSource::Tai { utc_offset_s } => (clock_tai()?, *utc_offset_s),
+            ClockSource::Phc { device, utc_offset_s } => (phc_clock_id(device), *utc_offset_s),
+        };
+
+        let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        // SAFETY: `ts` is a valid, writable timespec.
+        if unsafe { libc::clock_gettime(clock_id, &mut ts) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let secs = ts.tv_sec - offset_s as libc::time_t;
+        if secs < 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "clock reads before the Unix epoch"));
+        }
+        Ok(UtcTimestamp(secs as u64 * NANOS_PER_SEC + ts.tv_nsec as u64))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn clock_tai() -> io::Result<libc::clockid_t> {
+    Ok(libc::CLOCK_TAI)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clock_tai() -> io::Result<libc::clockid_t> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "CLOCK_TAI requires Linux"))
+}
+
+/// Dynamic POSIX clock id for an open PHC device (`FD_TO_CLOCKID`).
+fn phc_clock_id(device: &File) -> libc::clockid_t {
+    use std::os::unix::io::AsRawFd;
+    ((!device.as_raw_fd()) << 3) | 3
+}
+
+/// Linear mapping from raw counter ticks to UTC nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockMapping {
+    /// Counter value at the anchor.
+    pub raw_anchor: u64,
+    /// UTC time at the anchor.
+    pub utc_anchor: u64,
+    /// Nanoseconds per counter tick, scaled by 2^32.
+    pub mult: u64,
+}
+
+impl ClockMapping {
+    /// Mapping through one anchor with a known rate.
+    pub const fn new(raw_anchor: u64, utc_anchor: u64, mult: u64) -> Self {
+        Self { raw_anchor, utc_anchor, mult }
+    }
+
+    /// Derive the rate from two (raw, utc) samples.
+    ///
+    /// Returns `None` if the samples do not advance both clocks.
+    pub fn from_samples(first: (u64, u64), second: (u64, u64)) -> Option<Self> {
+        let raw_delta = second.0.checked_sub(first.0).filter(|&d| d > 0)?;
+        let utc_delta = second.1.checked_sub(first.1).filter(|&d| d > 0)?;
+        let mult = ((utc_delta as u128) << MAPPING_SHIFT) / raw_delta as u128;
+        Some(Self::new(second.0, second.1, mult as u64))
+    }
+
+    /// Convert a counter value to UTC.
+    ///
+    /// Values before the anchor are extrapolated backwards.
+    #[inline(always)]
+    pub fn to_utc(&self, raw: u64) -> UtcTimestamp {
+        let scale = |ticks: u64| ((ticks as u128 * self.mult as u128) >> MAPPING_SHIFT) as u64;
+        if raw >= self.raw_anchor {
+            UtcTimestamp(self.utc_anchor + scale(raw - self.raw_anchor))
+        } else {
+            UtcTimestamp(self.utc_anchor.saturating_sub(scale(self.raw_anchor - raw)))
+        }
+    }
+}
+
+/// UTC clock backed by the RDTSC counter and a reference `ClockSource`.
+pub struct UtcClock {
+    source: ClockSource,
+    counter: quanta::Clock,
+    mapping: ClockMapping,
+    /// Last anchor sample, used to refine the rate on resync.
+    last_anchor: (u64, u64),
+}
+
+impl UtcClock {
+    /// Create a clock and calibrate the counter rate over `calibration`.
+    pub fn new(source: ClockSource, calibration: std::time::Duration) -> io::Result<Self> {
+        let counter = quanta::Clock::new();
+
+        let first = sample_anchor(&source, &counter)?;
+        std::thread::sleep(calibration);
+        let second = sample_anchor(&source, &counter)?;
+
+        let mapping = ClockMapping::from_samples(first, second).ok_or_else(|| {
+            io::Error::other("reference clock or counter did not advance")
+        })?;
+
+        Ok(Self { source, counter, mapping, last_anchor: second })
+    }
+
+    /// Current UTC time from the counter (no syscall).
+    #[inline(always)]
+    pub fn now(&self) -> UtcTimestamp {
+        self.mapping.to_utc(self.counter.raw())
+    }
+
+    /// Read the reference clock directly (syscall).
+    pub fn now_precise(&self) -> io::Result<UtcTimestamp> {
+        self.source.read_utc()
+    }
+
+    /// Current counter-to-UTC mapping.
+    pub fn mapping(&self) -> ClockMapping {
+        self.mapping
+    }
+
+    /// Re-anchor to the reference clock and refine the counter rate.
+    ///
+    /// Returns the observed drift (reference minus mapped) in nanoseconds.
+    pub fn resync(&mut self) -> io::Result<i64> {
+        let anchor = sample_anchor(&self.source, &self.counter)?;
+        let predicted = self.mapping.to_utc(anchor.0).as_nanos();
+        let drift = anchor.1 as i64 - predicted as i64;
+
+        if let Some(mapping) = ClockMapping::from_samples(self.last_anchor, anchor) {
+            self.mapping = mapping;
+        }
+        self.last_anchor = anchor;
+        Ok(drift)
+    }
+}
+
+/// Sample (raw counter, reference UTC), using the reference read with
+/// the tightest counter bracket and the bracket midpoint.
+fn sample_anchor(source: &ClockSource, counter: &quanta::Clock) -> io::Result<(u64, u64)> {
+    let mut best: Option<(u64, u64, u64)> = None;
+
+    for _ in 0..ANCHOR_SAMPLES {
+        let before = counter.raw();
+        let utc = source.read_utc()?.as_nanos();
+        let after = counter.raw();
+
+        let width = after.wrapping_sub(before);
+        if best.is_none_or(|(w, _, _)| width < w) {
+            best = Some((width, before + width / 2, utc));
+        }
+    }
+
+    let (_, raw, utc) = best.expect("ANCHOR_SAMPLES is non-zero");
+    Ok((raw, utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_timestamp_format() {
+        assert_eq!(UtcTimestamp(0).to_string(), "1970-01-01T00:00:00.000000000Z");
+        // 2024-02-29 (leap day) 12:34:56.000000789
+        let ts = UtcTimestamp(1_709_210_096 * NANOS_PER_SEC + 789);
+        assert_eq!(ts.to_string(), "2024-02-29T12:34:56.000000789Z");
+    }
+
+    #[test]
+    fn test_clock_mapping() {
+        // 4 ticks per ns
+        let mapping = ClockMapping::from_samples((1_000, 5_000), (5_000, 6_000)).unwrap();
+        assert_eq!(mapping.to_utc(5_000).as_nanos(), 6_000);
+        assert_eq!(mapping.to_utc(9_000).as_nanos(), 7_000);
+        assert_eq!(mapping.to_utc(1_000).as_nanos(), 5_000);
+
+        assert!(ClockMapping::from_samples((1_000, 5_000), (1_000, 6_000)).is_none());
+    }
+
+    #[test]
+    fn test_realtime_clock_tracks_reference() {
+        let mut clock = UtcClock::new(ClockSource::Realtime, std::time::Duration::from_millis(5)).unwrap();
+        let a = clock.now();
+        let reference = clock.now_precise().unwrap();
+        let b = clock.now();
+        assert!(b >= a);
+        // Within 1ms of the system clock
+        assert!(reference.as_nanos().abs_diff(a.as_nanos()) < 1_000_000);
+
+        assert!(clock.resync().unwrap().abs() < 1_000_000);
+    }
+}
@@ -1,6 +1,10 @@
 //! Latency tracking and metrics with HdrHistogram.
 //!
-//! Provides nanosecond-precision latency measurement.
+//! Provides nanosecond-precision latency measurement and UTC timestamping.
+
+pub mod clock;
+
+pub use clock::{ClockSource, UtcClock, UtcTimestamp};
 
 use hdrhistogram::Histogram;
 
@@ -0,0 +1,343 @@
+//! Message framing for the gateway read path.
+//!
+//! Splits the front of a connection's read buffer into complete protocol
+//! messages. Kept free of socket state so the same loop can be driven by
+//! tests and fuzzers with arbitrary split/short reads.
+
+use mio::Token;
+
+use titan_core::SessionId;
+
+use titan_proto::{BatchIter, MessageParser, MessageType, MessageHeader};
+
+use crate::gateway::GatewayEvent;
+
+/// Decode every complete message at the front of `buffer`.
+///
+/// Order-entry, directory and logon messages are translated into `GatewayEvent`s
+/// tagged with `token`; order entry failing `validate_strict` becomes
+/// `Invalid` and a logon with a protocol version other than ours
+/// becomes `LogonRejected`. Batches are unpacked; other valid message
+/// types are skipped. Decoding stops at the first incomplete or invalid
+/// frame.
+///
+/// Messages are copied out of the buffer, so frames need not be aligned.
+///
+/// Returns the number of bytes consumed. A trailing partial message is
+/// left in place for the next read.
+pub fn decode_frames(token: Token, buffer: &[u8], events: &mut Vec<GatewayEvent>) -> usize {
+    let mut consumed = 0;
+    
+    while consumed + core::mem::size_of::<MessageHeader>() <= buffer.len() {
+        let frame = &buffer[consumed..];
+        
+        // Validate and get message length
+        let (msg_type, msg_len) = match MessageParser::validate_message(frame) {
+            Ok((t, l)) => (t, l),
+            Err(_) => break,
+        };
+        
+        if msg_len > frame.len() {
+            break; // Incomplete message
+        }
+        
+        if msg_type == MessageType::Batch {
+            // Unpack; an invalid inner message drops the rest of the batch
+            if let Ok(messages) = BatchIter::new(frame) {
+                for (inner_type, inner) in messages.map_while(Result::ok) {
+                    decode_message(token, inner_type, inner, events);
+                }
+            }
+        } else {
+            decode_message(token, msg_type, frame, events);
+        }
+        
+        consumed += msg_len;
+    }
+    
+    consumed
+}
+
+/// Pass `event` through if `frame` passes `validate_strict`, otherwise
+/// replace it with `Invalid`.
+fn checked(frame: &[u8], token: Token, order_id: u64, symbol_id: u32, event: GatewayEvent) -> GatewayEvent {
+    match MessageParser::validate_strict(frame) {
+        Ok(_) => event,
+        Err(error) => GatewayEvent::Invalid { token, session: SessionId::NONE, order_id, symbol_id, error },
+    }
+}
+
+/// Translate one message into its event, if it has one.
+fn decode_message(token: Token, msg_type: MessageType, frame: &[u8], events: &mut Vec<GatewayEvent>) {
+    match msg_type {
+        MessageType::NewOrder => {
+            if let Ok(order) = MessageParser::parse_new_order_copy(frame) {
+                let event = GatewayEvent::NewOrder {
+                    token,
+                    session: SessionId::NONE,
+                    order_id: order.get_order_id(),
+                    symbol_id: order.get_symbol_id(),
+                    side: order.side,
+                    order_type: order.order_type,
+                    price: order.get_price(),
+                    quantity: order.get_quantity(),
+                    display_qty: order.get_display_qty() as u64,
+                    participant: order.get_participant_id(),
+                    client_ref: order.client_order_id,
+                };
+                events.push(checked(frame, token, order.get_order_id(), order.get_symbol_id(), event));
+            }
+        }
+        MessageType::CancelOrder => {
+            if let Ok(cancel) = MessageParser::parse_cancel_copy(frame) {
+                let event = GatewayEvent::CancelOrder {
+                    token,
+                    session: SessionId::NONE,
+                    order_id: cancel.get_order_id(),
+                    symbol_id: cancel.get_symbol_id(),
+                    participant: cancel.get_participant_id(),
+                };
+                events.push(checked(frame, token, cancel.get_order_id(), cancel.get_symbol_id(), event));
+            }
+        }
+        MessageType::ModifyOrder => {
+            if let Ok(modify) = MessageParser::parse_modify_copy(frame) {
+                let event = GatewayEvent::ModifyOrder {
+                    token,
+                    session: SessionId::NONE,
+                    order_id: modify.get_order_id(),
+                    symbol_id: modify.get_symbol_id(),
+                    price: modify.get_price(),
+                    quantity: modify.get_quantity(),
+                    participant: modify.get_participant_id(),
+                };
+                events.push(checked(frame, token, modify.get_order_id(), modify.get_symbol_id(), event));
+            }
+        }
+        MessageType::MassQuote => {
+            if let Ok(quote) = MessageParser::parse_mass_quote_copy(frame) {
+                let event = GatewayEvent::MassQuote {
+                    token,
+                    session: SessionId::NONE,
+                    symbol_id: quote.get_symbol_id(),
+                    bid_order_id: quote.get_bid_order_id(),
+                    bid_price: quote.get_bid_price(),
+                    bid_qty: quote.get_bid_qty(),
+                    ask_order_id: quote.get_ask_order_id(),
+                    ask_price: quote.get_ask_price(),
+                    ask_qty: quote.get_ask_qty(),
+                    participant: quote.get_participant_id(),
+                };
+                events.push(checked(frame, token, 0, quote.get_symbol_id(), event));
+            }
+        }
+        MessageType::MassCancel => {
+            if let Ok(cancel) = MessageParser::parse_mass_cancel_copy(frame) {
+                let event = GatewayEvent::MassCancel {
+                    token,
+                    session: SessionId::NONE,
+                    symbol_id: cancel.get_symbol_id(),
+                    side: cancel.side,
+                    participant: cancel.get_participant_id(),
+                };
+                events.push(checked(frame, token, 0, cancel.get_symbol_id(), event));
+            }
+        }
+        MessageType::OrderStatusRequest => {
+            if let Ok(request) = MessageParser::parse_order_status_request_copy(frame) {
+                events.push(GatewayEvent::OrderStatusRequest {
+                    token,
+                    session: SessionId::NONE,
+                    order_id: request.get_order_id(),
+                    symbol_id: request.get_symbol_id(),
+                    participant: request.get_participant_id(),
+                });
+            }
+        }
+        MessageType::DirectoryRequest => {
+            if let Ok(request) = MessageParser::parse_directory_request_copy(frame) {
+                events.push(GatewayEvent::DirectoryRequest {
+                    token,
+                    session: SessionId::NONE,
+                    request_id: request.get_request_id(),
+                    symbol_id: request.get_symbol_id(),
+                });
+            }
+        }
+        MessageType::Logon => {
+            if let Ok(logon) = MessageParser::parse_logon_copy(frame) {
+                events.push(if logon.is_supported_version() {
+                    GatewayEvent::Logon {
+                        token,
+                        session: SessionId::NONE,
+                        session_id: logon.get_session_id(),
+                        heartbeat_ms: logon.get_heartbeat_ms(),
+                        credentials: logon.credentials,
+                    }
+                } else {
+                    GatewayEvent::LogonRejected {
+                        token,
+                        session_id: logon.get_session_id(),
+                        protocol_version: logon.get_protocol_version(),
+                    }
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Hold a session to a single participant.
+///
+/// The first order-entry event binds the session to the participant it
+/// names. Later events naming anyone else are replaced with
+/// `Unauthorized`, so a session can neither enter orders for nor cancel,
+/// modify or query the orders of another participant. A refused mass
+/// cancel reports order ID 0.
+pub fn authorize(bound: &mut Option<u32>, events: &mut [GatewayEvent]) {
+    for event in events {
+        let (token, session, order_id, participant) = match *event {
+            GatewayEvent::NewOrder { token, session, order_id, participant, .. }
+            | GatewayEvent::CancelOrder { token, session, order_id, participant, .. }
+            | GatewayEvent::ModifyOrder { token, session, order_id, participant, .. }
+            | GatewayEvent::OrderStatusRequest { token, session, order_id, participant, .. } => {
+                (token, session, order_id, participant)
+            }
+            GatewayEvent::MassQuote { token, session, bid_order_id, participant, .. } => {
+                (token, session, bid_order_id, participant)
+            }
+            GatewayEvent::MassCancel { token, session, participant, .. } => (token, session, 0, participant),
+            _ => continue,
+        };
+        if *bound.get_or_insert(participant) != participant {
+            *event = GatewayEvent::Unauthorized { token, session, order_id };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_proto::{
+        BatchBuilder, CancelOrderMessage, LogonMessage, MassCancelMessage, MassQuoteMessage, ModifyOrderMessage,
+        NewOrderMessage, OrderStatusRequestMessage, ValidationError, PROTOCOL_VERSION,
+    };
+    
+    fn wire_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(bytemuck::bytes_of(&NewOrderMessage::new(1, 10, 1, 0, 0, 100, 5)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&CancelOrderMessage::new(2, 10, 1)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&ModifyOrderMessage::new(3, 11, 1, 101, 7)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&MassQuoteMessage::new(4, 1, 12, 99, 5, 13, 102, 6)));
+        bytes
+    }
+    
+    #[test]
+    fn test_decode_complete_frames() {
+        let bytes = wire_bytes();
+        let mut events = Vec::new();
+        
+        let consumed = decode_frames(Token(3), &bytes, &mut events);
+        assert_eq!(consumed, bytes.len());
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], GatewayEvent::NewOrder { order_id: 10, .. }));
+        assert!(matches!(events[1], GatewayEvent::CancelOrder { order_id: 10, .. }));
+        assert!(matches!(events[2], GatewayEvent::ModifyOrder { order_id: 11, price: 101, quantity: 7, .. }));
+        assert!(matches!(events[3], GatewayEvent::MassQuote { bid_order_id: 12, bid_price: 99, ask_order_id: 13, ask_qty: 6, .. }));
+    }
+    
+    #[test]
+    fn test_partial_frame_left_in_buffer() {
+        let bytes = wire_bytes();
+        let mut events = Vec::new();
+        
+        // Whole NewOrder plus half of the cancel
+        let cut = 64 + 16;
+        let consumed = decode_frames(Token(3), &bytes[..cut], &mut events);
+        assert_eq!(consumed, 64);
+        assert_eq!(events.len(), 1);
+    }
+    
+    #[test]
+    fn test_session_bound_to_participant() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(bytemuck::bytes_of(&NewOrderMessage::new(1, 10, 1, 0, 0, 100, 5).with_participant(7)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&CancelOrderMessage::new(2, 20, 1).with_participant(8)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&ModifyOrderMessage::new(3, 10, 1, 101, 7).with_participant(7)));
+        let mut events = Vec::new();
+        decode_frames(Token(3), &bytes, &mut events);
+        
+        let mut bound = None;
+        authorize(&mut bound, &mut events);
+        assert_eq!(bound, Some(7));
+        assert!(matches!(events[0], GatewayEvent::NewOrder { participant: 7, .. }));
+        assert_eq!(events[1], GatewayEvent::Unauthorized { token: Token(3), session: SessionId::NONE, order_id: 20 });
+        assert!(matches!(events[2], GatewayEvent::ModifyOrder { participant: 7, .. }));
+    }
+    
+    #[test]
+    fn test_logon_version_checked() {
+        let logon = LogonMessage::new(1, 99, [7; 16], 1000);
+        let stale = LogonMessage { protocol_version: PROTOCOL_VERSION + 1, ..logon };
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(bytemuck::bytes_of(&logon));
+        bytes.extend_from_slice(bytemuck::bytes_of(&stale));
+        let mut events = Vec::new();
+        
+        assert_eq!(decode_frames(Token(3), &bytes, &mut events), 80);
+        assert_eq!(events[0], GatewayEvent::Logon {
+            token: Token(3), session: SessionId::NONE, session_id: 99, heartbeat_ms: 1000, credentials: [7; 16],
+        });
+        assert_eq!(events[1], GatewayEvent::LogonRejected { token: Token(3), session_id: 99, protocol_version: PROTOCOL_VERSION + 1 });
+    }
+    
+    #[test]
+    fn test_batch_unpacked() {
+        let bytes = wire_bytes();
+        let mut batch = [0u8; 256];
+        let mut builder = BatchBuilder::new(&mut batch);
+        assert!(builder.push(&bytes[..64]));
+        assert!(builder.push(&bytes[64..96]));
+        let size = builder.finish(1);
+        let mut events = Vec::new();
+        
+        assert_eq!(decode_frames(Token(3), &batch[..size], &mut events), size);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], GatewayEvent::NewOrder { order_id: 10, .. }));
+        assert!(matches!(events[1], GatewayEvent::CancelOrder { order_id: 10, .. }));
+    }
+    
+    #[test]
+    fn test_mass_cancel_and_status_request_authorized() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(bytemuck::bytes_of(&OrderStatusRequestMessage::new(1, 10, 1).with_participant(7)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&MassCancelMessage::new(2, 0, 2).with_participant(7)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&MassCancelMessage::new(3, 1, 0).with_participant(8)));
+        let mut events = Vec::new();
+        decode_frames(Token(3), &bytes, &mut events);
+        
+        let mut bound = None;
+        authorize(&mut bound, &mut events);
+        let session = SessionId::NONE;
+        assert_eq!(events[0], GatewayEvent::OrderStatusRequest { token: Token(3), session, order_id: 10, symbol_id: 1, participant: 7 });
+        assert_eq!(events[1], GatewayEvent::MassCancel { token: Token(3), session, symbol_id: 0, side: 2, participant: 7 });
+        assert_eq!(events[2], GatewayEvent::Unauthorized { token: Token(3), session, order_id: 0 });
+    }
+    
+    #[test]
+    fn test_invalid_fields_become_invalid_events() {
+        let mut bad = NewOrderMessage::new(1, 10, 1, 0, 0, 100, 5);
+        bad.side = 9;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(bytemuck::bytes_of(&bad));
+        bytes.extend_from_slice(bytemuck::bytes_of(&ModifyOrderMessage::new(2, 11, 1, 101, 0)));
+        bytes.extend_from_slice(bytemuck::bytes_of(&NewOrderMessage::new(3, 12, 1, 1, 0, 100, 5)));
+        let mut events = Vec::new();
+        
+        assert_eq!(decode_frames(Token(3), &bytes, &mut events), bytes.len());
+        let invalid = |order_id, error| GatewayEvent::Invalid { token: Token(3), session: SessionId::NONE, order_id, symbol_id: 1, error };
+        assert_eq!(events[0], invalid(10, ValidationError::InvalidSide));
+        assert_eq!(events[1], invalid(11, ValidationError::ZeroQuantity));
+        assert!(matches!(events[2], GatewayEvent::NewOrder { order_id: 12, .. }));
+    }
+}
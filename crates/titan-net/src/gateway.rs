@@ -8,13 +8,27 @@ use mio::net::{TcpListener, TcpStream};
 use std::collections::HashMap;
 use std::io::{self, Read, Write};
 use std::net::SocketAddr;
+use std::time::Instant;
 
-use titan_proto::{MessageParser, MessageType};
+use titan_core::{Fill, OrderId, OrderResult, SessionId};
+use titan_proto::{
+    HeartbeatMonitor, Liveness, LogonAckMessage, LogonStatus, OrderRejectMessage, Severity, SystemErrorCode, SystemErrorMessage, ValidationError,
+    truncate_utf8, MAX_TEXT_LEN,
+};
+
+use crate::framing::{authorize, decode_frames};
+use crate::routing::{Route, RoutingTable};
+use crate::session::{Authenticator, SessionGate, SessionState};
 
 const SERVER: Token = Token(0);
 const MAX_CONNECTIONS: usize = 1024;
-const READ_BUFFER_SIZE: usize = 4096;
+/// Per-connection read buffer size (largest frame the gateway can accept).
+pub const READ_BUFFER_SIZE: usize = 4096;
 const WRITE_BUFFER_SIZE: usize = 4096;
+/// Heartbeat interval for connections that have not logged on yet, or
+/// whose logon asked for none. A connection silent for `STALE_INTERVALS`
+/// intervals is closed.
+pub const DEFAULT_HEARTBEAT_MS: u32 = 30_000;
 
 /// Per-connection state.
 pub struct Connection {
@@ -25,10 +39,16 @@ pub struct Connection {
     write_pos: usize,
     write_len: usize,
     addr: SocketAddr,
+    /// Logon state.
+    session: SessionState,
+    /// Last traffic each way, for heartbeats and idle timeouts.
+    liveness: HeartbeatMonitor,
+    /// Participant this session acts for, bound by its first order entry.
+    participant: Option<u32>,
 }
 
 impl Connection {
-    fn new(stream: TcpStream, addr: SocketAddr) -> Self {
+    fn new(stream: TcpStream, addr: SocketAddr, liveness: HeartbeatMonitor) -> Self {
         Self {
             stream,
             read_buffer: [0; READ_BUFFER_SIZE],
@@ -37,6 +57,9 @@ impl Connection {
             write_pos: 0,
             write_len: 0,
             addr,
+            session: SessionState::Pending,
+            liveness,
+            participant: None,
         }
     }
     
@@ -57,31 +80,170 @@ impl Connection {
     pub fn addr(&self) -> SocketAddr {
         self.addr
     }
+    
+    /// Session opened by the connection's logon.
+    pub fn session(&self) -> Option<SessionId> {
+        match self.session {
+            SessionState::Established(session) => Some(session),
+            SessionState::Pending | SessionState::Closed => None,
+        }
+    }
+    
+    /// Participant this session acts for, once bound.
+    pub fn participant(&self) -> Option<u32> {
+        self.participant
+    }
 }
 
 /// Gateway event type for order processing.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GatewayEvent {
     /// New order received.
     NewOrder {
         token: Token,
+        session: SessionId,
         order_id: u64,
         symbol_id: u32,
         side: u8,
         order_type: u8,
         price: u64,
         quantity: u64,
+        /// Iceberg clip size (0 = fully displayed).
+        display_qty: u64,
+        /// Owning participant (0 = unattributed).
+        participant: u32,
+        /// Client order ID text (`NewOrderMessage::client_order_id`),
+        /// echoed on the order's execution reports.
+        client_ref: [u8; 16],
     },
     /// Cancel order received.
     CancelOrder {
         token: Token,
+        session: SessionId,
+        order_id: u64,
+        symbol_id: u32,
+        participant: u32,
+    },
+    /// Modify order received.
+    ModifyOrder {
+        token: Token,
+        session: SessionId,
         order_id: u64,
         symbol_id: u32,
+        price: u64,
+        quantity: u64,
+        participant: u32,
     },
+    /// Two-sided quote received (a zero quantity pulls that side).
+    MassQuote {
+        token: Token,
+        session: SessionId,
+        symbol_id: u32,
+        bid_order_id: u64,
+        bid_price: u64,
+        bid_qty: u64,
+        ask_order_id: u64,
+        ask_price: u64,
+        ask_qty: u64,
+        participant: u32,
+    },
+    /// Mass cancel received: every order of `participant` on `symbol_id`
+    /// (0 = all symbols) and `side` (2 = both).
+    MassCancel {
+        token: Token,
+        session: SessionId,
+        symbol_id: u32,
+        side: u8,
+        participant: u32,
+    },
+    /// Order status request received.
+    OrderStatusRequest {
+        token: Token,
+        session: SessionId,
+        order_id: u64,
+        symbol_id: u32,
+        participant: u32,
+    },
+    /// Symbol directory requested (`symbol_id` 0 = all symbols).
+    DirectoryRequest {
+        token: Token,
+        session: SessionId,
+        request_id: u32,
+        symbol_id: u32,
+    },
+    /// Order entry dropped for a field `validate_strict` refused; the
+    /// gateway has already queued an `OrderReject`.
+    Invalid {
+        token: Token,
+        session: SessionId,
+        order_id: u64,
+        symbol_id: u32,
+        error: ValidationError,
+    },
+    /// Order entry dropped: it named a participant other than the one the
+    /// session is bound to.
+    Unauthorized {
+        token: Token,
+        session: SessionId,
+        order_id: u64,
+    },
+    /// Logon accepted; `session` is the session it opened.
+    Logon {
+        token: Token,
+        session: SessionId,
+        session_id: u64,
+        heartbeat_ms: u32,
+        credentials: [u8; 16],
+    },
+    /// Logon turned away for its protocol version; the gateway has
+    /// already queued a `VersionMismatch` ack.
+    LogonRejected {
+        token: Token,
+        session_id: u64,
+        protocol_version: u16,
+    },
+    /// Logon turned away for its credentials, or repeated on an open
+    /// session; the gateway has queued a `Rejected` ack and closes the
+    /// connection.
+    LogonRefused {
+        token: Token,
+        session_id: u64,
+    },
+    /// Traffic before logon; the gateway has queued a `NotLoggedOn`
+    /// system error and closes the connection.
+    Unauthenticated { token: Token },
     /// Connection established.
     Connected { token: Token },
-    /// Connection closed.
-    Disconnected { token: Token },
+    /// The connection of a logged-on session dropped; its resting orders
+    /// should be cancelled. Followed by `Disconnected`.
+    SessionLost { token: Token, session: SessionId },
+    /// Connection closed (`session` is `SessionId::NONE` if it never
+    /// logged on).
+    Disconnected { token: Token, session: SessionId },
+}
+
+impl GatewayEvent {
+    /// Session field of events that belong to a logged-on session.
+    pub fn session_mut(&mut self) -> Option<&mut SessionId> {
+        match self {
+            GatewayEvent::NewOrder { session, .. }
+            | GatewayEvent::CancelOrder { session, .. }
+            | GatewayEvent::ModifyOrder { session, .. }
+            | GatewayEvent::MassQuote { session, .. }
+            | GatewayEvent::MassCancel { session, .. }
+            | GatewayEvent::OrderStatusRequest { session, .. }
+            | GatewayEvent::DirectoryRequest { session, .. }
+            | GatewayEvent::Invalid { session, .. }
+            | GatewayEvent::Unauthorized { session, .. }
+            | GatewayEvent::Logon { session, .. }
+            | GatewayEvent::SessionLost { session, .. }
+            | GatewayEvent::Disconnected { session, .. } => Some(session),
+            GatewayEvent::LogonRejected { .. }
+            | GatewayEvent::LogonRefused { .. }
+            | GatewayEvent::Unauthenticated { .. }
+            | GatewayEvent::Connected { .. } => None,
+        }
+    }
 }
 
 /// Network gateway.
@@ -91,6 +253,13 @@ pub struct Gateway {
     connections: HashMap<Token, Connection>,
     next_token: usize,
     events: Vec<GatewayEvent>,
+    routes: RoutingTable,
+    sessions: SessionGate,
+    /// Heartbeat interval used until a logon negotiates one.
+    heartbeat_ms: u32,
+    clock: Instant,
+    /// Nanoseconds since `clock`, as of the current poll.
+    now: u64,
 }
 
 impl Gateway {
@@ -110,18 +279,45 @@ impl Gateway {
             connections: HashMap::with_capacity(MAX_CONNECTIONS),
             next_token: 1,
             events: Vec::with_capacity(256),
+            routes: RoutingTable::new(),
+            sessions: SessionGate::default(),
+            heartbeat_ms: DEFAULT_HEARTBEAT_MS,
+            clock: Instant::now(),
+            now: 0,
         })
     }
     
+    /// Use `heartbeat_ms` instead of `DEFAULT_HEARTBEAT_MS` for new
+    /// connections, and for logons that ask for no heartbeats.
+    pub fn set_heartbeat_interval(&mut self, heartbeat_ms: u32) {
+        self.heartbeat_ms = heartbeat_ms;
+    }
+    
+    /// Check logon credentials with `authenticator` instead of accepting
+    /// every logon.
+    pub fn set_authenticator(&mut self, authenticator: impl Authenticator + Send + 'static) {
+        self.sessions = SessionGate::new(Box::new(authenticator));
+    }
+    
     /// Poll for events with optional timeout (in milliseconds).
     /// Returns slice of gateway events.
+    ///
+    /// Heartbeats and idle timeouts are checked on every poll, so poll at
+    /// least once per heartbeat interval.
     pub fn poll(&mut self, timeout_ms: Option<u64>) -> io::Result<&[GatewayEvent]> {
+        self.poll_at(timeout_ms, None)
+    }
+    
+    /// `poll`, taking the time after waiting from `now` (nanoseconds since
+    /// the gateway was bound) instead of the clock.
+    fn poll_at(&mut self, timeout_ms: Option<u64>, now: Option<u64>) -> io::Result<&[GatewayEvent]> {
         self.events.clear();
         
         let mut mio_events = Events::with_capacity(256);
         let timeout = timeout_ms.map(std::time::Duration::from_millis);
         
         self.poll.poll(&mut mio_events, timeout)?;
+        self.now = now.unwrap_or_else(|| self.clock.elapsed().as_nanos() as u64);
         
         for event in mio_events.iter() {
             match event.token() {
@@ -134,6 +330,8 @@ impl Gateway {
             }
         }
         
+        self.check_liveness()?;
+        
         Ok(&self.events)
     }
     
@@ -157,7 +355,8 @@ impl Gateway {
                         Interest::READABLE | Interest::WRITABLE,
                     )?;
                     
-                    self.connections.insert(token, Connection::new(stream, addr));
+                    let liveness = HeartbeatMonitor::new(self.heartbeat_ms, self.now);
+                    self.connections.insert(token, Connection::new(stream, addr, liveness));
                     self.events.push(GatewayEvent::Connected { token });
                 }
                 Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
@@ -172,8 +371,9 @@ impl Gateway {
         if is_readable {
             if let Some(should_close) = self.read_from_connection(token)? {
                 if should_close {
+                    // Best effort to deliver the acks of a refused session
+                    self.write_to_connection(token)?;
                     self.remove_connection(token);
-                    self.events.push(GatewayEvent::Disconnected { token });
                     return Ok(());
                 }
             }
@@ -187,6 +387,7 @@ impl Gateway {
     }
     
     fn read_from_connection(&mut self, token: Token) -> io::Result<Option<bool>> {
+        let now = self.now;
         let conn = match self.connections.get_mut(&token) {
             Some(c) => c,
             None => return Ok(None),
@@ -200,6 +401,7 @@ impl Gateway {
                 }
                 Ok(n) => {
                     conn.read_pos += n;
+                    conn.liveness.on_receive(now, 0);
                 }
                 Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
                 Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
@@ -210,71 +412,69 @@ impl Gateway {
         }
         
         // Parse messages from the read buffer
-        self.parse_messages(token);
+        let open = self.parse_messages(token);
         
-        Ok(Some(false))
+        Ok(Some(!open))
     }
     
-    fn parse_messages(&mut self, token: Token) {
+    /// Decode and answer what has arrived on a connection. Returns `false`
+    /// if the connection must be closed.
+    fn parse_messages(&mut self, token: Token) -> bool {
+        let (now, default_heartbeat_ms) = (self.now, self.heartbeat_ms);
         let conn = match self.connections.get_mut(&token) {
             Some(c) => c,
-            None => return,
+            None => return true,
         };
         
-        let mut consumed = 0;
+        let start = self.events.len();
+        let consumed = decode_frames(token, &conn.read_buffer[..conn.read_pos], &mut self.events);
+        let open = self.sessions.admit(&mut conn.session, &mut self.events[start..]);
+        authorize(&mut conn.participant, &mut self.events[start..]);
         
-        while consumed + 8 <= conn.read_pos {
-            let buffer = &conn.read_buffer[consumed..conn.read_pos];
-            
-            // Validate and get message length
-            let (msg_type, msg_len) = match MessageParser::validate_message(buffer) {
-                Ok((t, l)) => (t, l),
-                Err(_) => break,
-            };
-            
-            if consumed + msg_len > conn.read_pos {
-                break; // Incomplete message
-            }
-            
-            // Parse based on type
-            match msg_type {
-                MessageType::NewOrder => {
-                    if let Ok(order) = MessageParser::parse_new_order(buffer) {
-                        self.events.push(GatewayEvent::NewOrder {
-                            token,
-                            order_id: order.order_id,
-                            symbol_id: order.symbol_id,
-                            side: order.side,
-                            order_type: order.order_type,
-                            price: order.price,
-                            quantity: order.quantity,
-                        });
-                    }
+        // Answer logons, version mismatches and invalid fields here; the
+        // engine never sees them
+        for event in &self.events[start..] {
+            match *event {
+                GatewayEvent::Logon { session_id, heartbeat_ms, .. } => {
+                    // The ack confirms the interval both sides now keep
+                    let heartbeat_ms = if heartbeat_ms == 0 { default_heartbeat_ms } else { heartbeat_ms };
+                    conn.liveness = HeartbeatMonitor::new(heartbeat_ms, now);
+                    let ack = LogonAckMessage::new(0, session_id, LogonStatus::Accepted, heartbeat_ms);
+                    conn.queue_write(bytemuck::bytes_of(&ack));
+                }
+                GatewayEvent::LogonRefused { session_id, .. } => {
+                    let ack = LogonAckMessage::new(0, session_id, LogonStatus::Rejected, 0);
+                    conn.queue_write(bytemuck::bytes_of(&ack));
+                }
+                GatewayEvent::LogonRejected { session_id, .. } => {
+                    let ack = LogonAckMessage::new(0, session_id, LogonStatus::VersionMismatch, 0);
+                    conn.queue_write(bytemuck::bytes_of(&ack));
                 }
-                MessageType::CancelOrder => {
-                    if let Ok(cancel) = MessageParser::parse_cancel(buffer) {
-                        self.events.push(GatewayEvent::CancelOrder {
-                            token,
-                            order_id: cancel.order_id,
-                            symbol_id: cancel.symbol_id,
-                        });
-                    }
+                GatewayEvent::Invalid { order_id, symbol_id, error, .. } => {
+                    let text = error.description();
+                    let reject = OrderRejectMessage::new(0, order_id, symbol_id, error.reject_code(), text.len() as u8);
+                    queue_with_text(conn, bytemuck::bytes_of(&reject), text);
                 }
                 _ => {}
             }
-            
-            consumed += msg_len;
+        }
+        if self.events[start..].iter().any(|event| matches!(event, GatewayEvent::Unauthenticated { .. })) {
+            let text = "logon required";
+            let error = SystemErrorMessage::new(0, SystemErrorCode::NotLoggedOn, Severity::Fatal, text.len() as u8);
+            queue_with_text(conn, bytemuck::bytes_of(&error), text);
         }
         
         // Compact buffer
         if consumed > 0 {
-            let conn = self.connections.get_mut(&token).unwrap();
             conn.read_buffer.copy_within(consumed..conn.read_pos, 0);
             conn.read_pos -= consumed;
         }
+        
+        open
     }
     
     fn write_to_connection(&mut self, token: Token) -> io::Result<()> {
+        let now = self.now;
         let conn = match self.connections.get_mut(&token) {
             Some(c) => c,
             None => return Ok(()),
@@ -282,7 +482,10 @@ impl Gateway {
         
         while conn.write_pos < conn.write_len {
             match conn.stream.write(&conn.write_buffer[conn.write_pos..conn.write_len]) {
-                Ok(n) => conn.write_pos += n,
+                Ok(n) => {
+                    conn.write_pos += n;
+                    conn.liveness.on_send(now);
+                }
                 Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
                 Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
                 Err(_) => {
@@ -300,10 +503,43 @@ impl Gateway {
         Ok(())
     }
     
+    /// Send due heartbeats on logged-on sessions and close connections
+    /// that have been silent for `STALE_INTERVALS` intervals.
+    fn check_liveness(&mut self) -> io::Result<()> {
+        let now = self.now;
+        let (mut due, mut stale) = (Vec::new(), Vec::new());
+        for (&token, conn) in self.connections.iter_mut() {
+            match conn.liveness.poll(now) {
+                Liveness::Stale => stale.push(token),
+                Liveness::SendHeartbeat if conn.session().is_some() => {
+                    let heartbeat = conn.liveness.heartbeat(0, now);
+                    conn.queue_write(bytemuck::bytes_of(&heartbeat));
+                    due.push(token);
+                }
+                Liveness::SendHeartbeat | Liveness::Idle => {}
+            }
+        }
+        
+        // An idle socket gets no writable event to flush on
+        for token in due {
+            self.write_to_connection(token)?;
+        }
+        for token in stale {
+            self.remove_connection(token);
+        }
+        Ok(())
+    }
+    
     fn remove_connection(&mut self, token: Token) {
         if let Some(mut conn) = self.connections.remove(&token) {
             let _ = self.poll.registry().deregister(&mut conn.stream);
+            let session = conn.session().unwrap_or(SessionId::NONE);
+            if session != SessionId::NONE {
+                self.events.push(GatewayEvent::SessionLost { token, session });
+            }
+            self.events.push(GatewayEvent::Disconnected { token, session });
         }
+        self.routes.remove_token(token);
     }
     
     /// Send data to a connection.
@@ -315,8 +551,144 @@ impl Gateway {
         }
     }
     
+    /// Tell a connection why the venue is refusing its traffic. Text
+    /// beyond `MAX_TEXT_LEN` bytes is cut.
+    pub fn send_system_error(&mut self, token: Token, code: SystemErrorCode, severity: Severity, text: &str) -> bool {
+        let Some(conn) = self.connections.get_mut(&token) else {
+            return false;
+        };
+        let text = truncate_utf8(text, MAX_TEXT_LEN);
+        let error = SystemErrorMessage::new(0, code, severity, text.len() as u8);
+        queue_with_text(conn, bytemuck::bytes_of(&error), text)
+    }
+    
+    /// Send a system error to every connection, e.g. when the engine
+    /// halts.
+    pub fn broadcast_system_error(&mut self, code: SystemErrorCode, severity: Severity, text: &str) {
+        let text = truncate_utf8(text, MAX_TEXT_LEN);
+        let error = SystemErrorMessage::new(0, code, severity, text.len() as u8);
+        for conn in self.connections.values_mut() {
+            queue_with_text(conn, bytemuck::bytes_of(&error), text);
+        }
+    }
+    
+    /// Send reports on `order_id` to `route.token` from now on. Call
+    /// before submitting the order to the engine.
+    pub fn route_order(&mut self, order_id: OrderId, route: Route) {
+        self.routes.insert(order_id, route);
+    }
+    
+    /// Stop reporting on `order_id`, e.g. after a cancel.
+    pub fn unroute_order(&mut self, order_id: OrderId) -> Option<Route> {
+        self.routes.remove(order_id)
+    }
+    
+    /// Write the reports for the engine's result on `order_id` to its
+    /// connection, and fill reports to the makers it traded with.
+    pub fn report_result(&mut self, order_id: OrderId, result: &OrderResult, timestamp: u64) {
+        let connections = &mut self.connections;
+        self.routes.route_result(order_id, result, timestamp, &mut |token, report| {
+            if let Some(conn) = connections.get_mut(&token) {
+                conn.queue_write(report);
+            }
+        });
+    }
+    
+    /// Write fill reports for a fill outside order entry (auction uncross,
+    /// triggered stops) to both sides.
+    pub fn report_fill(&mut self, fill: &Fill) {
+        let connections = &mut self.connections;
+        self.routes.route_fill(fill, &mut |token, report| {
+            if let Some(conn) = connections.get_mut(&token) {
+                conn.queue_write(report);
+            }
+        });
+    }
+    
+    /// Address the gateway listens on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    
     /// Get number of active connections.
     pub fn connection_count(&self) -> usize {
         self.connections.len()
     }
 }
+
+/// Queue a text-carrying message: its fixed part, then `text`, which
+/// must fit `MAX_TEXT_LEN`, as one write.
+fn queue_with_text(conn: &mut Connection, fixed: &[u8], text: &str) -> bool {
+    let mut frame = [0u8; 64 + MAX_TEXT_LEN];
+    frame[..fixed.len()].copy_from_slice(fixed);
+    frame[fixed.len()..fixed.len() + text.len()].copy_from_slice(text.as_bytes());
+    conn.queue_write(&frame[..fixed.len() + text.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpStream as ClientStream;
+    use std::time::Duration;
+    use titan_proto::{DirectoryRequestMessage, LogonMessage, MessageParser};
+    
+    const MS: u64 = 1_000_000;
+    
+    /// Poll at `now` until `done` holds for the events seen so far.
+    fn poll_until(gateway: &mut Gateway, now: u64, done: impl Fn(&[GatewayEvent]) -> bool) -> Vec<GatewayEvent> {
+        let mut seen = Vec::new();
+        for _ in 0..200 {
+            seen.extend_from_slice(gateway.poll_at(Some(5), Some(now)).unwrap());
+            if done(&seen) {
+                return seen;
+            }
+        }
+        panic!("gateway did not produce the expected events: {seen:?}");
+    }
+    
+    fn logons(events: &[GatewayEvent]) -> usize {
+        events.iter().filter(|event| matches!(event, GatewayEvent::Logon { .. })).count()
+    }
+    
+    #[test]
+    fn test_heartbeats_and_idle_timeout() {
+        let mut gateway = Gateway::bind("127.0.0.1:0").unwrap();
+        gateway.set_heartbeat_interval(100);
+        let addr = gateway.local_addr().unwrap();
+        let mut idle = ClientStream::connect(addr).unwrap();
+        let mut busy = ClientStream::connect(addr).unwrap();
+        for (client, session_id) in [(&mut idle, 1), (&mut busy, 2)] {
+            client.write_all(bytemuck::bytes_of(&LogonMessage::new(1, session_id, [0; 16], 0))).unwrap();
+        }
+        let events = poll_until(&mut gateway, 0, |events| logons(events) == 2);
+        let idle_token = events.iter().find_map(|event| match *event {
+            GatewayEvent::Logon { token, session_id: 1, .. } => Some(token),
+            _ => None,
+        }).unwrap();
+        
+        // One interval later both sessions get a heartbeat
+        gateway.poll_at(Some(0), Some(150 * MS)).unwrap();
+        idle.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+        let mut wire = [0u8; 48];
+        idle.read_exact(&mut wire).unwrap();
+        assert!(MessageParser::parse_logon_ack(&wire[..24]).is_ok());
+        let heartbeat = MessageParser::parse_heartbeat(&wire[24..]).unwrap();
+        assert_eq!(heartbeat.get_timestamp(), 150 * MS);
+        
+        // Only the busy session keeps talking
+        busy.write_all(bytemuck::bytes_of(&DirectoryRequestMessage::new(2, 7, 0))).unwrap();
+        poll_until(&mut gateway, 180 * MS, |events| {
+            events.iter().any(|event| matches!(event, GatewayEvent::DirectoryRequest { request_id: 7, .. }))
+        });
+        
+        // Two silent intervals: the idle session is dropped, the busy one stays
+        let events = gateway.poll_at(Some(0), Some(250 * MS)).unwrap().to_vec();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], GatewayEvent::SessionLost { token, .. } if token == idle_token));
+        assert!(matches!(events[1], GatewayEvent::Disconnected { token, .. } if token == idle_token));
+        assert_eq!(gateway.connection_count(), 1);
+        
+        let mut rest = [0u8; 1];
+        assert_eq!(idle.read(&mut rest).unwrap(), 0);
+    }
+}
@@ -3,5 +3,11 @@
 //! Uses mio for non-blocking event-driven networking.
 
 pub mod gateway;
+pub mod framing;
+pub mod routing;
+pub mod session;
 
 pub use gateway::Gateway;
+pub use framing::decode_frames;
+pub use routing::{Route, RoutingTable};
+pub use session::{AllowAll, Authenticator, CredentialTable, SessionGate, SessionState};
@@ -0,0 +1,239 @@
+//! Execution report routing.
+//!
+//! The engine knows orders by `OrderId`; clients know them by connection.
+//! `RoutingTable` remembers which connection owns each live order and turns
+//! engine results into the reports its owner expects: fills for the taker
+//! and for the maker of every passive fill, an acknowledgement (an
+//! `ExecutionReport` of type `New`) when an order rests, and an
+//! `OrderReject` when the engine refuses it. Kept free of socket state;
+//! `Gateway` writes the reports to the owning connections.
+
+use mio::Token;
+use std::collections::HashMap;
+
+use titan_core::{Fill, OrderId, OrderResult, RejectReason, Side};
+use titan_proto::{ExecType, ExecutionReport, OrderRejectMessage, MAX_TEXT_LEN};
+
+/// Engine reject codes in `OrderReject` start here, clear of the gateway's
+/// validation codes.
+pub const ENGINE_REJECT_BASE: u8 = 32;
+
+/// Owner and state of a live order.
+#[derive(Clone, Copy, Debug)]
+pub struct Route {
+    /// Connection that entered the order.
+    pub token: Token,
+    pub symbol_id: u32,
+    pub side: Side,
+    /// Limit price in ticks.
+    pub price: u64,
+    /// Open quantity.
+    pub remaining: u64,
+    /// Client order ID bytes, echoed in every report.
+    pub client_order_id: [u8; 16],
+}
+
+/// Order ID to connection routing.
+#[derive(Debug, Default)]
+pub struct RoutingTable {
+    routes: HashMap<OrderId, Route>,
+    next_exec_id: u64,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route reports on `order_id` to `route.token`. Call before the order
+    /// reaches the engine.
+    pub fn insert(&mut self, order_id: OrderId, route: Route) {
+        self.routes.insert(order_id, route);
+    }
+
+    /// Stop routing `order_id`, e.g. once it is cancelled.
+    pub fn remove(&mut self, order_id: OrderId) -> Option<Route> {
+        self.routes.remove(&order_id)
+    }
+
+    pub fn get(&self, order_id: OrderId) -> Option<&Route> {
+        self.routes.get(&order_id)
+    }
+
+    /// Number of live orders.
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Forget every order of a connection.
+    pub fn remove_token(&mut self, token: Token) {
+        self.routes.retain(|_, route| route.token != token);
+    }
+
+    /// Report the engine's result for newly entered `order_id`, handing
+    /// each report to `out` with the connection it goes to. Orders that
+    /// are done stop being routed.
+    pub fn route_result(&mut self, order_id: OrderId, result: &OrderResult, timestamp: u64, out: &mut impl FnMut(Token, &[u8])) {
+        let Some(&route) = self.routes.get(&order_id) else {
+            return;
+        };
+        let (fills, exec_type): (&[Fill], Option<ExecType>) = match result {
+            OrderResult::Filled { fills } => (fills, None),
+            OrderResult::PartialFill { fills, .. } => (fills, Some(ExecType::New)),
+            OrderResult::Resting { .. } => (&[], Some(ExecType::New)),
+            OrderResult::Cancelled { fills, .. } => (fills, Some(ExecType::Canceled)),
+            OrderResult::Rejected { reason } => {
+                self.routes.remove(&order_id);
+                let (code, text) = reject_reason(*reason);
+                let reject = OrderRejectMessage::new(0, order_id.0, route.symbol_id, code, text.len() as u8);
+                let mut frame = [0u8; 24 + MAX_TEXT_LEN];
+                frame[..24].copy_from_slice(bytemuck::bytes_of(&reject));
+                frame[24..24 + text.len()].copy_from_slice(text.as_bytes());
+                out(route.token, &frame[..24 + text.len()]);
+                return;
+            }
+        };
+
+        for fill in fills {
+            self.fill(fill.taker_order_id, fill, timestamp, out);
+            self.fill(fill.maker_order_id, fill, timestamp, out);
+        }
+
+        if let Some(exec_type) = exec_type {
+            let Some(route) = self.routes.get(&order_id).copied() else {
+                return;
+            };
+            if exec_type == ExecType::Canceled {
+                self.routes.remove(&order_id);
+            }
+            let report = ExecutionReport::new(
+                0, order_id.0, self.exec_id(), route.symbol_id, route.side as u8, exec_type,
+                route.price, 0, route.remaining, timestamp,
+            ).with_client_order_id(route.client_order_id);
+            out(route.token, bytemuck::bytes_of(&report));
+        }
+    }
+
+    /// Report a fill that did not come from a new order, e.g. an auction
+    /// uncross, to both sides.
+    pub fn route_fill(&mut self, fill: &Fill, out: &mut impl FnMut(Token, &[u8])) {
+        self.fill(fill.taker_order_id, fill, fill.timestamp, out);
+        self.fill(fill.maker_order_id, fill, fill.timestamp, out);
+    }
+
+    /// Report `fill` to the owner of `order_id` and stop routing the order
+    /// once it is filled.
+    fn fill(&mut self, order_id: OrderId, fill: &Fill, timestamp: u64, out: &mut impl FnMut(Token, &[u8])) {
+        let Some(route) = self.routes.get_mut(&order_id) else {
+            return;
+        };
+        route.remaining = route.remaining.saturating_sub(fill.quantity.0);
+        let route = *route;
+        if route.remaining == 0 {
+            self.routes.remove(&order_id);
+        }
+        let report = ExecutionReport::new_fill(
+            0, order_id.0, self.exec_id(), route.symbol_id, route.side as u8,
+            fill.price.to_ticks(), fill.quantity.0, route.remaining, timestamp,
+        ).with_client_order_id(route.client_order_id);
+        out(route.token, bytemuck::bytes_of(&report));
+    }
+
+    fn exec_id(&mut self) -> u64 {
+        self.next_exec_id += 1;
+        self.next_exec_id
+    }
+}
+
+/// `OrderReject` code and text for an engine reject.
+fn reject_reason(reason: RejectReason) -> (u8, &'static str) {
+    let (offset, text) = match reason {
+        RejectReason::InvalidPrice => (0, "invalid price"),
+        RejectReason::InvalidQuantity => (1, "invalid quantity"),
+        RejectReason::PoolExhausted => (2, "order capacity exhausted"),
+        RejectReason::BookFull => (3, "price level full"),
+        RejectReason::PostOnlyWouldMatch => (4, "post-only order would match"),
+        RejectReason::SymbolNotFound => (5, "unknown symbol"),
+        RejectReason::InsufficientLiquidity => (6, "cannot fill completely"),
+        RejectReason::NoLiquidity => (7, "no liquidity"),
+        RejectReason::UnknownOrder => (8, "unknown order"),
+        RejectReason::DuplicateOrderId => (9, "duplicate order id"),
+        RejectReason::InvalidExpiry => (10, "invalid expiry"),
+        RejectReason::AlreadyLinked => (11, "order already linked"),
+        RejectReason::AuctionInProgress => (12, "order type not allowed in auction"),
+        RejectReason::PriceOutOfBand => (13, "price outside band"),
+        RejectReason::MmpTripped => (14, "market maker protection tripped"),
+        RejectReason::NotOwner => (15, "order belongs to another participant"),
+        RejectReason::ParticipantDisabled => (16, "participant disabled"),
+        RejectReason::OrderTooLarge => (17, "quantity above limit"),
+        RejectReason::NotionalTooLarge => (18, "notional above limit"),
+        RejectReason::TooManyOpenOrders => (19, "too many open orders"),
+        RejectReason::PriceDeviation => (20, "price deviates from reference"),
+        RejectReason::ShortSaleRestricted => (21, "short sale restricted"),
+    };
+    (ENGINE_REJECT_BASE + offset, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_core::{MatchingEngine, Order, OrderType, Price, Quantity, SymbolId};
+    use titan_proto::MessageParser;
+
+    fn route(token: usize, side: Side, price: u64, quantity: u64) -> Route {
+        Route { token: Token(token), symbol_id: 1, side, price, remaining: quantity, client_order_id: [token as u8; 16] }
+    }
+
+    fn submit(
+        engine: &mut MatchingEngine,
+        table: &mut RoutingTable,
+        id: u64,
+        route: Route,
+    ) -> Vec<(Token, Vec<u8>)> {
+        table.insert(OrderId(id), route);
+        let order = Order::new(
+            OrderId(id), SymbolId(1), route.side, OrderType::Limit,
+            Price::from_ticks(route.price), Quantity(route.remaining), id,
+        );
+        let result = engine.submit_order(order, id);
+        let mut sent = Vec::new();
+        table.route_result(OrderId(id), &result, id, &mut |token, bytes| sent.push((token, bytes.to_vec())));
+        sent
+    }
+
+    fn report(bytes: &[u8]) -> (u64, u8, u64, u64) {
+        let report: ExecutionReport = MessageParser::parse_copy(bytes).unwrap();
+        (report.get_order_id(), report.exec_type, report.get_exec_qty(), report.get_leaves_qty())
+    }
+
+    #[test]
+    fn test_reports_reach_taker_and_maker() {
+        let mut engine = MatchingEngine::new(SymbolId(1), 10, Price::ZERO);
+        let mut table = RoutingTable::new();
+
+        let sent = submit(&mut engine, &mut table, 10, route(1, Side::Sell, 100, 50));
+        assert_eq!(sent.len(), 1);
+        assert_eq!((sent[0].0, report(&sent[0].1)), (Token(1), (10, ExecType::New as u8, 0, 50)));
+
+        // Aggressive buy: fill to each side, then the rest is acknowledged
+        let sent = submit(&mut engine, &mut table, 11, route(2, Side::Buy, 100, 80));
+        let sent: Vec<_> = sent.iter().map(|(token, bytes)| (*token, report(bytes))).collect();
+        assert_eq!(sent, vec![
+            (Token(2), (11, ExecType::PartialFill as u8, 50, 30)),
+            (Token(1), (10, ExecType::Fill as u8, 50, 0)),
+            (Token(2), (11, ExecType::New as u8, 0, 30)),
+        ]);
+        assert!(table.get(OrderId(10)).is_none());
+        assert_eq!(table.get(OrderId(11)).map(|r| r.remaining), Some(30));
+
+        // Engine rejects go back as OrderReject with a reason text
+        let sent = submit(&mut engine, &mut table, 12, route(3, Side::Buy, 99, 0));
+        let (reject, text) = MessageParser::parse_order_reject(&sent[0].1).unwrap();
+        assert_eq!((sent[0].0, reject.reason, text), (Token(3), ENGINE_REJECT_BASE + 1, "invalid quantity"));
+        assert_eq!(table.len(), 1);
+    }
+}
@@ -0,0 +1,186 @@
+//! Logon handshake and session assignment.
+//!
+//! A connection must log on before anything else: `SessionGate` checks the
+//! logon's credentials with an `Authenticator`, assigns the connection a
+//! `SessionId` and stamps it on every later event. Traffic before logon, a
+//! refused logon and a second logon on an open session all end the
+//! connection. Kept free of socket state like `framing`; `Gateway` sends
+//! the acks and closes connections.
+
+use std::collections::HashMap;
+
+use titan_core::SessionId;
+
+use crate::gateway::GatewayEvent;
+
+/// Decides whether a logon may open a session.
+pub trait Authenticator {
+    /// Check the credentials a client presented for `session_id`.
+    fn authenticate(&mut self, session_id: u64, credentials: &[u8; 16]) -> bool;
+}
+
+/// Accepts every logon, for closed networks and tests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllowAll;
+
+impl Authenticator for AllowAll {
+    fn authenticate(&mut self, _session_id: u64, _credentials: &[u8; 16]) -> bool {
+        true
+    }
+}
+
+/// Fixed credentials per client session ID.
+#[derive(Clone, Debug, Default)]
+pub struct CredentialTable {
+    credentials: HashMap<u64, [u8; 16]>,
+}
+
+impl CredentialTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let `session_id` log on with `credentials`.
+    pub fn insert(&mut self, session_id: u64, credentials: [u8; 16]) {
+        self.credentials.insert(session_id, credentials);
+    }
+}
+
+impl Authenticator for CredentialTable {
+    fn authenticate(&mut self, session_id: u64, credentials: &[u8; 16]) -> bool {
+        self.credentials.get(&session_id) == Some(credentials)
+    }
+}
+
+/// Logon state of one connection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SessionState {
+    /// Waiting for a logon.
+    #[default]
+    Pending,
+    /// Logged on as this session.
+    Established(SessionId),
+    /// Refused; the connection is being closed.
+    Closed,
+}
+
+/// Logon checks and session IDs for all connections.
+pub struct SessionGate {
+    authenticator: Box<dyn Authenticator + Send>,
+    next_session: u32,
+}
+
+impl SessionGate {
+    pub fn new(authenticator: Box<dyn Authenticator + Send>) -> Self {
+        Self { authenticator, next_session: 1 }
+    }
+
+    /// Apply the handshake to a connection's newly decoded `events`.
+    ///
+    /// An accepted `Logon` gets its session and later events carry it. A
+    /// refused logon becomes `LogonRefused` and traffic before logon
+    /// becomes `Unauthenticated`; either closes the session, and every
+    /// event after it becomes `Unauthenticated`. Returns `false` once the
+    /// connection must be closed.
+    pub fn admit(&mut self, state: &mut SessionState, events: &mut [GatewayEvent]) -> bool {
+        for event in events {
+            let token = match *event {
+                GatewayEvent::Logon { token, session_id, credentials, .. } => {
+                    if *state == SessionState::Pending && self.authenticator.authenticate(session_id, &credentials) {
+                        let session = SessionId(self.next_session);
+                        self.next_session += 1;
+                        *state = SessionState::Established(session);
+                    } else if *state != SessionState::Closed {
+                        *state = SessionState::Closed;
+                        *event = GatewayEvent::LogonRefused { token, session_id };
+                        continue;
+                    }
+                    token
+                }
+                // Version mismatches are answered by the gateway and may retry
+                GatewayEvent::LogonRejected { .. } if *state != SessionState::Closed => continue,
+                GatewayEvent::NewOrder { token, .. }
+                | GatewayEvent::CancelOrder { token, .. }
+                | GatewayEvent::ModifyOrder { token, .. }
+                | GatewayEvent::MassQuote { token, .. }
+                | GatewayEvent::MassCancel { token, .. }
+                | GatewayEvent::OrderStatusRequest { token, .. }
+                | GatewayEvent::DirectoryRequest { token, .. }
+                | GatewayEvent::Invalid { token, .. }
+                | GatewayEvent::LogonRejected { token, .. } => token,
+                _ => continue,
+            };
+            match *state {
+                SessionState::Established(session) => {
+                    if let Some(tag) = event.session_mut() {
+                        *tag = session;
+                    }
+                }
+                SessionState::Pending | SessionState::Closed => {
+                    *state = SessionState::Closed;
+                    *event = GatewayEvent::Unauthenticated { token };
+                }
+            }
+        }
+        *state != SessionState::Closed
+    }
+}
+
+impl Default for SessionGate {
+    fn default() -> Self {
+        Self::new(Box::new(AllowAll))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framing::decode_frames;
+    use mio::Token;
+    use titan_proto::{LogonMessage, NewOrderMessage};
+
+    fn events(messages: &[&[u8]]) -> Vec<GatewayEvent> {
+        let bytes = messages.concat();
+        let mut events = Vec::new();
+        decode_frames(Token(3), &bytes, &mut events);
+        events
+    }
+
+    #[test]
+    fn test_logon_required_and_sessions_tagged() {
+        let mut credentials = CredentialTable::new();
+        credentials.insert(99, [7; 16]);
+        let mut gate = SessionGate::new(Box::new(credentials));
+        let order = NewOrderMessage::new(2, 10, 1, 0, 0, 100, 5);
+        let logon = LogonMessage::new(1, 99, [7; 16], 1000);
+
+        // Logon, then orders carry the session
+        let mut state = SessionState::Pending;
+        let mut batch = events(&[bytemuck::bytes_of(&logon), bytemuck::bytes_of(&order)]);
+        assert!(gate.admit(&mut state, &mut batch));
+        assert_eq!(state, SessionState::Established(SessionId(1)));
+        assert!(matches!(batch[0], GatewayEvent::Logon { session: SessionId(1), .. }));
+        assert!(matches!(batch[1], GatewayEvent::NewOrder { session: SessionId(1), order_id: 10, .. }));
+
+        // A second logon closes the session
+        let mut batch = events(&[bytemuck::bytes_of(&logon), bytemuck::bytes_of(&order)]);
+        assert!(!gate.admit(&mut state, &mut batch));
+        assert_eq!(batch, vec![
+            GatewayEvent::LogonRefused { token: Token(3), session_id: 99 },
+            GatewayEvent::Unauthenticated { token: Token(3) },
+        ]);
+
+        // Orders before logon
+        let mut state = SessionState::Pending;
+        let mut batch = events(&[bytemuck::bytes_of(&order), bytemuck::bytes_of(&logon)]);
+        assert!(!gate.admit(&mut state, &mut batch));
+        assert_eq!(batch[0], GatewayEvent::Unauthenticated { token: Token(3) });
+        assert_eq!(batch[1], GatewayEvent::Unauthenticated { token: Token(3) });
+
+        // Wrong credentials
+        let mut state = SessionState::Pending;
+        let mut batch = events(&[bytemuck::bytes_of(&LogonMessage::new(1, 99, [8; 16], 1000))]);
+        assert!(!gate.admit(&mut state, &mut batch));
+        assert_eq!(batch[0], GatewayEvent::LogonRefused { token: Token(3), session_id: 99 });
+    }
+}
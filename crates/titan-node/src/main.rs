@@ -11,13 +11,18 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
-use titan_core::{MatchingEngine, Price, SymbolId};
+use titan_core::{EngineManager, InstrumentSpec, ParticipantId, Price, SymbolId};
+use titan_metrics::{ClockSource, UtcClock};
+use titan_symbols::SymbolRegistry;
 use titan_node::metrics::{self, update_book_depth};
 use titan_node::snapshot::SnapshotManager;
 
 /// Orders between snapshots
 const SNAPSHOT_INTERVAL: u64 = 100_000;
 
+/// log2 of each symbol's order pool (1M orders)
+const POOL_BITS: u32 = 20;
+
 /// Shared engine state accessible across threads
 pub struct EngineState {
     /// Order counter for snapshot triggers
@@ -65,6 +70,19 @@ fn main() {
         }
     }
     
+    // Load symbol reference data
+    let symbols_path = std::env::var("TITAN_SYMBOLS").unwrap_or_else(|_| "config/symbols.conf".to_string());
+    let symbols = match titan_symbols::load_file(&symbols_path) {
+        Ok(registry) => {
+            println!("📚 Loaded {} symbols from {}", registry.len(), symbols_path);
+            registry
+        }
+        Err(e) => {
+            eprintln!("⚠️  Failed to load {}: {}; accepting all symbols", symbols_path, e);
+            SymbolRegistry::new()
+        }
+    };
+    
     // Shared state
     let state = Arc::new(EngineState::new());
     
@@ -72,13 +90,22 @@ fn main() {
     let _metrics_thread = metrics::spawn_metrics_thread();
     let _http_thread = metrics::spawn_http_server(9090);
     
-    // Create matching engine
-    let mut engine = MatchingEngine::new(
-        SymbolId(1),
-        20,  // 1M order pool capacity
-        Price::ZERO,
-    );
-    println!("⚡ Matching engine initialized (1M order capacity)");
+    // One matching engine per configured symbol; without reference data,
+    // engines are created on a symbol's first order
+    let mut engines = EngineManager::new();
+    for spec in symbols.iter() {
+        engines.add_symbol(spec.id, POOL_BITS, Price::ZERO, spec.instrument());
+    }
+    println!("⚡ Matching engines initialized ({} symbols, 1M order capacity each)", engines.len());
+    
+    // UTC clock for order/fill timestamps (PHC if configured)
+    let clock_source = match std::env::var("TITAN_PHC_DEVICE") {
+        Ok(path) => ClockSource::open_phc(&path).expect("Failed to open PTP hardware clock"),
+        Err(_) => ClockSource::Realtime,
+    };
+    let mut clock = UtcClock::new(clock_source, Duration::from_millis(10))
+        .expect("Failed to calibrate UTC clock");
+    println!("🕒 UTC clock calibrated at {}", clock.now());
     
     // Channel for Gateway -> Engine
     let (order_tx, order_rx) = crossbeam_channel::bounded::<titan_net::gateway::GatewayEvent>(4096);
@@ -98,10 +125,16 @@ fn main() {
                         for event in events {
                             // Forward all relevant events to the engine
                             match event {
-                                titan_net::gateway::GatewayEvent::NewOrder { .. } => {
+                                titan_net::gateway::GatewayEvent::NewOrder { .. }
+                                | titan_net::gateway::GatewayEvent::CancelOrder { .. }
+                                | titan_net::gateway::GatewayEvent::ModifyOrder { .. }
+                                | titan_net::gateway::GatewayEvent::MassQuote { .. } => {
                                     let _ = order_tx.send(*event);
                                 }
-                                _ => {} // Ignore connection events and cancels for now
+                                titan_net::gateway::GatewayEvent::Unauthorized { order_id, .. } => {
+                                    eprintln!("Order entry {} rejected: session bound to another participant", order_id);
+                                }
+                                _ => {} // Ignore connection events
                             }
                         }
                     }
@@ -141,7 +174,7 @@ fn main() {
         while let Ok(event) = order_rx.try_recv() {
             match event {
                 titan_net::gateway::GatewayEvent::NewOrder { 
-                    order_id, symbol_id, side, order_type, price, quantity, .. 
+                    order_id, symbol_id, side, order_type, price, quantity, display_qty, participant, .. 
                 } => {
                     let side = if side == 0 { titan_core::Side::Buy } else { titan_core::Side::Sell };
                     let order_type = match order_type {
@@ -149,44 +182,106 @@ fn main() {
                         1 => titan_core::OrderType::IOC,
                         2 => titan_core::OrderType::FOK,
                         3 => titan_core::OrderType::PostOnly,
+                        4 => titan_core::OrderType::Market,
                         _ => titan_core::OrderType::Limit,
                     };
                     
+                    let timestamp = clock.now().as_nanos();
+                    let price = titan_core::Price::from_ticks(price);
+                    let quantity = titan_core::Quantity(quantity);
+                    
+                    // Reference data checks (skipped when no registry is loaded)
+                    if !symbols.is_empty() {
+                        if let Err(e) = symbols.validate_order(titan_core::SymbolId(symbol_id), price, quantity) {
+                            eprintln!("Order {} rejected: {:?}", order_id, e);
+                            continue;
+                        }
+                    }
+                    
                     let order = titan_core::Order::new(
                         titan_core::OrderId(order_id),
                         titan_core::SymbolId(symbol_id),
                         side,
                         order_type,
+                        price,
+                        quantity,
+                        timestamp,
+                    ).with_display_qty(titan_core::Quantity(display_qty));
+                    
+                    // Submit to the symbol's engine
+                    if symbols.is_empty() && engines.get(SymbolId(symbol_id)).is_none() {
+                        engines.add_symbol(SymbolId(symbol_id), POOL_BITS, Price::ZERO, InstrumentSpec::default());
+                    }
+                    engines.submit_order_as(ParticipantId(participant), order, timestamp);
+                    state.order_count.fetch_add(1, Ordering::Relaxed);
+                }
+                titan_net::gateway::GatewayEvent::CancelOrder { order_id, symbol_id, participant, .. } => {
+                    let order_id = titan_core::OrderId(order_id);
+                    if let Err(e) = engines.cancel_as(SymbolId(symbol_id), ParticipantId(participant), order_id) {
+                        eprintln!("Cancel {} rejected: {:?}", order_id.0, e);
+                    }
+                }
+                titan_net::gateway::GatewayEvent::ModifyOrder { order_id, symbol_id, price, quantity, participant, .. } => {
+                    let result = engines.modify_as(
+                        SymbolId(symbol_id),
+                        ParticipantId(participant),
+                        titan_core::OrderId(order_id),
                         titan_core::Price::from_ticks(price),
                         titan_core::Quantity(quantity),
-                        0, // timestamp placeholder
                     );
-                    
-                    // Submit to engine
-                    // Using order_id as timestamp for consistency in this demo
-                    engine.submit_order(order, order_id);
-                    state.order_count.fetch_add(1, Ordering::Relaxed);
+                    if let titan_core::OrderResult::Rejected { reason } = result {
+                        eprintln!("Modify {} rejected: {:?}", order_id, reason);
+                    }
+                }
+                titan_net::gateway::GatewayEvent::MassQuote {
+                    symbol_id, bid_order_id, bid_price, bid_qty, ask_order_id, ask_price, ask_qty, participant, ..
+                } => {
+                    let side = |order_id, price, qty| titan_core::QuoteSide::new(
+                        titan_core::OrderId(order_id),
+                        titan_core::Price::from_ticks(price),
+                        titan_core::Quantity(qty),
+                    );
+                    let timestamp = clock.now().as_nanos();
+                    let results = engines.mass_quote(
+                        SymbolId(symbol_id),
+                        ParticipantId(participant),
+                        side(bid_order_id, bid_price, bid_qty),
+                        side(ask_order_id, ask_price, ask_qty),
+                        timestamp,
+                    );
+                    for result in results {
+                        if let titan_core::OrderResult::Rejected { reason } = result {
+                            eprintln!("Quote {}/{} rejected: {:?}", bid_order_id, ask_order_id, reason);
+                        }
+                    }
                 }
                 _ => {}
             }
         }
 
-        // Update book depth metrics every 100ms
+        // Update book depth metrics (lowest symbol ID) every 100ms
         if last_depth_update.elapsed() >= Duration::from_millis(100) {
-            let bid_levels: Vec<(u64, u64)> = engine.book.bids
-                .top_n_levels::<5>()
-                .iter()
-                .map(|(p, q)| (p.0, q.0))
-                .collect();
-            
-            let ask_levels: Vec<(u64, u64)> = engine.book.asks
-                .top_n_levels::<5>()
-                .iter()
-                .map(|(p, q)| (p.0, q.0))
-                .collect();
-            
-            update_book_depth(&bid_levels, &ask_levels);
+            if let Some(engine) = engines.iter().next() {
+                let bid_levels: Vec<(u64, u64)> = engine.book.bids
+                    .top_n_levels::<5>()
+                    .iter()
+                    .map(|(p, q)| (p.0, q.0))
+                    .collect();
+                
+                let ask_levels: Vec<(u64, u64)> = engine.book.asks
+                    .top_n_levels::<5>()
+                    .iter()
+                    .map(|(p, q)| (p.0, q.0))
+                    .collect();
+                
+                update_book_depth(&bid_levels, &ask_levels);
+            }
             last_depth_update = std::time::Instant::now();
+            
+            // Track the reference clock
+            if let Err(e) = clock.resync() {
+                eprintln!("Clock resync failed: {}", e);
+            }
         }
         
         // Check for snapshot trigger
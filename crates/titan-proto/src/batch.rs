@@ -0,0 +1,150 @@
+//! Batch container: several messages in one frame.
+//!
+//! Packs small messages into a single TCP write or UDP datagram. The
+//! batch is itself a message (`MessageType::Batch`) whose payload is a
+//! count followed by the messages back to back, each with its own header
+//! (and checksum trailer, if sealed with one).
+
+use core::mem::size_of;
+use crate::messages::{BatchMessage, MessageHeader, MessageType};
+use crate::parser::{MessageParser, ParseError};
+
+/// Packs messages into a batch at the front of a buffer.
+pub struct BatchBuilder<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+    count: u32,
+}
+
+impl<'a> BatchBuilder<'a> {
+    /// Start an empty batch in `buffer`.
+    ///
+    /// # Panics
+    /// Panics if `buffer` cannot hold the batch header.
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        assert!(buffer.len() >= size_of::<BatchMessage>(), "Buffer too small for a batch");
+        Self { buffer, len: size_of::<BatchMessage>(), count: 0 }
+    }
+
+    /// Append one complete message. Returns `false` if it does not fit,
+    /// leaving the batch as it was.
+    pub fn push(&mut self, message: &[u8]) -> bool {
+        let end = self.len + message.len();
+        if end > self.buffer.len() || end - size_of::<MessageHeader>() > u16::MAX as usize {
+            return false;
+        }
+        self.buffer[self.len..end].copy_from_slice(message);
+        self.len = end;
+        self.count += 1;
+        true
+    }
+
+    /// Number of messages packed so far.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Check if no message has been packed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Bytes left for messages.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.buffer.len() - self.len
+    }
+
+    /// Write the batch header, stamped with `sequence`, and return the
+    /// batch size.
+    pub fn finish(self, sequence: u32) -> usize {
+        let mut batch = BatchMessage {
+            header: MessageHeader::new(
+                MessageType::Batch as u8,
+                (self.len - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            ..BatchMessage::default()
+        };
+        batch.set_count(self.count);
+        self.buffer[..size_of::<BatchMessage>()].copy_from_slice(bytemuck::bytes_of(&batch));
+        self.len
+    }
+}
+
+/// Iterator over the messages of a batch, yielding each one's type and
+/// frame.
+///
+/// Every message is checked with `MessageParser::validate_message`; the
+/// first invalid one ends the iteration with its error.
+pub struct BatchIter<'a> {
+    messages: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> BatchIter<'a> {
+    /// Open the batch at the front of `buffer`.
+    pub fn new(buffer: &'a [u8]) -> Result<Self, ParseError> {
+        let batch: BatchMessage = MessageParser::parse_copy(buffer)?;
+        if batch.header.msg_type != MessageType::Batch as u8 {
+            return Err(ParseError::InvalidMessageType);
+        }
+        let end = batch.header.total_size();
+        if end < size_of::<BatchMessage>() {
+            return Err(ParseError::InvalidLength);
+        }
+        let messages = buffer.get(size_of::<BatchMessage>()..end).ok_or(ParseError::BufferTooSmall)?;
+        Ok(Self { messages, remaining: batch.get_count() })
+    }
+}
+
+impl<'a> Iterator for BatchIter<'a> {
+    type Item = Result<(MessageType, &'a [u8]), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        match MessageParser::validate_message(self.messages) {
+            Ok((msg_type, len)) => {
+                let (frame, rest) = self.messages.split_at(len);
+                self.messages = rest;
+                self.remaining -= 1;
+                Some(Ok((msg_type, frame)))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{CancelOrderMessage, NewOrderMessage};
+
+    #[test]
+    fn test_batch_round_trip() {
+        let order = NewOrderMessage::new(1, 10, 42, 0, 0, 10000, 100);
+        let cancel = CancelOrderMessage::new(2, 10, 42);
+        let mut buffer = [0u8; 128];
+        let mut batch = BatchBuilder::new(&mut buffer);
+        assert!(batch.push(bytemuck::bytes_of(&order)));
+        assert!(batch.push(bytemuck::bytes_of(&cancel)));
+        assert!(!batch.push(bytemuck::bytes_of(&order)));
+        let size = batch.finish(7);
+        assert_eq!(size, 16 + 64 + 32);
+
+        // The batch is a valid message in its own right
+        assert_eq!(MessageParser::validate_message(&buffer).unwrap(), (MessageType::Batch, size));
+        let mut messages = BatchIter::new(&buffer[..size]).unwrap();
+        assert_eq!(messages.next(), Some(Ok((MessageType::NewOrder, bytemuck::bytes_of(&order)))));
+        assert_eq!(messages.next(), Some(Ok((MessageType::CancelOrder, bytemuck::bytes_of(&cancel)))));
+        assert_eq!(messages.next(), None);
+        assert!(BatchIter::new(&buffer[..size - 1]).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Session liveness tracking.
+//!
+//! `HeartbeatMonitor` keeps the clock-side state of a session: when we
+//! last sent and received, and the highest sequence received, which goes
+//! into our heartbeats. The caller supplies the time (nanoseconds, any
+//! monotonic clock) and polls it from its event loop.
+
+use crate::messages::HeartbeatMessage;
+
+/// Intervals without traffic after which a peer counts as stale.
+pub const STALE_INTERVALS: u64 = 2;
+
+/// What a session should do next, from `HeartbeatMonitor::poll`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Liveness {
+    /// Nothing due.
+    Idle,
+    /// We have been quiet for an interval: send a heartbeat.
+    SendHeartbeat,
+    /// Nothing received for `STALE_INTERVALS` intervals: probe the peer
+    /// with a test request or drop it.
+    Stale,
+}
+
+/// Heartbeat timing for one session.
+#[derive(Clone, Copy, Debug)]
+pub struct HeartbeatMonitor {
+    interval_ns: u64,
+    last_sent: u64,
+    last_received: u64,
+    last_seen_sequence: u32,
+}
+
+impl HeartbeatMonitor {
+    /// Start monitoring at `now` with the interval agreed at logon.
+    pub fn new(heartbeat_ms: u32, now: u64) -> Self {
+        Self {
+            interval_ns: heartbeat_ms as u64 * 1_000_000,
+            last_sent: now,
+            last_received: now,
+            last_seen_sequence: 0,
+        }
+    }
+
+    /// Record a message from the peer (any type) with `sequence`.
+    pub fn on_receive(&mut self, now: u64, sequence: u32) {
+        self.last_received = now;
+        self.last_seen_sequence = self.last_seen_sequence.max(sequence);
+    }
+
+    /// Record a message sent to the peer (any type).
+    pub fn on_send(&mut self, now: u64) {
+        self.last_sent = now;
+    }
+
+    /// Check if a heartbeat is due.
+    pub fn should_send(&self, now: u64) -> bool {
+        now.saturating_sub(self.last_sent) >= self.interval_ns
+    }
+
+    /// Check if the peer has gone quiet for too long.
+    pub fn is_stale(&self, now: u64) -> bool {
+        now.saturating_sub(self.last_received) >= self.interval_ns * STALE_INTERVALS
+    }
+
+    /// What to do at `now`; staleness wins over a due heartbeat.
+    pub fn poll(&self, now: u64) -> Liveness {
+        if self.is_stale(now) {
+            Liveness::Stale
+        } else if self.should_send(now) {
+            Liveness::SendHeartbeat
+        } else {
+            Liveness::Idle
+        }
+    }
+
+    /// Highest sequence received from the peer.
+    pub fn last_seen_sequence(&self) -> u32 {
+        self.last_seen_sequence
+    }
+
+    /// Heartbeat to send at `now` with our `sequence`; records the send.
+    pub fn heartbeat(&mut self, sequence: u32, now: u64) -> HeartbeatMessage {
+        self.on_send(now);
+        HeartbeatMessage::new(sequence, now, self.last_seen_sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{MessageBuilder, MessageParser};
+
+    #[test]
+    fn test_monitor_sends_and_detects_stale_peer() {
+        const MS: u64 = 1_000_000;
+        let mut monitor = HeartbeatMonitor::new(100, 0);
+        assert_eq!(monitor.poll(50 * MS), Liveness::Idle);
+
+        // Traffic both ways keeps the session idle
+        monitor.on_receive(90 * MS, 7);
+        monitor.on_send(90 * MS);
+        assert_eq!(monitor.poll(150 * MS), Liveness::Idle);
+        assert_eq!(monitor.poll(190 * MS), Liveness::SendHeartbeat);
+
+        let heartbeat = monitor.heartbeat(3, 190 * MS);
+        assert_eq!((heartbeat.get_timestamp(), heartbeat.get_last_seen_sequence()), (190 * MS, 7));
+        assert_eq!(monitor.poll(200 * MS), Liveness::Idle);
+        assert_eq!(monitor.poll(290 * MS), Liveness::Stale);
+
+        // A late, older message does not move the sequence back
+        monitor.on_receive(295 * MS, 5);
+        assert_eq!((monitor.poll(296 * MS), monitor.last_seen_sequence()), (Liveness::SendHeartbeat, 7));
+
+        let mut wire = [0u8; 32];
+        let len = MessageBuilder::new().build_heartbeat(&mut wire, 300 * MS, monitor.last_seen_sequence());
+        assert_eq!(len, 24);
+        let parsed = MessageParser::parse_heartbeat(&wire).unwrap();
+        assert_eq!((parsed.get_timestamp(), parsed.get_last_seen_sequence()), (300 * MS, 7));
+    }
+}
@@ -0,0 +1,299 @@
+//! NASDAQ TotalView-ITCH 5.0 decoder.
+//!
+//! Decodes the order-level messages needed to rebuild a book from
+//! historical ITCH files: Add Order (`A`, and `F` with attribution),
+//! Order Executed (`E`, and `C` with price), Order Cancel (`X`), Order
+//! Delete (`D`) and Trade (`P`). Other message types are recognised by
+//! their type byte only.
+//!
+//! ITCH is big-endian and unaligned, so fields are read byte by byte
+//! instead of transmuted like the native messages. Prices are in units of
+//! 1/10000; timestamps are nanoseconds since midnight.
+
+use crate::parser::ParseError;
+
+/// Fields every ITCH message starts with (after the type byte).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItchHeader {
+    pub stock_locate: u16,
+    pub tracking_number: u16,
+    /// Nanoseconds since midnight (48 bits on the wire).
+    pub timestamp: u64,
+}
+
+/// Add Order (`A`/`F`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddOrder {
+    pub header: ItchHeader,
+    pub order_ref: u64,
+    /// `b'B'` or `b'S'`.
+    pub side: u8,
+    pub shares: u32,
+    /// Space-padded symbol.
+    pub stock: [u8; 8],
+    pub price: u32,
+    /// Market participant (`F` only).
+    pub attribution: Option<[u8; 4]>,
+}
+
+/// Order Executed (`E`/`C`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderExecuted {
+    pub header: ItchHeader,
+    pub order_ref: u64,
+    pub executed_shares: u32,
+    pub match_number: u64,
+    /// Execution price when it differs from the order's (`C` only).
+    pub price: Option<u32>,
+}
+
+/// Order Cancel (`X`): partial cancel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderCancel {
+    pub header: ItchHeader,
+    pub order_ref: u64,
+    pub cancelled_shares: u32,
+}
+
+/// Order Delete (`D`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderDelete {
+    pub header: ItchHeader,
+    pub order_ref: u64,
+}
+
+/// Trade (`P`): execution against a non-displayed order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItchTrade {
+    pub header: ItchHeader,
+    pub order_ref: u64,
+    pub side: u8,
+    pub shares: u32,
+    pub stock: [u8; 8],
+    pub price: u32,
+    pub match_number: u64,
+}
+
+/// Decoded ITCH message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItchMessage {
+    AddOrder(AddOrder),
+    OrderExecuted(OrderExecuted),
+    OrderCancel(OrderCancel),
+    OrderDelete(OrderDelete),
+    Trade(ItchTrade),
+    /// Any other message type.
+    Other(u8),
+}
+
+/// Big-endian field reader over one message (also used for OUCH).
+pub(crate) struct Fields<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// Read `bytes` from `pos`; the caller has checked the length.
+    pub(crate) fn new(bytes: &'a [u8], pos: usize) -> Self {
+        Self { bytes, pos }
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut out = [0; N];
+        out.copy_from_slice(&self.bytes[self.pos..self.pos + N]);
+        self.pos += N;
+        out
+    }
+
+    pub(crate) fn u8(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_be_bytes(self.take())
+    }
+
+    pub(crate) fn u32(&mut self) -> u32 {
+        u32::from_be_bytes(self.take())
+    }
+
+    fn u48(&mut self) -> u64 {
+        let [a, b, c, d, e, f] = self.take::<6>();
+        u64::from_be_bytes([0, 0, a, b, c, d, e, f])
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes(self.take())
+    }
+
+    fn header(&mut self) -> ItchHeader {
+        ItchHeader {
+            stock_locate: self.u16(),
+            tracking_number: self.u16(),
+            timestamp: self.u48(),
+        }
+    }
+}
+
+/// Wire length of a message type, if it is one we decode.
+fn message_len(msg_type: u8) -> Option<usize> {
+    match msg_type {
+        b'A' => Some(36),
+        b'F' => Some(40),
+        b'E' => Some(31),
+        b'C' => Some(36),
+        b'X' => Some(23),
+        b'D' => Some(19),
+        b'P' => Some(44),
+        _ => None,
+    }
+}
+
+/// Decode one unframed ITCH message.
+///
+/// `buffer` starts at the type byte; bytes past the message are ignored.
+pub fn decode(buffer: &[u8]) -> Result<ItchMessage, ParseError> {
+    let msg_type = *buffer.first().ok_or(ParseError::BufferTooSmall)?;
+    let Some(len) = message_len(msg_type) else {
+        return Ok(ItchMessage::Other(msg_type));
+    };
+    if buffer.len() < len {
+        return Err(ParseError::BufferTooSmall);
+    }
+
+    let mut f = Fields::new(buffer, 1);
+    let header = f.header();
+    let message = match msg_type {
+        b'A' | b'F' => ItchMessage::AddOrder(AddOrder {
+            header,
+            order_ref: f.u64(),
+            side: f.u8(),
+            shares: f.u32(),
+            stock: f.take(),
+            price: f.u32(),
+            attribution: (msg_type == b'F').then(|| f.take()),
+        }),
+        b'E' | b'C' => ItchMessage::OrderExecuted(OrderExecuted {
+            header,
+            order_ref: f.u64(),
+            executed_shares: f.u32(),
+            match_number: f.u64(),
+            // Skip the printable flag
+            price: (msg_type == b'C').then(|| {
+                f.u8();
+                f.u32()
+            }),
+        }),
+        b'X' => ItchMessage::OrderCancel(OrderCancel {
+            header,
+            order_ref: f.u64(),
+            cancelled_shares: f.u32(),
+        }),
+        b'D' => ItchMessage::OrderDelete(OrderDelete {
+            header,
+            order_ref: f.u64(),
+        }),
+        _ => ItchMessage::Trade(ItchTrade {
+            header,
+            order_ref: f.u64(),
+            side: f.u8(),
+            shares: f.u32(),
+            stock: f.take(),
+            price: f.u32(),
+            match_number: f.u64(),
+        }),
+    };
+    Ok(message)
+}
+
+/// Iterator over a BinaryFILE-framed ITCH stream (each message preceded
+/// by its big-endian `u16` length), as NASDAQ distributes historical
+/// files.
+///
+/// Messages of types not decoded come out as `Other`. Stops at the end
+/// of the buffer; a truncated final frame yields `BufferTooSmall` once.
+pub struct ItchFrames<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> ItchFrames<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Self { buffer }
+    }
+
+    /// Bytes not yet decoded.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buffer
+    }
+}
+
+impl Iterator for ItchFrames<'_> {
+    type Item = Result<ItchMessage, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let frame = self.buffer.get(..2).and_then(|len| {
+            let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+            self.buffer.get(2..2 + len)
+        });
+        let Some(frame) = frame else {
+            self.buffer = &[];
+            return Some(Err(ParseError::BufferTooSmall));
+        };
+        self.buffer = &self.buffer[2 + frame.len()..];
+
+        Some(decode(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_framed_order_lifecycle() {
+        // Add Order, then Order Executed, then an unhandled System Event
+        let mut add = [0u8; 36];
+        add[0] = b'A';
+        add[1..3].copy_from_slice(&7u16.to_be_bytes());
+        add[5..11].copy_from_slice(&34_200_000_000_000u64.to_be_bytes()[2..]);
+        add[11..19].copy_from_slice(&42u64.to_be_bytes());
+        add[19] = b'B';
+        add[20..24].copy_from_slice(&100u32.to_be_bytes());
+        add[24..32].copy_from_slice(b"AAPL    ");
+        add[32..36].copy_from_slice(&1_890_500u32.to_be_bytes());
+
+        let mut exec = [0u8; 31];
+        exec[0] = b'E';
+        exec[11..19].copy_from_slice(&42u64.to_be_bytes());
+        exec[19..23].copy_from_slice(&40u32.to_be_bytes());
+        exec[23..31].copy_from_slice(&9001u64.to_be_bytes());
+
+        let mut file = [0u8; 2 + 36 + 2 + 31 + 2 + 12];
+        let mut len = 0;
+        for msg in [&add[..], &exec[..], &[b'S'; 12][..]] {
+            file[len..len + 2].copy_from_slice(&(msg.len() as u16).to_be_bytes());
+            file[len + 2..len + 2 + msg.len()].copy_from_slice(msg);
+            len += 2 + msg.len();
+        }
+
+        let mut frames = ItchFrames::new(&file);
+        let messages = [(); 3].map(|_| frames.next().unwrap());
+        assert_eq!(frames.next(), None);
+        let Ok(ItchMessage::AddOrder(order)) = messages[0] else { panic!() };
+        assert_eq!((order.header.stock_locate, order.header.timestamp), (7, 34_200_000_000_000));
+        assert_eq!((order.order_ref, order.side, order.shares, order.price), (42, b'B', 100, 1_890_500));
+        assert_eq!((&order.stock, order.attribution), (b"AAPL    ", None));
+        let Ok(ItchMessage::OrderExecuted(fill)) = messages[1] else { panic!() };
+        assert_eq!((fill.order_ref, fill.executed_shares, fill.match_number, fill.price), (42, 40, 9001, None));
+        assert_eq!(messages[2], Ok(ItchMessage::Other(b'S')));
+
+        // Truncated final frame
+        let mut frames = ItchFrames::new(&file[..file.len() - 1]);
+        assert_eq!(frames.by_ref().count(), 3);
+        assert!(frames.remaining().is_empty());
+        assert_eq!(decode(&add[..20]), Err(ParseError::BufferTooSmall));
+    }
+}
@@ -5,8 +5,19 @@
 
 #![no_std]
 
+pub mod batch;
+pub mod crc;
+pub mod heartbeat;
+pub mod itch;
 pub mod messages;
+pub mod ouch;
 pub mod parser;
+pub mod sbe;
+pub mod tlv;
 
+pub use batch::{BatchBuilder, BatchIter};
+pub use crc::crc32c;
+pub use heartbeat::{HeartbeatMonitor, Liveness};
 pub use messages::*;
 pub use parser::*;
+pub use tlv::{Extension, ExtensionWriter, Extensions};
@@ -1,11 +1,34 @@
 //! Binary message definitions.
 //!
 //! All messages use fixed-size layouts for zero-copy parsing.
-//! Little-endian byte order is used throughout.
+//! Little-endian byte order is used throughout: constructors store
+//! multi-byte fields little-endian, and the generated `get_*`/`set_*`
+//! accessors convert, so the layouts read the same on big-endian hosts.
+//! Direct field access only sees host values on little-endian hosts.
 
 use bytemuck::{Pod, Zeroable};
 use core::mem::size_of;
 
+/// Generate little-endian `get_*`/`set_*` accessors for the multi-byte
+/// fields of a wire struct.
+macro_rules! wire_fields {
+    ($name:ident { $($field:ident: $ty:ty => $get:ident, $set:ident;)* }) => {
+        impl $name {
+            $(
+                #[inline(always)]
+                pub const fn $get(&self) -> $ty {
+                    <$ty>::from_le(self.$field)
+                }
+                
+                #[inline(always)]
+                pub fn $set(&mut self, value: $ty) {
+                    self.$field = value.to_le();
+                }
+            )*
+        }
+    };
+}
+
 /// Message type discriminator.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -14,17 +37,39 @@ pub enum MessageType {
     NewOrder = 0x01,
     CancelOrder = 0x02,
     ModifyOrder = 0x03,
+    MassQuote = 0x04,
+    MassCancel = 0x05,
+    OrderStatusRequest = 0x06,
+    DirectoryRequest = 0x07,
     
     // Outbound (engine → client)
     ExecutionReport = 0x10,
     OrderAck = 0x11,
     OrderReject = 0x12,
     CancelAck = 0x13,
+    OrderStatusReport = 0x14,
     
     // Market Data
     Trade = 0x20,
     Quote = 0x21,
     BookUpdate = 0x22,
+    Statistics = 0x23,
+    BookSnapshot = 0x24,
+    SnapshotStart = 0x25,
+    SnapshotLevel = 0x26,
+    SnapshotEnd = 0x27,
+    InstrumentDefinition = 0x28,
+    TradingStatus = 0x29,
+    
+    // Session
+    Logon = 0x30,
+    LogonAck = 0x31,
+    TestRequest = 0x32,
+    TestResponse = 0x33,
+    SequenceReset = 0x34,
+    
+    // Framing
+    Batch = 0x40,
     
     // System
     Heartbeat = 0xFE,
@@ -39,13 +84,31 @@ impl TryFrom<u8> for MessageType {
             0x01 => Ok(MessageType::NewOrder),
             0x02 => Ok(MessageType::CancelOrder),
             0x03 => Ok(MessageType::ModifyOrder),
+            0x04 => Ok(MessageType::MassQuote),
+            0x05 => Ok(MessageType::MassCancel),
+            0x06 => Ok(MessageType::OrderStatusRequest),
+            0x07 => Ok(MessageType::DirectoryRequest),
             0x10 => Ok(MessageType::ExecutionReport),
             0x11 => Ok(MessageType::OrderAck),
             0x12 => Ok(MessageType::OrderReject),
             0x13 => Ok(MessageType::CancelAck),
+            0x14 => Ok(MessageType::OrderStatusReport),
             0x20 => Ok(MessageType::Trade),
             0x21 => Ok(MessageType::Quote),
             0x22 => Ok(MessageType::BookUpdate),
+            0x23 => Ok(MessageType::Statistics),
+            0x24 => Ok(MessageType::BookSnapshot),
+            0x25 => Ok(MessageType::SnapshotStart),
+            0x26 => Ok(MessageType::SnapshotLevel),
+            0x27 => Ok(MessageType::SnapshotEnd),
+            0x28 => Ok(MessageType::InstrumentDefinition),
+            0x29 => Ok(MessageType::TradingStatus),
+            0x30 => Ok(MessageType::Logon),
+            0x31 => Ok(MessageType::LogonAck),
+            0x32 => Ok(MessageType::TestRequest),
+            0x33 => Ok(MessageType::TestResponse),
+            0x34 => Ok(MessageType::SequenceReset),
+            0x40 => Ok(MessageType::Batch),
             0xFE => Ok(MessageType::Heartbeat),
             0xFF => Ok(MessageType::SystemError),
             _ => Err(()),
@@ -53,13 +116,23 @@ impl TryFrom<u8> for MessageType {
     }
 }
 
+/// Header flag: a CRC32C of the message (header and payload, this flag
+/// set) follows it as a little-endian `u32` trailer.
+pub const FLAG_CHECKSUM: u8 = 0x01;
+
+/// Header flag: TLV extensions (see `tlv`) follow the fixed message and
+/// are counted in `length`. Without it fixed-size messages are exactly
+/// their struct size.
+pub const FLAG_EXTENSIONS: u8 = 0x02;
+
 /// Fixed-size message header (8 bytes).
 #[derive(Clone, Copy, Debug, Default)]
 #[repr(C, packed)]
 pub struct MessageHeader {
     /// Message type.
     pub msg_type: u8,
-    /// Message flags (reserved).
+    /// Message flags (`FLAG_CHECKSUM`, `FLAG_EXTENSIONS`; other bits
+    /// reserved).
     pub flags: u8,
     /// Payload length (excluding header).
     pub length: u16,
@@ -73,20 +146,25 @@ const _: () = assert!(size_of::<MessageHeader>() == 8);
 unsafe impl Pod for MessageHeader {}
 unsafe impl Zeroable for MessageHeader {}
 
+wire_fields!(MessageHeader {
+    length: u16 => get_length, set_length;
+    sequence: u32 => get_sequence, set_sequence;
+});
+
 impl MessageHeader {
     /// Create a new header.
     pub const fn new(msg_type: u8, length: u16, sequence: u32) -> Self {
         Self {
             msg_type,
             flags: 0,
-            length,
-            sequence,
+            length: length.to_le(),
+            sequence: sequence.to_le(),
         }
     }
     
     /// Get total message size (header + payload).
     pub const fn total_size(&self) -> usize {
-        size_of::<Self>() + self.length as usize
+        size_of::<Self>() + self.get_length() as usize
     }
 }
 
@@ -98,12 +176,13 @@ pub struct NewOrderMessage {
     pub order_id: u64,              // 8 bytes
     pub symbol_id: u32,             // 4 bytes
     pub side: u8,                   // 1 byte (0=Buy, 1=Sell)
-    pub order_type: u8,             // 1 byte (0=Limit, 1=IOC, 2=FOK, 3=PostOnly)
+    pub order_type: u8,             // 1 byte (0=Limit, 1=IOC, 2=FOK, 3=PostOnly, 4=Market, 5=GTD, 6=Day)
     pub _padding1: u16,             // 2 bytes (alignment)
     pub price: u64,                 // 8 bytes (fixed-point)
     pub quantity: u64,              // 8 bytes
-    pub client_order_id: [u8; 20],  // 20 bytes (client reference)
-    pub _reserved: [u8; 4],         // 4 bytes
+    pub client_order_id: [u8; 16],  // 16 bytes (client reference)
+    pub participant_id: u32,        // 4 bytes (owning participant, 0 = unattributed)
+    pub display_qty: u32,           // 4 bytes (iceberg clip, 0 = fully displayed)
 }
 
 const _: () = assert!(size_of::<NewOrderMessage>() == 64);
@@ -111,6 +190,15 @@ const _: () = assert!(size_of::<NewOrderMessage>() == 64);
 unsafe impl Pod for NewOrderMessage {}
 unsafe impl Zeroable for NewOrderMessage {}
 
+wire_fields!(NewOrderMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+    participant_id: u32 => get_participant_id, set_participant_id;
+    display_qty: u32 => get_display_qty, set_display_qty;
+});
+
 impl NewOrderMessage {
     /// Create a new order message.
     pub fn new(
@@ -128,17 +216,88 @@ impl NewOrderMessage {
                 (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
                 sequence,
             ),
-            order_id,
-            symbol_id,
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
             side,
             order_type,
             _padding1: 0,
-            price,
-            quantity,
-            client_order_id: [0; 20],
-            _reserved: [0; 4],
+            price: price.to_le(),
+            quantity: quantity.to_le(),
+            client_order_id: [0; 16],
+            participant_id: 0,
+            display_qty: 0,
         }
     }
+    
+    /// Send as an iceberg showing `display_qty` at a time.
+    pub fn with_display_qty(mut self, display_qty: u32) -> Self {
+        self.set_display_qty(display_qty);
+        self
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+    
+    /// Set the client order ID (see `encode_client_order_id`).
+    pub fn set_client_order_id(&mut self, id: &str) {
+        self.client_order_id = encode_client_order_id(id);
+    }
+    
+    /// Send with client order ID `id` (see `encode_client_order_id`).
+    pub fn with_client_order_id(mut self, id: &str) -> Self {
+        self.set_client_order_id(id);
+        self
+    }
+    
+    /// Client order ID without its zero padding.
+    pub fn client_order_id_str(&self) -> &str {
+        client_order_id_str(&self.client_order_id)
+    }
+}
+
+/// Encode a client order ID into the 16-byte wire field.
+///
+/// IDs longer than the field are cut at the last character boundary that
+/// fits; shorter ones are padded with zeros.
+pub fn encode_client_order_id(id: &str) -> [u8; 16] {
+    encode_padded(id)
+}
+
+/// Text in a zero-padded 16-byte field, cut at a character boundary.
+fn encode_padded(text: &str) -> [u8; 16] {
+    let text = truncate_utf8(text, 16);
+    let mut field = [0; 16];
+    field[..text.len()].copy_from_slice(text.as_bytes());
+    field
+}
+
+/// Longest prefix of `text` of at most `max` bytes that ends on a
+/// character boundary.
+pub fn truncate_utf8(text: &str, max: usize) -> &str {
+    let mut end = text.len().min(max);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Decode a client order ID field: the text before its zero padding, up
+/// to the first byte that is not valid UTF-8.
+pub fn client_order_id_str(field: &[u8; 16]) -> &str {
+    padded_str(field)
+}
+
+/// Text of a zero-padded 16-byte field, up to the first byte that is not
+/// valid UTF-8.
+fn padded_str(field: &[u8; 16]) -> &str {
+    let len = field.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    match core::str::from_utf8(&field[..len]) {
+        Ok(id) => id,
+        Err(e) => core::str::from_utf8(&field[..e.valid_up_to()]).unwrap_or_default(),
+    }
 }
 
 /// Cancel Order message (32 bytes).
@@ -148,7 +307,8 @@ pub struct CancelOrderMessage {
     pub header: MessageHeader,      // 8 bytes
     pub order_id: u64,              // 8 bytes
     pub symbol_id: u32,             // 4 bytes
-    pub _reserved: [u8; 12],        // 12 bytes
+    pub participant_id: u32,        // 4 bytes (must own the order)
+    pub _reserved: [u8; 8],         // 8 bytes
 }
 
 const _: () = assert!(size_of::<CancelOrderMessage>() == 32);
@@ -156,6 +316,12 @@ const _: () = assert!(size_of::<CancelOrderMessage>() == 32);
 unsafe impl Pod for CancelOrderMessage {}
 unsafe impl Zeroable for CancelOrderMessage {}
 
+wire_fields!(CancelOrderMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    participant_id: u32 => get_participant_id, set_participant_id;
+});
+
 impl CancelOrderMessage {
     pub fn new(sequence: u32, order_id: u64, symbol_id: u32) -> Self {
         Self {
@@ -164,11 +330,482 @@ impl CancelOrderMessage {
                 (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
                 sequence,
             ),
-            order_id,
-            symbol_id,
-            _reserved: [0; 12],
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            participant_id: 0,
+            _reserved: [0; 8],
+        }
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+}
+
+/// Modify Order message (48 bytes).
+///
+/// `quantity` is the new open quantity. Sizing down at the same price keeps
+/// time priority; any other change loses it.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct ModifyOrderMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub order_id: u64,              // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub participant_id: u32,        // 4 bytes (must own the order)
+    pub price: u64,                 // 8 bytes (fixed-point)
+    pub quantity: u64,              // 8 bytes
+    pub _reserved: [u8; 8],         // 8 bytes
+}
+
+const _: () = assert!(size_of::<ModifyOrderMessage>() == 48);
+
+unsafe impl Pod for ModifyOrderMessage {}
+unsafe impl Zeroable for ModifyOrderMessage {}
+
+wire_fields!(ModifyOrderMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    participant_id: u32 => get_participant_id, set_participant_id;
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+});
+
+impl ModifyOrderMessage {
+    pub fn new(sequence: u32, order_id: u64, symbol_id: u32, price: u64, quantity: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::ModifyOrder as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            participant_id: 0,
+            price: price.to_le(),
+            quantity: quantity.to_le(),
+            _reserved: [0; 8],
+        }
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+}
+
+/// Mass Quote message (64 bytes).
+///
+/// Sets both sides of a market maker's quote on one symbol in a single
+/// message. Each side updates the live quote order in place where it can;
+/// a zero quantity pulls that side. The order IDs name the orders placed
+/// when a side has no live quote.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct MassQuoteMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub participant_id: u32,        // 4 bytes (quoting participant)
+    pub bid_order_id: u64,          // 8 bytes
+    pub bid_price: u64,             // 8 bytes (fixed-point)
+    pub bid_qty: u64,               // 8 bytes (0 = no bid)
+    pub ask_order_id: u64,          // 8 bytes
+    pub ask_price: u64,             // 8 bytes (fixed-point)
+    pub ask_qty: u64,               // 8 bytes (0 = no ask)
+}
+
+const _: () = assert!(size_of::<MassQuoteMessage>() == 64);
+
+unsafe impl Pod for MassQuoteMessage {}
+unsafe impl Zeroable for MassQuoteMessage {}
+
+wire_fields!(MassQuoteMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    participant_id: u32 => get_participant_id, set_participant_id;
+    bid_order_id: u64 => get_bid_order_id, set_bid_order_id;
+    bid_price: u64 => get_bid_price, set_bid_price;
+    bid_qty: u64 => get_bid_qty, set_bid_qty;
+    ask_order_id: u64 => get_ask_order_id, set_ask_order_id;
+    ask_price: u64 => get_ask_price, set_ask_price;
+    ask_qty: u64 => get_ask_qty, set_ask_qty;
+});
+
+impl MassQuoteMessage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sequence: u32,
+        symbol_id: u32,
+        bid_order_id: u64,
+        bid_price: u64,
+        bid_qty: u64,
+        ask_order_id: u64,
+        ask_price: u64,
+        ask_qty: u64,
+    ) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::MassQuote as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            participant_id: 0,
+            bid_order_id: bid_order_id.to_le(),
+            bid_price: bid_price.to_le(),
+            bid_qty: bid_qty.to_le(),
+            ask_order_id: ask_order_id.to_le(),
+            ask_price: ask_price.to_le(),
+            ask_qty: ask_qty.to_le(),
+        }
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+}
+
+/// `MassCancelMessage::side` value cancelling both sides.
+pub const MASS_CANCEL_BOTH_SIDES: u8 = 2;
+
+/// Mass Cancel message (24 bytes).
+///
+/// Cancels every resting order of `participant_id` on `symbol_id` (0 = all
+/// symbols) and `side` (0 = Buy, 1 = Sell, `MASS_CANCEL_BOTH_SIDES`).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct MassCancelMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes (0 = all symbols)
+    pub participant_id: u32,        // 4 bytes (must own the orders)
+    pub side: u8,                   // 1 byte
+    pub _padding: [u8; 7],          // 7 bytes
+}
+
+const _: () = assert!(size_of::<MassCancelMessage>() == 24);
+
+unsafe impl Pod for MassCancelMessage {}
+unsafe impl Zeroable for MassCancelMessage {}
+
+wire_fields!(MassCancelMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    participant_id: u32 => get_participant_id, set_participant_id;
+});
+
+impl MassCancelMessage {
+    pub fn new(sequence: u32, symbol_id: u32, side: u8) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::MassCancel as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            participant_id: 0,
+            side,
+            _padding: [0; 7],
+        }
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+}
+
+/// Order Status Request message (24 bytes), answered with an
+/// `OrderStatusReportMessage`.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct OrderStatusRequestMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub order_id: u64,              // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub participant_id: u32,        // 4 bytes (must own the order)
+}
+
+const _: () = assert!(size_of::<OrderStatusRequestMessage>() == 24);
+
+unsafe impl Pod for OrderStatusRequestMessage {}
+unsafe impl Zeroable for OrderStatusRequestMessage {}
+
+wire_fields!(OrderStatusRequestMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    participant_id: u32 => get_participant_id, set_participant_id;
+});
+
+impl OrderStatusRequestMessage {
+    pub fn new(sequence: u32, order_id: u64, symbol_id: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::OrderStatusRequest as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            participant_id: 0,
+        }
+    }
+    
+    /// Send on behalf of `participant_id`.
+    pub fn with_participant(mut self, participant_id: u32) -> Self {
+        self.set_participant_id(participant_id);
+        self
+    }
+}
+
+/// State of an order in an `OrderStatusReportMessage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OrderState {
+    /// Resting, nothing filled.
+    Open = 0,
+    /// Resting, partly filled.
+    PartiallyFilled = 1,
+    /// Fully filled.
+    Filled = 2,
+    /// Cancelled or expired.
+    Cancelled = 3,
+    /// No such order for this participant.
+    Unknown = 4,
+}
+
+impl TryFrom<u8> for OrderState {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(OrderState::Open),
+            1 => Ok(OrderState::PartiallyFilled),
+            2 => Ok(OrderState::Filled),
+            3 => Ok(OrderState::Cancelled),
+            4 => Ok(OrderState::Unknown),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Order Status Report message (outbound, 48 bytes).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct OrderStatusReportMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub order_id: u64,              // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub side: u8,                   // 1 byte
+    pub state: u8,                  // 1 byte (OrderState)
+    pub _padding: u16,              // 2 bytes
+    pub price: u64,                 // 8 bytes
+    pub leaves_qty: u64,            // 8 bytes
+    pub cum_qty: u64,               // 8 bytes
+}
+
+const _: () = assert!(size_of::<OrderStatusReportMessage>() == 48);
+
+unsafe impl Pod for OrderStatusReportMessage {}
+unsafe impl Zeroable for OrderStatusReportMessage {}
+
+wire_fields!(OrderStatusReportMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    price: u64 => get_price, set_price;
+    leaves_qty: u64 => get_leaves_qty, set_leaves_qty;
+    cum_qty: u64 => get_cum_qty, set_cum_qty;
+});
+
+impl OrderStatusReportMessage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sequence: u32,
+        order_id: u64,
+        symbol_id: u32,
+        side: u8,
+        state: OrderState,
+        price: u64,
+        leaves_qty: u64,
+        cum_qty: u64,
+    ) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::OrderStatusReport as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            side,
+            state: state as u8,
+            _padding: 0,
+            price: price.to_le(),
+            leaves_qty: leaves_qty.to_le(),
+            cum_qty: cum_qty.to_le(),
+        }
+    }
+    
+    /// Decoded state (`None` if unknown).
+    pub fn order_state(&self) -> Option<OrderState> {
+        OrderState::try_from(self.state).ok()
+    }
+}
+
+/// Longest text an `OrderRejectMessage` or `SystemErrorMessage` carries.
+pub const MAX_TEXT_LEN: usize = 128;
+
+/// Order Reject message (outbound, 24 bytes + text).
+///
+/// Followed by `text_len` bytes of UTF-8 giving the reason in words
+/// (at most `MAX_TEXT_LEN`); `header.length` covers the text, so readers
+/// that do not want it skip it like any other message.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct OrderRejectMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub order_id: u64,              // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub reason: u8,                 // 1 byte (reject code)
+    pub text_len: u8,               // 1 byte
+    pub _padding: u16,              // 2 bytes
+}
+
+const _: () = assert!(size_of::<OrderRejectMessage>() == 24);
+
+unsafe impl Pod for OrderRejectMessage {}
+unsafe impl Zeroable for OrderRejectMessage {}
+
+wire_fields!(OrderRejectMessage {
+    order_id: u64 => get_order_id, set_order_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+});
+
+impl OrderRejectMessage {
+    /// Header of a reject whose text is `text_len` bytes.
+    pub fn new(sequence: u32, order_id: u64, symbol_id: u32, reason: u8, text_len: u8) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::OrderReject as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>() + text_len as usize) as u16,
+                sequence,
+            ),
+            order_id: order_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            reason,
+            text_len,
+            _padding: 0,
+        }
+    }
+}
+
+/// Why the venue is refusing traffic, in a `SystemErrorMessage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum SystemErrorCode {
+    /// Matching is halted; orders are not accepted.
+    EngineHalted = 1,
+    /// The engine has no room for more orders.
+    PoolExhausted = 2,
+    /// Unexpected failure inside the venue.
+    InternalError = 3,
+    /// The session is sending faster than its rate limit.
+    Throttled = 4,
+    /// Traffic arrived before a successful logon.
+    NotLoggedOn = 5,
+}
+
+impl TryFrom<u16> for SystemErrorCode {
+    type Error = ();
+    
+    fn try_from(value: u16) -> Result<Self, ()> {
+        match value {
+            1 => Ok(SystemErrorCode::EngineHalted),
+            2 => Ok(SystemErrorCode::PoolExhausted),
+            3 => Ok(SystemErrorCode::InternalError),
+            4 => Ok(SystemErrorCode::Throttled),
+            5 => Ok(SystemErrorCode::NotLoggedOn),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How bad a `SystemErrorMessage` is for the session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Severity {
+    /// For information; traffic continues.
+    Info = 0,
+    /// Traffic is being refused for now; retry later.
+    Warning = 1,
+    /// The session is being closed.
+    Fatal = 2,
+}
+
+impl TryFrom<u8> for Severity {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(Severity::Info),
+            1 => Ok(Severity::Warning),
+            2 => Ok(Severity::Fatal),
+            _ => Err(()),
+        }
+    }
+}
+
+/// System Error message (outbound, 16 bytes + text), laid out like
+/// `OrderRejectMessage`.
+///
+/// `code` is a `SystemErrorCode`; the optional text adds detail.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct SystemErrorMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub code: u16,                  // 2 bytes
+    pub severity: u8,               // 1 byte
+    pub text_len: u8,               // 1 byte
+    pub _padding: [u8; 4],          // 4 bytes
+}
+
+const _: () = assert!(size_of::<SystemErrorMessage>() == 16);
+
+unsafe impl Pod for SystemErrorMessage {}
+unsafe impl Zeroable for SystemErrorMessage {}
+
+wire_fields!(SystemErrorMessage {
+    code: u16 => get_code, set_code;
+});
+
+impl SystemErrorMessage {
+    /// Header of an error whose text is `text_len` bytes.
+    pub fn new(sequence: u32, code: SystemErrorCode, severity: Severity, text_len: u8) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::SystemError as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>() + text_len as usize) as u16,
+                sequence,
+            ),
+            code: (code as u16).to_le(),
+            severity: severity as u8,
+            text_len,
+            _padding: [0; 4],
         }
     }
+    
+    /// Decoded error code (`None` if unknown).
+    pub fn error_code(&self) -> Option<SystemErrorCode> {
+        SystemErrorCode::try_from(self.get_code()).ok()
+    }
+    
+    /// Decoded severity (`None` if unknown).
+    pub fn severity(&self) -> Option<Severity> {
+        Severity::try_from(self.severity).ok()
+    }
 }
 
 /// Execution type for reports.
@@ -180,9 +817,13 @@ pub enum ExecType {
     PartialFill = 2,
     Canceled = 3,
     Rejected = 4,
+    Replaced = 5,
 }
 
-/// Execution Report (outbound, 64 bytes).
+/// Execution Report (outbound, 80 bytes).
+///
+/// Carries the client order ID of the order it reports on, so clients can
+/// correlate fills without tracking `order_id`s.
 #[derive(Clone, Copy, Debug, Default)]
 #[repr(C, packed)]
 pub struct ExecutionReport {
@@ -197,13 +838,24 @@ pub struct ExecutionReport {
     pub exec_qty: u64,              // 8 bytes
     pub leaves_qty: u64,            // 8 bytes (remaining qty)
     pub timestamp: u64,             // 8 bytes
+    pub client_order_id: [u8; 16],  // 16 bytes (from the order)
 }
 
-const _: () = assert!(size_of::<ExecutionReport>() == 64);
+const _: () = assert!(size_of::<ExecutionReport>() == 80);
 
 unsafe impl Pod for ExecutionReport {}
 unsafe impl Zeroable for ExecutionReport {}
 
+wire_fields!(ExecutionReport {
+    order_id: u64 => get_order_id, set_order_id;
+    exec_id: u64 => get_exec_id, set_exec_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    exec_price: u64 => get_exec_price, set_exec_price;
+    exec_qty: u64 => get_exec_qty, set_exec_qty;
+    leaves_qty: u64 => get_leaves_qty, set_leaves_qty;
+    timestamp: u64 => get_timestamp, set_timestamp;
+});
+
 impl ExecutionReport {
     pub fn new_fill(
         sequence: u32,
@@ -217,29 +869,59 @@ impl ExecutionReport {
         timestamp: u64,
     ) -> Self {
         let exec_type = if leaves_qty == 0 {
-            ExecType::Fill as u8
+            ExecType::Fill
         } else {
-            ExecType::PartialFill as u8
+            ExecType::PartialFill
         };
         
+        Self::new(sequence, order_id, exec_id, symbol_id, side, exec_type, price, qty, leaves_qty, timestamp)
+    }
+    
+    /// Build a report of any execution type.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sequence: u32,
+        order_id: u64,
+        exec_id: u64,
+        symbol_id: u32,
+        side: u8,
+        exec_type: ExecType,
+        price: u64,
+        qty: u64,
+        leaves_qty: u64,
+        timestamp: u64,
+    ) -> Self {
         Self {
             header: MessageHeader::new(
                 MessageType::ExecutionReport as u8,
                 (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
                 sequence,
             ),
-            order_id,
-            exec_id,
-            symbol_id,
+            order_id: order_id.to_le(),
+            exec_id: exec_id.to_le(),
+            symbol_id: symbol_id.to_le(),
             side,
-            exec_type,
+            exec_type: exec_type as u8,
             _padding1: 0,
-            exec_price: price,
-            exec_qty: qty,
-            leaves_qty,
-            timestamp,
+            exec_price: price.to_le(),
+            exec_qty: qty.to_le(),
+            leaves_qty: leaves_qty.to_le(),
+            timestamp: timestamp.to_le(),
+            client_order_id: [0; 16],
         }
     }
+    
+    /// Report on the order with this client order ID (raw field bytes,
+    /// as in `NewOrderMessage::client_order_id`).
+    pub fn with_client_order_id(mut self, client_order_id: [u8; 16]) -> Self {
+        self.client_order_id = client_order_id;
+        self
+    }
+    
+    /// Client order ID without its zero padding.
+    pub fn client_order_id_str(&self) -> &str {
+        client_order_id_str(&self.client_order_id)
+    }
 }
 
 /// Quote message (32 bytes).
@@ -258,6 +940,12 @@ const _: () = assert!(size_of::<QuoteMessage>() == 32);
 unsafe impl Pod for QuoteMessage {}
 unsafe impl Zeroable for QuoteMessage {}
 
+wire_fields!(QuoteMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    bid_price: u64 => get_bid_price, set_bid_price;
+    ask_price: u64 => get_ask_price, set_ask_price;
+});
+
 /// Trade message (48 bytes).
 #[derive(Clone, Copy, Debug, Default)]
 #[repr(C, packed)]
@@ -277,16 +965,941 @@ const _: () = assert!(size_of::<TradeMessage>() == 48);
 unsafe impl Pod for TradeMessage {}
 unsafe impl Zeroable for TradeMessage {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
+wire_fields!(TradeMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+    timestamp: u64 => get_timestamp, set_timestamp;
+    trade_id: u64 => get_trade_id, set_trade_id;
+});
+
+/// Session statistics message (96 bytes).
+///
+/// Prices are raw fixed-point values; zero means no trade yet (or, for
+/// `close_price`, a session still open).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct StatisticsMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub _padding: u32,              // 4 bytes
+    pub last_price: u64,            // 8 bytes
+    pub last_qty: u64,              // 8 bytes
+    pub open_price: u64,            // 8 bytes
+    pub high_price: u64,            // 8 bytes
+    pub low_price: u64,             // 8 bytes
+    pub close_price: u64,           // 8 bytes
+    pub volume: u64,                // 8 bytes
+    pub trade_count: u64,           // 8 bytes
+    pub timestamp: u64,             // 8 bytes
+    pub _reserved: u64,             // 8 bytes
+}
+
+const _: () = assert!(size_of::<StatisticsMessage>() == 96);
+
+unsafe impl Pod for StatisticsMessage {}
+unsafe impl Zeroable for StatisticsMessage {}
+
+wire_fields!(StatisticsMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    last_price: u64 => get_last_price, set_last_price;
+    last_qty: u64 => get_last_qty, set_last_qty;
+    open_price: u64 => get_open_price, set_open_price;
+    high_price: u64 => get_high_price, set_high_price;
+    low_price: u64 => get_low_price, set_low_price;
+    close_price: u64 => get_close_price, set_close_price;
+    volume: u64 => get_volume, set_volume;
+    trade_count: u64 => get_trade_count, set_trade_count;
+    timestamp: u64 => get_timestamp, set_timestamp;
+});
+
+impl StatisticsMessage {
+    /// Statistics for `symbol_id` at `timestamp` with every figure zero;
+    /// fill in the rest with struct update syntax.
+    pub fn new(sequence: u32, symbol_id: u32, timestamp: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::Statistics as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            timestamp: timestamp.to_le(),
+            ..Self::default()
+        }
+    }
+}
+
+/// What a book update does to its price level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BookAction {
+    /// Level added.
+    New = 0,
+    /// Level quantity changed.
+    Change = 1,
+    /// Level removed (quantity zero).
+    Delete = 2,
+}
+
+impl TryFrom<u8> for BookAction {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(BookAction::New),
+            1 => Ok(BookAction::Change),
+            2 => Ok(BookAction::Delete),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Book update message (40 bytes).
+///
+/// New total quantity of one price level; zero removes the level.
+/// `update_seq` numbers a symbol's book updates without gaps, apart from
+/// the header sequence shared with the rest of the feed.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct BookUpdateMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub side: u8,                   // 1 byte
+    pub action: u8,                 // 1 byte (BookAction)
+    pub _padding: [u8; 2],          // 2 bytes
+    pub update_seq: u64,            // 8 bytes
+    pub price: u64,                 // 8 bytes
+    pub quantity: u64,              // 8 bytes
+}
+
+const _: () = assert!(size_of::<BookUpdateMessage>() == 40);
+
+unsafe impl Pod for BookUpdateMessage {}
+unsafe impl Zeroable for BookUpdateMessage {}
+
+wire_fields!(BookUpdateMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    update_seq: u64 => get_update_seq, set_update_seq;
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+});
+
+impl BookUpdateMessage {
+    /// Level `price` on `side` of `symbol_id` now holds `quantity`.
+    ///
+    /// The action is `Delete` for a zero quantity and `Change` otherwise;
+    /// mark a level that was not in the book with `with_action`.
+    pub fn new(sequence: u32, symbol_id: u32, update_seq: u64, side: u8, price: u64, quantity: u64) -> Self {
+        let action = if quantity == 0 { BookAction::Delete } else { BookAction::Change };
+        Self {
+            header: MessageHeader::new(
+                MessageType::BookUpdate as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            side,
+            action: action as u8,
+            _padding: [0; 2],
+            update_seq: update_seq.to_le(),
+            price: price.to_le(),
+            quantity: quantity.to_le(),
+        }
+    }
+    
+    /// Set the update action.
+    pub fn with_action(mut self, action: BookAction) -> Self {
+        self.action = action as u8;
+        self
+    }
+    
+    /// Update action (`None` if the byte is unknown).
+    pub fn book_action(&self) -> Option<BookAction> {
+        BookAction::try_from(self.action).ok()
+    }
+}
+
+/// Levels per side in a book snapshot.
+pub const SNAPSHOT_DEPTH: usize = 10;
+
+/// One level of a book snapshot (16 bytes).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C, packed)]
+pub struct SnapshotLevel {
+    pub price: u64,                 // 8 bytes
+    pub quantity: u64,              // 8 bytes
+}
+
+unsafe impl Pod for SnapshotLevel {}
+unsafe impl Zeroable for SnapshotLevel {}
+
+wire_fields!(SnapshotLevel {
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+});
+
+/// Book snapshot message (344 bytes).
+///
+/// Best `SNAPSHOT_DEPTH` levels of each side, best first, as of book
+/// update `update_seq`; updates up to and including it are already
+/// reflected.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct BookSnapshotMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub bid_count: u8,              // 1 byte
+    pub ask_count: u8,              // 1 byte
+    pub _padding: [u8; 2],          // 2 bytes
+    pub update_seq: u64,            // 8 bytes
+    pub bids: [SnapshotLevel; SNAPSHOT_DEPTH],  // 160 bytes
+    pub asks: [SnapshotLevel; SNAPSHOT_DEPTH],  // 160 bytes
+}
+
+const _: () = assert!(size_of::<BookSnapshotMessage>() == 344);
+
+unsafe impl Pod for BookSnapshotMessage {}
+unsafe impl Zeroable for BookSnapshotMessage {}
+
+wire_fields!(BookSnapshotMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    update_seq: u64 => get_update_seq, set_update_seq;
+});
+
+impl BookSnapshotMessage {
+    /// Empty snapshot of `symbol_id` as of `update_seq`; add levels with
+    /// `push_level`.
+    pub fn new(sequence: u32, symbol_id: u32, update_seq: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::BookSnapshot as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            update_seq: update_seq.to_le(),
+            ..Self::default()
+        }
+    }
+    
+    /// Append a level to `side` (0 = bids, 1 = asks), worse than those
+    /// already there. Returns `false` if the side is full.
+    pub fn push_level(&mut self, side: u8, price: u64, quantity: u64) -> bool {
+        let level = SnapshotLevel { price: price.to_le(), quantity: quantity.to_le() };
+        let (levels, count) = match side {
+            0 => (&mut self.bids, &mut self.bid_count),
+            _ => (&mut self.asks, &mut self.ask_count),
+        };
+        let Some(slot) = levels.get_mut(*count as usize) else {
+            return false;
+        };
+        *slot = level;
+        *count += 1;
+        true
+    }
+    
+    /// Populated bid levels, best first.
+    pub fn bid_levels(&self) -> &[SnapshotLevel] {
+        let bids = &self.bids;
+        &bids[..(self.bid_count as usize).min(SNAPSHOT_DEPTH)]
+    }
+    
+    /// Populated ask levels, best first.
+    pub fn ask_levels(&self) -> &[SnapshotLevel] {
+        let asks = &self.asks;
+        &asks[..(self.ask_count as usize).min(SNAPSHOT_DEPTH)]
+    }
+}
+
+/// Snapshot start message (24 bytes).
+///
+/// Opens a full-depth book image of `symbol_id` as of book update
+/// `update_seq`, sent as `level_count` `SnapshotLevelMessage`s and closed
+/// by a `SnapshotEndMessage`. Unlike `BookSnapshotMessage` it is not
+/// limited to `SNAPSHOT_DEPTH` levels.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct SnapshotStartMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub level_count: u32,           // 4 bytes
+    pub update_seq: u64,            // 8 bytes
+}
+
+const _: () = assert!(size_of::<SnapshotStartMessage>() == 24);
+
+unsafe impl Pod for SnapshotStartMessage {}
+unsafe impl Zeroable for SnapshotStartMessage {}
+
+wire_fields!(SnapshotStartMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    level_count: u32 => get_level_count, set_level_count;
+    update_seq: u64 => get_update_seq, set_update_seq;
+});
+
+impl SnapshotStartMessage {
+    pub fn new(sequence: u32, symbol_id: u32, update_seq: u64, level_count: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::SnapshotStart as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            level_count: level_count.to_le(),
+            update_seq: update_seq.to_le(),
+        }
+    }
+}
+
+/// Snapshot level message (32 bytes): one level of the image opened by
+/// `SnapshotStartMessage`.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct SnapshotLevelMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub side: u8,                   // 1 byte
+    pub _padding: [u8; 3],          // 3 bytes
+    pub price: u64,                 // 8 bytes
+    pub quantity: u64,              // 8 bytes
+}
+
+const _: () = assert!(size_of::<SnapshotLevelMessage>() == 32);
+
+unsafe impl Pod for SnapshotLevelMessage {}
+unsafe impl Zeroable for SnapshotLevelMessage {}
+
+wire_fields!(SnapshotLevelMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    price: u64 => get_price, set_price;
+    quantity: u64 => get_quantity, set_quantity;
+});
+
+impl SnapshotLevelMessage {
+    pub fn new(sequence: u32, symbol_id: u32, side: u8, price: u64, quantity: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::SnapshotLevel as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            side,
+            _padding: [0; 3],
+            price: price.to_le(),
+            quantity: quantity.to_le(),
+        }
+    }
+}
+
+/// Snapshot end message (32 bytes).
+///
+/// Closes the image: a receiver that got `level_count` levels whose
+/// `SnapshotChecksum` equals `checksum` holds the book as of `update_seq`
+/// and applies book updates from `update_seq + 1`.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct SnapshotEndMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub level_count: u32,           // 4 bytes
+    pub update_seq: u64,            // 8 bytes
+    pub checksum: u64,              // 8 bytes
+}
+
+const _: () = assert!(size_of::<SnapshotEndMessage>() == 32);
+
+unsafe impl Pod for SnapshotEndMessage {}
+unsafe impl Zeroable for SnapshotEndMessage {}
+
+wire_fields!(SnapshotEndMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    level_count: u32 => get_level_count, set_level_count;
+    update_seq: u64 => get_update_seq, set_update_seq;
+    checksum: u64 => get_checksum, set_checksum;
+});
+
+impl SnapshotEndMessage {
+    pub fn new(sequence: u32, symbol_id: u32, update_seq: u64, level_count: u32, checksum: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::SnapshotEnd as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            level_count: level_count.to_le(),
+            update_seq: update_seq.to_le(),
+            checksum: checksum.to_le(),
+        }
+    }
+}
+
+/// Trading state of a symbol in a `TradingStatusMessage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TradingState {
+    /// Call auction: orders rest without matching until the uncross.
+    PreOpen = 0,
+    /// Continuous trading.
+    Open = 1,
+    /// Trading suspended; orders are not accepted.
+    Halted = 2,
+    /// Session over.
+    Closed = 3,
+}
+
+impl TryFrom<u8> for TradingState {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(TradingState::PreOpen),
+            1 => Ok(TradingState::Open),
+            2 => Ok(TradingState::Halted),
+            3 => Ok(TradingState::Closed),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Trading Status message (32 bytes), published when a symbol changes
+/// trading state.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct TradingStatusMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub state: u8,                  // 1 byte (TradingState)
+    pub _padding: u8,               // 1 byte
+    pub reason: u16,                // 2 bytes (venue-defined, 0 = scheduled)
+    pub timestamp: u64,             // 8 bytes
+    pub _reserved: [u8; 8],         // 8 bytes
+}
+
+const _: () = assert!(size_of::<TradingStatusMessage>() == 32);
+
+unsafe impl Pod for TradingStatusMessage {}
+unsafe impl Zeroable for TradingStatusMessage {}
+
+wire_fields!(TradingStatusMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    reason: u16 => get_reason, set_reason;
+    timestamp: u64 => get_timestamp, set_timestamp;
+});
+
+impl TradingStatusMessage {
+    pub fn new(sequence: u32, symbol_id: u32, state: TradingState, reason: u16, timestamp: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::TradingStatus as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            state: state as u8,
+            _padding: 0,
+            reason: reason.to_le(),
+            timestamp: timestamp.to_le(),
+            _reserved: [0; 8],
+        }
+    }
+    
+    /// Decoded state (`None` if unknown).
+    pub fn trading_state(&self) -> Option<TradingState> {
+        TradingState::try_from(self.state).ok()
+    }
+}
+
+/// Running checksum of snapshot levels, in the order they are sent.
+///
+/// FNV-1a (64-bit) over each level's side byte, then price and quantity
+/// as little-endian `u64`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotChecksum(u64);
+
+impl SnapshotChecksum {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    
+    /// Checksum of no levels.
+    pub const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+    
+    /// Fold in the next level.
+    pub fn add(&mut self, side: u8, price: u64, quantity: u64) {
+        let mut hash = self.0;
+        let bytes = core::iter::once(side).chain(price.to_le_bytes()).chain(quantity.to_le_bytes());
+        for byte in bytes {
+            hash = (hash ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+        self.0 = hash;
+    }
+    
+    /// Checksum so far.
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for SnapshotChecksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wire protocol version spoken by this build.
+///
+/// Bumped whenever a message layout changes; a peer logging on with any
+/// other version is turned away rather than mis-parsed.
+///
+/// Version 2: `ExecutionReport` carries the client order ID.
+pub const PROTOCOL_VERSION: u16 = 2;
+
+/// Directory Request message (24 bytes), answered with one
+/// `InstrumentDefinitionMessage` per symbol.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct DirectoryRequestMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub request_id: u32,            // 4 bytes (echoed in the definitions)
+    pub symbol_id: u32,             // 4 bytes (0 = all symbols)
+    pub _reserved: [u8; 8],         // 8 bytes
+}
+
+const _: () = assert!(size_of::<DirectoryRequestMessage>() == 24);
+
+unsafe impl Pod for DirectoryRequestMessage {}
+unsafe impl Zeroable for DirectoryRequestMessage {}
+
+wire_fields!(DirectoryRequestMessage {
+    request_id: u32 => get_request_id, set_request_id;
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+});
+
+impl DirectoryRequestMessage {
+    pub fn new(sequence: u32, request_id: u32, symbol_id: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::DirectoryRequest as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            request_id: request_id.to_le(),
+            symbol_id: symbol_id.to_le(),
+            _reserved: [0; 8],
+        }
+    }
+}
+
+/// Trading status of an instrument in an `InstrumentDefinitionMessage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InstrumentStatus {
+    /// Accepting orders.
+    Active = 0,
+    /// Temporarily not trading.
+    Halted = 1,
+    /// Listed but not tradable.
+    Inactive = 2,
+}
+
+impl TryFrom<u8> for InstrumentStatus {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(InstrumentStatus::Active),
+            1 => Ok(InstrumentStatus::Halted),
+            2 => Ok(InstrumentStatus::Inactive),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Instrument Definition message (64 bytes).
+///
+/// Reference data for one symbol, sent in answer to a
+/// `DirectoryRequestMessage` (`request_id` echoed, `remaining` counting
+/// down to 0 on the last definition) or published on the feed
+/// (`request_id` 0).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct InstrumentDefinitionMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub symbol_id: u32,             // 4 bytes
+    pub request_id: u32,            // 4 bytes
+    pub tick_size: u64,             // 8 bytes (fixed-point)
+    pub lot_size: u64,              // 8 bytes
+    pub remaining: u32,             // 4 bytes (definitions still to come)
+    pub status: u8,                 // 1 byte (InstrumentStatus)
+    pub _padding: [u8; 3],          // 3 bytes
+    pub symbol: [u8; 16],           // 16 bytes (UTF-8, zero-padded)
+    pub _reserved: [u8; 8],         // 8 bytes
+}
+
+const _: () = assert!(size_of::<InstrumentDefinitionMessage>() == 64);
+
+unsafe impl Pod for InstrumentDefinitionMessage {}
+unsafe impl Zeroable for InstrumentDefinitionMessage {}
+
+wire_fields!(InstrumentDefinitionMessage {
+    symbol_id: u32 => get_symbol_id, set_symbol_id;
+    request_id: u32 => get_request_id, set_request_id;
+    tick_size: u64 => get_tick_size, set_tick_size;
+    lot_size: u64 => get_lot_size, set_lot_size;
+    remaining: u32 => get_remaining, set_remaining;
+});
+
+impl InstrumentDefinitionMessage {
+    /// Definition of `symbol` (cut to 16 bytes).
+    pub fn new(
+        sequence: u32,
+        symbol_id: u32,
+        symbol: &str,
+        tick_size: u64,
+        lot_size: u64,
+        status: InstrumentStatus,
+    ) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::InstrumentDefinition as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            symbol_id: symbol_id.to_le(),
+            request_id: 0,
+            tick_size: tick_size.to_le(),
+            lot_size: lot_size.to_le(),
+            remaining: 0,
+            status: status as u8,
+            _padding: [0; 3],
+            symbol: encode_padded(symbol),
+            _reserved: [0; 8],
+        }
+    }
+    
+    /// Answer to request `request_id` with `remaining` definitions after
+    /// this one.
+    pub fn in_response(mut self, request_id: u32, remaining: u32) -> Self {
+        self.set_request_id(request_id);
+        self.set_remaining(remaining);
+        self
+    }
+    
+    /// Symbol without its zero padding.
+    pub fn symbol_str(&self) -> &str {
+        padded_str(&self.symbol)
+    }
+    
+    /// Decoded status (`None` if unknown).
+    pub fn instrument_status(&self) -> Option<InstrumentStatus> {
+        InstrumentStatus::try_from(self.status).ok()
+    }
+}
+
+/// Outcome of a logon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LogonStatus {
+    /// Session established.
+    Accepted = 0,
+    /// Client protocol version not supported; `protocol_version` of the
+    /// ack carries the server's.
+    VersionMismatch = 1,
+    /// Credentials refused.
+    Rejected = 2,
+}
+
+impl TryFrom<u8> for LogonStatus {
+    type Error = ();
+    
+    fn try_from(value: u8) -> Result<Self, ()> {
+        match value {
+            0 => Ok(LogonStatus::Accepted),
+            1 => Ok(LogonStatus::VersionMismatch),
+            2 => Ok(LogonStatus::Rejected),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Logon message (40 bytes), the first message of a session.
+///
+/// `heartbeat_ms` is the interval the client asks for; the ack returns
+/// the one granted.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct LogonMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub protocol_version: u16,      // 2 bytes
+    pub _padding: u16,              // 2 bytes
+    pub heartbeat_ms: u32,          // 4 bytes
+    pub session_id: u64,            // 8 bytes
+    pub credentials: [u8; 16],      // 16 bytes
+}
+
+const _: () = assert!(size_of::<LogonMessage>() == 40);
+
+unsafe impl Pod for LogonMessage {}
+unsafe impl Zeroable for LogonMessage {}
+
+wire_fields!(LogonMessage {
+    protocol_version: u16 => get_protocol_version, set_protocol_version;
+    heartbeat_ms: u32 => get_heartbeat_ms, set_heartbeat_ms;
+    session_id: u64 => get_session_id, set_session_id;
+});
+
+impl LogonMessage {
+    /// Logon speaking `PROTOCOL_VERSION`.
+    pub fn new(sequence: u32, session_id: u64, credentials: [u8; 16], heartbeat_ms: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::Logon as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            protocol_version: PROTOCOL_VERSION.to_le(),
+            _padding: 0,
+            heartbeat_ms: heartbeat_ms.to_le(),
+            session_id: session_id.to_le(),
+            credentials,
+        }
+    }
+    
+    /// Whether this build can parse the client's messages.
+    pub fn is_supported_version(&self) -> bool {
+        self.get_protocol_version() == PROTOCOL_VERSION
+    }
+}
+
+/// Logon acknowledgement (24 bytes).
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct LogonAckMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub protocol_version: u16,      // 2 bytes
+    pub status: u8,                 // 1 byte
+    pub _padding: u8,               // 1 byte
+    pub heartbeat_ms: u32,          // 4 bytes
+    pub session_id: u64,            // 8 bytes
+}
+
+const _: () = assert!(size_of::<LogonAckMessage>() == 24);
+
+unsafe impl Pod for LogonAckMessage {}
+unsafe impl Zeroable for LogonAckMessage {}
+
+wire_fields!(LogonAckMessage {
+    protocol_version: u16 => get_protocol_version, set_protocol_version;
+    heartbeat_ms: u32 => get_heartbeat_ms, set_heartbeat_ms;
+    session_id: u64 => get_session_id, set_session_id;
+});
+
+impl LogonAckMessage {
+    pub fn new(sequence: u32, session_id: u64, status: LogonStatus, heartbeat_ms: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::LogonAck as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            protocol_version: PROTOCOL_VERSION.to_le(),
+            status: status as u8,
+            _padding: 0,
+            heartbeat_ms: heartbeat_ms.to_le(),
+            session_id: session_id.to_le(),
+        }
+    }
+    
+    /// Decoded status (`None` if unknown).
+    pub fn logon_status(&self) -> Option<LogonStatus> {
+        LogonStatus::try_from(self.status).ok()
+    }
+}
+
+/// Test request / test response message (16 bytes).
+///
+/// A peer that has heard nothing for a heartbeat interval sends a
+/// `TestRequest`; the other side must answer with a `TestResponse`
+/// echoing `test_id`, or be considered gone.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct TestRequestMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub test_id: u64,               // 8 bytes
+}
+
+const _: () = assert!(size_of::<TestRequestMessage>() == 16);
+
+unsafe impl Pod for TestRequestMessage {}
+unsafe impl Zeroable for TestRequestMessage {}
+
+wire_fields!(TestRequestMessage {
+    test_id: u64 => get_test_id, set_test_id;
+});
+
+impl TestRequestMessage {
+    pub fn new(sequence: u32, test_id: u64) -> Self {
+        Self::with_type(MessageType::TestRequest, sequence, test_id)
+    }
+    
+    /// Response to this request.
+    pub fn response(&self, sequence: u32) -> Self {
+        Self::with_type(MessageType::TestResponse, sequence, self.get_test_id())
+    }
+    
+    fn with_type(msg_type: MessageType, sequence: u32, test_id: u64) -> Self {
+        Self {
+            header: MessageHeader::new(
+                msg_type as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            test_id: test_id.to_le(),
+        }
+    }
+}
+
+/// Sequence reset message (16 bytes).
+///
+/// Tells the receiver the sender's next message carries `new_sequence`.
+/// With `gap_fill` set it stands in for messages the sender will not
+/// resend (header sequence = first of them), and `new_sequence` must move
+/// forward; without it the reset is unconditional. Either way the
+/// session stays up.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct SequenceResetMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub new_sequence: u32,          // 4 bytes
+    pub gap_fill: u8,               // 1 byte
+    pub _padding: [u8; 3],          // 3 bytes
+}
+
+const _: () = assert!(size_of::<SequenceResetMessage>() == 16);
+
+unsafe impl Pod for SequenceResetMessage {}
+unsafe impl Zeroable for SequenceResetMessage {}
+
+wire_fields!(SequenceResetMessage {
+    new_sequence: u32 => get_new_sequence, set_new_sequence;
+});
+
+impl SequenceResetMessage {
+    pub fn new(sequence: u32, new_sequence: u32, gap_fill: bool) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::SequenceReset as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            new_sequence: new_sequence.to_le(),
+            gap_fill: gap_fill as u8,
+            _padding: [0; 3],
+        }
+    }
+    
+    /// Check if this fills a gap rather than resetting outright.
+    pub fn is_gap_fill(&self) -> bool {
+        self.gap_fill != 0
+    }
+}
+
+/// Heartbeat message (24 bytes).
+///
+/// Sent by each side when it has sent nothing else for a heartbeat
+/// interval. `last_seen_sequence` is the highest sequence the sender has
+/// received, so the peer can spot lost messages without waiting for the
+/// next one.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct HeartbeatMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub timestamp: u64,             // 8 bytes (sender clock, nanoseconds)
+    pub last_seen_sequence: u32,    // 4 bytes
+    pub _padding: u32,              // 4 bytes
+}
+
+const _: () = assert!(size_of::<HeartbeatMessage>() == 24);
+
+unsafe impl Pod for HeartbeatMessage {}
+unsafe impl Zeroable for HeartbeatMessage {}
+
+wire_fields!(HeartbeatMessage {
+    timestamp: u64 => get_timestamp, set_timestamp;
+    last_seen_sequence: u32 => get_last_seen_sequence, set_last_seen_sequence;
+});
+
+impl HeartbeatMessage {
+    pub fn new(sequence: u32, timestamp: u64, last_seen_sequence: u32) -> Self {
+        Self {
+            header: MessageHeader::new(
+                MessageType::Heartbeat as u8,
+                (size_of::<Self>() - size_of::<MessageHeader>()) as u16,
+                sequence,
+            ),
+            timestamp: timestamp.to_le(),
+            last_seen_sequence: last_seen_sequence.to_le(),
+            _padding: 0,
+        }
+    }
+}
+
+/// Batch header (16 bytes), followed by `count` complete messages back to
+/// back. `header.length` covers the rest of this header and the messages,
+/// so a receiver that does not unpack batches skips them whole.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C, packed)]
+pub struct BatchMessage {
+    pub header: MessageHeader,      // 8 bytes
+    pub count: u32,                 // 4 bytes
+    pub _padding: u32,              // 4 bytes
+}
+
+const _: () = assert!(size_of::<BatchMessage>() == 16);
+
+unsafe impl Pod for BatchMessage {}
+unsafe impl Zeroable for BatchMessage {}
+
+wire_fields!(BatchMessage {
+    count: u32 => get_count, set_count;
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
     #[test]
     fn test_message_sizes() {
         assert_eq!(size_of::<MessageHeader>(), 8);
         assert_eq!(size_of::<NewOrderMessage>(), 64);
         assert_eq!(size_of::<CancelOrderMessage>(), 32);
-        assert_eq!(size_of::<ExecutionReport>(), 64);
+        assert_eq!(size_of::<ModifyOrderMessage>(), 48);
+        assert_eq!(size_of::<MassCancelMessage>(), 24);
+        assert_eq!(size_of::<OrderStatusRequestMessage>(), 24);
+        assert_eq!(size_of::<OrderStatusReportMessage>(), 48);
+        assert_eq!(size_of::<ExecutionReport>(), 80);
+        assert_eq!(size_of::<OrderRejectMessage>(), 24);
+        assert_eq!(size_of::<SystemErrorMessage>(), 16);
+        assert_eq!(size_of::<StatisticsMessage>(), 96);
+        assert_eq!(size_of::<BookUpdateMessage>(), 40);
+        assert_eq!(size_of::<BookSnapshotMessage>(), 344);
+        assert_eq!(size_of::<SnapshotStartMessage>(), 24);
+        assert_eq!(size_of::<SnapshotLevelMessage>(), 32);
+        assert_eq!(size_of::<SnapshotEndMessage>(), 32);
+        assert_eq!(size_of::<LogonMessage>(), 40);
+        assert_eq!(size_of::<LogonAckMessage>(), 24);
+        assert_eq!(size_of::<TestRequestMessage>(), 16);
+        assert_eq!(size_of::<SequenceResetMessage>(), 16);
+        assert_eq!(size_of::<HeartbeatMessage>(), 24);
+        assert_eq!(size_of::<DirectoryRequestMessage>(), 24);
+        assert_eq!(size_of::<InstrumentDefinitionMessage>(), 64);
+        assert_eq!(size_of::<TradingStatusMessage>(), 32);
+        assert_eq!(size_of::<BatchMessage>(), 16);
     }
     
     #[test]
@@ -299,5 +1912,36 @@ mod tests {
         assert_eq!(msg_type, MessageType::NewOrder as u8);
         assert_eq!(order_id, 12345);
         assert_eq!(symbol_id, 42);
+        
+        let display_qty = msg.with_display_qty(25).display_qty;
+        assert_eq!(display_qty, 25);
+        let participant_id = msg.with_participant(7).participant_id;
+        assert_eq!(participant_id, 7);
+    }
+    
+    #[test]
+    fn test_client_order_id_text() {
+        let msg = NewOrderMessage::new(1, 12345, 42, 0, 0, 10000, 100).with_client_order_id("ORD-1");
+        assert_eq!(&msg.client_order_id[..6], b"ORD-1\0");
+        assert_eq!(msg.client_order_id_str(), "ORD-1");
+        
+        // Cut to 16 bytes without splitting the two-byte 'é'
+        let long = msg.with_client_order_id("ABCDEFGHIJKLMNOé");
+        assert_eq!(long.client_order_id_str(), "ABCDEFGHIJKLMNO");
+        
+        let report = ExecutionReport::new_fill(1, 12345, 1, 42, 0, 10000, 100, 0, 0)
+            .with_client_order_id(msg.client_order_id);
+        assert_eq!(report.client_order_id_str(), "ORD-1");
+    }
+    
+    #[test]
+    fn test_fields_little_endian_on_wire() {
+        let mut msg = CancelOrderMessage::new(0x0102_0304, 0x1122_3344_5566_7788, 42);
+        msg.set_participant_id(0xAABB_CCDD);
+        let bytes = bytemuck::bytes_of(&msg);
+        assert_eq!(&bytes[4..8], &[0x04, 0x03, 0x02, 0x01]);
+        assert_eq!(&bytes[8..16], &[0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+        assert_eq!(&bytes[20..24], &[0xDD, 0xCC, 0xBB, 0xAA]);
+        assert_eq!((msg.header.get_sequence(), msg.get_order_id(), msg.get_participant_id()), (0x0102_0304, 0x1122_3344_5566_7788, 0xAABB_CCDD));
     }
 }
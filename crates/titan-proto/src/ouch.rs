@@ -0,0 +1,276 @@
+//! OUCH 4.2 order-entry messages.
+//!
+//! Lets connectivity stacks that already speak NASDAQ OUCH submit orders
+//! without adopting the native protocol. Covers the inbound Enter Order
+//! (`O`), Replace Order (`U`) and Cancel Order (`X`) messages, decoded and
+//! encoded in their big-endian wire form, and maps each onto the native
+//! message the engine consumes.
+//!
+//! OUCH identifies orders by a 14-byte client token and instruments by an
+//! 8-byte symbol; resolving those to Titan order and symbol ids is up to
+//! the session layer, which passes them in when mapping.
+
+use crate::itch::Fields;
+use crate::messages::{CancelOrderMessage, ModifyOrderMessage, NewOrderMessage};
+use crate::parser::ParseError;
+
+/// Time in force meaning immediate-or-cancel.
+pub const OUCH_TIF_IOC: u32 = 0;
+/// Time in force meaning until the end of the market session.
+pub const OUCH_TIF_MARKET_HOURS: u32 = 99_998;
+/// Time in force meaning until the end of the system day.
+pub const OUCH_TIF_SYSTEM_HOURS: u32 = 99_999;
+
+/// Enter Order (`O`, 49 bytes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OuchEnterOrder {
+    pub token: [u8; 14],
+    /// `B`, `S`, `T` (sell short) or `E` (sell short exempt).
+    pub side: u8,
+    pub shares: u32,
+    pub stock: [u8; 8],
+    pub price: u32,
+    pub time_in_force: u32,
+    pub firm: [u8; 4],
+    /// `Y` displayed, `N` hidden, `P` post-only, ...
+    pub display: u8,
+    pub capacity: u8,
+    pub intermarket_sweep: u8,
+    pub minimum_quantity: u32,
+    pub cross_type: u8,
+    pub customer_type: u8,
+}
+
+/// Replace Order (`U`, 47 bytes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OuchReplaceOrder {
+    pub existing_token: [u8; 14],
+    pub replacement_token: [u8; 14],
+    pub shares: u32,
+    pub price: u32,
+    pub time_in_force: u32,
+    pub display: u8,
+    pub intermarket_sweep: u8,
+    pub minimum_quantity: u32,
+}
+
+/// Cancel Order (`X`, 19 bytes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OuchCancelOrder {
+    pub token: [u8; 14],
+    /// Shares to leave open; 0 cancels the whole order.
+    pub shares: u32,
+}
+
+/// Decoded OUCH order-entry message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OuchMessage {
+    EnterOrder(OuchEnterOrder),
+    ReplaceOrder(OuchReplaceOrder),
+    CancelOrder(OuchCancelOrder),
+}
+
+impl OuchMessage {
+    /// Wire length of a message type (`None` if not an inbound message).
+    pub fn wire_len(msg_type: u8) -> Option<usize> {
+        match msg_type {
+            b'O' => Some(49),
+            b'U' => Some(47),
+            b'X' => Some(19),
+            _ => None,
+        }
+    }
+
+    /// Decode one message starting at its type byte.
+    ///
+    /// Returns the message and the bytes it took.
+    pub fn decode(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        let msg_type = *buffer.first().ok_or(ParseError::BufferTooSmall)?;
+        let len = Self::wire_len(msg_type).ok_or(ParseError::InvalidMessageType)?;
+        if buffer.len() < len {
+            return Err(ParseError::BufferTooSmall);
+        }
+
+        let mut f = Fields::new(buffer, 1);
+        let message = match msg_type {
+            b'O' => OuchMessage::EnterOrder(OuchEnterOrder {
+                token: f.take(),
+                side: f.u8(),
+                shares: f.u32(),
+                stock: f.take(),
+                price: f.u32(),
+                time_in_force: f.u32(),
+                firm: f.take(),
+                display: f.u8(),
+                capacity: f.u8(),
+                intermarket_sweep: f.u8(),
+                minimum_quantity: f.u32(),
+                cross_type: f.u8(),
+                customer_type: f.u8(),
+            }),
+            b'U' => OuchMessage::ReplaceOrder(OuchReplaceOrder {
+                existing_token: f.take(),
+                replacement_token: f.take(),
+                shares: f.u32(),
+                price: f.u32(),
+                time_in_force: f.u32(),
+                display: f.u8(),
+                intermarket_sweep: f.u8(),
+                minimum_quantity: f.u32(),
+            }),
+            _ => OuchMessage::CancelOrder(OuchCancelOrder {
+                token: f.take(),
+                shares: f.u32(),
+            }),
+        };
+        Ok((message, len))
+    }
+
+    /// Encode into `buffer`, returning the bytes written.
+    ///
+    /// # Panics
+    /// Panics if `buffer` is shorter than the message.
+    pub fn encode(&self, buffer: &mut [u8]) -> usize {
+        let mut w = Writer { bytes: buffer, pos: 0 };
+        match *self {
+            OuchMessage::EnterOrder(o) => {
+                w.put(b"O");
+                w.put(&o.token);
+                w.put(&[o.side]);
+                w.put(&o.shares.to_be_bytes());
+                w.put(&o.stock);
+                w.put(&o.price.to_be_bytes());
+                w.put(&o.time_in_force.to_be_bytes());
+                w.put(&o.firm);
+                w.put(&[o.display, o.capacity, o.intermarket_sweep]);
+                w.put(&o.minimum_quantity.to_be_bytes());
+                w.put(&[o.cross_type, o.customer_type]);
+            }
+            OuchMessage::ReplaceOrder(r) => {
+                w.put(b"U");
+                w.put(&r.existing_token);
+                w.put(&r.replacement_token);
+                w.put(&r.shares.to_be_bytes());
+                w.put(&r.price.to_be_bytes());
+                w.put(&r.time_in_force.to_be_bytes());
+                w.put(&[r.display, r.intermarket_sweep]);
+                w.put(&r.minimum_quantity.to_be_bytes());
+            }
+            OuchMessage::CancelOrder(c) => {
+                w.put(b"X");
+                w.put(&c.token);
+                w.put(&c.shares.to_be_bytes());
+            }
+        }
+        w.pos
+    }
+}
+
+/// Sequential writer for `encode`.
+struct Writer<'a> {
+    bytes: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn put(&mut self, data: &[u8]) {
+        self.bytes[self.pos..self.pos + data.len()].copy_from_slice(data);
+        self.pos += data.len();
+    }
+}
+
+impl OuchEnterOrder {
+    /// Native side (0 = Buy, 1 = Sell); all sell variants are sells.
+    pub fn native_side(&self) -> u8 {
+        if self.side == b'B' { 0 } else { 1 }
+    }
+
+    /// Native order type: IOC for time in force 0, Day for the market
+    /// session, PostOnly for post-only display, otherwise Limit.
+    pub fn native_order_type(&self) -> u8 {
+        match (self.time_in_force, self.display) {
+            (OUCH_TIF_IOC, _) => 1,
+            (_, b'P') => 3,
+            (OUCH_TIF_MARKET_HOURS, _) => 6,
+            _ => 0,
+        }
+    }
+
+    /// Map to the native new order, keeping the token as the client
+    /// order id.
+    pub fn to_new_order(&self, sequence: u32, order_id: u64, symbol_id: u32) -> NewOrderMessage {
+        let mut order = NewOrderMessage::new(
+            sequence,
+            order_id,
+            symbol_id,
+            self.native_side(),
+            self.native_order_type(),
+            self.price as u64,
+            self.shares as u64,
+        );
+        order.client_order_id[..14].copy_from_slice(&self.token);
+        order
+    }
+}
+
+impl OuchReplaceOrder {
+    /// Map to the native modify of the order `existing_token` names.
+    pub fn to_modify(&self, sequence: u32, order_id: u64, symbol_id: u32) -> ModifyOrderMessage {
+        ModifyOrderMessage::new(sequence, order_id, symbol_id, self.price as u64, self.shares as u64)
+    }
+}
+
+impl OuchCancelOrder {
+    /// Check if the whole order goes, rather than being reduced to
+    /// `shares`.
+    pub fn is_full_cancel(&self) -> bool {
+        self.shares == 0
+    }
+
+    /// Map to the native cancel, or `None` for a reduction, which the
+    /// session maps to a modify at the order's current price.
+    pub fn to_cancel(&self, sequence: u32, order_id: u64, symbol_id: u32) -> Option<CancelOrderMessage> {
+        self.is_full_cancel().then(|| CancelOrderMessage::new(sequence, order_id, symbol_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_native_mapping() {
+        let enter = OuchEnterOrder {
+            token: *b"ORD00000000001",
+            side: b'S',
+            shares: 300,
+            stock: *b"AAPL    ",
+            price: 1_890_500,
+            time_in_force: OUCH_TIF_MARKET_HOURS,
+            firm: *b"FIRM",
+            display: b'Y',
+            capacity: b'A',
+            intermarket_sweep: b'N',
+            minimum_quantity: 0,
+            cross_type: b'N',
+            customer_type: b'R',
+        };
+        let cancel = OuchCancelOrder { token: enter.token, shares: 0 };
+        let mut wire = [0u8; 80];
+        let len = OuchMessage::EnterOrder(enter).encode(&mut wire);
+        let cancel_len = OuchMessage::CancelOrder(cancel).encode(&mut wire[len..]);
+        assert_eq!((len, cancel_len), (49, 19));
+
+        assert_eq!(OuchMessage::decode(&wire).unwrap(), (OuchMessage::EnterOrder(enter), 49));
+        assert_eq!(OuchMessage::decode(&wire[49..]).unwrap(), (OuchMessage::CancelOrder(cancel), 19));
+        assert_eq!(OuchMessage::decode(&wire[..48]), Err(ParseError::BufferTooSmall));
+        assert_eq!(OuchMessage::decode(b"Q"), Err(ParseError::InvalidMessageType));
+
+        let order = enter.to_new_order(1, 77, 42);
+        let (order_id, price, quantity) = (order.order_id, order.price, order.quantity);
+        assert_eq!((order_id, order.side, order.order_type, price, quantity), (77, 1, 6, 1_890_500, 300));
+        assert_eq!(&order.client_order_id[..14], b"ORD00000000001");
+        assert!(cancel.to_cancel(2, 77, 42).is_some());
+        assert!(OuchCancelOrder { shares: 100, ..cancel }.to_cancel(2, 77, 42).is_none());
+    }
+}
@@ -2,8 +2,9 @@
 //!
 //! Uses bytemuck for safe transmutation from raw bytes.
 
-use bytemuck::try_from_bytes;
+use bytemuck::{pod_read_unaligned, try_from_bytes, Pod};
 use core::mem::size_of;
+use crate::crc::crc32c;
 use crate::messages::*;
 
 /// Parse error types.
@@ -17,8 +18,68 @@ pub enum ParseError {
     InvalidLength,
     /// Buffer is not properly aligned.
     MisalignedBuffer,
+    /// Checksum trailer does not match the message.
+    BadChecksum,
+    /// Text field is not valid UTF-8.
+    InvalidText,
 }
 
+/// Error from `MessageParser::validate_strict`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Message failed `validate_message`.
+    Parse(ParseError),
+    /// Side is not Buy (0) or Sell (1), or both for a mass cancel.
+    InvalidSide,
+    /// Order type is not one the engine knows.
+    InvalidOrderType,
+    /// Price is zero where the order needs one.
+    ZeroPrice,
+    /// Quantity is zero where the order needs one.
+    ZeroQuantity,
+    /// A reserved field or header flag bit is set.
+    ReservedNotZero,
+}
+
+impl From<ParseError> for ValidationError {
+    fn from(e: ParseError) -> Self {
+        ValidationError::Parse(e)
+    }
+}
+
+impl ValidationError {
+    /// Reason code for the `OrderRejectMessage` answering the message.
+    pub const fn reject_code(&self) -> u8 {
+        match self {
+            ValidationError::Parse(_) => 1,
+            ValidationError::InvalidSide => 2,
+            ValidationError::InvalidOrderType => 3,
+            ValidationError::ZeroPrice => 4,
+            ValidationError::ZeroQuantity => 5,
+            ValidationError::ReservedNotZero => 6,
+        }
+    }
+    
+    /// Reason in words for the reject text.
+    pub const fn description(&self) -> &'static str {
+        match self {
+            ValidationError::Parse(_) => "malformed message",
+            ValidationError::InvalidSide => "invalid side",
+            ValidationError::InvalidOrderType => "invalid order type",
+            ValidationError::ZeroPrice => "price must be non-zero",
+            ValidationError::ZeroQuantity => "quantity must be non-zero",
+            ValidationError::ReservedNotZero => "reserved field must be zero",
+        }
+    }
+}
+
+/// Highest valid `side` (Sell).
+const MAX_SIDE: u8 = 1;
+/// Highest valid `order_type` (Day).
+const MAX_ORDER_TYPE: u8 = 6;
+/// Market orders carry no price.
+const ORDER_TYPE_MARKET: u8 = 4;
+
 /// Zero-copy message parser.
 pub struct MessageParser;
 
@@ -56,6 +117,96 @@ impl MessageParser {
             .map_err(|_| ParseError::MisalignedBuffer)
     }
     
+    /// Parse a Modify message (zero-copy).
+    #[inline(always)]
+    pub fn parse_modify(buffer: &[u8]) -> Result<&ModifyOrderMessage, ParseError> {
+        if buffer.len() < size_of::<ModifyOrderMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<ModifyOrderMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a MassQuote message (zero-copy).
+    #[inline(always)]
+    pub fn parse_mass_quote(buffer: &[u8]) -> Result<&MassQuoteMessage, ParseError> {
+        if buffer.len() < size_of::<MassQuoteMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<MassQuoteMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a MassCancel message (zero-copy).
+    #[inline(always)]
+    pub fn parse_mass_cancel(buffer: &[u8]) -> Result<&MassCancelMessage, ParseError> {
+        if buffer.len() < size_of::<MassCancelMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<MassCancelMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse an OrderStatusRequest message (zero-copy).
+    #[inline(always)]
+    pub fn parse_order_status_request(buffer: &[u8]) -> Result<&OrderStatusRequestMessage, ParseError> {
+        if buffer.len() < size_of::<OrderStatusRequestMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<OrderStatusRequestMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse an OrderStatusReport message (zero-copy).
+    #[inline(always)]
+    pub fn parse_order_status_report(buffer: &[u8]) -> Result<&OrderStatusReportMessage, ParseError> {
+        if buffer.len() < size_of::<OrderStatusReportMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<OrderStatusReportMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse an OrderReject message and its text (zero-copy).
+    #[inline]
+    pub fn parse_order_reject(buffer: &[u8]) -> Result<(&OrderRejectMessage, &str), ParseError> {
+        let (fixed, text) = Self::split_text(buffer, size_of::<OrderRejectMessage>())?;
+        let reject: &OrderRejectMessage = try_from_bytes(fixed).map_err(|_| ParseError::MisalignedBuffer)?;
+        if reject.text_len as usize != text.len() {
+            return Err(ParseError::InvalidLength);
+        }
+        Ok((reject, text))
+    }
+    
+    /// Parse a SystemError message and its text (zero-copy).
+    #[inline]
+    pub fn parse_system_error(buffer: &[u8]) -> Result<(&SystemErrorMessage, &str), ParseError> {
+        let (fixed, text) = Self::split_text(buffer, size_of::<SystemErrorMessage>())?;
+        let error: &SystemErrorMessage = try_from_bytes(fixed).map_err(|_| ParseError::MisalignedBuffer)?;
+        if error.text_len as usize != text.len() {
+            return Err(ParseError::InvalidLength);
+        }
+        Ok((error, text))
+    }
+    
+    /// Split a text-carrying message into its `fixed`-byte prefix and the
+    /// text that `header.length` says follows it.
+    fn split_text(buffer: &[u8], fixed: usize) -> Result<(&[u8], &str), ParseError> {
+        let end = Self::parse_header(buffer)?.total_size();
+        if end < fixed || end - fixed > MAX_TEXT_LEN {
+            return Err(ParseError::InvalidLength);
+        }
+        let message = buffer.get(..end).ok_or(ParseError::BufferTooSmall)?;
+        let (fixed, text) = message.split_at(fixed);
+        let text = core::str::from_utf8(text).map_err(|_| ParseError::InvalidText)?;
+        Ok((fixed, text))
+    }
+    
     /// Parse an ExecutionReport (zero-copy).
     #[inline(always)]
     pub fn parse_execution_report(buffer: &[u8]) -> Result<&ExecutionReport, ParseError> {
@@ -67,7 +218,249 @@ impl MessageParser {
             .map_err(|_| ParseError::MisalignedBuffer)
     }
     
+    /// Parse a BookUpdate message (zero-copy).
+    #[inline(always)]
+    pub fn parse_book_update(buffer: &[u8]) -> Result<&BookUpdateMessage, ParseError> {
+        if buffer.len() < size_of::<BookUpdateMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<BookUpdateMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a BookSnapshot message (zero-copy).
+    #[inline(always)]
+    pub fn parse_book_snapshot(buffer: &[u8]) -> Result<&BookSnapshotMessage, ParseError> {
+        if buffer.len() < size_of::<BookSnapshotMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<BookSnapshotMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a SnapshotStart message (zero-copy).
+    #[inline(always)]
+    pub fn parse_snapshot_start(buffer: &[u8]) -> Result<&SnapshotStartMessage, ParseError> {
+        if buffer.len() < size_of::<SnapshotStartMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<SnapshotStartMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a SnapshotLevel message (zero-copy).
+    #[inline(always)]
+    pub fn parse_snapshot_level(buffer: &[u8]) -> Result<&SnapshotLevelMessage, ParseError> {
+        if buffer.len() < size_of::<SnapshotLevelMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<SnapshotLevelMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a SnapshotEnd message (zero-copy).
+    #[inline(always)]
+    pub fn parse_snapshot_end(buffer: &[u8]) -> Result<&SnapshotEndMessage, ParseError> {
+        if buffer.len() < size_of::<SnapshotEndMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<SnapshotEndMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a TradingStatus message (zero-copy).
+    #[inline(always)]
+    pub fn parse_trading_status(buffer: &[u8]) -> Result<&TradingStatusMessage, ParseError> {
+        if buffer.len() < size_of::<TradingStatusMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<TradingStatusMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a Logon message (zero-copy).
+    #[inline(always)]
+    pub fn parse_logon(buffer: &[u8]) -> Result<&LogonMessage, ParseError> {
+        if buffer.len() < size_of::<LogonMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<LogonMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a LogonAck message (zero-copy).
+    #[inline(always)]
+    pub fn parse_logon_ack(buffer: &[u8]) -> Result<&LogonAckMessage, ParseError> {
+        if buffer.len() < size_of::<LogonAckMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<LogonAckMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a TestRequest or TestResponse message (zero-copy).
+    #[inline(always)]
+    pub fn parse_test_request(buffer: &[u8]) -> Result<&TestRequestMessage, ParseError> {
+        if buffer.len() < size_of::<TestRequestMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<TestRequestMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a SequenceReset message (zero-copy).
+    #[inline(always)]
+    pub fn parse_sequence_reset(buffer: &[u8]) -> Result<&SequenceResetMessage, ParseError> {
+        if buffer.len() < size_of::<SequenceResetMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<SequenceResetMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a DirectoryRequest message (zero-copy).
+    #[inline(always)]
+    pub fn parse_directory_request(buffer: &[u8]) -> Result<&DirectoryRequestMessage, ParseError> {
+        if buffer.len() < size_of::<DirectoryRequestMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<DirectoryRequestMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse an InstrumentDefinition message (zero-copy).
+    #[inline(always)]
+    pub fn parse_instrument_definition(buffer: &[u8]) -> Result<&InstrumentDefinitionMessage, ParseError> {
+        if buffer.len() < size_of::<InstrumentDefinitionMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<InstrumentDefinitionMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Parse a Heartbeat message (zero-copy).
+    #[inline(always)]
+    pub fn parse_heartbeat(buffer: &[u8]) -> Result<&HeartbeatMessage, ParseError> {
+        if buffer.len() < size_of::<HeartbeatMessage>() {
+            return Err(ParseError::BufferTooSmall);
+        }
+        
+        try_from_bytes(&buffer[..size_of::<HeartbeatMessage>()])
+            .map_err(|_| ParseError::MisalignedBuffer)
+    }
+    
+    /// Copy a message of type `T` out of `buffer`, whatever its
+    /// alignment.
+    ///
+    /// Costs a copy of the message instead of borrowing it, but works at
+    /// any offset of a read buffer.
+    #[inline(always)]
+    pub fn parse_copy<T: Pod>(buffer: &[u8]) -> Result<T, ParseError> {
+        let bytes = buffer.get(..size_of::<T>()).ok_or(ParseError::BufferTooSmall)?;
+        Ok(pod_read_unaligned(bytes))
+    }
+    
+    /// Parse a NewOrder message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_new_order_copy(buffer: &[u8]) -> Result<NewOrderMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a CancelOrder message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_cancel_copy(buffer: &[u8]) -> Result<CancelOrderMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a ModifyOrder message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_modify_copy(buffer: &[u8]) -> Result<ModifyOrderMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a MassQuote message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_mass_quote_copy(buffer: &[u8]) -> Result<MassQuoteMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a MassCancel message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_mass_cancel_copy(buffer: &[u8]) -> Result<MassCancelMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse an OrderStatusRequest message into an owned copy (any
+    /// alignment).
+    #[inline(always)]
+    pub fn parse_order_status_request_copy(buffer: &[u8]) -> Result<OrderStatusRequestMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a DirectoryRequest message into an owned copy (any
+    /// alignment).
+    #[inline(always)]
+    pub fn parse_directory_request_copy(buffer: &[u8]) -> Result<DirectoryRequestMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Parse a Logon message into an owned copy (any alignment).
+    #[inline(always)]
+    pub fn parse_logon_copy(buffer: &[u8]) -> Result<LogonMessage, ParseError> {
+        Self::parse_copy(buffer)
+    }
+    
+    /// Size of a fixed-size message type (`None` if the header's
+    /// `length` decides).
+    #[inline]
+    pub fn message_size(msg_type: MessageType) -> Option<usize> {
+        let size = match msg_type {
+            MessageType::NewOrder => size_of::<NewOrderMessage>(),
+            MessageType::CancelOrder => size_of::<CancelOrderMessage>(),
+            MessageType::ModifyOrder => size_of::<ModifyOrderMessage>(),
+            MessageType::MassQuote => size_of::<MassQuoteMessage>(),
+            MessageType::MassCancel => size_of::<MassCancelMessage>(),
+            MessageType::OrderStatusRequest => size_of::<OrderStatusRequestMessage>(),
+            MessageType::OrderStatusReport => size_of::<OrderStatusReportMessage>(),
+            MessageType::ExecutionReport => size_of::<ExecutionReport>(),
+            MessageType::Quote => size_of::<QuoteMessage>(),
+            MessageType::Trade => size_of::<TradeMessage>(),
+            MessageType::Statistics => size_of::<StatisticsMessage>(),
+            MessageType::BookUpdate => size_of::<BookUpdateMessage>(),
+            MessageType::BookSnapshot => size_of::<BookSnapshotMessage>(),
+            MessageType::SnapshotStart => size_of::<SnapshotStartMessage>(),
+            MessageType::SnapshotLevel => size_of::<SnapshotLevelMessage>(),
+            MessageType::SnapshotEnd => size_of::<SnapshotEndMessage>(),
+            MessageType::Logon => size_of::<LogonMessage>(),
+            MessageType::LogonAck => size_of::<LogonAckMessage>(),
+            MessageType::TestRequest | MessageType::TestResponse => size_of::<TestRequestMessage>(),
+            MessageType::SequenceReset => size_of::<SequenceResetMessage>(),
+            MessageType::Heartbeat => size_of::<HeartbeatMessage>(),
+            MessageType::DirectoryRequest => size_of::<DirectoryRequestMessage>(),
+            MessageType::InstrumentDefinition => size_of::<InstrumentDefinitionMessage>(),
+            MessageType::TradingStatus => size_of::<TradingStatusMessage>(),
+            _ => return None,
+        };
+        Some(size)
+    }
+    
     /// Determine message type and validate length.
+    ///
+    /// Returns the frame length to consume: the message, plus its
+    /// checksum trailer if the header has `FLAG_CHECKSUM`, in which case
+    /// the checksum is verified too.
     #[inline]
     pub fn validate_message(buffer: &[u8]) -> Result<(MessageType, usize), ParseError> {
         let header = Self::parse_header(buffer)?;
@@ -78,23 +471,99 @@ impl MessageParser {
         let msg_type = MessageType::try_from(msg_type_byte)
             .map_err(|_| ParseError::InvalidMessageType)?;
         
-        // Copy length to avoid reference to packed struct
-        let header_length = header.length;
+        let header_length = header.get_length();
         
-        let expected_len = match msg_type {
-            MessageType::NewOrder => size_of::<NewOrderMessage>(),
-            MessageType::CancelOrder => size_of::<CancelOrderMessage>(),
-            MessageType::ExecutionReport => size_of::<ExecutionReport>(),
-            MessageType::Quote => size_of::<QuoteMessage>(),
-            MessageType::Trade => size_of::<TradeMessage>(),
-            _ => size_of::<MessageHeader>() + header_length as usize,
+        let full_len = size_of::<MessageHeader>() + header_length as usize;
+        let expected_len = match Self::message_size(msg_type) {
+            // Extensions make the message longer than its struct, never shorter
+            Some(fixed) if header.flags & FLAG_EXTENSIONS != 0 => {
+                if full_len < fixed {
+                    return Err(ParseError::InvalidLength);
+                }
+                full_len
+            }
+            Some(fixed) => fixed,
+            None => full_len,
+        };
+        
+        // Text messages: fixed prefix plus at most MAX_TEXT_LEN bytes
+        let text_prefix = match msg_type {
+            MessageType::OrderReject => Some(size_of::<OrderRejectMessage>()),
+            MessageType::SystemError => Some(size_of::<SystemErrorMessage>()),
+            _ => None,
         };
+        if text_prefix.is_some_and(|fixed| expected_len < fixed || expected_len - fixed > MAX_TEXT_LEN) {
+            return Err(ParseError::InvalidLength);
+        }
         
         if buffer.len() < expected_len {
             return Err(ParseError::BufferTooSmall);
         }
         
-        Ok((msg_type, expected_len))
+        if header.flags & FLAG_CHECKSUM == 0 {
+            return Ok((msg_type, expected_len));
+        }
+        let frame_len = expected_len + size_of::<u32>();
+        let Some(trailer) = buffer.get(expected_len..frame_len) else {
+            return Err(ParseError::BufferTooSmall);
+        };
+        if u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) != crc32c(&buffer[..expected_len]) {
+            return Err(ParseError::BadChecksum);
+        }
+        Ok((msg_type, frame_len))
+    }
+    
+    /// Validate a message like `validate_message`, then check the fields
+    /// of order-entry messages: sides and order types in range, price and
+    /// quantity non-zero where required, reserved fields and flag bits
+    /// zero.
+    ///
+    /// Meant for inbound traffic, so bad values are answered with a
+    /// reject instead of reaching the engine.
+    pub fn validate_strict(buffer: &[u8]) -> Result<(MessageType, usize), ValidationError> {
+        let (msg_type, len) = Self::validate_message(buffer)?;
+        if Self::parse_header(buffer)?.flags & !(FLAG_CHECKSUM | FLAG_EXTENSIONS) != 0 {
+            return Err(ValidationError::ReservedNotZero);
+        }
+        
+        let require = |ok: bool, error: ValidationError| if ok { Ok(()) } else { Err(error) };
+        match msg_type {
+            MessageType::NewOrder => {
+                let order = Self::parse_new_order_copy(buffer)?;
+                require(order.side <= MAX_SIDE, ValidationError::InvalidSide)?;
+                require(order.order_type <= MAX_ORDER_TYPE, ValidationError::InvalidOrderType)?;
+                require(order.get_price() != 0 || order.order_type == ORDER_TYPE_MARKET, ValidationError::ZeroPrice)?;
+                require(order.get_quantity() != 0, ValidationError::ZeroQuantity)?;
+                require(order._padding1 == 0, ValidationError::ReservedNotZero)?;
+            }
+            MessageType::CancelOrder => {
+                let cancel = Self::parse_cancel_copy(buffer)?;
+                require(cancel._reserved == [0; 8], ValidationError::ReservedNotZero)?;
+            }
+            MessageType::ModifyOrder => {
+                let modify = Self::parse_modify_copy(buffer)?;
+                require(modify.get_price() != 0, ValidationError::ZeroPrice)?;
+                require(modify.get_quantity() != 0, ValidationError::ZeroQuantity)?;
+                require(modify._reserved == [0; 8], ValidationError::ReservedNotZero)?;
+            }
+            MessageType::MassQuote => {
+                // A zero quantity pulls the side, so only quoted sides need a price
+                let quote = Self::parse_mass_quote_copy(buffer)?;
+                require(quote.get_bid_qty() == 0 || quote.get_bid_price() != 0, ValidationError::ZeroPrice)?;
+                require(quote.get_ask_qty() == 0 || quote.get_ask_price() != 0, ValidationError::ZeroPrice)?;
+            }
+            MessageType::DirectoryRequest => {
+                let request = Self::parse_directory_request_copy(buffer)?;
+                require(request._reserved == [0; 8], ValidationError::ReservedNotZero)?;
+            }
+            MessageType::MassCancel => {
+                let cancel = Self::parse_mass_cancel_copy(buffer)?;
+                require(cancel.side <= MASS_CANCEL_BOTH_SIDES, ValidationError::InvalidSide)?;
+                require(cancel._padding == [0; 7], ValidationError::ReservedNotZero)?;
+            }
+            _ => {}
+        }
+        Ok((msg_type, len))
     }
 }
 
@@ -102,6 +571,8 @@ impl MessageParser {
 pub struct MessageBuilder {
     sequence: u32,
     exec_id: u64,
+    /// Append a CRC32C trailer to every message.
+    checksums: bool,
 }
 
 impl MessageBuilder {
@@ -110,9 +581,37 @@ impl MessageBuilder {
         Self {
             sequence: 0,
             exec_id: 0,
+            checksums: false,
         }
     }
     
+    /// Turn checksum trailers on or off for the messages built from now
+    /// on (off by default). Each message then takes 4 more bytes.
+    pub fn set_checksums(&mut self, enabled: bool) {
+        self.checksums = enabled;
+    }
+    
+    /// Check if messages get checksum trailers.
+    #[inline]
+    pub fn checksums(&self) -> bool {
+        self.checksums
+    }
+    
+    /// Finish the `size`-byte message at the front of `buffer`: with
+    /// checksums on, flag it and append its CRC32C trailer.
+    ///
+    /// Returns the frame size. The `build_*` methods call this already.
+    #[inline]
+    pub fn seal(&self, buffer: &mut [u8], size: usize) -> usize {
+        if !self.checksums {
+            return size;
+        }
+        buffer[1] |= FLAG_CHECKSUM;
+        let crc = crc32c(&buffer[..size]);
+        buffer[size..size + 4].copy_from_slice(&crc.to_le_bytes());
+        size + 4
+    }
+    
     /// Get next sequence number.
     #[inline(always)]
     pub fn next_sequence(&mut self) -> u32 {
@@ -156,7 +655,7 @@ impl MessageBuilder {
         debug_assert!(buffer.len() >= size);
         
         buffer[..size].copy_from_slice(bytemuck::bytes_of(&report));
-        size
+        self.seal(buffer, size)
     }
     
     /// Build a quote message into a buffer.
@@ -182,7 +681,251 @@ impl MessageBuilder {
         
         let size = size_of::<QuoteMessage>();
         buffer[..size].copy_from_slice(bytemuck::bytes_of(&quote));
-        size
+        self.seal(buffer, size)
+    }
+    
+    /// Build a book update into a buffer, stamping the next sequence
+    /// number.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_book_update(
+        &mut self,
+        buffer: &mut [u8],
+        symbol_id: u32,
+        update_seq: u64,
+        side: u8,
+        action: BookAction,
+        price: u64,
+        quantity: u64,
+    ) -> usize {
+        let update = BookUpdateMessage::new(self.next_sequence(), symbol_id, update_seq, side, price, quantity)
+            .with_action(action);
+        
+        let size = size_of::<BookUpdateMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&update));
+        self.seal(buffer, size)
+    }
+    
+    /// Build the start of a full-depth snapshot into a buffer, stamping
+    /// the next sequence number.
+    #[inline]
+    pub fn build_snapshot_start(&mut self, buffer: &mut [u8], symbol_id: u32, update_seq: u64, level_count: u32) -> usize {
+        let start = SnapshotStartMessage::new(self.next_sequence(), symbol_id, update_seq, level_count);
+        
+        let size = size_of::<SnapshotStartMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&start));
+        self.seal(buffer, size)
+    }
+    
+    /// Build one snapshot level into a buffer, stamping the next sequence
+    /// number and folding the level into `checksum`.
+    #[inline]
+    pub fn build_snapshot_level(
+        &mut self,
+        buffer: &mut [u8],
+        checksum: &mut SnapshotChecksum,
+        symbol_id: u32,
+        side: u8,
+        price: u64,
+        quantity: u64,
+    ) -> usize {
+        checksum.add(side, price, quantity);
+        let level = SnapshotLevelMessage::new(self.next_sequence(), symbol_id, side, price, quantity);
+        
+        let size = size_of::<SnapshotLevelMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&level));
+        self.seal(buffer, size)
+    }
+    
+    /// Build the end of a snapshot into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    pub fn build_snapshot_end(
+        &mut self,
+        buffer: &mut [u8],
+        symbol_id: u32,
+        update_seq: u64,
+        level_count: u32,
+        checksum: SnapshotChecksum,
+    ) -> usize {
+        let end = SnapshotEndMessage::new(self.next_sequence(), symbol_id, update_seq, level_count, checksum.value());
+        
+        let size = size_of::<SnapshotEndMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&end));
+        self.seal(buffer, size)
+    }
+    
+    /// Build the reply to `logon` into a buffer, stamping the next sequence
+    /// number: `VersionMismatch` if its protocol version is not ours,
+    /// otherwise `status`.
+    #[inline]
+    pub fn build_logon_ack(
+        &mut self,
+        buffer: &mut [u8],
+        logon: &LogonMessage,
+        status: LogonStatus,
+        heartbeat_ms: u32,
+    ) -> usize {
+        let status = if logon.is_supported_version() { status } else { LogonStatus::VersionMismatch };
+        let ack = LogonAckMessage::new(self.next_sequence(), logon.get_session_id(), status, heartbeat_ms);
+        
+        let size = size_of::<LogonAckMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&ack));
+        self.seal(buffer, size)
+    }
+    
+    /// Build an order reject with a reason in words into a buffer,
+    /// stamping the next sequence number. Text beyond `MAX_TEXT_LEN`
+    /// bytes is cut at a character boundary.
+    #[inline]
+    pub fn build_order_reject(&mut self, buffer: &mut [u8], order_id: u64, symbol_id: u32, reason: u8, text: &str) -> usize {
+        let text = truncate_utf8(text, MAX_TEXT_LEN);
+        let reject = OrderRejectMessage::new(self.next_sequence(), order_id, symbol_id, reason, text.len() as u8);
+        
+        let fixed = size_of::<OrderRejectMessage>();
+        buffer[..fixed].copy_from_slice(bytemuck::bytes_of(&reject));
+        buffer[fixed..fixed + text.len()].copy_from_slice(text.as_bytes());
+        self.seal(buffer, fixed + text.len())
+    }
+    
+    /// Build a system error with text into a buffer, stamping the next
+    /// sequence number. Text beyond `MAX_TEXT_LEN` bytes is cut at a
+    /// character boundary.
+    #[inline]
+    pub fn build_system_error(&mut self, buffer: &mut [u8], code: SystemErrorCode, severity: Severity, text: &str) -> usize {
+        let text = truncate_utf8(text, MAX_TEXT_LEN);
+        let error = SystemErrorMessage::new(self.next_sequence(), code, severity, text.len() as u8);
+        
+        let fixed = size_of::<SystemErrorMessage>();
+        buffer[..fixed].copy_from_slice(bytemuck::bytes_of(&error));
+        buffer[fixed..fixed + text.len()].copy_from_slice(text.as_bytes());
+        self.seal(buffer, fixed + text.len())
+    }
+    
+    /// Build an order status report into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_order_status_report(
+        &mut self,
+        buffer: &mut [u8],
+        order_id: u64,
+        symbol_id: u32,
+        side: u8,
+        state: OrderState,
+        price: u64,
+        leaves_qty: u64,
+        cum_qty: u64,
+    ) -> usize {
+        let report = OrderStatusReportMessage::new(
+            self.next_sequence(),
+            order_id,
+            symbol_id,
+            side,
+            state,
+            price,
+            leaves_qty,
+            cum_qty,
+        );
+        
+        let size = size_of::<OrderStatusReportMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&report));
+        self.seal(buffer, size)
+    }
+    
+    /// Build a test request into a buffer, stamping the next sequence
+    /// number.
+    #[inline]
+    pub fn build_test_request(&mut self, buffer: &mut [u8], test_id: u64) -> usize {
+        let request = TestRequestMessage::new(self.next_sequence(), test_id);
+        
+        let size = size_of::<TestRequestMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&request));
+        self.seal(buffer, size)
+    }
+    
+    /// Build the answer to `request` into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    pub fn build_test_response(&mut self, buffer: &mut [u8], request: &TestRequestMessage) -> usize {
+        let response = request.response(self.next_sequence());
+        
+        let size = size_of::<TestRequestMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&response));
+        self.seal(buffer, size)
+    }
+    
+    /// Build a sequence reset into a buffer and continue numbering from
+    /// `new_sequence`.
+    ///
+    /// The reset itself takes the next sequence number, so as a gap fill
+    /// it covers the messages from there up to `new_sequence`.
+    #[inline]
+    pub fn build_sequence_reset(&mut self, buffer: &mut [u8], new_sequence: u32, gap_fill: bool) -> usize {
+        let reset = SequenceResetMessage::new(self.next_sequence(), new_sequence, gap_fill);
+        self.sequence = new_sequence.wrapping_sub(1);
+        
+        let size = size_of::<SequenceResetMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&reset));
+        self.seal(buffer, size)
+    }
+    
+    /// Build an instrument definition into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    pub fn build_instrument_definition(&mut self, buffer: &mut [u8], definition: InstrumentDefinitionMessage) -> usize {
+        let mut definition = definition;
+        definition.header.set_sequence(self.next_sequence());
+        
+        let size = size_of::<InstrumentDefinitionMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&definition));
+        self.seal(buffer, size)
+    }
+    
+    /// Build a trading status message into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    pub fn build_trading_status(
+        &mut self,
+        buffer: &mut [u8],
+        symbol_id: u32,
+        state: TradingState,
+        reason: u16,
+        timestamp: u64,
+    ) -> usize {
+        let status = TradingStatusMessage::new(self.next_sequence(), symbol_id, state, reason, timestamp);
+        
+        let size = size_of::<TradingStatusMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&status));
+        self.seal(buffer, size)
+    }
+    
+    /// Build a heartbeat into a buffer, stamping the next sequence number.
+    #[inline]
+    pub fn build_heartbeat(&mut self, buffer: &mut [u8], timestamp: u64, last_seen_sequence: u32) -> usize {
+        let heartbeat = HeartbeatMessage::new(self.next_sequence(), timestamp, last_seen_sequence);
+        
+        let size = size_of::<HeartbeatMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&heartbeat));
+        self.seal(buffer, size)
+    }
+    
+    /// Build a statistics message into a buffer, stamping the next
+    /// sequence number.
+    #[inline]
+    pub fn build_statistics(&mut self, buffer: &mut [u8], stats: StatisticsMessage) -> usize {
+        let stats = StatisticsMessage {
+            header: MessageHeader::new(
+                MessageType::Statistics as u8,
+                (size_of::<StatisticsMessage>() - size_of::<MessageHeader>()) as u16,
+                self.next_sequence(),
+            ),
+            ..stats
+        };
+        
+        let size = size_of::<StatisticsMessage>();
+        buffer[..size].copy_from_slice(bytemuck::bytes_of(&stats));
+        self.seal(buffer, size)
     }
 }
 
@@ -221,6 +964,323 @@ mod tests {
         assert_eq!(len, 64);
     }
     
+    #[test]
+    fn test_parse_modify() {
+        let msg = ModifyOrderMessage::new(7, 12345, 42, 10100, 50);
+        let bytes = bytemuck::bytes_of(&msg);
+        
+        let (msg_type, len) = MessageParser::validate_message(bytes).unwrap();
+        assert_eq!(msg_type, MessageType::ModifyOrder);
+        assert_eq!(len, 48);
+        
+        let parsed = MessageParser::parse_modify(bytes).unwrap();
+        let order_id = parsed.order_id;
+        let price = parsed.price;
+        let quantity = parsed.quantity;
+        assert_eq!((order_id, price, quantity), (12345, 10100, 50));
+    }
+    
+    #[test]
+    fn test_parse_mass_quote() {
+        let msg = MassQuoteMessage::new(3, 42, 77, 9900, 10, 78, 10100, 20).with_participant(5);
+        let bytes = bytemuck::bytes_of(&msg);
+        
+        let (msg_type, len) = MessageParser::validate_message(bytes).unwrap();
+        assert_eq!((msg_type, len), (MessageType::MassQuote, 64));
+        
+        let parsed = MessageParser::parse_mass_quote(bytes).unwrap();
+        let (bid_order_id, ask_order_id, participant_id) = (parsed.bid_order_id, parsed.ask_order_id, parsed.participant_id);
+        let (bid_price, bid_qty, ask_price, ask_qty) = (parsed.bid_price, parsed.bid_qty, parsed.ask_price, parsed.ask_qty);
+        assert_eq!((bid_order_id, ask_order_id, participant_id), (77, 78, 5));
+        assert_eq!((bid_price, bid_qty, ask_price, ask_qty), (9900, 10, 10100, 20));
+    }
+    
+    #[test]
+    fn test_build_statistics() {
+        let mut builder = MessageBuilder::new();
+        let mut buffer = [0u8; 128];
+        let stats = StatisticsMessage { volume: 500, trade_count: 3, ..StatisticsMessage::new(0, 42, 9) };
+        let size = builder.build_statistics(&mut buffer, stats);
+        
+        let (msg_type, len) = MessageParser::validate_message(&buffer[..size]).unwrap();
+        assert_eq!((msg_type, len), (MessageType::Statistics, 96));
+        let parsed: &StatisticsMessage = bytemuck::from_bytes(&buffer[..size]);
+        let (sequence, symbol_id, volume, timestamp) = (parsed.header.sequence, parsed.symbol_id, parsed.volume, parsed.timestamp);
+        assert_eq!((sequence, symbol_id, volume, timestamp), (1, 42, 500, 9));
+    }
+    
+    #[test]
+    fn test_build_book_update() {
+        let mut builder = MessageBuilder::new();
+        let mut buffer = [0u8; 64];
+        let size = builder.build_book_update(&mut buffer, 42, 17, 1, BookAction::New, 10100, 30);
+        
+        let (msg_type, len) = MessageParser::validate_message(&buffer[..size]).unwrap();
+        assert_eq!((msg_type, len), (MessageType::BookUpdate, 40));
+        let parsed = MessageParser::parse_book_update(&buffer[..size]).unwrap();
+        let (sequence, update_seq, side, price, quantity) = (parsed.header.sequence, parsed.update_seq, parsed.side, parsed.price, parsed.quantity);
+        assert_eq!((sequence, update_seq, side, price, quantity), (1, 17, 1, 10100, 30));
+        assert_eq!(parsed.book_action(), Some(BookAction::New));
+        assert_eq!(BookUpdateMessage::new(0, 42, 18, 1, 10100, 0).book_action(), Some(BookAction::Delete));
+    }
+    
+    #[test]
+    fn test_snapshot_sequence_round_trip() {
+        let mut builder = MessageBuilder::new();
+        let levels = [(0, 9900, 10), (0, 9800, 5), (1, 10100, 7)];
+        let mut wire = [0u8; 256];
+        let mut checksum = SnapshotChecksum::new();
+        let mut len = builder.build_snapshot_start(&mut wire, 42, 77, levels.len() as u32);
+        for (side, price, quantity) in levels {
+            len += builder.build_snapshot_level(&mut wire[len..], &mut checksum, 42, side, price, quantity);
+        }
+        len += builder.build_snapshot_end(&mut wire[len..], 42, 77, levels.len() as u32, checksum);
+        assert_eq!(len, 24 + 3 * 32 + 32);
+        
+        // Receiver side: walk the messages and recompute the checksum
+        let mut offset = 0;
+        let mut received = SnapshotChecksum::new();
+        let mut count = 0;
+        while offset < len {
+            let (msg_type, size) = MessageParser::validate_message(&wire[offset..len]).unwrap();
+            let msg = &wire[offset..offset + size];
+            match msg_type {
+                MessageType::SnapshotStart => {
+                    let start = MessageParser::parse_snapshot_start(msg).unwrap();
+                    let (update_seq, level_count) = (start.update_seq, start.level_count);
+                    assert_eq!((update_seq, level_count), (77, 3));
+                }
+                MessageType::SnapshotLevel => {
+                    let level = MessageParser::parse_snapshot_level(msg).unwrap();
+                    received.add(level.side, level.price, level.quantity);
+                    count += 1;
+                }
+                MessageType::SnapshotEnd => {
+                    let end = MessageParser::parse_snapshot_end(msg).unwrap();
+                    let (level_count, sum) = (end.level_count, end.checksum);
+                    assert_eq!((level_count, sum), (count, received.value()));
+                }
+                _ => unreachable!(),
+            }
+            offset += size;
+        }
+        
+        // Order matters
+        let mut swapped = SnapshotChecksum::new();
+        swapped.add(0, 9800, 5);
+        swapped.add(0, 9900, 10);
+        swapped.add(1, 10100, 7);
+        assert_ne!(swapped, received);
+    }
+    
+    #[test]
+    fn test_checksum_trailer_verified() {
+        let mut builder = MessageBuilder::new();
+        builder.set_checksums(true);
+        let mut buffer = [0u8; 64];
+        let size = builder.build_quote(&mut buffer, 42, 9900, 10100);
+        assert_eq!(size, size_of::<QuoteMessage>() + 4);
+        
+        let (msg_type, len) = MessageParser::validate_message(&buffer[..size]).unwrap();
+        assert_eq!((msg_type, len), (MessageType::Quote, size));
+        assert_eq!(MessageParser::validate_message(&buffer[..size - 1]), Err(ParseError::BufferTooSmall));
+        
+        // Flip a payload bit
+        buffer[20] ^= 0x10;
+        assert_eq!(MessageParser::validate_message(&buffer[..size]), Err(ParseError::BadChecksum));
+    }
+    
+    #[test]
+    fn test_logon_version_mismatch_rejected() {
+        let logon = LogonMessage::new(1, 99, *b"token-0123456789", 1000);
+        let mut wire = [0u8; 64];
+        wire[..40].copy_from_slice(bytemuck::bytes_of(&logon));
+        assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::Logon, 40));
+        let parsed = MessageParser::parse_logon(&wire).unwrap();
+        assert!(parsed.is_supported_version());
+        
+        let mut builder = MessageBuilder::new();
+        let mut reply = [0u8; 64];
+        let size = builder.build_logon_ack(&mut reply, parsed, LogonStatus::Accepted, 500);
+        let ack = MessageParser::parse_logon_ack(&reply[..size]).unwrap();
+        let (session_id, heartbeat_ms) = (ack.session_id, ack.heartbeat_ms);
+        assert_eq!((ack.logon_status(), session_id, heartbeat_ms), (Some(LogonStatus::Accepted), 99, 500));
+        
+        // A client from a future protocol revision
+        let newer = LogonMessage { protocol_version: PROTOCOL_VERSION + 1, ..logon };
+        builder.build_logon_ack(&mut reply, &newer, LogonStatus::Accepted, 500);
+        let ack = MessageParser::parse_logon_ack(&reply).unwrap();
+        let version = ack.protocol_version;
+        assert_eq!((ack.logon_status(), version), (Some(LogonStatus::VersionMismatch), PROTOCOL_VERSION));
+    }
+    
+    #[test]
+    fn test_session_liveness_and_gap_fill() {
+        let mut builder = MessageBuilder::new();
+        let mut wire = [0u8; 64];
+        let size = builder.build_test_request(&mut wire, 0xABCD);
+        assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::TestRequest, size));
+        let request = *MessageParser::parse_test_request(&wire).unwrap();
+        
+        let mut peer = MessageBuilder::new();
+        let size = peer.build_test_response(&mut wire, &request);
+        assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::TestResponse, size));
+        let test_id = MessageParser::parse_test_request(&wire).unwrap().test_id;
+        assert_eq!(test_id, 0xABCD);
+        
+        // Skip sequences 2..=4 rather than resend them
+        builder.build_sequence_reset(&mut wire, 5, true);
+        assert_eq!(MessageParser::validate_message(&wire).unwrap().0, MessageType::SequenceReset);
+        let reset = MessageParser::parse_sequence_reset(&wire).unwrap();
+        let (sequence, new_sequence) = (reset.header.sequence, reset.new_sequence);
+        assert_eq!((sequence, new_sequence, reset.is_gap_fill()), (2, 5, true));
+        assert_eq!(builder.next_sequence(), 5);
+    }
+    
+    #[test]
+    fn test_copy_parse_at_odd_offset() {
+        let order = NewOrderMessage::new(1, 12345, 42, 0, 0, 10000, 100);
+        let mut buffer = [0u8; 72];
+        buffer[3..67].copy_from_slice(bytemuck::bytes_of(&order));
+        
+        let copied = MessageParser::parse_new_order_copy(&buffer[3..]).unwrap();
+        assert_eq!(bytemuck::bytes_of(&copied), bytemuck::bytes_of(&order));
+        assert_eq!(copied.get_order_id(), 12345);
+        assert_eq!(MessageParser::parse_cancel_copy(&buffer[60..]).unwrap_err(), ParseError::BufferTooSmall);
+    }
+    
+    #[test]
+    fn test_order_status_round_trip() {
+        let request = OrderStatusRequestMessage::new(1, 555, 42).with_participant(7);
+        let bytes = bytemuck::bytes_of(&request);
+        assert_eq!(MessageParser::validate_message(bytes).unwrap(), (MessageType::OrderStatusRequest, 24));
+        let parsed = MessageParser::parse_order_status_request(bytes).unwrap();
+        assert_eq!((parsed.get_order_id(), parsed.get_participant_id()), (555, 7));
+        
+        let mut builder = MessageBuilder::new();
+        let mut buffer = [0u8; 64];
+        let size = builder.build_order_status_report(&mut buffer, 555, 42, 0, OrderState::PartiallyFilled, 10000, 60, 40);
+        assert_eq!(MessageParser::validate_message(&buffer).unwrap(), (MessageType::OrderStatusReport, size));
+        let report = MessageParser::parse_order_status_report(&buffer).unwrap();
+        assert_eq!(report.order_state(), Some(OrderState::PartiallyFilled));
+        assert_eq!((report.get_leaves_qty(), report.get_cum_qty()), (60, 40));
+    }
+    
+    #[test]
+    fn test_reject_text_round_trip() {
+        let mut builder = MessageBuilder::new();
+        let mut wire = [0u8; 256];
+        let mut len = builder.build_order_reject(&mut wire, 77, 42, 3, "price outside collar");
+        let long = "x".repeat(200);
+        len += builder.build_system_error(&mut wire[len..], SystemErrorCode::InternalError, Severity::Fatal, &long);
+        
+        // The parser skips each message whatever its text length
+        let (msg_type, size) = MessageParser::validate_message(&wire).unwrap();
+        assert_eq!((msg_type, size), (MessageType::OrderReject, 24 + 20));
+        let (reject, text) = MessageParser::parse_order_reject(&wire).unwrap();
+        assert_eq!((reject.get_order_id(), reject.reason, text), (77, 3, "price outside collar"));
+        
+        let (msg_type, rest) = MessageParser::validate_message(&wire[size..len]).unwrap();
+        assert_eq!((msg_type, rest), (MessageType::SystemError, 16 + MAX_TEXT_LEN));
+        let (error, text) = MessageParser::parse_system_error(&wire[size..]).unwrap();
+        assert_eq!((error.error_code(), error.severity(), text.len()), (Some(SystemErrorCode::InternalError), Some(Severity::Fatal), MAX_TEXT_LEN));
+        
+        // Not UTF-8
+        wire[24] = 0xFF;
+        assert_eq!(MessageParser::parse_order_reject(&wire).unwrap_err(), ParseError::InvalidText);
+    }
+    
+    #[test]
+    fn test_validate_strict() {
+        let order = NewOrderMessage::new(1, 10, 42, 0, 0, 10000, 100);
+        let checks = [
+            (order, Ok((MessageType::NewOrder, 64))),
+            (NewOrderMessage { side: 2, ..order }, Err(ValidationError::InvalidSide)),
+            (NewOrderMessage { order_type: 7, ..order }, Err(ValidationError::InvalidOrderType)),
+            (NewOrderMessage::new(1, 10, 42, 0, 4, 0, 100), Ok((MessageType::NewOrder, 64))),
+            (NewOrderMessage::new(1, 10, 42, 0, 0, 0, 100), Err(ValidationError::ZeroPrice)),
+            (NewOrderMessage::new(1, 10, 42, 0, 0, 10000, 0), Err(ValidationError::ZeroQuantity)),
+            (NewOrderMessage { _padding1: 1, ..order }, Err(ValidationError::ReservedNotZero)),
+        ];
+        for (message, expected) in checks {
+            assert_eq!(MessageParser::validate_strict(bytemuck::bytes_of(&message)), expected);
+        }
+        
+        let mut flagged = order;
+        flagged.header.flags = 0x80;
+        assert_eq!(MessageParser::validate_strict(bytemuck::bytes_of(&flagged)), Err(ValidationError::ReservedNotZero));
+        let cancel = MassCancelMessage::new(2, 42, 3);
+        assert_eq!(MessageParser::validate_strict(bytemuck::bytes_of(&cancel)), Err(ValidationError::InvalidSide));
+        let quote = MassQuoteMessage::new(3, 42, 1, 0, 0, 2, 0, 5);
+        assert_eq!(MessageParser::validate_strict(bytemuck::bytes_of(&quote)), Err(ValidationError::ZeroPrice));
+        assert_eq!(
+            MessageParser::validate_strict(&[0u8; 4]),
+            Err(ValidationError::Parse(ParseError::BufferTooSmall))
+        );
+    }
+    
+    #[test]
+    fn test_directory_round_trip() {
+        let request = DirectoryRequestMessage::new(1, 5, 0);
+        assert_eq!(
+            MessageParser::validate_strict(bytemuck::bytes_of(&request)).unwrap(),
+            (MessageType::DirectoryRequest, 24)
+        );
+        
+        let mut builder = MessageBuilder::new();
+        let mut wire = [0u8; 64];
+        let definition = InstrumentDefinitionMessage::new(0, 42, "AAPL", 100, 10, InstrumentStatus::Active);
+        let len = builder.build_instrument_definition(&mut wire, definition.in_response(5, 0));
+        assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::InstrumentDefinition, len));
+        
+        let parsed = MessageParser::parse_instrument_definition(&wire).unwrap();
+        assert_eq!((parsed.get_symbol_id(), parsed.symbol_str(), parsed.get_tick_size(), parsed.get_lot_size()), (42, "AAPL", 100, 10));
+        assert_eq!((parsed.get_request_id(), parsed.get_remaining(), parsed.header.get_sequence()), (5, 0, 1));
+        assert_eq!(parsed.instrument_status(), Some(InstrumentStatus::Active));
+    }
+    
+    #[test]
+    fn test_trading_status_round_trip() {
+        let mut builder = MessageBuilder::new();
+        let mut wire = [0u8; 32];
+        let len = builder.build_trading_status(&mut wire, 42, TradingState::Halted, 7, 1_000);
+        assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::TradingStatus, len));
+        
+        let status = MessageParser::parse_trading_status(&wire).unwrap();
+        assert_eq!((status.get_symbol_id(), status.trading_state(), status.get_reason(), status.get_timestamp()), (42, Some(TradingState::Halted), 7, 1_000));
+        wire[12] = 9;
+        assert_eq!(MessageParser::parse_trading_status(&wire).unwrap().trading_state(), None);
+    }
+    
+    #[test]
+    fn test_system_error_codes_round_trip() {
+        let codes = [
+            SystemErrorCode::EngineHalted,
+            SystemErrorCode::PoolExhausted,
+            SystemErrorCode::InternalError,
+            SystemErrorCode::Throttled,
+            SystemErrorCode::NotLoggedOn,
+        ];
+        let mut builder = MessageBuilder::new();
+        let mut wire = [0u8; 64];
+        for code in codes {
+            for severity in [Severity::Info, Severity::Warning, Severity::Fatal] {
+                let len = builder.build_system_error(&mut wire, code, severity, "halted");
+                assert_eq!(MessageParser::validate_message(&wire).unwrap(), (MessageType::SystemError, len));
+                let (error, text) = MessageParser::parse_system_error(&wire).unwrap();
+                assert_eq!((error.error_code(), error.severity(), text), (Some(code), Some(severity), "halted"));
+            }
+        }
+        
+        // Unknown codes and severities do not decode
+        assert_eq!(SystemErrorCode::try_from(0), Err(()));
+        assert_eq!(Severity::try_from(3), Err(()));
+        wire[8..10].copy_from_slice(&99u16.to_le_bytes());
+        wire[10] = 7;
+        let (error, _) = MessageParser::parse_system_error(&wire).unwrap();
+        assert_eq!((error.error_code(), error.severity()), (None, None));
+    }
+    
     #[test]
     fn test_buffer_too_small() {
         let buffer = [0u8; 4]; // Too small for header
@@ -0,0 +1,152 @@
+//! Simple Binary Encoding (SBE) framing of the native message set.
+//!
+//! Native payloads are already fixed-size little-endian blocks, which is
+//! what SBE puts after its message header. `encode` therefore swaps the
+//! native header for the standard SBE one (blockLength, templateId,
+//! schemaId, version) and `decode` swaps it back, so SBE decoders can read
+//! Titan feeds with a schema declaring each message type as a template
+//! whose id is its `MessageType` value.
+//!
+//! The sequence number lives in the native header only; SBE transports
+//! carry it in their packet header, so `decode` takes it as an argument.
+
+use core::mem::size_of;
+use crate::messages::{MessageHeader, MessageType, PROTOCOL_VERSION};
+use crate::parser::{MessageParser, ParseError};
+
+/// SBE schema id of the Titan message set.
+pub const SBE_SCHEMA_ID: u16 = 1;
+
+/// Size of the SBE message header.
+pub const SBE_HEADER_SIZE: usize = 8;
+
+/// Standard SBE message header (little-endian on the wire).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SbeHeader {
+    /// Root block size following the header.
+    pub block_length: u16,
+    /// Message template, the native `MessageType` value.
+    pub template_id: u16,
+    pub schema_id: u16,
+    /// Schema version, `PROTOCOL_VERSION` when encoded here.
+    pub version: u16,
+}
+
+impl SbeHeader {
+    /// Read a header from the front of `buffer`.
+    pub fn read(buffer: &[u8]) -> Result<Self, ParseError> {
+        let bytes = buffer.get(..SBE_HEADER_SIZE).ok_or(ParseError::BufferTooSmall)?;
+        let field = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        Ok(Self {
+            block_length: field(0),
+            template_id: field(2),
+            schema_id: field(4),
+            version: field(6),
+        })
+    }
+
+    /// Write the header to the front of `buffer`.
+    pub fn write(&self, buffer: &mut [u8]) {
+        let fields = [self.block_length, self.template_id, self.schema_id, self.version];
+        for (i, field) in fields.into_iter().enumerate() {
+            buffer[i * 2..i * 2 + 2].copy_from_slice(&field.to_le_bytes());
+        }
+    }
+
+    /// Size of the whole SBE message.
+    pub fn message_len(&self) -> usize {
+        SBE_HEADER_SIZE + self.block_length as usize
+    }
+}
+
+/// Re-encode the native message at the front of `message` as SBE into
+/// `out`. A checksum trailer is dropped.
+///
+/// Returns the SBE message size.
+pub fn encode(message: &[u8], out: &mut [u8]) -> Result<usize, ParseError> {
+    let (msg_type, _) = MessageParser::validate_message(message)?;
+    let block_length = MessageParser::parse_header(message)?.get_length();
+    let header = SbeHeader {
+        block_length,
+        template_id: msg_type as u16,
+        schema_id: SBE_SCHEMA_ID,
+        version: PROTOCOL_VERSION,
+    };
+    let len = header.message_len();
+    if out.len() < len {
+        return Err(ParseError::BufferTooSmall);
+    }
+
+    header.write(out);
+    out[SBE_HEADER_SIZE..len].copy_from_slice(&message[size_of::<MessageHeader>()..len]);
+    Ok(len)
+}
+
+/// Decode the SBE message at the front of `sbe` into a native message in
+/// `out`, stamped with `sequence`.
+///
+/// As SBE prescribes for schema evolution, a block longer than ours
+/// (newer version) is truncated and a shorter one (older version) is
+/// zero-extended. Returns the native type and size; the SBE message took
+/// `SbeHeader::message_len` bytes.
+pub fn decode(sbe: &[u8], sequence: u32, out: &mut [u8]) -> Result<(MessageType, usize), ParseError> {
+    let header = SbeHeader::read(sbe)?;
+    if header.schema_id != SBE_SCHEMA_ID {
+        return Err(ParseError::InvalidMessageType);
+    }
+    let msg_type = u8::try_from(header.template_id)
+        .ok()
+        .and_then(|t| MessageType::try_from(t).ok())
+        .ok_or(ParseError::InvalidMessageType)?;
+    let block = sbe.get(SBE_HEADER_SIZE..header.message_len()).ok_or(ParseError::BufferTooSmall)?;
+
+    let len = MessageParser::message_size(msg_type).unwrap_or(size_of::<MessageHeader>() + block.len());
+    if out.len() < len {
+        return Err(ParseError::BufferTooSmall);
+    }
+    let payload = &mut out[size_of::<MessageHeader>()..len];
+    let copied = block.len().min(payload.len());
+    payload[..copied].copy_from_slice(&block[..copied]);
+    payload[copied..].fill(0);
+
+    let native = MessageHeader::new(msg_type as u8, (len - size_of::<MessageHeader>()) as u16, sequence);
+    out[..size_of::<MessageHeader>()].copy_from_slice(bytemuck::bytes_of(&native));
+    Ok((msg_type, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::NewOrderMessage;
+
+    #[test]
+    fn test_round_trip_and_schema_evolution() {
+        let order = NewOrderMessage::new(9, 12345, 42, 1, 0, 10100, 300).with_display_qty(25);
+        let mut sbe = [0u8; 96];
+        let len = encode(bytemuck::bytes_of(&order), &mut sbe).unwrap();
+        assert_eq!(len, 64);
+        let header = SbeHeader::read(&sbe).unwrap();
+        assert_eq!(header, SbeHeader { block_length: 56, template_id: 0x01, schema_id: SBE_SCHEMA_ID, version: PROTOCOL_VERSION });
+
+        let mut native = [0u8; 64];
+        assert_eq!(decode(&sbe, 9, &mut native).unwrap(), (MessageType::NewOrder, 64));
+        assert_eq!(&native[..], bytemuck::bytes_of(&order));
+
+        // A newer sender appended a field: the extra bytes are skipped
+        let newer = SbeHeader { block_length: 60, ..header };
+        newer.write(&mut sbe);
+        assert_eq!(decode(&sbe, 9, &mut native).unwrap(), (MessageType::NewOrder, 64));
+        assert_eq!(&native[..], bytemuck::bytes_of(&order));
+
+        // An older sender lacks the trailing field: it reads as zero
+        let older = SbeHeader { block_length: 52, ..header };
+        older.write(&mut sbe);
+        decode(&sbe, 9, &mut native).unwrap();
+        let display_qty = MessageParser::parse_new_order(&native).unwrap().display_qty;
+        assert_eq!(display_qty, 0);
+
+        let foreign = SbeHeader { schema_id: 99, ..header };
+        foreign.write(&mut sbe);
+        assert_eq!(decode(&sbe, 9, &mut native), Err(ParseError::InvalidMessageType));
+    }
+}
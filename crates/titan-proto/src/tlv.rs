@@ -0,0 +1,185 @@
+//! TLV extension fields after fixed-size messages.
+//!
+//! A fixed-size message may be followed by extensions, each a
+//! little-endian `u16` tag, a `u16` value length and the value. The header
+//! flag `FLAG_EXTENSIONS` marks such a message and its `length` then
+//! covers the extensions, so readers that know neither the tag nor
+//! extensions at all skip them. Messages without the flag keep the
+//! fixed-size fast path.
+
+use core::mem::size_of;
+use crate::messages::{MessageHeader, FLAG_CHECKSUM, FLAG_EXTENSIONS};
+use crate::parser::{MessageParser, ParseError};
+
+/// Self-trade prevention group (`u32`): orders of one group never match
+/// each other.
+pub const TAG_STP_GROUP: u16 = 1;
+
+/// Expiry of a GTD order (`u64`, nanoseconds since the Unix epoch).
+pub const TAG_EXPIRY_TIME: u16 = 2;
+
+/// Size of an extension's tag and length.
+pub const TLV_HEADER_SIZE: usize = 4;
+
+/// One extension field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extension<'a> {
+    pub tag: u16,
+    pub value: &'a [u8],
+}
+
+impl Extension<'_> {
+    /// Value as a `u32`, if it is 4 bytes.
+    pub fn as_u32(&self) -> Option<u32> {
+        self.value.try_into().ok().map(u32::from_le_bytes)
+    }
+
+    /// Value as a `u64`, if it is 8 bytes.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.value.try_into().ok().map(u64::from_le_bytes)
+    }
+}
+
+/// Appends extensions to the message at the front of a buffer.
+///
+/// Extend before sealing: a checksum must cover the extensions.
+pub struct ExtensionWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> ExtensionWriter<'a> {
+    /// Start extending the unsealed message at the front of `buffer`.
+    pub fn new(buffer: &'a mut [u8]) -> Result<Self, ParseError> {
+        let header: MessageHeader = MessageParser::parse_copy(buffer)?;
+        if header.flags & FLAG_CHECKSUM != 0 {
+            return Err(ParseError::InvalidLength);
+        }
+        let len = header.total_size();
+        if buffer.len() < len {
+            return Err(ParseError::BufferTooSmall);
+        }
+        Ok(Self { buffer, len })
+    }
+
+    /// Append one extension. Returns `false` if it does not fit, leaving
+    /// the message as it was.
+    pub fn push(&mut self, tag: u16, value: &[u8]) -> bool {
+        let end = self.len + TLV_HEADER_SIZE + value.len();
+        if end > self.buffer.len() || value.len() > u16::MAX as usize || end - size_of::<MessageHeader>() > u16::MAX as usize {
+            return false;
+        }
+        self.buffer[self.len..self.len + 2].copy_from_slice(&tag.to_le_bytes());
+        self.buffer[self.len + 2..self.len + 4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+        self.buffer[self.len + TLV_HEADER_SIZE..end].copy_from_slice(value);
+        self.len = end;
+        true
+    }
+
+    /// Append a `u32` extension.
+    pub fn push_u32(&mut self, tag: u16, value: u32) -> bool {
+        self.push(tag, &value.to_le_bytes())
+    }
+
+    /// Append a `u64` extension.
+    pub fn push_u64(&mut self, tag: u16, value: u64) -> bool {
+        self.push(tag, &value.to_le_bytes())
+    }
+
+    /// Flag the message as extended, update its length and return the
+    /// message size.
+    pub fn finish(self) -> usize {
+        let mut header: MessageHeader = bytemuck::pod_read_unaligned(&self.buffer[..size_of::<MessageHeader>()]);
+        header.flags |= FLAG_EXTENSIONS;
+        header.set_length((self.len - size_of::<MessageHeader>()) as u16);
+        self.buffer[..size_of::<MessageHeader>()].copy_from_slice(bytemuck::bytes_of(&header));
+        self.len
+    }
+}
+
+/// Iterator over the extensions of a message.
+///
+/// A truncated extension ends the iteration with `InvalidLength`.
+pub struct Extensions<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Extensions<'a> {
+    /// Extensions of the message at the front of `buffer`; none if it
+    /// lacks `FLAG_EXTENSIONS`.
+    pub fn new(buffer: &'a [u8]) -> Result<Self, ParseError> {
+        let (msg_type, _) = MessageParser::validate_message(buffer)?;
+        let header: MessageHeader = MessageParser::parse_copy(buffer)?;
+        if header.flags & FLAG_EXTENSIONS == 0 {
+            return Ok(Self { bytes: &[] });
+        }
+        let fixed = MessageParser::message_size(msg_type).ok_or(ParseError::InvalidMessageType)?;
+        Ok(Self { bytes: &buffer[fixed..header.total_size()] })
+    }
+
+    /// First extension with `tag`.
+    pub fn find(self, tag: u16) -> Option<Extension<'a>> {
+        self.map_while(Result::ok).find(|extension| extension.tag == tag)
+    }
+}
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = Result<Extension<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let extension = self.bytes.get(..TLV_HEADER_SIZE).and_then(|tlv| {
+            let tag = u16::from_le_bytes([tlv[0], tlv[1]]);
+            let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+            let value = self.bytes.get(TLV_HEADER_SIZE..TLV_HEADER_SIZE + len)?;
+            Some(Extension { tag, value })
+        });
+        let Some(extension) = extension else {
+            self.bytes = &[];
+            return Some(Err(ParseError::InvalidLength));
+        };
+        self.bytes = &self.bytes[TLV_HEADER_SIZE + extension.value.len()..];
+        Some(Ok(extension))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{MessageType, NewOrderMessage};
+
+    #[test]
+    fn test_extensions_round_trip() {
+        let order = NewOrderMessage::new(1, 10, 42, 0, 5, 10000, 100);
+        let mut buffer = [0u8; 128];
+        buffer[..64].copy_from_slice(bytemuck::bytes_of(&order));
+        let mut writer = ExtensionWriter::new(&mut buffer).unwrap();
+        assert!(writer.push_u32(TAG_STP_GROUP, 7));
+        assert!(writer.push_u64(TAG_EXPIRY_TIME, 1_700_000_000_000_000_000));
+        assert!(writer.push(900, b"future"));
+        let size = writer.finish();
+        assert_eq!(size, 64 + 8 + 12 + 10);
+
+        // Framing covers the extensions; the fixed part parses as before
+        assert_eq!(MessageParser::validate_message(&buffer).unwrap(), (MessageType::NewOrder, size));
+        assert_eq!(MessageParser::parse_new_order_copy(&buffer).unwrap().get_order_id(), 10);
+        let extensions = || Extensions::new(&buffer).unwrap();
+        assert_eq!(extensions().find(TAG_STP_GROUP).and_then(|e| e.as_u32()), Some(7));
+        assert_eq!(extensions().find(TAG_EXPIRY_TIME).and_then(|e| e.as_u64()), Some(1_700_000_000_000_000_000));
+        assert_eq!(extensions().find(900).map(|e| e.value), Some(&b"future"[..]));
+        assert_eq!(extensions().count(), 3);
+
+        // A plain message has none
+        assert_eq!(Extensions::new(bytemuck::bytes_of(&order)).unwrap().next(), None);
+
+        // A value running past the message is an error
+        buffer[size - 8] = 200;
+        let mut extensions = Extensions::new(&buffer).unwrap();
+        assert!(extensions.next().unwrap().is_ok());
+        assert!(extensions.next().unwrap().is_ok());
+        assert_eq!(extensions.next(), Some(Err(ParseError::InvalidLength)));
+        assert_eq!(extensions.next(), None);
+    }
+}
@@ -44,6 +44,10 @@ struct Args {
     #[arg(long, default_value = "127.0.0.1:8080")]
     host: String,
     
+    /// Session ID to log on with (csv mode)
+    #[arg(long, default_value = "1")]
+    session_id: u64,
+    
     /// Rate limit in orders per second (0 = unlimited)
     #[arg(short, long, default_value = "0")]
     rate_limit: u64,
@@ -332,6 +336,13 @@ fn run_csv_replay(args: &Args) {
         }
     };
     
+    // The gateway drops connections that send orders before logging on
+    let logon = titan_proto::LogonMessage::new(0, args.session_id, [0; 16], 1000);
+    if let Err(e) = stream.write_all(bytemuck::bytes_of(&logon)) {
+        eprintln!("❌ Failed to log on: {}", e);
+        return;
+    }
+    
     let mut rate_limiter = RateLimiter::new(args.rate_limit);
     let mut latency = LatencyHistogram::new();
     let mut order_count = 0u64;
@@ -387,6 +398,7 @@ fn run_csv_replay(args: &Args) {
             "ioc" => 1,
             "fok" => 2,
             "post_only" => 3,
+            "market" => 4,
             _ => 0, // Default to limit
         };
         
@@ -0,0 +1,215 @@
+//! Async adapters over `SpscRing`.
+//!
+//! A ring built with the `AsyncWait` strategy can be driven from async
+//! code: `AsyncConsumer::recv` and `AsyncProducer::send` register the
+//! task's waker and return `Pending` instead of spinning, and the other
+//! side wakes it on its next publish or consume. Either side can stay a
+//! plain `Producer`/`Consumer` on a hot thread, so monitors and test
+//! harnesses sit on the same rings as the matching path. No runtime is
+//! assumed; any executor works.
+
+use core::future::poll_fn;
+use core::sync::atomic::{fence, AtomicBool, Ordering};
+use core::task::{Poll, Waker};
+use std::sync::Mutex;
+use crate::wait::WaitStrategy;
+use crate::{Consumer, Producer};
+
+/// Waker slot shared by a task and the side that wakes it.
+#[derive(Debug, Default)]
+struct AtomicWaker {
+    waker: Mutex<Option<Waker>>,
+    registered: AtomicBool,
+}
+
+impl AtomicWaker {
+    /// Store `waker` to be woken by the next `wake`. The caller must
+    /// re-check the ring afterwards.
+    fn register(&self, waker: &Waker) {
+        let mut slot = self.waker.lock().unwrap_or_else(|e| e.into_inner());
+        if !slot.as_ref().is_some_and(|old| old.will_wake(waker)) {
+            *slot = Some(waker.clone());
+        }
+        self.registered.store(true, Ordering::Relaxed);
+        // Pairs with the fence in `wake`: either the waker is seen there
+        // or the ring change is seen by the caller's re-check.
+        fence(Ordering::SeqCst);
+    }
+
+    /// Wake the registered task, if any.
+    #[inline]
+    fn wake(&self) {
+        fence(Ordering::SeqCst);
+        if self.registered.load(Ordering::Relaxed) && self.registered.swap(false, Ordering::Relaxed) {
+            let waker = self.waker.lock().unwrap_or_else(|e| e.into_inner()).take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Wait strategy of rings with async handles.
+///
+/// Each publish and consume pays a fence and an atomic load, plus a wakeup
+/// only while a task is waiting. A blocking `consume` busy waits.
+#[derive(Debug, Default)]
+pub struct AsyncWait {
+    /// Task waiting for entries.
+    consumer: AtomicWaker,
+    /// Task waiting for space.
+    producer: AtomicWaker,
+}
+
+impl WaitStrategy for AsyncWait {
+    #[inline(always)]
+    fn wait<F: Fn() -> bool>(&self, _spins: u32, _ready: F) {
+        core::hint::spin_loop();
+    }
+
+    #[inline]
+    fn signal(&self) {
+        self.consumer.wake();
+    }
+
+    #[inline]
+    fn signal_consumed(&self) {
+        self.producer.wake();
+    }
+}
+
+/// Async producer handle.
+pub struct AsyncProducer<'a, T: Copy, const N: usize> {
+    inner: Producer<'a, T, N, AsyncWait>,
+}
+
+impl<'a, T: Copy, const N: usize> AsyncProducer<'a, T, N> {
+    /// Wrap the producer of an `AsyncWait` ring.
+    pub fn new(inner: Producer<'a, T, N, AsyncWait>) -> Self {
+        Self { inner }
+    }
+
+    /// Publish a value, waiting for space without blocking the thread.
+    pub async fn send(&mut self, value: T) {
+        poll_fn(|cx| {
+            if self.inner.try_publish(value) {
+                return Poll::Ready(());
+            }
+            self.inner.ring.wait.producer.register(cx.waker());
+            if self.inner.try_publish(value) {
+                return Poll::Ready(());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Attempt to publish a value (see `Producer::try_publish`).
+    #[inline]
+    pub fn try_send(&mut self, value: T) -> bool {
+        self.inner.try_publish(value)
+    }
+
+    /// Unwrap the producer.
+    pub fn into_inner(self) -> Producer<'a, T, N, AsyncWait> {
+        self.inner
+    }
+}
+
+/// Async consumer handle.
+pub struct AsyncConsumer<'a, T: Copy, const N: usize> {
+    inner: Consumer<'a, T, N, AsyncWait>,
+}
+
+impl<'a, T: Copy, const N: usize> AsyncConsumer<'a, T, N> {
+    /// Wrap the consumer of an `AsyncWait` ring.
+    pub fn new(inner: Consumer<'a, T, N, AsyncWait>) -> Self {
+        Self { inner }
+    }
+
+    /// Consume a value, waiting for one without blocking the thread.
+    pub async fn recv(&mut self) -> T {
+        poll_fn(|cx| {
+            if let Some(value) = self.inner.try_consume() {
+                return Poll::Ready(value);
+            }
+            self.inner.ring.wait.consumer.register(cx.waker());
+            match self.inner.try_consume() {
+                Some(value) => Poll::Ready(value),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Attempt to consume a value (see `Consumer::try_consume`).
+    #[inline]
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.inner.try_consume()
+    }
+
+    /// Unwrap the consumer.
+    pub fn into_inner(self) -> Consumer<'a, T, N, AsyncWait> {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpscRing;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::Context;
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    /// Waker unparking the thread polling the future.
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_tasks_woken_across_threads() {
+        let mut ring: SpscRing<u64, 4, AsyncWait> = SpscRing::new();
+        let (producer, consumer) = ring.split();
+        let (mut producer, mut consumer) = (AsyncProducer::new(producer), AsyncConsumer::new(consumer));
+
+        thread::scope(|s| {
+            // The producer outruns the ring and waits for space
+            s.spawn(move || {
+                block_on(async {
+                    for i in 0..1000 {
+                        producer.send(i).await;
+                    }
+                })
+            });
+            let sum = block_on(async {
+                let mut sum = 0;
+                for _ in 0..1000 {
+                    sum += consumer.recv().await;
+                }
+                sum
+            });
+            assert_eq!(sum, 999 * 1000 / 2);
+        });
+        assert_eq!(consumer.try_recv(), None);
+    }
+}
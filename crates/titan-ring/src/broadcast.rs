@@ -0,0 +1,297 @@
+//! Broadcast (single-producer multi-consumer) ring buffer.
+//!
+//! Every consumer sees every entry: each has its own read cursor, and the
+//! producer may only reuse a slot once the slowest consumer has read it.
+//! One engine output ring can so feed the market data publisher, drop
+//! copy and journal threads without copying into a queue per reader.
+//!
+//! Consumers can also be chained into a pipeline (`with_dependencies`): a
+//! consumer gated on others only reads an entry once they all have, so
+//! e.g. journal -> match -> publish stages share one ring, each entry
+//! journaled before it is matched.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::Ordering;
+use crate::PaddedAtomicU64;
+
+/// Ring of `N` entries (power of 2) read by `C` consumers.
+#[repr(C)]
+pub struct BroadcastRing<T: Copy, const N: usize, const C: usize> {
+    /// Write cursor (owned by producer).
+    write_cursor: PaddedAtomicU64,
+
+    /// Slowest read cursor last seen by the producer.
+    cached_min_read: PaddedAtomicU64,
+
+    /// Read cursor of each consumer.
+    read_cursors: [PaddedAtomicU64; C],
+
+    /// Consumers each consumer must trail (bit j = consumer j).
+    gates: [u64; C],
+
+    /// The actual buffer.
+    buffer: UnsafeCell<[MaybeUninit<T>; N]>,
+}
+
+// SAFETY: One producer writes slots no consumer can still read; consumers
+// only read slots published with release ordering.
+unsafe impl<T: Copy + Send, const N: usize, const C: usize> Send for BroadcastRing<T, N, C> {}
+unsafe impl<T: Copy + Send, const N: usize, const C: usize> Sync for BroadcastRing<T, N, C> {}
+
+impl<T: Copy, const N: usize, const C: usize> BroadcastRing<T, N, C> {
+    const MASK: u64 = (N - 1) as u64;
+
+    /// Create a new ring buffer.
+    ///
+    /// # Panics
+    /// Panics if N is not a power of 2 or there are no consumers.
+    pub fn new() -> Self {
+        assert!(N.is_power_of_two(), "Buffer size must be power of 2");
+        assert!(C > 0, "At least one consumer required");
+
+        Self {
+            write_cursor: PaddedAtomicU64::new(0),
+            cached_min_read: PaddedAtomicU64::new(0),
+            read_cursors: core::array::from_fn(|_| PaddedAtomicU64::new(0)),
+            gates: [0; C],
+            buffer: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
+        }
+    }
+
+    /// Create a pipeline: consumer `i` only reads entries every consumer
+    /// in `dependencies[i]` has already consumed.
+    ///
+    /// # Panics
+    /// Panics as `new`, if there are more than 64 consumers, or if a
+    /// consumer depends on itself or a later one (no cycles).
+    pub fn with_dependencies(dependencies: [&[usize]; C]) -> Self {
+        assert!(C <= 64, "At most 64 pipeline stages");
+        let mut ring = Self::new();
+        for (id, deps) in dependencies.iter().enumerate() {
+            for &dep in deps.iter() {
+                assert!(dep < id, "Stage {} may only depend on earlier stages", id);
+                ring.gates[id] |= 1 << dep;
+            }
+        }
+        ring
+    }
+
+    /// Get buffer capacity.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Split into the producer and one handle per consumer.
+    pub fn split(&mut self) -> (BroadcastProducer<'_, T, N, C>, [BroadcastConsumer<'_, T, N, C>; C]) {
+        let ring = &*self;
+        (
+            BroadcastProducer { ring },
+            core::array::from_fn(|id| BroadcastConsumer { ring, id, cached_write: 0 }),
+        )
+    }
+
+    /// Position of the slowest consumer.
+    #[inline]
+    fn min_read(&self) -> u64 {
+        self.read_cursors
+            .iter()
+            .map(|cursor| cursor.value.load(Ordering::Acquire))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Position consumer `id` may read up to: the write cursor, held back
+    /// by the consumers it depends on.
+    #[inline]
+    fn read_limit(&self, id: usize) -> u64 {
+        let mut limit = self.write_cursor.value.load(Ordering::Acquire);
+        let mut gates = self.gates[id];
+        while gates != 0 {
+            let dep = gates.trailing_zeros() as usize;
+            limit = limit.min(self.read_cursors[dep].value.load(Ordering::Acquire));
+            gates &= gates - 1;
+        }
+        limit
+    }
+}
+
+impl<T: Copy, const N: usize, const C: usize> Default for BroadcastRing<T, N, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Producer handle (write-only).
+pub struct BroadcastProducer<'a, T: Copy, const N: usize, const C: usize> {
+    ring: &'a BroadcastRing<T, N, C>,
+}
+
+impl<'a, T: Copy, const N: usize, const C: usize> BroadcastProducer<'a, T, N, C> {
+    /// Attempt to publish a value to every consumer.
+    ///
+    /// Returns `false` if the slowest consumer is a full ring behind.
+    #[inline(always)]
+    pub fn try_publish(&mut self, value: T) -> bool {
+        let write_pos = self.ring.write_cursor.value.load(Ordering::Relaxed);
+
+        let cached_min = self.ring.cached_min_read.value.load(Ordering::Relaxed);
+        if write_pos - cached_min >= N as u64 {
+            let min_read = self.ring.min_read();
+            self.ring.cached_min_read.value.store(min_read, Ordering::Relaxed);
+
+            if write_pos - min_read >= N as u64 {
+                return false;
+            }
+        }
+
+        let idx = (write_pos & BroadcastRing::<T, N, C>::MASK) as usize;
+        unsafe {
+            let buffer = &mut *self.ring.buffer.get();
+            buffer[idx].write(value);
+        }
+
+        self.ring.write_cursor.value.store(write_pos + 1, Ordering::Release);
+
+        true
+    }
+
+    /// Publish a value, spinning until the slowest consumer makes room.
+    #[inline]
+    pub fn publish(&mut self, value: T) {
+        while !self.try_publish(value) {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Room left before the slowest consumer blocks publishing.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        let write_pos = self.ring.write_cursor.value.load(Ordering::Relaxed);
+        N - (write_pos - self.ring.min_read()) as usize
+    }
+}
+
+/// Handle of one consumer (read-only).
+pub struct BroadcastConsumer<'a, T: Copy, const N: usize, const C: usize> {
+    ring: &'a BroadcastRing<T, N, C>,
+    /// Index of this consumer's read cursor.
+    id: usize,
+    /// Read limit last seen (write position, or a dependency's cursor).
+    cached_write: u64,
+}
+
+impl<'a, T: Copy, const N: usize, const C: usize> BroadcastConsumer<'a, T, N, C> {
+    /// Index of this consumer (`0..C`).
+    #[inline(always)]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Attempt to consume the next value.
+    ///
+    /// Returns `None` if this consumer has read everything published (and
+    /// released by the consumers it depends on).
+    #[inline(always)]
+    pub fn try_consume(&mut self) -> Option<T> {
+        let cursor = &self.ring.read_cursors[self.id].value;
+        let read_pos = cursor.load(Ordering::Relaxed);
+
+        if read_pos >= self.cached_write {
+            self.cached_write = self.ring.read_limit(self.id);
+
+            if read_pos >= self.cached_write {
+                return None;
+            }
+        }
+
+        let idx = (read_pos & BroadcastRing::<T, N, C>::MASK) as usize;
+        let value = unsafe {
+            let buffer = &*self.ring.buffer.get();
+            buffer[idx].assume_init_read()
+        };
+
+        // Release the slot (for this consumer)
+        cursor.store(read_pos + 1, Ordering::Release);
+
+        Some(value)
+    }
+
+    /// Consume a value, spinning until one is available (BUSY WAIT).
+    #[inline(always)]
+    pub fn consume(&mut self) -> T {
+        loop {
+            if let Some(value) = self.try_consume() {
+                return value;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Number of entries this consumer can read now.
+    #[inline]
+    pub fn available(&self) -> usize {
+        let write_pos = self.ring.read_limit(self.id);
+        let read_pos = self.ring.read_cursors[self.id].value.load(Ordering::Relaxed);
+        (write_pos - read_pos) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_consumer_sees_every_entry() {
+        let mut ring: BroadcastRing<u64, 4, 3> = BroadcastRing::new();
+        let (mut producer, [mut feed, mut drop_copy, mut journal]) = ring.split();
+        assert_eq!(journal.id(), 2);
+
+        for i in 0..4 {
+            assert!(producer.try_publish(i));
+        }
+        // Full until the slowest consumer reads
+        assert!(!producer.try_publish(4));
+
+        for i in 0..4 {
+            assert_eq!(feed.try_consume(), Some(i));
+            assert_eq!(drop_copy.try_consume(), Some(i));
+        }
+        assert!(!producer.try_publish(4));
+        assert_eq!(journal.try_consume(), Some(0));
+        assert_eq!(producer.remaining_capacity(), 1);
+        assert!(producer.try_publish(4));
+
+        assert_eq!(feed.try_consume(), Some(4));
+        assert_eq!(feed.try_consume(), None);
+        assert_eq!(journal.available(), 4);
+        let rest: [u64; 4] = core::array::from_fn(|_| journal.consume());
+        assert_eq!(rest, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_pipeline_stages_gate_each_other() {
+        // journal -> match -> publish
+        let mut ring: BroadcastRing<u64, 4, 3> = BroadcastRing::with_dependencies([&[], &[0], &[1]]);
+        let (mut producer, [mut journal, mut matcher, mut publisher]) = ring.split();
+
+        for i in 0..4 {
+            assert!(producer.try_publish(i));
+        }
+        assert_eq!(matcher.try_consume(), None);
+        assert_eq!(journal.try_consume(), Some(0));
+        assert_eq!(journal.try_consume(), Some(1));
+        assert_eq!(matcher.available(), 2);
+        assert_eq!(publisher.available(), 0);
+
+        assert_eq!(matcher.try_consume(), Some(0));
+        assert_eq!(publisher.try_consume(), Some(0));
+        assert_eq!(publisher.try_consume(), None);
+
+        // Slots come back only once the last stage is done
+        assert_eq!(producer.remaining_capacity(), 1);
+        assert!(producer.try_publish(4));
+        assert!(!producer.try_publish(5));
+    }
+}
@@ -0,0 +1,365 @@
+//! Heap-allocated SPSC ring sized at runtime.
+//!
+//! `SpscRing<T, N>` keeps its buffer inline, so a large `N` has to be
+//! boxed by the caller and is fixed at compile time. `HeapSpscRing` takes
+//! its capacity at construction (from config, say) and allocates the
+//! buffer separately; the cursors stay cache-line padded exactly as in
+//! `SpscRing`, which shares their logic.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use crate::wait::{BusySpin, WaitStrategy};
+use crate::{Cursors, Watermark};
+
+/// Single-Producer Single-Consumer ring buffer with a heap buffer.
+#[repr(C)]
+pub struct HeapSpscRing<T: Copy, W: WaitStrategy = BusySpin> {
+    /// Producer and consumer positions.
+    cursors: Cursors,
+
+    /// Consumer wait strategy.
+    wait: W,
+
+    /// Capacity - 1.
+    mask: u64,
+
+    /// The actual buffer.
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+// SAFETY: Same single-producer single-consumer protocol as `SpscRing`.
+unsafe impl<T: Copy + Send, W: WaitStrategy + Send> Send for HeapSpscRing<T, W> {}
+unsafe impl<T: Copy + Send, W: WaitStrategy + Sync> Sync for HeapSpscRing<T, W> {}
+
+impl<T: Copy, W: WaitStrategy + Default> HeapSpscRing<T, W> {
+    /// Create a ring of `capacity` entries.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of 2.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_wait(capacity, W::default())
+    }
+}
+
+impl<T: Copy + 'static, W: WaitStrategy + 'static> HeapSpscRing<T, W> {
+    /// Split into handles sharing ownership of the ring (see
+    /// `SpscRing::split_owned`).
+    ///
+    /// # Panics
+    /// Panics if the ring is shared with other `Arc`s.
+    pub fn split_owned(mut self: Arc<Self>) -> (HeapProducer<'static, T, W>, HeapConsumer<'static, T, W>) {
+        assert!(Arc::get_mut(&mut self).is_some(), "Ring already shared");
+        // SAFETY: each handle keeps the ring alive for as long as it holds
+        // the reference.
+        let ring: &'static Self = unsafe { &*Arc::as_ptr(&self) };
+        (
+            HeapProducer { ring, claimed: None, watermark: Watermark::new(), _owner: Some(Arc::clone(&self)) },
+            HeapConsumer { ring, _owner: Some(self) },
+        )
+    }
+}
+
+impl<T: Copy, W: WaitStrategy> HeapSpscRing<T, W> {
+    /// Create a ring of `capacity` entries whose consumer waits with `wait`.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of 2.
+    pub fn with_capacity_and_wait(capacity: usize, wait: W) -> Self {
+        assert!(capacity.is_power_of_two(), "Buffer size must be power of 2");
+
+        Self {
+            cursors: Cursors::new(),
+            wait,
+            mask: (capacity - 1) as u64,
+            buffer: (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        }
+    }
+
+    /// Get buffer capacity.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Split into producer and consumer handles.
+    pub fn split(&mut self) -> (HeapProducer<'_, T, W>, HeapConsumer<'_, T, W>) {
+        let ring = &*self;
+        (
+            HeapProducer { ring, claimed: None, watermark: Watermark::new(), _owner: None },
+            HeapConsumer { ring, _owner: None },
+        )
+    }
+
+    /// Slot holding position `pos` (valid for the rest of the buffer, so
+    /// runs can be sliced from it).
+    #[inline(always)]
+    fn slot(&self, pos: u64) -> *mut MaybeUninit<T> {
+        unsafe { UnsafeCell::raw_get(self.buffer.as_ptr().add((pos & self.mask) as usize)) }
+    }
+}
+
+/// Producer handle of a `HeapSpscRing` (write-only).
+pub struct HeapProducer<'a, T: Copy, W: WaitStrategy = BusySpin> {
+    ring: &'a HeapSpscRing<T, W>,
+    /// Position and length of the claimed run, not yet committed.
+    claimed: Option<(u64, u64)>,
+    /// Slow-consumer detection.
+    watermark: Watermark,
+    /// Keeps the ring alive (handles from `split_owned`).
+    _owner: Option<Arc<HeapSpscRing<T, W>>>,
+}
+
+impl<'a, T: Copy, W: WaitStrategy> HeapProducer<'a, T, W> {
+    /// Attempt to publish a value.
+    ///
+    /// Returns `false` if buffer is full.
+    #[inline(always)]
+    pub fn try_publish(&mut self, value: T) -> bool {
+        let Some(write_pos) = self.ring.cursors.next_write(self.ring.mask + 1) else {
+            return false;
+        };
+
+        unsafe {
+            (*self.ring.slot(write_pos)).write(value);
+        }
+
+        self.ring.cursors.publish(write_pos + 1);
+        self.watermark.check(&self.ring.cursors, write_pos + 1);
+        self.ring.wait.signal();
+
+        true
+    }
+
+    /// Publish a value, spinning until space is available.
+    #[inline]
+    pub fn publish(&mut self, value: T) {
+        while !self.try_publish(value) {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Claim the next slot to build an entry in place (see
+    /// `Producer::try_claim`).
+    #[inline(always)]
+    pub fn try_claim(&mut self) -> Option<&mut MaybeUninit<T>> {
+        self.try_claim_batch(1).map(|run| &mut run[0])
+    }
+
+    /// Claim a contiguous run of up to `max` slots (see
+    /// `Producer::try_claim_batch`).
+    #[inline(always)]
+    pub fn try_claim_batch(&mut self, max: usize) -> Option<&mut [MaybeUninit<T>]> {
+        let (write_pos, len) = self.ring.cursors.claim(self.ring.mask + 1, max.max(1) as u64)?;
+        self.claimed = Some((write_pos, len));
+
+        unsafe { Some(core::slice::from_raw_parts_mut(self.ring.slot(write_pos), len as usize)) }
+    }
+
+    /// Publish the claimed slots, which the caller must have initialized.
+    /// Does nothing if no slot is claimed.
+    #[inline(always)]
+    pub fn commit(&mut self) {
+        if let Some((write_pos, len)) = self.claimed.take() {
+            self.ring.cursors.publish(write_pos + len);
+            self.watermark.check(&self.ring.cursors, write_pos + len);
+            self.ring.wait.signal();
+        }
+    }
+
+    /// Batch publish for efficiency, one cursor update per claimed run.
+    #[inline]
+    pub fn publish_batch(&mut self, values: &[T]) {
+        let mut rest = values;
+        while !rest.is_empty() {
+            let Some(run) = self.try_claim_batch(rest.len()) else {
+                core::hint::spin_loop();
+                continue;
+            };
+            let len = run.len();
+            for (slot, &value) in run.iter_mut().zip(rest) {
+                slot.write(value);
+            }
+            self.commit();
+            rest = &rest[len..];
+        }
+    }
+
+    /// Check remaining capacity.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.ring.capacity() - self.ring.cursors.len()
+    }
+
+    /// Flag the consumer as lagging whenever a publish leaves more than
+    /// `depth` entries unread (see `Producer::set_high_watermark`).
+    pub fn set_high_watermark(&mut self, depth: usize) {
+        self.watermark.set(depth);
+    }
+
+    /// Check if the last publish left the ring above the high watermark.
+    #[inline]
+    pub fn is_consumer_lagging(&self) -> bool {
+        self.watermark.lagging
+    }
+
+    /// Number of times the ring depth rose above the high watermark.
+    #[inline]
+    pub fn watermark_crossings(&self) -> u64 {
+        self.watermark.crossings
+    }
+}
+
+/// Consumer handle of a `HeapSpscRing` (read-only).
+pub struct HeapConsumer<'a, T: Copy, W: WaitStrategy = BusySpin> {
+    ring: &'a HeapSpscRing<T, W>,
+    /// Keeps the ring alive (handles from `split_owned`).
+    _owner: Option<Arc<HeapSpscRing<T, W>>>,
+}
+
+impl<'a, T: Copy, W: WaitStrategy> HeapConsumer<'a, T, W> {
+    /// Attempt to consume a value.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn try_consume(&mut self) -> Option<T> {
+        let read_pos = self.ring.cursors.next_read(self.ring.mask + 1)?;
+
+        let value = unsafe { (*self.ring.slot(read_pos)).assume_init_read() };
+
+        self.ring.cursors.release(read_pos + 1);
+        self.ring.wait.signal_consumed();
+
+        Some(value)
+    }
+
+    /// Borrow the next entry in place without consuming it.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn peek_ref(&self) -> Option<&T> {
+        let read_pos = self.ring.cursors.next_read(self.ring.mask + 1)?;
+        unsafe { Some((*self.ring.slot(read_pos)).assume_init_ref()) }
+    }
+
+    /// Copy of the next entry, left in the ring.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<T> {
+        self.peek_ref().copied()
+    }
+
+    /// Copy the next entries into `buffer` without consuming them.
+    ///
+    /// Returns number of items copied.
+    #[inline]
+    pub fn peek_n(&self, buffer: &mut [T]) -> usize {
+        let Some(read_pos) = self.ring.cursors.next_read(self.ring.mask + 1) else {
+            return 0;
+        };
+        let count = buffer.len().min(self.ring.cursors.len());
+
+        for (pos, slot) in (read_pos..).zip(&mut buffer[..count]) {
+            *slot = unsafe { (*self.ring.slot(pos)).assume_init_read() };
+        }
+        count
+    }
+
+    /// Consume the next entry without copying it out (after `peek_ref`).
+    ///
+    /// Returns `false` if buffer is empty.
+    #[inline(always)]
+    pub fn advance(&mut self) -> bool {
+        match self.ring.cursors.next_read(self.ring.mask + 1) {
+            Some(read_pos) => {
+                self.ring.cursors.release(read_pos + 1);
+                self.ring.wait.signal_consumed();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consume a value, waiting per the ring's wait strategy until one
+    /// is available.
+    #[inline(always)]
+    pub fn consume(&mut self) -> T {
+        let mut spins = 0u32;
+        loop {
+            if let Some(value) = self.try_consume() {
+                return value;
+            }
+            let ring = self.ring;
+            ring.wait.wait(spins, || ring.cursors.has_unread());
+            spins = spins.saturating_add(1);
+        }
+    }
+
+    /// Batch consume for efficiency.
+    ///
+    /// Returns number of items consumed.
+    #[inline]
+    pub fn consume_batch(&mut self, buffer: &mut [T]) -> usize {
+        let mut count = 0;
+        while count < buffer.len() {
+            let len = self.consume_batch_zero_copy(buffer.len() - count, |run| {
+                buffer[count..count + run.len()].copy_from_slice(run);
+            });
+            if len == 0 {
+                break;
+            }
+            count += len;
+        }
+        count
+    }
+
+    /// Hand the next contiguous run of up to `max` entries to `f` in
+    /// place, then consume them all at once (see
+    /// `Consumer::consume_batch_zero_copy`).
+    #[inline]
+    pub fn consume_batch_zero_copy<F: FnOnce(&[T])>(&mut self, max: usize, f: F) -> usize {
+        let Some((read_pos, len)) = self.ring.cursors.readable(self.ring.mask + 1, max.max(1) as u64) else {
+            return 0;
+        };
+
+        // SAFETY: every slot of the run was published initialized.
+        f(unsafe { core::slice::from_raw_parts(self.ring.slot(read_pos) as *const T, len as usize) });
+
+        self.ring.cursors.release(read_pos + len);
+        self.ring.wait.signal_consumed();
+        len as usize
+    }
+
+    /// Check number of items available to consume.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.ring.cursors.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_capacity_wraps() {
+        let mut ring: HeapSpscRing<u64> = HeapSpscRing::with_capacity(4);
+        assert_eq!(ring.capacity(), 4);
+        let (mut producer, mut consumer) = ring.split();
+
+        for round in 0..10 {
+            for i in 0..4 {
+                assert!(producer.try_publish(round * 4 + i));
+            }
+            assert!(!producer.try_publish(0));
+            assert_eq!(consumer.available(), 4);
+            for i in 0..4 {
+                assert_eq!(consumer.try_consume(), Some(round * 4 + i));
+            }
+        }
+        assert_eq!(consumer.try_consume(), None);
+        assert_eq!(producer.remaining_capacity(), 4);
+    }
+}
@@ -1,10 +1,43 @@
 //! Lock-free SPSC Ring Buffer (Disruptor pattern).
 //!
 //! This module implements a Single-Producer Single-Consumer ring buffer
-//! with cache-line padding to prevent false sharing.
+//! with cache-line padding to prevent false sharing. Variants: sized at
+//! runtime on the heap (`heap`, `alloc` feature), moving owned (non-`Copy`)
+//! values (`owned`), living in shared memory between processes (`shm`,
+//! `shm` feature), and broadcast (`broadcast`), whose every consumer reads
+//! every entry.
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "async")]
+pub mod async_ring;
+pub mod broadcast;
+#[cfg(feature = "alloc")]
+pub mod heap;
+pub mod owned;
+#[cfg(all(feature = "shm", unix))]
+pub mod shm;
+pub mod wait;
+
+#[cfg(feature = "async")]
+pub use async_ring::{AsyncConsumer, AsyncProducer, AsyncWait};
+pub use broadcast::{BroadcastConsumer, BroadcastProducer, BroadcastRing};
+#[cfg(feature = "alloc")]
+pub use heap::{HeapConsumer, HeapProducer, HeapSpscRing};
+pub use owned::{OwnedConsumer, OwnedProducer, OwnedSpscRing};
+#[cfg(all(feature = "shm", unix))]
+pub use shm::{ShmConsumer, ShmProducer, ShmSpscRing};
+pub use wait::{BusySpin, WaitStrategy};
+#[cfg(feature = "std")]
+pub use wait::{SpinThenPark, SpinThenYield};
+
 use core::cell::UnsafeCell;
 use core::sync::atomic::{AtomicU64, Ordering};
 use core::mem::MaybeUninit;
@@ -15,24 +48,21 @@ pub const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024; // 1M entries
 /// Padded atomic counter to prevent false sharing.
 /// Uses 128-byte alignment to ensure it occupies its own cache line.
 #[repr(C, align(128))]
-struct PaddedAtomicU64 {
-    value: AtomicU64,
+pub(crate) struct PaddedAtomicU64 {
+    pub(crate) value: AtomicU64,
 }
 
 impl PaddedAtomicU64 {
-    const fn new(v: u64) -> Self {
+    pub(crate) const fn new(v: u64) -> Self {
         Self {
             value: AtomicU64::new(v),
         }
     }
 }
 
-/// Single-Producer Single-Consumer lock-free ring buffer.
-///
-/// Uses atomic sequencing inspired by the LMAX Disruptor pattern.
-/// The buffer provides wait-free operations for both producer and consumer.
+/// Cursors of an SPSC ring, shared by the inline and heap variants.
 #[repr(C)]
-pub struct SpscRing<T: Copy, const N: usize = DEFAULT_BUFFER_SIZE> {
+pub(crate) struct Cursors {
     /// Write cursor (owned by producer).
     write_cursor: PaddedAtomicU64,
     
@@ -44,6 +74,173 @@ pub struct SpscRing<T: Copy, const N: usize = DEFAULT_BUFFER_SIZE> {
     
     /// Cached write position for consumer.
     cached_write: PaddedAtomicU64,
+}
+
+impl Cursors {
+    pub(crate) const fn new() -> Self {
+        Self {
+            write_cursor: PaddedAtomicU64::new(0),
+            cached_read: PaddedAtomicU64::new(0),
+            read_cursor: PaddedAtomicU64::new(0),
+            cached_write: PaddedAtomicU64::new(0),
+        }
+    }
+    
+    /// Position the producer writes next, or `None` if `capacity`
+    /// entries are unread.
+    #[inline(always)]
+    pub(crate) fn next_write(&self, capacity: u64) -> Option<u64> {
+        self.claim(capacity, 1).map(|(write_pos, _)| write_pos)
+    }
+    
+    /// Position and length of the run of free slots the producer writes
+    /// next: at most `max`, stopping at the end of the buffer. `None` if
+    /// buffer is full.
+    #[inline(always)]
+    pub(crate) fn claim(&self, capacity: u64, max: u64) -> Option<(u64, u64)> {
+        let write_pos = self.write_cursor.value.load(Ordering::Relaxed);
+        
+        // Check free space using cached read position
+        let cached_read = self.cached_read.value.load(Ordering::Relaxed);
+        let mut free = capacity - (write_pos - cached_read);
+        if free < max {
+            // Refresh cached read position
+            let current_read = self.read_cursor.value.load(Ordering::Acquire);
+            self.cached_read.value.store(current_read, Ordering::Relaxed);
+            free = capacity - (write_pos - current_read);
+            
+            if free == 0 {
+                return None; // Buffer is actually full
+            }
+        }
+        let to_end = capacity - (write_pos & (capacity - 1));
+        Some((write_pos, free.min(max).min(to_end)))
+    }
+    
+    /// Make entries before `write_pos` visible to the consumer.
+    #[inline(always)]
+    pub(crate) fn publish(&self, write_pos: u64) {
+        // Release barrier ensures writes are visible
+        self.write_cursor.value.store(write_pos, Ordering::Release);
+    }
+    
+    /// Position the consumer reads next, or `None` if nothing is unread.
+    #[inline(always)]
+    pub(crate) fn next_read(&self, capacity: u64) -> Option<u64> {
+        self.readable(capacity, 1).map(|(read_pos, _)| read_pos)
+    }
+    
+    /// Position and length of the run of unread entries the consumer
+    /// reads next: at most `max`, stopping at the end of the buffer.
+    /// `None` if buffer is empty.
+    #[inline(always)]
+    pub(crate) fn readable(&self, capacity: u64, max: u64) -> Option<(u64, u64)> {
+        let read_pos = self.read_cursor.value.load(Ordering::Relaxed);
+        
+        // Check unread entries using cached write position
+        let cached_write = self.cached_write.value.load(Ordering::Relaxed);
+        let mut unread = cached_write - read_pos;
+        if unread < max {
+            // Refresh cached write position
+            let current_write = self.write_cursor.value.load(Ordering::Acquire);
+            self.cached_write.value.store(current_write, Ordering::Relaxed);
+            unread = current_write - read_pos;
+            
+            if unread == 0 {
+                return None; // Buffer is actually empty
+            }
+        }
+        let to_end = capacity - (read_pos & (capacity - 1));
+        Some((read_pos, unread.min(max).min(to_end)))
+    }
+    
+    /// Hand slots before `read_pos` back to the producer.
+    #[inline(always)]
+    pub(crate) fn release(&self, read_pos: u64) {
+        self.read_cursor.value.store(read_pos, Ordering::Release);
+    }
+    
+    /// Check if the consumer has something to read (wait strategy check).
+    #[inline]
+    pub(crate) fn has_unread(&self) -> bool {
+        self.write_cursor.value.load(Ordering::Acquire) > self.read_cursor.value.load(Ordering::Relaxed)
+    }
+    
+    /// Number of unread entries.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        let write_pos = self.write_cursor.value.load(Ordering::Acquire);
+        let read_pos = self.read_cursor.value.load(Ordering::Acquire);
+        (write_pos - read_pos) as usize
+    }
+    
+    /// Unread entries once `write_pos` is published, from the cached read
+    /// position unless that puts them above `limit`.
+    #[inline(always)]
+    pub(crate) fn depth_within(&self, write_pos: u64, limit: u64) -> u64 {
+        let depth = write_pos - self.cached_read.value.load(Ordering::Relaxed);
+        if depth <= limit {
+            return depth;
+        }
+        let current_read = self.read_cursor.value.load(Ordering::Acquire);
+        self.cached_read.value.store(current_read, Ordering::Relaxed);
+        write_pos - current_read
+    }
+    
+    /// Positions of the unread entries.
+    #[inline]
+    pub(crate) fn unread(&self) -> core::ops::Range<u64> {
+        let read_pos = self.read_cursor.value.load(Ordering::Acquire);
+        read_pos..self.write_cursor.value.load(Ordering::Acquire)
+    }
+}
+
+/// Producer-side slow-consumer detection.
+///
+/// After each publish the producer compares the ring depth with the high
+/// watermark. The cached read position bounds the depth from above, so the
+/// consumer's cursor is only read once the cached figure crosses the mark.
+pub(crate) struct Watermark {
+    /// Depth above which the consumer counts as lagging (`u64::MAX` = off).
+    high: u64,
+    /// Depth was above `high` at the last publish.
+    lagging: bool,
+    /// Times the depth rose above `high`.
+    crossings: u64,
+}
+
+impl Watermark {
+    pub(crate) const fn new() -> Self {
+        Self { high: u64::MAX, lagging: false, crossings: 0 }
+    }
+    
+    #[inline(always)]
+    pub(crate) fn check(&mut self, cursors: &Cursors, write_pos: u64) {
+        let lagging = cursors.depth_within(write_pos, self.high) > self.high;
+        if lagging && !self.lagging {
+            self.crossings += 1;
+        }
+        self.lagging = lagging;
+    }
+    
+    pub(crate) fn set(&mut self, depth: usize) {
+        self.high = depth as u64;
+        self.lagging = false;
+    }
+}
+
+/// Single-Producer Single-Consumer lock-free ring buffer.
+///
+/// Uses atomic sequencing inspired by the LMAX Disruptor pattern.
+/// The buffer provides wait-free operations for both producer and consumer;
+/// `W` decides how a blocking `consume` waits on an empty ring.
+#[repr(C)]
+pub struct SpscRing<T: Copy, const N: usize = DEFAULT_BUFFER_SIZE, W: WaitStrategy = BusySpin> {
+    /// Producer and consumer positions.
+    cursors: Cursors,
+    
+    /// Consumer wait strategy.
+    wait: W,
     
     /// The actual buffer.
     buffer: UnsafeCell<[MaybeUninit<T>; N]>,
@@ -51,24 +248,32 @@ pub struct SpscRing<T: Copy, const N: usize = DEFAULT_BUFFER_SIZE> {
 
 // SAFETY: Ring buffer is designed for single-producer single-consumer,
 // with proper atomic synchronization between the two.
-unsafe impl<T: Copy + Send, const N: usize> Send for SpscRing<T, N> {}
-unsafe impl<T: Copy + Send, const N: usize> Sync for SpscRing<T, N> {}
+unsafe impl<T: Copy + Send, const N: usize, W: WaitStrategy + Send> Send for SpscRing<T, N, W> {}
+unsafe impl<T: Copy + Send, const N: usize, W: WaitStrategy + Sync> Sync for SpscRing<T, N, W> {}
 
-impl<T: Copy, const N: usize> SpscRing<T, N> {
-    const MASK: u64 = (N - 1) as u64;
-    
+impl<T: Copy, const N: usize, W: WaitStrategy + Default> SpscRing<T, N, W> {
     /// Create a new ring buffer.
     ///
     /// # Panics
     /// Panics if N is not a power of 2.
     pub fn new() -> Self {
+        Self::with_wait_strategy(W::default())
+    }
+}
+
+impl<T: Copy, const N: usize, W: WaitStrategy> SpscRing<T, N, W> {
+    const MASK: u64 = (N - 1) as u64;
+    
+    /// Create a new ring buffer whose consumer waits with `wait`.
+    ///
+    /// # Panics
+    /// Panics if N is not a power of 2.
+    pub fn with_wait_strategy(wait: W) -> Self {
         assert!(N.is_power_of_two(), "Buffer size must be power of 2");
         
         Self {
-            write_cursor: PaddedAtomicU64::new(0),
-            cached_read: PaddedAtomicU64::new(0),
-            read_cursor: PaddedAtomicU64::new(0),
-            cached_write: PaddedAtomicU64::new(0),
+            cursors: Cursors::new(),
+            wait,
             buffer: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
         }
     }
@@ -83,54 +288,83 @@ impl<T: Copy, const N: usize> SpscRing<T, N> {
     ///
     /// # Safety
     /// Must only be called once. Multiple producers or consumers will cause UB.
-    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+    pub fn split(&mut self) -> (Producer<'_, T, N, W>, Consumer<'_, T, N, W>) {
         (
-            Producer { ring: self },
-            Consumer { ring: self },
+            Producer {
+                ring: self,
+                claimed: None,
+                watermark: Watermark::new(),
+                #[cfg(feature = "alloc")]
+                _owner: None,
+            },
+            Consumer {
+                ring: self,
+                #[cfg(feature = "alloc")]
+                _owner: None,
+            },
         )
     }
 }
 
-impl<T: Copy, const N: usize> Default for SpscRing<T, N> {
+#[cfg(feature = "alloc")]
+impl<T: Copy + 'static, const N: usize, W: WaitStrategy + 'static> SpscRing<T, N, W> {
+    /// Split into producer and consumer handles that share ownership of
+    /// the ring, so each can move to its own thread; the ring is freed
+    /// with the last handle.
+    ///
+    /// # Panics
+    /// Panics if the ring is shared with other `Arc`s.
+    pub fn split_owned(mut self: Arc<Self>) -> (Producer<'static, T, N, W>, Consumer<'static, T, N, W>) {
+        assert!(Arc::get_mut(&mut self).is_some(), "Ring already shared");
+        // SAFETY: each handle keeps the ring alive for as long as it holds
+        // the reference.
+        let ring: &'static Self = unsafe { &*Arc::as_ptr(&self) };
+        (
+            Producer { ring, claimed: None, watermark: Watermark::new(), _owner: Some(Arc::clone(&self)) },
+            Consumer { ring, _owner: Some(self) },
+        )
+    }
+}
+
+impl<T: Copy, const N: usize, W: WaitStrategy + Default> Default for SpscRing<T, N, W> {
     fn default() -> Self {
         Self::new()
     }
 }
 
 /// Producer handle (write-only).
-pub struct Producer<'a, T: Copy, const N: usize = DEFAULT_BUFFER_SIZE> {
-    ring: &'a SpscRing<T, N>,
+pub struct Producer<'a, T: Copy, const N: usize = DEFAULT_BUFFER_SIZE, W: WaitStrategy = BusySpin> {
+    ring: &'a SpscRing<T, N, W>,
+    /// Position and length of the run handed out by `try_claim` or
+    /// `try_claim_batch`, not yet committed.
+    claimed: Option<(u64, u64)>,
+    /// Slow-consumer detection.
+    watermark: Watermark,
+    /// Keeps the ring alive (handles from `split_owned`).
+    #[cfg(feature = "alloc")]
+    _owner: Option<Arc<SpscRing<T, N, W>>>,
 }
 
-impl<'a, T: Copy, const N: usize> Producer<'a, T, N> {
+impl<'a, T: Copy, const N: usize, W: WaitStrategy> Producer<'a, T, N, W> {
     /// Attempt to publish a value.
     ///
     /// Returns `false` if buffer is full.
     #[inline(always)]
     pub fn try_publish(&mut self, value: T) -> bool {
-        let write_pos = self.ring.write_cursor.value.load(Ordering::Relaxed);
-        
-        // Check if buffer is full using cached read position
-        let cached_read = self.ring.cached_read.value.load(Ordering::Relaxed);
-        if write_pos - cached_read >= N as u64 {
-            // Refresh cached read position
-            let current_read = self.ring.read_cursor.value.load(Ordering::Acquire);
-            self.ring.cached_read.value.store(current_read, Ordering::Relaxed);
-            
-            if write_pos - current_read >= N as u64 {
-                return false; // Buffer is actually full
-            }
-        }
+        let Some(write_pos) = self.ring.cursors.next_write(N as u64) else {
+            return false;
+        };
         
         // Write the value
-        let idx = (write_pos & SpscRing::<T, N>::MASK) as usize;
+        let idx = (write_pos & SpscRing::<T, N, W>::MASK) as usize;
         unsafe {
             let buffer = &mut *self.ring.buffer.get();
             buffer[idx].write(value);
         }
         
-        // Publish (release barrier ensures writes are visible)
-        self.ring.write_cursor.value.store(write_pos + 1, Ordering::Release);
+        self.ring.cursors.publish(write_pos + 1);
+        self.watermark.check(&self.ring.cursors, write_pos + 1);
+        self.ring.wait.signal();
         
         true
     }
@@ -143,69 +377,187 @@ impl<'a, T: Copy, const N: usize> Producer<'a, T, N> {
         }
     }
     
-    /// Batch publish for efficiency.
+    /// Claim the next slot to build an entry in place.
+    ///
+    /// The entry is invisible to the consumer until `commit`; claiming
+    /// again before that replaces the claim, starting at the same slot.
+    /// Returns `None` if buffer is full.
+    #[inline(always)]
+    pub fn try_claim(&mut self) -> Option<&mut MaybeUninit<T>> {
+        self.try_claim_batch(1).map(|run| &mut run[0])
+    }
+    
+    /// Claim a contiguous run of up to `max` slots (shorter if the ring is
+    /// nearly full or the run would wrap), published together by `commit`.
+    ///
+    /// Returns `None` if buffer is full.
+    #[inline(always)]
+    pub fn try_claim_batch(&mut self, max: usize) -> Option<&mut [MaybeUninit<T>]> {
+        let (write_pos, len) = self.ring.cursors.claim(N as u64, max.max(1) as u64)?;
+        self.claimed = Some((write_pos, len));
+        
+        let idx = (write_pos & SpscRing::<T, N, W>::MASK) as usize;
+        unsafe {
+            let buffer = &mut *self.ring.buffer.get();
+            Some(&mut buffer[idx..idx + len as usize])
+        }
+    }
+    
+    /// Publish the slots from `try_claim`/`try_claim_batch`.
+    ///
+    /// The caller must have initialized them all. Does nothing if no slot
+    /// is claimed.
+    #[inline(always)]
+    pub fn commit(&mut self) {
+        if let Some((write_pos, len)) = self.claimed.take() {
+            self.ring.cursors.publish(write_pos + len);
+            self.watermark.check(&self.ring.cursors, write_pos + len);
+            self.ring.wait.signal();
+        }
+    }
+    
+    /// Batch publish for efficiency: copies into claimed runs and
+    /// publishes each with a single cursor update.
     #[inline]
     pub fn publish_batch(&mut self, values: &[T]) {
-        for &value in values {
-            self.publish(value);
+        let mut rest = values;
+        while !rest.is_empty() {
+            let Some(run) = self.try_claim_batch(rest.len()) else {
+                core::hint::spin_loop();
+                continue;
+            };
+            let len = run.len();
+            for (slot, &value) in run.iter_mut().zip(rest) {
+                slot.write(value);
+            }
+            self.commit();
+            rest = &rest[len..];
         }
     }
     
     /// Check remaining capacity.
     #[inline]
     pub fn remaining_capacity(&self) -> usize {
-        let write_pos = self.ring.write_cursor.value.load(Ordering::Relaxed);
-        let read_pos = self.ring.read_cursor.value.load(Ordering::Acquire);
-        N - (write_pos - read_pos) as usize
+        N - self.ring.cursors.len()
+    }
+    
+    /// Flag the consumer as lagging whenever a publish leaves more than
+    /// `depth` entries unread, so upstream can shed load before the ring
+    /// fills.
+    pub fn set_high_watermark(&mut self, depth: usize) {
+        self.watermark.set(depth);
+    }
+    
+    /// Check if the last publish left the ring above the high watermark.
+    #[inline]
+    pub fn is_consumer_lagging(&self) -> bool {
+        self.watermark.lagging
+    }
+    
+    /// Number of times the ring depth rose above the high watermark.
+    #[inline]
+    pub fn watermark_crossings(&self) -> u64 {
+        self.watermark.crossings
     }
 }
 
 /// Consumer handle (read-only).
-pub struct Consumer<'a, T: Copy, const N: usize = DEFAULT_BUFFER_SIZE> {
-    ring: &'a SpscRing<T, N>,
+pub struct Consumer<'a, T: Copy, const N: usize = DEFAULT_BUFFER_SIZE, W: WaitStrategy = BusySpin> {
+    ring: &'a SpscRing<T, N, W>,
+    /// Keeps the ring alive (handles from `split_owned`).
+    #[cfg(feature = "alloc")]
+    _owner: Option<Arc<SpscRing<T, N, W>>>,
 }
 
-impl<'a, T: Copy, const N: usize> Consumer<'a, T, N> {
+impl<'a, T: Copy, const N: usize, W: WaitStrategy> Consumer<'a, T, N, W> {
     /// Attempt to consume a value.
     ///
     /// Returns `None` if buffer is empty.
     #[inline(always)]
     pub fn try_consume(&mut self) -> Option<T> {
-        let read_pos = self.ring.read_cursor.value.load(Ordering::Relaxed);
-        
-        // Check if buffer is empty using cached write position
-        let cached_write = self.ring.cached_write.value.load(Ordering::Relaxed);
-        if read_pos >= cached_write {
-            // Refresh cached write position
-            let current_write = self.ring.write_cursor.value.load(Ordering::Acquire);
-            self.ring.cached_write.value.store(current_write, Ordering::Relaxed);
-            
-            if read_pos >= current_write {
-                return None; // Buffer is actually empty
-            }
-        }
+        let read_pos = self.ring.cursors.next_read(N as u64)?;
         
         // Read the value
-        let idx = (read_pos & SpscRing::<T, N>::MASK) as usize;
+        let idx = (read_pos & SpscRing::<T, N, W>::MASK) as usize;
         let value = unsafe {
             let buffer = &*self.ring.buffer.get();
             buffer[idx].assume_init_read()
         };
         
-        // Acknowledge consumption (release barrier)
-        self.ring.read_cursor.value.store(read_pos + 1, Ordering::Release);
+        // Acknowledge consumption
+        self.ring.cursors.release(read_pos + 1);
+        self.ring.wait.signal_consumed();
         
         Some(value)
     }
     
-    /// Consume a value, spinning until one is available (BUSY WAIT).
+    /// Borrow the next entry in place without consuming it.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn peek_ref(&self) -> Option<&T> {
+        let read_pos = self.ring.cursors.next_read(N as u64)?;
+        
+        let idx = (read_pos & SpscRing::<T, N, W>::MASK) as usize;
+        unsafe {
+            let buffer = &*self.ring.buffer.get();
+            Some(buffer[idx].assume_init_ref())
+        }
+    }
+    
+    /// Copy of the next entry, left in the ring.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<T> {
+        self.peek_ref().copied()
+    }
+    
+    /// Copy the next entries into `buffer` without consuming them.
+    ///
+    /// Returns number of items copied.
+    #[inline]
+    pub fn peek_n(&self, buffer: &mut [T]) -> usize {
+        let Some(read_pos) = self.ring.cursors.next_read(N as u64) else {
+            return 0;
+        };
+        let count = buffer.len().min(self.ring.cursors.len());
+        
+        let ring = unsafe { &*self.ring.buffer.get() };
+        for (pos, slot) in (read_pos..).zip(&mut buffer[..count]) {
+            let idx = (pos & SpscRing::<T, N, W>::MASK) as usize;
+            *slot = unsafe { ring[idx].assume_init_read() };
+        }
+        count
+    }
+    
+    /// Consume the next entry without copying it out (after `peek_ref`).
+    ///
+    /// Returns `false` if buffer is empty.
+    #[inline(always)]
+    pub fn advance(&mut self) -> bool {
+        match self.ring.cursors.next_read(N as u64) {
+            Some(read_pos) => {
+                self.ring.cursors.release(read_pos + 1);
+                self.ring.wait.signal_consumed();
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Consume a value, waiting per the ring's wait strategy until one
+    /// is available (BUSY WAIT by default).
     #[inline(always)]
     pub fn consume(&mut self) -> T {
+        let mut spins = 0u32;
         loop {
             if let Some(value) = self.try_consume() {
                 return value;
             }
-            core::hint::spin_loop();
+            let ring = self.ring;
+            ring.wait.wait(spins, || ring.cursors.has_unread());
+            spins = spins.saturating_add(1);
         }
     }
     
@@ -215,24 +567,45 @@ impl<'a, T: Copy, const N: usize> Consumer<'a, T, N> {
     #[inline]
     pub fn consume_batch(&mut self, buffer: &mut [T]) -> usize {
         let mut count = 0;
-        for slot in buffer.iter_mut() {
-            match self.try_consume() {
-                Some(value) => {
-                    *slot = value;
-                    count += 1;
-                }
-                None => break,
+        while count < buffer.len() {
+            let len = self.consume_batch_zero_copy(buffer.len() - count, |run| {
+                buffer[count..count + run.len()].copy_from_slice(run);
+            });
+            if len == 0 {
+                break;
             }
+            count += len;
         }
         count
     }
     
+    /// Hand the next contiguous run of up to `max` entries to `f` in
+    /// place, then consume them all with a single cursor update.
+    ///
+    /// Returns number of items consumed (`f` is not called if none).
+    #[inline]
+    pub fn consume_batch_zero_copy<F: FnOnce(&[T])>(&mut self, max: usize, f: F) -> usize {
+        let Some((read_pos, len)) = self.ring.cursors.readable(N as u64, max.max(1) as u64) else {
+            return 0;
+        };
+        
+        let idx = (read_pos & SpscRing::<T, N, W>::MASK) as usize;
+        let run = unsafe {
+            let buffer = &*self.ring.buffer.get();
+            &buffer[idx..idx + len as usize]
+        };
+        // SAFETY: every slot of the run was published initialized.
+        f(unsafe { &*(run as *const [MaybeUninit<T>] as *const [T]) });
+        
+        self.ring.cursors.release(read_pos + len);
+        self.ring.wait.signal_consumed();
+        len as usize
+    }
+    
     /// Check number of items available to consume.
     #[inline]
     pub fn available(&self) -> usize {
-        let write_pos = self.ring.write_cursor.value.load(Ordering::Acquire);
-        let read_pos = self.ring.read_cursor.value.load(Ordering::Relaxed);
-        (write_pos - read_pos) as usize
+        self.ring.cursors.len()
     }
 }
 
@@ -317,4 +690,121 @@ mod tests {
         producer.try_publish(2);
         assert_eq!(consumer.available(), 2);
     }
+    
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parked_consumer_woken_by_producer() {
+        let mut ring: std::boxed::Box<SpscRing<u64, 64, SpinThenPark>> =
+            std::boxed::Box::new(SpscRing::with_wait_strategy(SpinThenPark::new(10)));
+        let (mut producer, mut consumer) = ring.split();
+        
+        std::thread::scope(|scope| {
+            let reader = scope.spawn(move || (0..1000).map(|_| consumer.consume()).sum::<u64>());
+            for i in 0..1000 {
+                if i % 100 == 0 {
+                    // Let the consumer run dry and park
+                    std::thread::sleep(std::time::Duration::from_millis(2));
+                }
+                producer.publish(i);
+            }
+            assert_eq!(reader.join().unwrap(), 999 * 1000 / 2);
+        });
+    }
+    
+    #[test]
+    fn test_claim_commit_and_peek_ref() {
+        let mut ring: SpscRing<[u64; 8], 2> = SpscRing::new();
+        let (mut producer, mut consumer) = ring.split();
+        
+        producer.try_claim().unwrap().write([7; 8]);
+        assert_eq!(consumer.peek_ref(), None);
+        producer.commit();
+        producer.commit();
+        assert_eq!(consumer.available(), 1);
+        
+        producer.try_claim().unwrap().write([8; 8]);
+        producer.commit();
+        assert!(producer.try_claim().is_none());
+        
+        assert_eq!(consumer.peek_ref(), Some(&[7; 8]));
+        assert!(consumer.advance());
+        assert_eq!(consumer.try_consume(), Some([8; 8]));
+        assert!(!consumer.advance());
+    }
+    
+    #[test]
+    fn test_batch_runs_stop_at_wrap() {
+        let mut ring: SpscRing<u64, 8> = SpscRing::new();
+        let (mut producer, mut consumer) = ring.split();
+        
+        producer.publish_batch(&[0, 1, 2, 3, 4, 5]);
+        let mut seen = [0; 8];
+        assert_eq!(consumer.consume_batch_zero_copy(4, |run| seen[..4].copy_from_slice(run)), 4);
+        assert_eq!(seen[..4], [0, 1, 2, 3]);
+        
+        // Six free slots, but only two before the end of the buffer
+        let run = producer.try_claim_batch(6).unwrap();
+        assert_eq!(run.len(), 2);
+        run[0].write(6);
+        run[1].write(7);
+        producer.commit();
+        producer.publish_batch(&[8, 9, 10, 11]);
+        assert_eq!(producer.remaining_capacity(), 0);
+        
+        assert_eq!(consumer.consume_batch_zero_copy(8, |run| assert_eq!(run, [4, 5, 6, 7])), 4);
+        assert_eq!(consumer.consume_batch(&mut seen), 4);
+        assert_eq!(seen[..4], [8, 9, 10, 11]);
+        assert_eq!(consumer.consume_batch_zero_copy(8, |_| unreachable!()), 0);
+    }
+    
+    #[test]
+    fn test_peek_leaves_entries() {
+        let mut ring: SpscRing<u64, 4> = SpscRing::new();
+        let (mut producer, mut consumer) = ring.split();
+        
+        assert_eq!(consumer.peek(), None);
+        producer.publish_batch(&[1, 2, 3]);
+        consumer.try_consume();
+        producer.publish_batch(&[4, 5]);
+        
+        // Spans the wrap
+        let mut next = [0; 8];
+        assert_eq!(consumer.peek(), Some(2));
+        assert_eq!(consumer.peek_n(&mut next), 4);
+        assert_eq!(next[..4], [2, 3, 4, 5]);
+        assert_eq!(consumer.peek_n(&mut next[..1]), 1);
+        assert_eq!(consumer.available(), 4);
+        assert_eq!(consumer.try_consume(), Some(2));
+    }
+    
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_owned_handles_move_to_threads() {
+        let ring: std::sync::Arc<SpscRing<u64, 64>> = std::sync::Arc::new(SpscRing::new());
+        let (mut producer, mut consumer) = ring.split_owned();
+        
+        let reader = std::thread::spawn(move || (0..1000).map(|_| consumer.consume()).sum::<u64>());
+        std::thread::spawn(move || (0..1000).for_each(|i| producer.publish(i)));
+        assert_eq!(reader.join().unwrap(), 999 * 1000 / 2);
+    }
+    
+    #[test]
+    fn test_high_watermark_crossings() {
+        let mut ring: SpscRing<u64, 8> = SpscRing::new();
+        let (mut producer, mut consumer) = ring.split();
+        producer.set_high_watermark(4);
+        
+        producer.publish_batch(&[0, 1, 2, 3]);
+        assert!(!producer.is_consumer_lagging());
+        producer.publish_batch(&[4, 5]);
+        assert!(producer.is_consumer_lagging());
+        assert_eq!(producer.watermark_crossings(), 1);
+        
+        // Consumer catches up, then falls behind again
+        assert_eq!(consumer.consume_batch(&mut [0; 4]), 4);
+        producer.try_publish(6);
+        assert!(!producer.is_consumer_lagging());
+        producer.publish_batch(&[7, 8]);
+        assert_eq!(producer.watermark_crossings(), 2);
+    }
 }
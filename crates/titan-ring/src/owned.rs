@@ -0,0 +1,199 @@
+//! SPSC ring of owned (non-`Copy`) values.
+//!
+//! `SpscRing` copies entries in and out, which rules out owning types
+//! (boxed snapshots, fill lists). `OwnedSpscRing` moves each value in on
+//! publish and out on consume; a publish that finds the ring full hands
+//! the value back. Entries still in the ring when it is dropped are
+//! dropped with it.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use crate::{Cursors, DEFAULT_BUFFER_SIZE};
+
+/// Single-Producer Single-Consumer ring buffer moving values.
+#[repr(C)]
+pub struct OwnedSpscRing<T, const N: usize = DEFAULT_BUFFER_SIZE> {
+    /// Producer and consumer positions.
+    cursors: Cursors,
+
+    /// The actual buffer.
+    buffer: UnsafeCell<[MaybeUninit<T>; N]>,
+}
+
+// SAFETY: Same single-producer single-consumer protocol as `SpscRing`;
+// each value moves from the producer thread to the consumer thread.
+unsafe impl<T: Send, const N: usize> Send for OwnedSpscRing<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for OwnedSpscRing<T, N> {}
+
+impl<T, const N: usize> OwnedSpscRing<T, N> {
+    const MASK: u64 = (N - 1) as u64;
+
+    /// Create a new ring buffer.
+    ///
+    /// # Panics
+    /// Panics if N is not a power of 2.
+    pub fn new() -> Self {
+        assert!(N.is_power_of_two(), "Buffer size must be power of 2");
+
+        Self {
+            cursors: Cursors::new(),
+            buffer: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
+        }
+    }
+
+    /// Get buffer capacity.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Split into producer and consumer handles.
+    pub fn split(&mut self) -> (OwnedProducer<'_, T, N>, OwnedConsumer<'_, T, N>) {
+        let ring = &*self;
+        (OwnedProducer { ring }, OwnedConsumer { ring })
+    }
+
+    /// Slot holding position `pos`.
+    #[inline(always)]
+    fn slot(&self, pos: u64) -> *mut MaybeUninit<T> {
+        unsafe { (*self.buffer.get()).as_mut_ptr().add((pos & Self::MASK) as usize) }
+    }
+}
+
+impl<T, const N: usize> Default for OwnedSpscRing<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for OwnedSpscRing<T, N> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            for pos in self.cursors.unread() {
+                unsafe { (*self.slot(pos)).assume_init_drop() };
+            }
+        }
+    }
+}
+
+/// Producer handle of an `OwnedSpscRing` (write-only).
+pub struct OwnedProducer<'a, T, const N: usize = DEFAULT_BUFFER_SIZE> {
+    ring: &'a OwnedSpscRing<T, N>,
+}
+
+impl<'a, T, const N: usize> OwnedProducer<'a, T, N> {
+    /// Attempt to publish a value.
+    ///
+    /// Returns the value back if buffer is full.
+    #[inline(always)]
+    pub fn try_publish(&mut self, value: T) -> Result<(), T> {
+        let Some(write_pos) = self.ring.cursors.next_write(N as u64) else {
+            return Err(value);
+        };
+
+        unsafe {
+            (*self.ring.slot(write_pos)).write(value);
+        }
+        self.ring.cursors.publish(write_pos + 1);
+
+        Ok(())
+    }
+
+    /// Publish a value, spinning until space is available.
+    #[inline]
+    pub fn publish(&mut self, mut value: T) {
+        while let Err(back) = self.try_publish(value) {
+            value = back;
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Check remaining capacity.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.ring.cursors.len()
+    }
+}
+
+/// Consumer handle of an `OwnedSpscRing` (read-only).
+pub struct OwnedConsumer<'a, T, const N: usize = DEFAULT_BUFFER_SIZE> {
+    ring: &'a OwnedSpscRing<T, N>,
+}
+
+impl<'a, T, const N: usize> OwnedConsumer<'a, T, N> {
+    /// Attempt to consume a value.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn try_consume(&mut self) -> Option<T> {
+        let read_pos = self.ring.cursors.next_read(N as u64)?;
+
+        let value = unsafe { (*self.ring.slot(read_pos)).assume_init_read() };
+        self.ring.cursors.release(read_pos + 1);
+
+        Some(value)
+    }
+
+    /// Consume a value, spinning until one is available (BUSY WAIT).
+    #[inline(always)]
+    pub fn consume(&mut self) -> T {
+        loop {
+            if let Some(value) = self.try_consume() {
+                return value;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Borrow the next entry in place without consuming it.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn peek_ref(&self) -> Option<&T> {
+        let read_pos = self.ring.cursors.next_read(N as u64)?;
+        unsafe { Some((*self.ring.slot(read_pos)).assume_init_ref()) }
+    }
+
+    /// Check number of items available to consume.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.ring.cursors.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Counts its drops.
+    struct Tracked<'a>(u32, &'a Cell<u32>);
+
+    impl Drop for Tracked<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_unconsumed_values_dropped_with_ring() {
+        let drops = Cell::new(0);
+        let mut ring: OwnedSpscRing<Tracked<'_>, 4> = OwnedSpscRing::new();
+        let (mut producer, mut consumer) = ring.split();
+
+        for i in 0..4 {
+            assert!(producer.try_publish(Tracked(i, &drops)).is_ok());
+        }
+        let back = producer.try_publish(Tracked(4, &drops)).unwrap_err();
+        assert_eq!(back.0, 4);
+        drop(back);
+
+        assert_eq!(consumer.peek_ref().map(|t| t.0), Some(0));
+        assert_eq!(consumer.try_consume().map(|t| t.0), Some(0));
+        assert_eq!(drops.get(), 2);
+
+        // Three still in the ring
+        drop(ring);
+        assert_eq!(drops.get(), 5);
+    }
+}
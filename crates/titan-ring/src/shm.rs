@@ -0,0 +1,365 @@
+//! SPSC ring in a shared memory segment, for a producer and consumer in
+//! separate processes (gateway and engine, say).
+//!
+//! The segment starts with a header (magic, layout version, entry size and
+//! capacity), followed by the same padded cursors as `SpscRing` and then
+//! the buffer. One process creates the segment, under a `/dev/shm` name or
+//! as a memfd whose descriptor it hands over; the other attaches to it,
+//! which fails unless the header matches. The creator writes the magic
+//! last, so a half-initialized segment is never attached.
+//!
+//! Entries are copied between address spaces bit for bit: `T` must be
+//! plain data (no pointers or references) with the same layout in both
+//! processes.
+
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use crate::Cursors;
+
+/// Magic at the start of every ring segment.
+pub const SHM_MAGIC: u64 = u64::from_le_bytes(*b"TITANSHM");
+
+/// Segment layout version.
+pub const SHM_VERSION: u32 = 1;
+
+/// Segment header.
+#[repr(C)]
+struct Header {
+    /// `SHM_MAGIC` once initialized.
+    magic: AtomicU64,
+    version: u32,
+    /// `size_of::<T>()` of the creator.
+    entry_size: u32,
+    capacity: u64,
+}
+
+/// Start of a segment (the buffer follows).
+#[repr(C)]
+struct Segment {
+    header: Header,
+    cursors: Cursors,
+}
+
+/// Offset of the buffer in a segment of `T`s.
+fn buffer_offset<T>() -> usize {
+    size_of::<Segment>().next_multiple_of(align_of::<T>())
+}
+
+/// Size of a segment of `capacity` `T`s.
+fn segment_len<T>(capacity: usize) -> usize {
+    buffer_offset::<T>() + capacity * size_of::<T>()
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn c_name(name: &str) -> io::Result<CString> {
+    CString::new(name).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "name contains NUL"))
+}
+
+/// Single-Producer Single-Consumer ring buffer in shared memory.
+pub struct ShmSpscRing<T: Copy> {
+    segment: NonNull<Segment>,
+    /// Mapped length.
+    len: usize,
+    /// Capacity - 1.
+    mask: u64,
+    fd: OwnedFd,
+    _entries: PhantomData<T>,
+}
+
+// SAFETY: Same single-producer single-consumer protocol as `SpscRing`.
+unsafe impl<T: Copy + Send> Send for ShmSpscRing<T> {}
+unsafe impl<T: Copy + Send> Sync for ShmSpscRing<T> {}
+
+impl<T: Copy> ShmSpscRing<T> {
+    /// Create a ring of `capacity` entries in the new segment `name`
+    /// (`/name`, under `/dev/shm`).
+    ///
+    /// Fails if the segment exists; `unlink` it first.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of 2.
+    pub fn create(name: &str, capacity: usize) -> io::Result<Self> {
+        let name = c_name(name)?;
+        let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_CREAT | libc::O_EXCL | libc::O_RDWR, 0o600) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Self::init(unsafe { OwnedFd::from_raw_fd(fd) }, capacity)
+    }
+
+    /// Create a ring of `capacity` entries in an anonymous memfd; the
+    /// other process attaches with `attach_fd` on the inherited or
+    /// passed descriptor (`as_raw_fd`).
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of 2.
+    #[cfg(target_os = "linux")]
+    pub fn create_memfd(name: &str, capacity: usize) -> io::Result<Self> {
+        let name = c_name(name)?;
+        let fd = unsafe { libc::memfd_create(name.as_ptr(), 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Self::init(unsafe { OwnedFd::from_raw_fd(fd) }, capacity)
+    }
+
+    /// Attach to the segment `name` created by another process.
+    pub fn attach(name: &str) -> io::Result<Self> {
+        let name = c_name(name)?;
+        let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_RDWR, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Self::attach_fd(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Attach to the ring segment open as `fd`.
+    ///
+    /// Fails with `InvalidData` if the segment is not an initialized ring
+    /// of this version and entry size.
+    pub fn attach_fd(fd: OwnedFd) -> io::Result<Self> {
+        let mut stat: libc::stat = unsafe { core::mem::zeroed() };
+        if unsafe { libc::fstat(fd.as_raw_fd(), &mut stat) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let len = stat.st_size as usize;
+        if len < size_of::<Segment>() {
+            return Err(invalid("segment too small for a ring header"));
+        }
+
+        let mut ring = Self::map(fd, len)?;
+        let header = &ring.segment().header;
+        if header.magic.load(Ordering::Acquire) != SHM_MAGIC {
+            return Err(invalid("not an initialized ring segment"));
+        }
+        if header.version != SHM_VERSION {
+            return Err(invalid("ring segment version mismatch"));
+        }
+        if header.entry_size as usize != size_of::<T>() {
+            return Err(invalid("ring entry size mismatch"));
+        }
+        let capacity = header.capacity as usize;
+        if !capacity.is_power_of_two() || segment_len::<T>(capacity) > len {
+            return Err(invalid("ring capacity does not fit the segment"));
+        }
+        ring.mask = header.capacity - 1;
+        Ok(ring)
+    }
+
+    /// Remove the segment `name` (attached mappings stay valid).
+    pub fn unlink(name: &str) -> io::Result<()> {
+        let name = c_name(name)?;
+        if unsafe { libc::shm_unlink(name.as_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Size the segment open as `fd`, map it and write the header.
+    fn init(fd: OwnedFd, capacity: usize) -> io::Result<Self> {
+        assert!(capacity.is_power_of_two(), "Buffer size must be power of 2");
+
+        let len = segment_len::<T>(capacity);
+        if unsafe { libc::ftruncate(fd.as_raw_fd(), len as libc::off_t) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut ring = Self::map(fd, len)?;
+        ring.mask = (capacity - 1) as u64;
+
+        let segment = ring.segment.as_ptr();
+        unsafe {
+            core::ptr::addr_of_mut!((*segment).cursors).write(Cursors::new());
+            let header = &mut (*segment).header;
+            header.version = SHM_VERSION;
+            header.entry_size = size_of::<T>() as u32;
+            header.capacity = capacity as u64;
+            header.magic.store(SHM_MAGIC, Ordering::Release);
+        }
+        Ok(ring)
+    }
+
+    fn map(fd: OwnedFd, len: usize) -> io::Result<Self> {
+        let addr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            segment: NonNull::new(addr.cast()).expect("mmap returned null"),
+            len,
+            mask: 0,
+            fd,
+            _entries: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    fn segment(&self) -> &Segment {
+        unsafe { self.segment.as_ref() }
+    }
+
+    /// Slot holding position `pos`.
+    #[inline(always)]
+    fn slot(&self, pos: u64) -> *mut T {
+        unsafe {
+            let buffer = self.segment.as_ptr().cast::<u8>().add(buffer_offset::<T>());
+            buffer.cast::<T>().add((pos & self.mask) as usize)
+        }
+    }
+
+    /// Get buffer capacity.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        (self.mask + 1) as usize
+    }
+
+    /// Producer handle (for the process that writes).
+    pub fn producer(&mut self) -> ShmProducer<'_, T> {
+        ShmProducer { ring: self }
+    }
+
+    /// Consumer handle (for the process that reads).
+    pub fn consumer(&mut self) -> ShmConsumer<'_, T> {
+        ShmConsumer { ring: self }
+    }
+
+    /// Split into producer and consumer handles (both sides in this
+    /// process).
+    pub fn split(&mut self) -> (ShmProducer<'_, T>, ShmConsumer<'_, T>) {
+        let ring = &*self;
+        (ShmProducer { ring }, ShmConsumer { ring })
+    }
+}
+
+impl<T: Copy> AsRawFd for ShmSpscRing<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl<T: Copy> Drop for ShmSpscRing<T> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.segment.as_ptr().cast(), self.len);
+        }
+    }
+}
+
+/// Producer handle of a `ShmSpscRing` (write-only).
+pub struct ShmProducer<'a, T: Copy> {
+    ring: &'a ShmSpscRing<T>,
+}
+
+impl<'a, T: Copy> ShmProducer<'a, T> {
+    /// Attempt to publish a value.
+    ///
+    /// Returns `false` if buffer is full.
+    #[inline(always)]
+    pub fn try_publish(&mut self, value: T) -> bool {
+        let cursors = &self.ring.segment().cursors;
+        let Some(write_pos) = cursors.next_write(self.ring.mask + 1) else {
+            return false;
+        };
+
+        unsafe {
+            self.ring.slot(write_pos).write(value);
+        }
+        cursors.publish(write_pos + 1);
+
+        true
+    }
+
+    /// Publish a value, spinning until space is available.
+    #[inline]
+    pub fn publish(&mut self, value: T) {
+        while !self.try_publish(value) {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Check remaining capacity.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.ring.capacity() - self.ring.segment().cursors.len()
+    }
+}
+
+/// Consumer handle of a `ShmSpscRing` (read-only).
+pub struct ShmConsumer<'a, T: Copy> {
+    ring: &'a ShmSpscRing<T>,
+}
+
+impl<'a, T: Copy> ShmConsumer<'a, T> {
+    /// Attempt to consume a value.
+    ///
+    /// Returns `None` if buffer is empty.
+    #[inline(always)]
+    pub fn try_consume(&mut self) -> Option<T> {
+        let cursors = &self.ring.segment().cursors;
+        let read_pos = cursors.next_read(self.ring.mask + 1)?;
+
+        let value = unsafe { self.ring.slot(read_pos).read() };
+        cursors.release(read_pos + 1);
+
+        Some(value)
+    }
+
+    /// Consume a value, spinning until one is available (BUSY WAIT).
+    #[inline(always)]
+    pub fn consume(&mut self) -> T {
+        loop {
+            if let Some(value) = self.try_consume() {
+                return value;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Check number of items available to consume.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.ring.segment().cursors.len()
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attached_mapping_sees_entries() {
+        let mut created: ShmSpscRing<[u64; 2]> = ShmSpscRing::create_memfd("titan-ring-test", 4).unwrap();
+        let fd = created.fd.try_clone().unwrap();
+        let mut attached: ShmSpscRing<[u64; 2]> = ShmSpscRing::attach_fd(fd).unwrap();
+        assert_eq!(attached.capacity(), 4);
+
+        let mut producer = created.producer();
+        let mut consumer = attached.consumer();
+        for i in 0..6 {
+            assert!(producer.try_publish([i, i * 10]));
+            assert_eq!(consumer.try_consume(), Some([i, i * 10]));
+        }
+        assert_eq!(consumer.try_consume(), None);
+
+        // Another entry type does not attach
+        let fd = created.fd.try_clone().unwrap();
+        let err = ShmSpscRing::<u64>::attach_fd(fd).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
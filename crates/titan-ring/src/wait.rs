@@ -0,0 +1,135 @@
+//! Consumer wait strategies.
+//!
+//! A ring's wait strategy decides what a blocking `consume` does while
+//! the ring is empty. `BusySpin` keeps the core hot for the lowest
+//! latency; with the `std` feature, `SpinThenYield` gives the core back
+//! to the scheduler and `SpinThenPark` sleeps until the producer signals
+//! a publish, for consumers off the latency-critical path. `AsyncWait`
+//! (`async` feature) wakes async tasks instead.
+
+/// How a consumer waits for entries and how the producer wakes it.
+pub trait WaitStrategy {
+    /// Wait once for the producer. `spins` counts the consecutive empty
+    /// polls so far; `ready` re-checks the ring and must be consulted
+    /// before sleeping.
+    fn wait<F: Fn() -> bool>(&self, spins: u32, ready: F);
+
+    /// Called by the producer after every publish.
+    fn signal(&self);
+
+    /// Called by the consumer after freeing slots.
+    #[inline(always)]
+    fn signal_consumed(&self) {}
+}
+
+/// Spin on the ring (BUSY WAIT).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BusySpin;
+
+impl WaitStrategy for BusySpin {
+    #[inline(always)]
+    fn wait<F: Fn() -> bool>(&self, _spins: u32, _ready: F) {
+        core::hint::spin_loop();
+    }
+
+    #[inline(always)]
+    fn signal(&self) {}
+}
+
+/// Spin `spin_limit` times, then yield the thread between polls.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct SpinThenYield {
+    /// Empty polls before yielding.
+    pub spin_limit: u32,
+}
+
+#[cfg(feature = "std")]
+impl Default for SpinThenYield {
+    fn default() -> Self {
+        Self { spin_limit: 1000 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl WaitStrategy for SpinThenYield {
+    #[inline]
+    fn wait<F: Fn() -> bool>(&self, spins: u32, _ready: F) {
+        if spins < self.spin_limit {
+            core::hint::spin_loop();
+        } else {
+            std::thread::yield_now();
+        }
+    }
+
+    #[inline(always)]
+    fn signal(&self) {}
+}
+
+/// Spin `spin_limit` times, then park until the producer publishes.
+///
+/// The producer pays one atomic load per publish, plus a lock and
+/// wakeup only while the consumer is asleep.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SpinThenPark {
+    /// Empty polls before parking.
+    pub spin_limit: u32,
+    sleeping: core::sync::atomic::AtomicBool,
+    lock: std::sync::Mutex<()>,
+    wakeup: std::sync::Condvar,
+}
+
+#[cfg(feature = "std")]
+impl SpinThenPark {
+    /// Park after `spin_limit` empty polls.
+    pub fn new(spin_limit: u32) -> Self {
+        Self {
+            spin_limit,
+            sleeping: core::sync::atomic::AtomicBool::new(false),
+            lock: std::sync::Mutex::new(()),
+            wakeup: std::sync::Condvar::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SpinThenPark {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+#[cfg(feature = "std")]
+impl WaitStrategy for SpinThenPark {
+    fn wait<F: Fn() -> bool>(&self, spins: u32, ready: F) {
+        use core::sync::atomic::{fence, Ordering};
+
+        if spins < self.spin_limit {
+            core::hint::spin_loop();
+            return;
+        }
+
+        // The lock is held from announcing the sleep until `wait` releases
+        // it, so a producer that sees `sleeping` notifies a waiting thread;
+        // one that does not has published before `ready` runs.
+        let guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.sleeping.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        if !ready() {
+            drop(self.wakeup.wait(guard));
+        }
+        self.sleeping.store(false, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn signal(&self) {
+        use core::sync::atomic::{fence, Ordering};
+
+        fence(Ordering::SeqCst);
+        if self.sleeping.load(Ordering::Relaxed) {
+            let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+            self.wakeup.notify_one();
+        }
+    }
+}
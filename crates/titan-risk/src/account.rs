@@ -0,0 +1,56 @@
+//! Per-account risk state.
+
+use crate::limits::RiskLimits;
+
+/// Account identifier (dense index into the risk table).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct AccountId(pub u32);
+
+impl AccountId {
+    /// Get raw index.
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Running risk state for one account.
+#[derive(Clone, Copy, Debug)]
+pub struct AccountState {
+    /// Limits applied to this account.
+    pub limits: RiskLimits,
+    /// Kill switch: when set, every new order is rejected.
+    pub killed: bool,
+    /// Start of the current rate window (ns).
+    pub window_start_ns: u64,
+    /// Orders accepted in the current rate window.
+    pub window_count: u32,
+    /// Number of working orders.
+    pub open_orders: u32,
+    /// Notional of working orders.
+    pub open_notional: u128,
+    /// Working buy quantity.
+    pub open_buy_qty: u64,
+    /// Working sell quantity.
+    pub open_sell_qty: u64,
+    /// Net filled position (positive = long).
+    pub position: i64,
+}
+
+impl AccountState {
+    /// Create a fresh account with the given limits.
+    pub const fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            killed: false,
+            window_start_ns: 0,
+            window_count: 0,
+            open_orders: 0,
+            open_notional: 0,
+            open_buy_qty: 0,
+            open_sell_qty: 0,
+            position: 0,
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Pre-trade risk checks.
+//!
+//! `RiskGate` owns fixed-size account and reference-price tables. Every
+//! check is O(1) and allocation-free.
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use titan_core::{OrderId, OrderType, Price, Quantity, Side, SymbolId};
+
+use crate::account::{AccountId, AccountState};
+use crate::limits::RiskLimits;
+
+/// Basis points in one unit.
+const BPS_SCALE: u128 = 10_000;
+
+/// Order entering the risk gate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderRequest {
+    pub account: AccountId,
+    pub order_id: OrderId,
+    pub symbol: SymbolId,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+impl OrderRequest {
+    /// Notional in tick-quantity units.
+    #[inline(always)]
+    pub const fn notional(&self) -> u128 {
+        self.price.to_ticks() as u128 * self.quantity.0 as u128
+    }
+}
+
+/// Why the risk gate refused an order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RiskRejectReason {
+    /// Account index outside the risk table.
+    UnknownAccount = 1,
+    /// Account kill switch is engaged.
+    AccountKilled = 2,
+    /// Order rate limit exceeded.
+    RateLimit = 3,
+    /// Too many working orders.
+    OpenOrderLimit = 4,
+    /// Single order notional too large.
+    OrderNotionalLimit = 5,
+    /// Working notional would exceed the limit.
+    OpenNotionalLimit = 6,
+    /// Worst-case position would exceed the limit.
+    PositionLimit = 7,
+    /// Price too far from the reference price.
+    PriceDeviation = 8,
+}
+
+/// Structured reject event.
+///
+/// `observed` is the value that breached `limit`, in the units of the
+/// check (orders, tick-quantity, contracts or basis points).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RiskReject {
+    pub account: AccountId,
+    pub order_id: OrderId,
+    pub symbol: SymbolId,
+    pub reason: RiskRejectReason,
+    pub observed: u128,
+    pub limit: u128,
+    pub timestamp: u64,
+}
+
+/// Per-account pre-trade risk gate.
+pub struct RiskGate {
+    /// Account table indexed by `AccountId`.
+    accounts: Box<[AccountState]>,
+    /// Reference prices indexed by `SymbolId` (zero = unknown).
+    reference: Box<[Price]>,
+}
+
+impl RiskGate {
+    /// Create a gate for `max_accounts` accounts and `max_symbols` symbols,
+    /// all starting with `limits`.
+    pub fn new(max_accounts: usize, max_symbols: usize, limits: RiskLimits) -> Self {
+        Self {
+            accounts: vec![AccountState::new(limits); max_accounts].into_boxed_slice(),
+            reference: vec![Price::ZERO; max_symbols].into_boxed_slice(),
+        }
+    }
+
+    /// Get account state.
+    #[inline]
+    pub fn account(&self, account: AccountId) -> Option<&AccountState> {
+        self.accounts.get(account.index())
+    }
+
+    /// Replace the limits for one account.
+    pub fn set_limits(&mut self, account: AccountId, limits: RiskLimits) -> bool {
+        match self.accounts.get_mut(account.index()) {
+            Some(state) => {
+                state.limits = limits;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the reference price used by the fat-finger check.
+    pub fn set_reference_price(&mut self, symbol: SymbolId, price: Price) -> bool {
+        match self.reference.get_mut(symbol.0 as usize) {
+            Some(slot) => {
+                *slot = price;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Engage or release the account kill switch.
+    pub fn set_killed(&mut self, account: AccountId, killed: bool) -> bool {
+        match self.accounts.get_mut(account.index()) {
+            Some(state) => {
+                state.killed = killed;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run every check and, on success, book the order as working.
+    pub fn check_new_order(&mut self, req: &OrderRequest, now_ns: u64) -> Result<(), RiskReject> {
+        let reference = self.reference.get(req.symbol.0 as usize).copied().unwrap_or(Price::ZERO);
+        let reject = |reason, observed: u128, limit: u128| RiskReject {
+            account: req.account,
+            order_id: req.order_id,
+            symbol: req.symbol,
+            reason,
+            observed,
+            limit,
+            timestamp: now_ns,
+        };
+
+        let state = match self.accounts.get_mut(req.account.index()) {
+            Some(state) => state,
+            None => return Err(reject(RiskRejectReason::UnknownAccount, req.account.0 as u128, 0)),
+        };
+        let limits = state.limits;
+
+        if state.killed {
+            return Err(reject(RiskRejectReason::AccountKilled, 1, 0));
+        }
+
+        // Rate: fixed window, reset once the window has elapsed
+        if now_ns.wrapping_sub(state.window_start_ns) >= limits.rate_window_ns {
+            state.window_start_ns = now_ns;
+            state.window_count = 0;
+        }
+        if limits.max_orders_per_window != 0 && state.window_count >= limits.max_orders_per_window {
+            return Err(reject(
+                RiskRejectReason::RateLimit,
+                state.window_count as u128 + 1,
+                limits.max_orders_per_window as u128,
+            ));
+        }
+        // Rejected orders still count against the rate
+        state.window_count += 1;
+
+        if limits.max_open_orders != 0 && state.open_orders >= limits.max_open_orders {
+            return Err(reject(
+                RiskRejectReason::OpenOrderLimit,
+                state.open_orders as u128 + 1,
+                limits.max_open_orders as u128,
+            ));
+        }
+
+        // Fat-finger: |price - reference| / reference in basis points
+        if limits.max_price_deviation_bps != 0 && !reference.is_zero() {
+            let px = req.price.to_ticks() as u128;
+            let rf = reference.to_ticks() as u128;
+            let deviation_bps = px.abs_diff(rf) * BPS_SCALE / rf;
+            if deviation_bps > limits.max_price_deviation_bps as u128 {
+                return Err(reject(
+                    RiskRejectReason::PriceDeviation,
+                    deviation_bps,
+                    limits.max_price_deviation_bps as u128,
+                ));
+            }
+        }
+
+        let notional = req.notional();
+        if limits.max_order_notional != 0 && notional > limits.max_order_notional {
+            return Err(reject(RiskRejectReason::OrderNotionalLimit, notional, limits.max_order_notional));
+        }
+
+        let open_notional = state.open_notional + notional;
+        if limits.max_open_notional != 0 && open_notional > limits.max_open_notional {
+            return Err(reject(RiskRejectReason::OpenNotionalLimit, open_notional, limits.max_open_notional));
+        }
+
+        // Position: assume every working order on this side fills
+        let qty = req.quantity.0;
+        let worst_case = match req.side {
+            Side::Buy => (state.position as i128 + state.open_buy_qty as i128 + qty as i128).unsigned_abs(),
+            Side::Sell => (state.position as i128 - state.open_sell_qty as i128 - qty as i128).unsigned_abs(),
+        };
+        if limits.max_position != 0 && worst_case > limits.max_position as u128 {
+            return Err(reject(RiskRejectReason::PositionLimit, worst_case, limits.max_position as u128));
+        }
+
+        state.open_orders += 1;
+        state.open_notional = open_notional;
+        match req.side {
+            Side::Buy => state.open_buy_qty += qty,
+            Side::Sell => state.open_sell_qty += qty,
+        }
+
+        Ok(())
+    }
+
+    /// Apply a fill against a working order booked at `order_price`.
+    ///
+    /// Open notional is released at the price the order was booked at,
+    /// not the trade price, so price-improved fills release exactly what
+    /// `check_new_order` reserved.
+    pub fn on_fill(&mut self, account: AccountId, side: Side, order_price: Price, qty: Quantity) {
+        if let Some(state) = self.accounts.get_mut(account.index()) {
+            let notional = order_price.to_ticks() as u128 * qty.0 as u128;
+            state.open_notional = state.open_notional.saturating_sub(notional);
+            match side {
+                Side::Buy => {
+                    state.open_buy_qty = state.open_buy_qty.saturating_sub(qty.0);
+                    state.position = state.position.saturating_add(qty.0 as i64);
+                }
+                Side::Sell => {
+                    state.open_sell_qty = state.open_sell_qty.saturating_sub(qty.0);
+                    state.position = state.position.saturating_sub(qty.0 as i64);
+                }
+            }
+        }
+    }
+
+    /// Release a working order that left the book (filled, cancelled or
+    /// rejected downstream) with `leaves_qty` unfilled at `price`.
+    pub fn on_order_closed(&mut self, account: AccountId, side: Side, price: Price, leaves_qty: Quantity) {
+        if let Some(state) = self.accounts.get_mut(account.index()) {
+            let notional = price.to_ticks() as u128 * leaves_qty.0 as u128;
+            state.open_orders = state.open_orders.saturating_sub(1);
+            state.open_notional = state.open_notional.saturating_sub(notional);
+            match side {
+                Side::Buy => state.open_buy_qty = state.open_buy_qty.saturating_sub(leaves_qty.0),
+                Side::Sell => state.open_sell_qty = state.open_sell_qty.saturating_sub(leaves_qty.0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(account: u32, side: Side, price_ticks: u64, qty: u64) -> OrderRequest {
+        OrderRequest {
+            account: AccountId(account),
+            order_id: OrderId(1),
+            symbol: SymbolId(0),
+            side,
+            order_type: OrderType::Limit,
+            price: Price::from_ticks(price_ticks),
+            quantity: Quantity(qty),
+        }
+    }
+
+    #[test]
+    fn test_unknown_account_and_kill_switch() {
+        let mut gate = RiskGate::new(2, 1, RiskLimits::UNLIMITED);
+
+        let err = gate.check_new_order(&request(5, Side::Buy, 100, 1), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::UnknownAccount);
+
+        gate.set_killed(AccountId(1), true);
+        let err = gate.check_new_order(&request(1, Side::Buy, 100, 1), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::AccountKilled);
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 1), 0).is_ok());
+
+        gate.set_killed(AccountId(1), false);
+        assert!(gate.check_new_order(&request(1, Side::Buy, 100, 1), 0).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let limits = RiskLimits { max_orders_per_window: 2, rate_window_ns: 1_000, ..RiskLimits::UNLIMITED };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 1), 10).is_ok());
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 1), 20).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Buy, 100, 1), 30).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::RateLimit);
+        assert_eq!(err.limit, 2);
+
+        // New window
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 1), 1_010).is_ok());
+    }
+
+    #[test]
+    fn test_open_orders_released_on_close() {
+        let limits = RiskLimits { max_open_orders: 1, ..RiskLimits::UNLIMITED };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::OpenOrderLimit);
+
+        gate.on_order_closed(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity(10));
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).is_ok());
+    }
+
+    #[test]
+    fn test_notional_limits() {
+        let limits = RiskLimits {
+            max_order_notional: 1_000,
+            max_open_notional: 1_500,
+            ..RiskLimits::UNLIMITED
+        };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        let err = gate.check_new_order(&request(0, Side::Buy, 100, 11), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::OrderNotionalLimit);
+        assert_eq!(err.observed, 1_100);
+
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Sell, 100, 6), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::OpenNotionalLimit);
+        assert_eq!(err.observed, 1_600);
+    }
+
+    #[test]
+    fn test_position_limit_counts_working_orders() {
+        let limits = RiskLimits { max_position: 100, ..RiskLimits::UNLIMITED };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 60), 0).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Buy, 100, 50), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::PositionLimit);
+
+        // Fill moves working qty into position; sells reduce it
+        gate.on_fill(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity(60));
+        gate.on_order_closed(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity::ZERO);
+        assert_eq!(gate.account(AccountId(0)).unwrap().position, 60);
+        assert!(gate.check_new_order(&request(0, Side::Sell, 100, 160), 0).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Sell, 100, 1), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::PositionLimit);
+    }
+
+    #[test]
+    fn test_improved_fill_releases_booked_notional() {
+        let limits = RiskLimits { max_open_notional: 1_000, ..RiskLimits::UNLIMITED };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        // Buy 10 @ 100 trades 4 @ 95: release 4 * 100, not 4 * 95
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).is_ok());
+        gate.on_fill(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity(4));
+        assert_eq!(gate.account(AccountId(0)).unwrap().open_notional, 600);
+        gate.on_fill(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity(6));
+        gate.on_order_closed(AccountId(0), Side::Buy, Price::from_ticks(100), Quantity::ZERO);
+        assert_eq!(gate.account(AccountId(0)).unwrap().open_notional, 0);
+        assert!(gate.check_new_order(&request(0, Side::Buy, 100, 10), 0).is_ok());
+    }
+
+    #[test]
+    fn test_fat_finger() {
+        let limits = RiskLimits { max_price_deviation_bps: 500, ..RiskLimits::UNLIMITED };
+        let mut gate = RiskGate::new(1, 1, limits);
+
+        // No reference yet: check skipped
+        assert!(gate.check_new_order(&request(0, Side::Buy, 1, 1), 0).is_ok());
+
+        gate.set_reference_price(SymbolId(0), Price::from_ticks(10_000));
+        assert!(gate.check_new_order(&request(0, Side::Buy, 10_500, 1), 0).is_ok());
+        let err = gate.check_new_order(&request(0, Side::Sell, 9_400, 1), 0).unwrap_err();
+        assert_eq!(err.reason, RiskRejectReason::PriceDeviation);
+        assert_eq!(err.observed, 600);
+    }
+}
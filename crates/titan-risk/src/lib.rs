@@ -0,0 +1,28 @@
+//! Pre-trade risk gateway.
+//!
+//! Sits between the network gateway and the matching engine in the
+//! ring-based pipeline and enforces per-account limits before an order
+//! can reach the book.
+//!
+//! ## Checks
+//! - Order rate per account (fixed window)
+//! - Open order count and open notional
+//! - Net position limit including working orders
+//! - Fat-finger price deviation from a per-symbol reference
+//! - Account-level kill switch
+//!
+//! All state is sized at construction; the check path never allocates.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod limits;
+pub mod account;
+pub mod gate;
+pub mod stage;
+
+pub use limits::RiskLimits;
+pub use account::{AccountId, AccountState};
+pub use gate::{OrderRequest, RiskGate, RiskReject, RiskRejectReason};
+pub use stage::{ExecutionFeedback, RiskCommand, RiskStage};
@@ -0,0 +1,51 @@
+//! Risk limit configuration.
+
+/// Per-account risk limits.
+///
+/// Notional values are in tick-quantity units (price ticks × quantity).
+/// A limit of zero disables the corresponding check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RiskLimits {
+    /// Maximum orders accepted per rate window.
+    pub max_orders_per_window: u32,
+    /// Rate window length in nanoseconds.
+    pub rate_window_ns: u64,
+    /// Maximum number of working orders.
+    pub max_open_orders: u32,
+    /// Maximum notional of a single order.
+    pub max_order_notional: u128,
+    /// Maximum notional across all working orders.
+    pub max_open_notional: u128,
+    /// Maximum absolute net position, counting working orders.
+    pub max_position: u64,
+    /// Maximum deviation from the reference price, in basis points.
+    pub max_price_deviation_bps: u32,
+}
+
+impl RiskLimits {
+    /// Limits with every check disabled.
+    pub const UNLIMITED: Self = Self {
+        max_orders_per_window: 0,
+        rate_window_ns: 1_000_000_000,
+        max_open_orders: 0,
+        max_order_notional: 0,
+        max_open_notional: 0,
+        max_position: 0,
+        max_price_deviation_bps: 0,
+    };
+}
+
+impl Default for RiskLimits {
+    /// Conservative defaults for a newly onboarded account.
+    fn default() -> Self {
+        Self {
+            max_orders_per_window: 1_000,
+            rate_window_ns: 1_000_000_000,
+            max_open_orders: 500,
+            max_order_notional: 100_000_000,
+            max_open_notional: 1_000_000_000,
+            max_position: 100_000,
+            max_price_deviation_bps: 1_000, // 10%
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Ring-pipeline stage.
+//!
+//! ```text
+//! gateway ──RiskCommand──▶ RiskStage ──RiskCommand──▶ engine
+//!                             │  ▲
+//!                   RiskReject│  │ExecutionFeedback
+//!                             ▼  │
+//!                          gateway / engine
+//! ```
+//!
+//! The stage only consumes a command once there is room downstream for
+//! its result, so a slow engine applies back-pressure to the gateway
+//! instead of dropping orders.
+
+use titan_core::{OrderId, Price, Quantity, Side, SymbolId};
+use titan_ring::{Consumer, Producer};
+
+use crate::account::AccountId;
+use crate::gate::{OrderRequest, RiskGate, RiskReject};
+
+/// Command flowing from the gateway to the engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiskCommand {
+    /// New order; subject to risk checks.
+    New(OrderRequest),
+    /// Cancel request; always passed through.
+    Cancel {
+        account: AccountId,
+        order_id: OrderId,
+        symbol: SymbolId,
+    },
+}
+
+/// Execution feedback from the engine, used to keep risk state current.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionFeedback {
+    /// Part or all of a working order traded at `price`; `order_price`
+    /// is the limit price the order was booked at.
+    Fill {
+        account: AccountId,
+        side: Side,
+        price: Price,
+        order_price: Price,
+        quantity: Quantity,
+    },
+    /// A working order left the book with `leaves_qty` unfilled.
+    Closed {
+        account: AccountId,
+        side: Side,
+        price: Price,
+        leaves_qty: Quantity,
+    },
+    /// Reference price update for the fat-finger check.
+    Reference { symbol: SymbolId, price: Price },
+}
+
+/// Risk stage wrapping a `RiskGate`.
+pub struct RiskStage {
+    gate: RiskGate,
+    accepted: u64,
+    rejected: u64,
+}
+
+impl RiskStage {
+    /// Create a stage around `gate`.
+    pub fn new(gate: RiskGate) -> Self {
+        Self { gate, accepted: 0, rejected: 0 }
+    }
+
+    /// Access the gate (limits, kill switch, reference prices).
+    pub fn gate(&mut self) -> &mut RiskGate {
+        &mut self.gate
+    }
+
+    /// Orders forwarded to the engine.
+    pub fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Orders rejected by risk.
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Apply all pending engine feedback.
+    ///
+    /// Feedback should be drained before `poll` so that fills and
+    /// closes free up capacity for new orders.
+    pub fn drain_feedback<const F: usize>(&mut self, feedback: &mut Consumer<'_, ExecutionFeedback, F>) -> usize {
+        let mut count = 0;
+        while let Some(event) = feedback.try_consume() {
+            match event {
+                ExecutionFeedback::Fill { account, side, order_price, quantity, .. } => {
+                    self.gate.on_fill(account, side, order_price, quantity);
+                }
+                ExecutionFeedback::Closed { account, side, price, leaves_qty } => {
+                    self.gate.on_order_closed(account, side, price, leaves_qty);
+                }
+                ExecutionFeedback::Reference { symbol, price } => {
+                    self.gate.set_reference_price(symbol, price);
+                }
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Process up to `max` inbound commands.
+    ///
+    /// Returns the number of commands consumed.
+    pub fn poll<const I: usize, const O: usize, const R: usize>(
+        &mut self,
+        inbound: &mut Consumer<'_, RiskCommand, I>,
+        outbound: &mut Producer<'_, RiskCommand, O>,
+        rejects: &mut Producer<'_, RiskReject, R>,
+        now_ns: u64,
+        max: usize,
+    ) -> usize {
+        // Each command produces at most one message on either ring
+        let budget = max
+            .min(inbound.available())
+            .min(outbound.remaining_capacity())
+            .min(rejects.remaining_capacity());
+
+        for _ in 0..budget {
+            let Some(cmd) = inbound.try_consume() else {
+                return budget;
+            };
+
+            match cmd {
+                RiskCommand::New(req) => match self.gate.check_new_order(&req, now_ns) {
+                    Ok(()) => {
+                        outbound.publish(cmd);
+                        self.accepted += 1;
+                    }
+                    Err(reject) => {
+                        rejects.publish(reject);
+                        self.rejected += 1;
+                    }
+                },
+                RiskCommand::Cancel { .. } => outbound.publish(cmd),
+            }
+        }
+
+        budget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gate::RiskRejectReason;
+    use crate::limits::RiskLimits;
+    use titan_core::OrderType;
+    use titan_ring::SpscRing;
+
+    fn new_order(order_id: u64, qty: u64) -> RiskCommand {
+        RiskCommand::New(OrderRequest {
+            account: AccountId(0),
+            order_id: OrderId(order_id),
+            symbol: SymbolId(0),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            price: Price::from_ticks(100),
+            quantity: Quantity(qty),
+        })
+    }
+
+    #[test]
+    fn test_stage_routes_accepts_and_rejects() {
+        let limits = RiskLimits { max_open_orders: 1, ..RiskLimits::UNLIMITED };
+        let mut stage = RiskStage::new(RiskGate::new(1, 1, limits));
+
+        let mut in_ring: SpscRing<RiskCommand, 16> = SpscRing::new();
+        let mut out_ring: SpscRing<RiskCommand, 16> = SpscRing::new();
+        let mut rej_ring: SpscRing<RiskReject, 16> = SpscRing::new();
+        let mut fb_ring: SpscRing<ExecutionFeedback, 16> = SpscRing::new();
+        let (mut gw, mut inbound) = in_ring.split();
+        let (mut outbound, mut engine) = out_ring.split();
+        let (mut rejects, mut rejected) = rej_ring.split();
+        let (mut fb_tx, mut fb_rx) = fb_ring.split();
+
+        gw.publish(new_order(1, 10));
+        gw.publish(new_order(2, 10));
+        gw.publish(RiskCommand::Cancel { account: AccountId(0), order_id: OrderId(1), symbol: SymbolId(0) });
+
+        assert_eq!(stage.poll(&mut inbound, &mut outbound, &mut rejects, 0, 64), 3);
+        assert_eq!(engine.try_consume(), Some(new_order(1, 10)));
+        assert!(matches!(engine.try_consume(), Some(RiskCommand::Cancel { .. })));
+        assert_eq!(rejected.try_consume().unwrap().reason, RiskRejectReason::OpenOrderLimit);
+        assert_eq!((stage.accepted(), stage.rejected()), (1, 1));
+
+        // Engine reports the cancel; capacity is released
+        fb_tx.publish(ExecutionFeedback::Closed {
+            account: AccountId(0),
+            side: Side::Buy,
+            price: Price::from_ticks(100),
+            leaves_qty: Quantity(10),
+        });
+        assert_eq!(stage.drain_feedback(&mut fb_rx), 1);
+
+        gw.publish(new_order(3, 10));
+        assert_eq!(stage.poll(&mut inbound, &mut outbound, &mut rejects, 0, 64), 1);
+        assert_eq!(engine.try_consume(), Some(new_order(3, 10)));
+
+        // Fill at an improved price releases the booked notional
+        fb_tx.publish(ExecutionFeedback::Fill {
+            account: AccountId(0),
+            side: Side::Buy,
+            price: Price::from_ticks(95),
+            order_price: Price::from_ticks(100),
+            quantity: Quantity(10),
+        });
+        assert_eq!(stage.drain_feedback(&mut fb_rx), 1);
+        let account = *stage.gate().account(AccountId(0)).unwrap();
+        assert_eq!((account.open_notional, account.position), (0, 10));
+    }
+
+    #[test]
+    fn test_stage_backpressure() {
+        let mut stage = RiskStage::new(RiskGate::new(1, 1, RiskLimits::UNLIMITED));
+
+        let mut in_ring: SpscRing<RiskCommand, 8> = SpscRing::new();
+        let mut out_ring: SpscRing<RiskCommand, 2> = SpscRing::new();
+        let mut rej_ring: SpscRing<RiskReject, 8> = SpscRing::new();
+        let (mut gw, mut inbound) = in_ring.split();
+        let (mut outbound, mut engine) = out_ring.split();
+        let (mut rejects, _rejected) = rej_ring.split();
+
+        for id in 1..=4 {
+            gw.publish(new_order(id, 1));
+        }
+
+        // Only as many as the engine ring can take
+        assert_eq!(stage.poll(&mut inbound, &mut outbound, &mut rejects, 0, 64), 2);
+        assert_eq!(inbound.available(), 2);
+
+        engine.try_consume();
+        engine.try_consume();
+        assert_eq!(stage.poll(&mut inbound, &mut outbound, &mut rejects, 0, 64), 2);
+    }
+}
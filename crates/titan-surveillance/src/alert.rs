@@ -0,0 +1,61 @@
+//! Alerts and alert sinks.
+
+use titan_core::{OrderId, Price, Quantity, SymbolId};
+
+use crate::event::{AccountId, OwnerId};
+
+/// What was detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertKind {
+    /// Maker and taker belong to the same account.
+    SelfMatch {
+        maker_order_id: OrderId,
+        taker_order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    },
+    /// Maker and taker belong to different accounts of the same owner.
+    WashTrade {
+        maker_account: AccountId,
+        taker_account: AccountId,
+        maker_order_id: OrderId,
+        taker_order_id: OrderId,
+        price: Price,
+        quantity: Quantity,
+    },
+    /// Orders placed near the touch were mostly cancelled within the window.
+    Layering {
+        placed: u32,
+        cancelled: u32,
+        window_start: u64,
+    },
+}
+
+/// A surveillance alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub symbol: SymbolId,
+    /// Account the alert is attributed to (taker for fills).
+    pub account: AccountId,
+    pub owner: OwnerId,
+    pub timestamp: u64,
+}
+
+/// Destination for alerts (log, queue, compliance system, ...).
+pub trait AlertSink {
+    fn on_alert(&mut self, alert: &Alert);
+}
+
+/// Collect alerts in memory.
+impl AlertSink for Vec<Alert> {
+    fn on_alert(&mut self, alert: &Alert) {
+        self.push(*alert);
+    }
+}
+
+impl<S: AlertSink + ?Sized> AlertSink for &mut S {
+    fn on_alert(&mut self, alert: &Alert) {
+        (**self).on_alert(alert)
+    }
+}
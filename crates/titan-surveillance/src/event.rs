@@ -0,0 +1,52 @@
+//! Surveillance input events.
+
+use titan_core::{OrderId, Price, Quantity, Side, SymbolId};
+
+/// Trading account identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct AccountId(pub u32);
+
+/// Beneficial owner identifier (one owner may control many accounts).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct OwnerId(pub u32);
+
+/// Event observed by the surveillance monitor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurveillanceEvent {
+    /// Order accepted onto the book.
+    OrderAdded {
+        order_id: OrderId,
+        symbol: SymbolId,
+        account: AccountId,
+        owner: OwnerId,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        timestamp: u64,
+    },
+    /// Resting order cancelled by its owner.
+    OrderCancelled {
+        order_id: OrderId,
+        timestamp: u64,
+    },
+    /// Trade between a resting maker and an incoming taker.
+    Fill {
+        symbol: SymbolId,
+        maker_order_id: OrderId,
+        taker_order_id: OrderId,
+        maker_account: AccountId,
+        taker_account: AccountId,
+        maker_owner: OwnerId,
+        taker_owner: OwnerId,
+        price: Price,
+        quantity: Quantity,
+        timestamp: u64,
+    },
+    /// Top of book changed.
+    Quote {
+        symbol: SymbolId,
+        best_bid: Option<Price>,
+        best_ask: Option<Price>,
+        timestamp: u64,
+    },
+}
@@ -0,0 +1,17 @@
+//! Post-trade market surveillance.
+//!
+//! Consumes the order/fill event stream off the hot path and flags
+//! suspicious activity through a pluggable `AlertSink`.
+//!
+//! ## Detectors
+//! - Self-match: both sides of a fill from the same account
+//! - Wash trade: both sides from the same beneficial owner
+//! - Layering/spoofing: high cancel ratio on orders placed near the touch
+
+pub mod event;
+pub mod alert;
+pub mod monitor;
+
+pub use event::{AccountId, OwnerId, SurveillanceEvent};
+pub use alert::{Alert, AlertKind, AlertSink};
+pub use monitor::{SurveillanceConfig, SurveillanceMonitor};
@@ -0,0 +1,330 @@
+//! Surveillance monitor.
+
+use std::collections::HashMap;
+
+use titan_core::{OrderId, Price, Side, SymbolId};
+
+use crate::alert::{Alert, AlertKind, AlertSink};
+use crate::event::{AccountId, OwnerId, SurveillanceEvent};
+
+/// Detector thresholds.
+#[derive(Clone, Copy, Debug)]
+pub struct SurveillanceConfig {
+    /// Orders within this many ticks of the same-side touch count as
+    /// "near the touch" for layering detection.
+    pub near_touch_ticks: u64,
+    /// Layering evaluation window (ns).
+    pub window_ns: u64,
+    /// Minimum near-touch orders in a window before layering can trigger.
+    pub min_orders: u32,
+    /// Cancel ratio (percent) at or above which layering triggers.
+    pub cancel_ratio_pct: u32,
+}
+
+impl Default for SurveillanceConfig {
+    fn default() -> Self {
+        Self {
+            near_touch_ticks: 2,
+            window_ns: 1_000_000_000,
+            min_orders: 20,
+            cancel_ratio_pct: 90,
+        }
+    }
+}
+
+/// Resting order tracked for cancel attribution.
+#[derive(Clone, Copy, Debug)]
+struct TrackedOrder {
+    symbol: SymbolId,
+    account: AccountId,
+    owner: OwnerId,
+    remaining: u64,
+    near_touch: bool,
+}
+
+/// Per (account, symbol) layering window.
+#[derive(Clone, Copy, Debug, Default)]
+struct WindowStats {
+    start: u64,
+    placed: u32,
+    cancelled: u32,
+    alerted: bool,
+}
+
+/// Top of book per symbol.
+#[derive(Clone, Copy, Debug, Default)]
+struct Touch {
+    bid: Option<Price>,
+    ask: Option<Price>,
+}
+
+/// Stateful post-trade surveillance over the order/fill stream.
+pub struct SurveillanceMonitor<S: AlertSink> {
+    config: SurveillanceConfig,
+    sink: S,
+    orders: HashMap<OrderId, TrackedOrder>,
+    windows: HashMap<(AccountId, SymbolId), WindowStats>,
+    touch: HashMap<SymbolId, Touch>,
+    alerts_raised: u64,
+}
+
+impl<S: AlertSink> SurveillanceMonitor<S> {
+    /// Create a monitor delivering alerts to `sink`.
+    pub fn new(config: SurveillanceConfig, sink: S) -> Self {
+        Self {
+            config,
+            sink,
+            orders: HashMap::new(),
+            windows: HashMap::new(),
+            touch: HashMap::new(),
+            alerts_raised: 0,
+        }
+    }
+
+    /// Access the alert sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Total alerts raised.
+    pub fn alerts_raised(&self) -> u64 {
+        self.alerts_raised
+    }
+
+    /// Number of resting orders being tracked.
+    pub fn tracked_orders(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Process one event.
+    pub fn on_event(&mut self, event: &SurveillanceEvent) {
+        match *event {
+            SurveillanceEvent::Quote { symbol, best_bid, best_ask, .. } => {
+                self.touch.insert(symbol, Touch { bid: best_bid, ask: best_ask });
+            }
+            SurveillanceEvent::OrderAdded { order_id, symbol, account, owner, side, price, quantity, timestamp } => {
+                let near_touch = self.is_near_touch(symbol, side, price);
+                self.orders.insert(order_id, TrackedOrder {
+                    symbol,
+                    account,
+                    owner,
+                    remaining: quantity.0,
+                    near_touch,
+                });
+                if near_touch {
+                    self.window(account, symbol, timestamp).placed += 1;
+                }
+            }
+            SurveillanceEvent::OrderCancelled { order_id, timestamp } => {
+                if let Some(order) = self.orders.remove(&order_id) {
+                    if order.near_touch {
+                        self.window(order.account, order.symbol, timestamp).cancelled += 1;
+                        self.check_layering(order.account, order.owner, order.symbol, timestamp);
+                    }
+                }
+            }
+            SurveillanceEvent::Fill {
+                symbol, maker_order_id, taker_order_id,
+                maker_account, taker_account, maker_owner, taker_owner,
+                price, quantity, timestamp,
+            } => {
+                if let Some(maker) = self.orders.get_mut(&maker_order_id) {
+                    maker.remaining = maker.remaining.saturating_sub(quantity.0);
+                    if maker.remaining == 0 {
+                        self.orders.remove(&maker_order_id);
+                    }
+                }
+
+                let kind = if maker_account == taker_account {
+                    Some(AlertKind::SelfMatch { maker_order_id, taker_order_id, price, quantity })
+                } else if maker_owner == taker_owner {
+                    Some(AlertKind::WashTrade {
+                        maker_account, taker_account, maker_order_id, taker_order_id, price, quantity,
+                    })
+                } else {
+                    None
+                };
+
+                if let Some(kind) = kind {
+                    self.raise(Alert { kind, symbol, account: taker_account, owner: taker_owner, timestamp });
+                }
+            }
+        }
+    }
+
+    /// Process a batch of events in order.
+    pub fn on_events(&mut self, events: &[SurveillanceEvent]) {
+        for event in events {
+            self.on_event(event);
+        }
+    }
+
+    /// Whether `price` is within `near_touch_ticks` of the same-side touch.
+    ///
+    /// An order that improves (or establishes) the touch is near it.
+    fn is_near_touch(&self, symbol: SymbolId, side: Side, price: Price) -> bool {
+        let touch = self.touch.get(&symbol).copied().unwrap_or_default();
+        let best = match side {
+            Side::Buy => touch.bid,
+            Side::Sell => touch.ask,
+        };
+        match best {
+            None => true,
+            Some(best) => {
+                let (px, best) = (price.to_ticks(), best.to_ticks());
+                let behind = match side {
+                    Side::Buy => best.saturating_sub(px),
+                    Side::Sell => px.saturating_sub(best),
+                };
+                behind <= self.config.near_touch_ticks
+            }
+        }
+    }
+
+    /// Get the current layering window, rolling it if expired.
+    fn window(&mut self, account: AccountId, symbol: SymbolId, now: u64) -> &mut WindowStats {
+        let window_ns = self.config.window_ns;
+        let stats = self.windows.entry((account, symbol)).or_insert(WindowStats { start: now, ..Default::default() });
+        if now.saturating_sub(stats.start) >= window_ns {
+            *stats = WindowStats { start: now, ..Default::default() };
+        }
+        stats
+    }
+
+    fn check_layering(&mut self, account: AccountId, owner: OwnerId, symbol: SymbolId, now: u64) {
+        let config = self.config;
+        let Some(stats) = self.windows.get_mut(&(account, symbol)) else {
+            return;
+        };
+
+        let triggered = !stats.alerted
+            && stats.placed >= config.min_orders
+            && stats.cancelled as u64 * 100 >= stats.placed as u64 * config.cancel_ratio_pct as u64;
+
+        if triggered {
+            stats.alerted = true;
+            let kind = AlertKind::Layering {
+                placed: stats.placed,
+                cancelled: stats.cancelled,
+                window_start: stats.start,
+            };
+            self.raise(Alert { kind, symbol, account, owner, timestamp: now });
+        }
+    }
+
+    fn raise(&mut self, alert: Alert) {
+        self.alerts_raised += 1;
+        self.sink.on_alert(&alert);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use titan_core::Quantity;
+
+    const SYM: SymbolId = SymbolId(1);
+
+    fn added(order_id: u64, account: u32, owner: u32, side: Side, ticks: u64, ts: u64) -> SurveillanceEvent {
+        SurveillanceEvent::OrderAdded {
+            order_id: OrderId(order_id),
+            symbol: SYM,
+            account: AccountId(account),
+            owner: OwnerId(owner),
+            side,
+            price: Price::from_ticks(ticks),
+            quantity: Quantity(100),
+            timestamp: ts,
+        }
+    }
+
+    fn fill(maker: (u64, u32, u32), taker: (u64, u32, u32)) -> SurveillanceEvent {
+        SurveillanceEvent::Fill {
+            symbol: SYM,
+            maker_order_id: OrderId(maker.0),
+            taker_order_id: OrderId(taker.0),
+            maker_account: AccountId(maker.1),
+            taker_account: AccountId(taker.1),
+            maker_owner: OwnerId(maker.2),
+            taker_owner: OwnerId(taker.2),
+            price: Price::from_ticks(100),
+            quantity: Quantity(100),
+            timestamp: 0,
+        }
+    }
+
+    fn quote(bid: u64, ask: u64) -> SurveillanceEvent {
+        SurveillanceEvent::Quote {
+            symbol: SYM,
+            best_bid: Some(Price::from_ticks(bid)),
+            best_ask: Some(Price::from_ticks(ask)),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_self_match_and_wash_trade() {
+        let mut monitor = SurveillanceMonitor::new(SurveillanceConfig::default(), Vec::new());
+
+        monitor.on_event(&added(1, 7, 70, Side::Sell, 100, 0));
+        monitor.on_event(&fill((1, 7, 70), (2, 7, 70)));
+        monitor.on_event(&fill((3, 8, 70), (4, 9, 70)));
+        monitor.on_event(&fill((5, 8, 80), (6, 9, 90)));
+
+        let alerts = monitor.sink();
+        assert_eq!(alerts.len(), 2);
+        assert!(matches!(alerts[0].kind, AlertKind::SelfMatch { .. }));
+        assert!(matches!(
+            alerts[1].kind,
+            AlertKind::WashTrade { maker_account: AccountId(8), taker_account: AccountId(9), .. }
+        ));
+
+        // Fully filled maker is no longer tracked
+        assert_eq!(monitor.tracked_orders(), 0);
+    }
+
+    #[test]
+    fn test_layering_near_touch() {
+        let config = SurveillanceConfig { min_orders: 5, cancel_ratio_pct: 80, ..Default::default() };
+        let mut monitor = SurveillanceMonitor::new(config, Vec::new());
+        monitor.on_event(&quote(100, 102));
+
+        // Far-from-touch orders are ignored
+        for id in 100..110 {
+            monitor.on_event(&added(id, 1, 1, Side::Buy, 50, 10));
+            monitor.on_event(&SurveillanceEvent::OrderCancelled { order_id: OrderId(id), timestamp: 11 });
+        }
+        assert!(monitor.sink().is_empty());
+
+        for id in 1..=5 {
+            monitor.on_event(&added(id, 1, 1, Side::Buy, 99, 20));
+        }
+        for id in 1..=4 {
+            monitor.on_event(&SurveillanceEvent::OrderCancelled { order_id: OrderId(id), timestamp: 30 });
+        }
+
+        assert_eq!(monitor.sink().len(), 1);
+        assert_eq!(
+            monitor.sink()[0].kind,
+            AlertKind::Layering { placed: 5, cancelled: 4, window_start: 20 }
+        );
+
+        // Only one alert per window
+        monitor.on_event(&SurveillanceEvent::OrderCancelled { order_id: OrderId(5), timestamp: 31 });
+        assert_eq!(monitor.alerts_raised(), 1);
+    }
+
+    #[test]
+    fn test_layering_window_rolls() {
+        let config = SurveillanceConfig { min_orders: 2, cancel_ratio_pct: 100, window_ns: 100, ..Default::default() };
+        let mut monitor = SurveillanceMonitor::new(config, Vec::new());
+
+        monitor.on_event(&added(1, 1, 1, Side::Sell, 100, 0));
+        monitor.on_event(&added(2, 1, 1, Side::Sell, 100, 0));
+        monitor.on_event(&SurveillanceEvent::OrderCancelled { order_id: OrderId(1), timestamp: 50 });
+        // Window expired: the second cancel starts a fresh window
+        monitor.on_event(&SurveillanceEvent::OrderCancelled { order_id: OrderId(2), timestamp: 150 });
+
+        assert!(monitor.sink().is_empty());
+    }
+}
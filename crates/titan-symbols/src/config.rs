@@ -0,0 +1,245 @@
+//! Symbol configuration file loader.
+//!
+//! INI-style format, one section per symbol:
+//!
+//! ```text
+//! # Prices are in raw units (1/100 of a currency unit)
+//! [AAPL]
+//! id = 1
+//! tick_table = 0:1, 100000:5     # or: tick = 1
+//! lot_size = 100
+//! min_qty = 100
+//! max_qty = 1000000
+//! price_band_bps = 1000
+//! hours = 13:30-20:00            # UTC; omit for 24h trading
+//! ```
+
+use std::fmt;
+use std::path::Path;
+
+use titan_core::{Price, Quantity, SymbolId};
+
+use crate::registry::{SymbolError, SymbolRegistry};
+use crate::spec::{SymbolSpec, TickBand, TickTable, TradingHours, MAX_TICK_BANDS};
+
+/// Configuration load error.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// File could not be read.
+    Io(std::io::Error),
+    /// Malformed line (1-based line number).
+    Syntax { line: usize, message: String },
+    /// Section is missing a required key.
+    MissingKey { symbol: String, key: &'static str },
+    /// Registry rejected the symbol.
+    Registry { symbol: String, error: SymbolError },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "I/O error: {}", e),
+            ConfigError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ConfigError::MissingKey { symbol, key } => write!(f, "[{}]: missing `{}`", symbol, key),
+            ConfigError::Registry { symbol, error } => write!(f, "[{}]: {:?}", symbol, error),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+/// Load a registry from a configuration file.
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<SymbolRegistry, ConfigError> {
+    parse_str(&std::fs::read_to_string(path)?)
+}
+
+/// Section being accumulated.
+struct Section {
+    name: String,
+    id: Option<u32>,
+    spec: SymbolSpec,
+}
+
+impl Section {
+    fn finish(self, registry: &mut SymbolRegistry) -> Result<(), ConfigError> {
+        let id = self.id.ok_or(ConfigError::MissingKey { symbol: self.name.clone(), key: "id" })?;
+        let spec = SymbolSpec { id: SymbolId(id), ..self.spec };
+        registry.insert(spec).map_err(|error| ConfigError::Registry { symbol: self.name, error })
+    }
+}
+
+/// Parse a registry from configuration text.
+pub fn parse_str(text: &str) -> Result<SymbolRegistry, ConfigError> {
+    let mut registry = SymbolRegistry::new();
+    let mut section: Option<Section> = None;
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let syntax = |message: String| ConfigError::Syntax { line: line_no, message };
+        let line = raw.split('#').next().unwrap_or("").trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(syntax("empty symbol name".into()));
+            }
+            if let Some(done) = section.take() {
+                done.finish(&mut registry)?;
+            }
+            section = Some(Section {
+                name: name.to_string(),
+                id: None,
+                spec: SymbolSpec::new(SymbolId::INVALID, name, 1),
+            });
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| syntax(format!("expected `key = value`, got `{}`", line)))?;
+        let current = section
+            .as_mut()
+            .ok_or_else(|| syntax("key outside of a [SYMBOL] section".into()))?;
+        let spec = &mut current.spec;
+
+        let number = |v: &str| v.parse::<u64>().map_err(|_| syntax(format!("invalid number `{}` for `{}`", v, key)));
+
+        match key {
+            "id" => {
+                let id = number(value)?;
+                current.id = Some(u32::try_from(id).map_err(|_| syntax(format!("id {} out of range", id)))?);
+            }
+            "tick" => {
+                let tick = number(value)?;
+                if tick == 0 {
+                    return Err(syntax("tick must be non-zero".into()));
+                }
+                spec.tick_table = TickTable::uniform(tick);
+            }
+            "tick_table" => spec.tick_table = parse_tick_table(value).map_err(syntax)?,
+            "lot_size" => spec.lot_size = Quantity(number(value)?),
+            "min_qty" => spec.min_qty = Quantity(number(value)?),
+            "max_qty" => spec.max_qty = Quantity(number(value)?),
+            "price_band_bps" => {
+                let bps = number(value)?;
+                spec.price_band_bps = u32::try_from(bps).map_err(|_| syntax(format!("band {} out of range", bps)))?;
+            }
+            "hours" => spec.trading_hours = parse_hours(value).map_err(syntax)?,
+            _ => return Err(syntax(format!("unknown key `{}`", key))),
+        }
+    }
+
+    if let Some(done) = section {
+        done.finish(&mut registry)?;
+    }
+    Ok(registry)
+}
+
+/// Parse `from:tick, from:tick, ...`.
+fn parse_tick_table(value: &str) -> Result<TickTable, String> {
+    let mut bands = [TickBand { from: Price::ZERO, tick: 0 }; MAX_TICK_BANDS];
+    let mut len = 0;
+
+    for entry in value.split(',') {
+        if len == MAX_TICK_BANDS {
+            return Err(format!("at most {} tick bands", MAX_TICK_BANDS));
+        }
+        let (from, tick) = entry
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("expected `from:tick`, got `{}`", entry.trim()))?;
+        let from = from.trim().parse::<u64>().map_err(|_| format!("invalid band start `{}`", from.trim()))?;
+        let tick = tick.trim().parse::<u64>().map_err(|_| format!("invalid tick `{}`", tick.trim()))?;
+        bands[len] = TickBand { from: Price::from_raw(from), tick };
+        len += 1;
+    }
+
+    TickTable::from_bands(&bands[..len])
+        .ok_or_else(|| "tick bands must start at 0, ascend, and have non-zero ticks".to_string())
+}
+
+/// Parse `HH:MM-HH:MM` (UTC).
+fn parse_hours(value: &str) -> Result<TradingHours, String> {
+    let (open, close) = value.split_once('-').ok_or_else(|| format!("expected `HH:MM-HH:MM`, got `{}`", value))?;
+    Ok(TradingHours { open: parse_hhmm(open.trim())?, close: parse_hhmm(close.trim())? })
+}
+
+fn parse_hhmm(value: &str) -> Result<u32, String> {
+    let invalid = || format!("invalid time `{}`", value);
+    let (h, m) = value.split_once(':').ok_or_else(invalid)?;
+    let h: u32 = h.parse().map_err(|_| invalid())?;
+    let m: u32 = m.parse().map_err(|_| invalid())?;
+    if h > 23 || m > 59 {
+        return Err(invalid());
+    }
+    Ok(h * 3600 + m * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "
+        # Equities
+        [AAPL]
+        id = 1
+        tick_table = 0:1, 100000:5
+        lot_size = 100
+        min_qty = 100
+        price_band_bps = 1000   # 10%
+        hours = 13:30-20:00
+
+        [BTCUSD]
+        id = 2
+        tick = 50
+    ";
+
+    #[test]
+    fn test_parse_sample() {
+        let registry = parse_str(SAMPLE).unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let aapl = registry.by_name("AAPL").unwrap();
+        assert_eq!(aapl.id, SymbolId(1));
+        assert_eq!(aapl.tick_table.tick_at(Price::from_raw(200_000)), 5);
+        assert_eq!(aapl.lot_size, Quantity(100));
+        assert_eq!(aapl.price_band_bps, 1_000);
+        assert_eq!(aapl.trading_hours, TradingHours { open: 13 * 3600 + 1800, close: 20 * 3600 });
+
+        let btc = registry.get(SymbolId(2)).unwrap();
+        assert_eq!(btc.name, "BTCUSD");
+        assert_eq!(btc.tick_table, TickTable::uniform(50));
+        assert_eq!(btc.trading_hours, TradingHours::ALWAYS);
+    }
+
+    #[test]
+    fn test_shipped_config() {
+        let registry = parse_str(include_str!("../../../config/symbols.conf")).unwrap();
+        assert!(registry.resolve("AAPL").is_some());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse_str("id = 1"), Err(ConfigError::Syntax { line: 1, .. })));
+        assert!(matches!(parse_str("[A]\nid = x"), Err(ConfigError::Syntax { line: 2, .. })));
+        assert!(matches!(parse_str("[A]\nfoo = 1"), Err(ConfigError::Syntax { line: 2, .. })));
+        assert!(matches!(parse_str("[A]\ntick = 1"), Err(ConfigError::MissingKey { key: "id", .. })));
+        assert!(matches!(parse_str("[A]\nid=1\ntick_table = 5:1"), Err(ConfigError::Syntax { line: 3, .. })));
+        assert!(matches!(parse_str("[A]\nid=1\nhours = 25:00-01:00"), Err(ConfigError::Syntax { line: 3, .. })));
+        assert!(matches!(
+            parse_str("[A]\nid = 1\n[B]\nid = 1"),
+            Err(ConfigError::Registry { error: SymbolError::DuplicateSymbol, .. })
+        ));
+    }
+}
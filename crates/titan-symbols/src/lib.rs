@@ -0,0 +1,16 @@
+//! Symbol reference data.
+//!
+//! Single source of truth for the symbol universe shared by the engine,
+//! gateway and feed: name ↔ `SymbolId` mapping, tick tables, lot sizes,
+//! price band parameters and trading hours.
+//!
+//! Loaded once at startup (see `config` for the file format) and read-only
+//! afterwards; lookups by `SymbolId` are a single array index.
+
+pub mod spec;
+pub mod registry;
+pub mod config;
+
+pub use spec::{SymbolSpec, TickTable, TickBand, TradingHours};
+pub use registry::{SymbolRegistry, SymbolError};
+pub use config::{ConfigError, load_file, parse_str};
@@ -0,0 +1,238 @@
+//! Symbol registry.
+
+use std::collections::HashMap;
+
+use titan_core::{Price, Quantity, SymbolId};
+use titan_proto::InstrumentDefinitionMessage;
+
+use crate::spec::SymbolSpec;
+
+/// Reference data validation errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolError {
+    /// Symbol id or name is not registered.
+    UnknownSymbol,
+    /// Symbol id or name is already registered.
+    DuplicateSymbol,
+    /// Symbol id is too large for the dense table.
+    IdOutOfRange,
+    /// Price is not a multiple of the applicable tick.
+    OffTick,
+    /// Quantity is not a multiple of the lot size.
+    OddLot,
+    /// Quantity below the symbol minimum.
+    BelowMinQty,
+    /// Quantity above the symbol maximum.
+    AboveMaxQty,
+    /// Price outside the band around the reference price.
+    OutsidePriceBand,
+    /// Outside trading hours.
+    MarketClosed,
+}
+
+/// Largest accepted `SymbolId` (keeps the dense table bounded).
+pub const MAX_SYMBOL_ID: u32 = 1 << 20;
+
+/// Symbol universe, indexed by `SymbolId` and by name.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolRegistry {
+    /// Dense table indexed by `SymbolId`.
+    specs: Vec<Option<SymbolSpec>>,
+    by_name: HashMap<String, SymbolId>,
+}
+
+impl SymbolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a symbol.
+    pub fn insert(&mut self, spec: SymbolSpec) -> Result<(), SymbolError> {
+        if spec.id.0 >= MAX_SYMBOL_ID {
+            return Err(SymbolError::IdOutOfRange);
+        }
+        if self.get(spec.id).is_some() || self.by_name.contains_key(&spec.name) {
+            return Err(SymbolError::DuplicateSymbol);
+        }
+
+        let idx = spec.id.0 as usize;
+        if idx >= self.specs.len() {
+            self.specs.resize(idx + 1, None);
+        }
+        self.by_name.insert(spec.name.clone(), spec.id);
+        self.specs[idx] = Some(spec);
+        Ok(())
+    }
+
+    /// Look up by id.
+    #[inline]
+    pub fn get(&self, id: SymbolId) -> Option<&SymbolSpec> {
+        self.specs.get(id.0 as usize).and_then(Option::as_ref)
+    }
+
+    /// Look up by name.
+    pub fn by_name(&self, name: &str) -> Option<&SymbolSpec> {
+        self.by_name.get(name).and_then(|&id| self.get(id))
+    }
+
+    /// Resolve a name to its id.
+    pub fn resolve(&self, name: &str) -> Option<SymbolId> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Name of a symbol id.
+    pub fn name(&self, id: SymbolId) -> Option<&str> {
+        self.get(id).map(|spec| spec.name.as_str())
+    }
+
+    /// Number of registered symbols.
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    /// Whether no symbols are registered.
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    /// Iterate registered symbols in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &SymbolSpec> {
+        self.specs.iter().flatten()
+    }
+
+    /// Answer to a directory request: the definition of `symbol_id`, or of
+    /// every symbol for 0, with `remaining` counting down to the last.
+    /// Empty for an unknown symbol.
+    pub fn directory(&self, request_id: u32, symbol_id: u32) -> Vec<InstrumentDefinitionMessage> {
+        let specs: Vec<_> = match symbol_id {
+            0 => self.iter().collect(),
+            id => self.get(SymbolId(id)).into_iter().collect(),
+        };
+        let count = specs.len() as u32;
+        specs
+            .into_iter()
+            .zip((0..count).rev())
+            .map(|(spec, remaining)| spec.definition().in_response(request_id, remaining))
+            .collect()
+    }
+
+    /// Static order checks: tick grid, lot size and quantity bounds.
+    pub fn validate_order(&self, id: SymbolId, price: Price, qty: Quantity) -> Result<&SymbolSpec, SymbolError> {
+        let spec = self.get(id).ok_or(SymbolError::UnknownSymbol)?;
+
+        if !spec.tick_table.is_on_tick(price) {
+            return Err(SymbolError::OffTick);
+        }
+        if qty < spec.min_qty {
+            return Err(SymbolError::BelowMinQty);
+        }
+        if qty > spec.max_qty {
+            return Err(SymbolError::AboveMaxQty);
+        }
+        if !qty.0.is_multiple_of(spec.lot_size.0.max(1)) {
+            return Err(SymbolError::OddLot);
+        }
+        Ok(spec)
+    }
+
+    /// Session and price band checks against live state.
+    pub fn check_market(&self, id: SymbolId, price: Price, reference: Price, utc_ns: u64) -> Result<(), SymbolError> {
+        let spec = self.get(id).ok_or(SymbolError::UnknownSymbol)?;
+
+        if !spec.trading_hours.is_open(utc_ns) {
+            return Err(SymbolError::MarketClosed);
+        }
+        if let Some((low, high)) = spec.price_band(reference) {
+            if price < low || price > high {
+                return Err(SymbolError::OutsidePriceBand);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::TradingHours;
+
+    fn registry() -> SymbolRegistry {
+        let mut registry = SymbolRegistry::new();
+        let mut aapl = SymbolSpec::new(SymbolId(1), "AAPL", 1);
+        aapl.lot_size = Quantity(100);
+        aapl.min_qty = Quantity(100);
+        aapl.max_qty = Quantity(10_000);
+        aapl.price_band_bps = 1_000;
+        aapl.trading_hours = TradingHours { open: 3600, close: 7200 };
+        registry.insert(aapl).unwrap();
+        registry.insert(SymbolSpec::new(SymbolId(7), "BTCUSD", 50)).unwrap();
+        registry
+    }
+
+    #[test]
+    fn test_lookup() {
+        let registry = registry();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.resolve("BTCUSD"), Some(SymbolId(7)));
+        assert_eq!(registry.name(SymbolId(1)), Some("AAPL"));
+        assert!(registry.get(SymbolId(3)).is_none());
+        assert_eq!(registry.iter().map(|s| s.id).collect::<Vec<_>>(), vec![SymbolId(1), SymbolId(7)]);
+    }
+
+    #[test]
+    fn test_duplicates_rejected() {
+        let mut registry = registry();
+        assert_eq!(registry.insert(SymbolSpec::new(SymbolId(1), "MSFT", 1)), Err(SymbolError::DuplicateSymbol));
+        assert_eq!(registry.insert(SymbolSpec::new(SymbolId(2), "AAPL", 1)), Err(SymbolError::DuplicateSymbol));
+        assert_eq!(
+            registry.insert(SymbolSpec::new(SymbolId(MAX_SYMBOL_ID), "HUGE", 1)),
+            Err(SymbolError::IdOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let registry = registry();
+        let px = Price::from_raw(15_000);
+
+        assert!(registry.validate_order(SymbolId(1), px, Quantity(200)).is_ok());
+        assert_eq!(registry.validate_order(SymbolId(2), px, Quantity(200)).err(), Some(SymbolError::UnknownSymbol));
+        assert_eq!(registry.validate_order(SymbolId(1), px, Quantity(150)).err(), Some(SymbolError::OddLot));
+        assert_eq!(registry.validate_order(SymbolId(1), px, Quantity(0)).err(), Some(SymbolError::BelowMinQty));
+        assert_eq!(registry.validate_order(SymbolId(1), px, Quantity(20_000)).err(), Some(SymbolError::AboveMaxQty));
+        assert_eq!(
+            registry.validate_order(SymbolId(7), Price::from_raw(15_010), Quantity(1)).err(),
+            Some(SymbolError::OffTick)
+        );
+    }
+
+    #[test]
+    fn test_check_market() {
+        let registry = registry();
+        let open = 5_000 * 1_000_000_000;
+        let reference = Price::from_raw(10_000);
+
+        assert!(registry.check_market(SymbolId(1), Price::from_raw(10_900), reference, open).is_ok());
+        assert_eq!(
+            registry.check_market(SymbolId(1), Price::from_raw(11_100), reference, open),
+            Err(SymbolError::OutsidePriceBand)
+        );
+        assert_eq!(
+            registry.check_market(SymbolId(1), reference, reference, 0),
+            Err(SymbolError::MarketClosed)
+        );
+    }
+
+    #[test]
+    fn test_directory() {
+        let registry = registry();
+        let all = registry.directory(9, 0);
+        let entries: Vec<_> = all.iter().map(|d| (d.get_symbol_id(), d.symbol_str(), d.get_remaining())).collect();
+        assert_eq!(entries, vec![(1, "AAPL", 1), (7, "BTCUSD", 0)]);
+        assert_eq!((all[0].get_request_id(), all[0].get_lot_size(), all[1].get_tick_size()), (9, 100, 50));
+
+        assert_eq!(registry.directory(9, 7).len(), 1);
+        assert!(registry.directory(9, 3).is_empty());
+    }
+}
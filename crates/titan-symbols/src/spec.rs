@@ -0,0 +1,232 @@
+//! Per-symbol reference data.
+
+use titan_core::{InstrumentSpec, Price, Quantity, SymbolId};
+use titan_proto::{InstrumentDefinitionMessage, InstrumentStatus};
+
+/// Maximum number of bands in a tick table.
+pub const MAX_TICK_BANDS: usize = 8;
+
+/// Seconds per day.
+const SECS_PER_DAY: u32 = 86_400;
+
+/// One band of a tick table: prices at or above `from` trade in
+/// increments of `tick` (both in raw price units).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TickBand {
+    pub from: Price,
+    pub tick: u64,
+}
+
+/// Price-dependent tick size schedule.
+///
+/// Bands are sorted by `from`; the first band always starts at zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TickTable {
+    bands: [TickBand; MAX_TICK_BANDS],
+    len: u8,
+}
+
+impl TickTable {
+    /// Single tick size for all prices.
+    pub const fn uniform(tick: u64) -> Self {
+        let band = TickBand { from: Price::ZERO, tick };
+        Self { bands: [band; MAX_TICK_BANDS], len: 1 }
+    }
+
+    /// Build from bands sorted by ascending `from`.
+    ///
+    /// Returns `None` if the bands are empty, unsorted, too many, do not
+    /// start at zero, or contain a zero tick.
+    pub fn from_bands(bands: &[TickBand]) -> Option<Self> {
+        if bands.is_empty() || bands.len() > MAX_TICK_BANDS || !bands[0].from.is_zero() {
+            return None;
+        }
+        if bands.iter().any(|b| b.tick == 0) || bands.windows(2).any(|w| w[0].from >= w[1].from) {
+            return None;
+        }
+
+        let mut table = Self::uniform(bands[0].tick);
+        table.bands[..bands.len()].copy_from_slice(bands);
+        table.len = bands.len() as u8;
+        Some(table)
+    }
+
+    /// Active bands.
+    #[inline]
+    pub fn bands(&self) -> &[TickBand] {
+        &self.bands[..self.len as usize]
+    }
+
+    /// Tick size (raw units) applicable at `price`.
+    #[inline]
+    pub fn tick_at(&self, price: Price) -> u64 {
+        self.bands()
+            .iter()
+            .rev()
+            .find(|b| price >= b.from)
+            .map_or(self.bands[0].tick, |b| b.tick)
+    }
+
+    /// Whether `price` lies on the tick grid of its band.
+    #[inline]
+    pub fn is_on_tick(&self, price: Price) -> bool {
+        price.as_raw().is_multiple_of(self.tick_at(price))
+    }
+}
+
+/// Daily continuous trading session, in seconds since UTC midnight.
+///
+/// `open == close` means the symbol trades around the clock. Sessions
+/// that cross midnight (`open > close`) are supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradingHours {
+    pub open: u32,
+    pub close: u32,
+}
+
+impl TradingHours {
+    /// Always open.
+    pub const ALWAYS: Self = Self { open: 0, close: 0 };
+
+    /// Whether the session is open at `utc_ns` (nanoseconds since epoch).
+    pub fn is_open(&self, utc_ns: u64) -> bool {
+        if self.open == self.close {
+            return true;
+        }
+        let tod = ((utc_ns / 1_000_000_000) % SECS_PER_DAY as u64) as u32;
+        if self.open < self.close {
+            tod >= self.open && tod < self.close
+        } else {
+            tod >= self.open || tod < self.close
+        }
+    }
+}
+
+/// Reference data for one symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolSpec {
+    pub id: SymbolId,
+    pub name: String,
+    pub tick_table: TickTable,
+    /// Order quantities must be a multiple of this.
+    pub lot_size: Quantity,
+    /// Smallest accepted order quantity.
+    pub min_qty: Quantity,
+    /// Largest accepted order quantity.
+    pub max_qty: Quantity,
+    /// Maximum deviation from the reference price, in basis points
+    /// (0 = no band).
+    pub price_band_bps: u32,
+    pub trading_hours: TradingHours,
+}
+
+impl SymbolSpec {
+    /// Spec with uniform tick size and default limits.
+    pub fn new(id: SymbolId, name: &str, tick: u64) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            tick_table: TickTable::uniform(tick),
+            lot_size: Quantity(1),
+            min_qty: Quantity(1),
+            max_qty: Quantity::MAX,
+            price_band_bps: 0,
+            trading_hours: TradingHours::ALWAYS,
+        }
+    }
+
+    /// Engine-side parameters. The book grid uses the finest tick in the
+    /// table; per-band ticks are still enforced by `validate_order`.
+    pub fn instrument(&self) -> InstrumentSpec {
+        let tick = self.tick_table.bands().iter().map(|b| b.tick).min().unwrap_or(1);
+        InstrumentSpec {
+            lot_size: self.lot_size,
+            min_qty: self.min_qty,
+            max_qty: self.max_qty,
+            ..InstrumentSpec::with_tick_size(tick)
+        }
+    }
+
+    /// Definition message for the directory, with the finest tick as
+    /// its tick size.
+    pub fn definition(&self) -> InstrumentDefinitionMessage {
+        InstrumentDefinitionMessage::new(
+            0,
+            self.id.0,
+            &self.name,
+            self.instrument().tick_size,
+            self.lot_size.0,
+            InstrumentStatus::Active,
+        )
+    }
+
+    /// Price band around `reference` as (low, high), or `None` if unbanded.
+    pub fn price_band(&self, reference: Price) -> Option<(Price, Price)> {
+        if self.price_band_bps == 0 || reference.is_zero() {
+            return None;
+        }
+        let width = (reference.as_raw() as u128 * self.price_band_bps as u128 / 10_000) as u64;
+        Some((
+            reference.saturating_sub(Price::from_raw(width)),
+            reference.saturating_add(Price::from_raw(width)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_table_bands() {
+        let table = TickTable::from_bands(&[
+            TickBand { from: Price::ZERO, tick: 1 },
+            TickBand { from: Price::from_raw(1_000), tick: 5 },
+            TickBand { from: Price::from_raw(10_000), tick: 50 },
+        ]).unwrap();
+
+        assert_eq!(table.tick_at(Price::from_raw(999)), 1);
+        assert_eq!(table.tick_at(Price::from_raw(1_000)), 5);
+        assert_eq!(table.tick_at(Price::from_raw(20_000)), 50);
+        assert!(table.is_on_tick(Price::from_raw(1_005)));
+        assert!(!table.is_on_tick(Price::from_raw(10_010)));
+
+        let mut spec = SymbolSpec::new(SymbolId(1), "TEST", 5);
+        spec.tick_table = table;
+        assert_eq!(spec.instrument().tick_size, 1);
+
+        assert!(TickTable::from_bands(&[TickBand { from: Price::from_raw(1), tick: 1 }]).is_none());
+        assert!(TickTable::from_bands(&[
+            TickBand { from: Price::ZERO, tick: 1 },
+            TickBand { from: Price::ZERO, tick: 2 },
+        ]).is_none());
+    }
+
+    #[test]
+    fn test_trading_hours() {
+        let at = |h: u64, m: u64| (h * 3600 + m * 60) * 1_000_000_000;
+        let day = TradingHours { open: 9 * 3600 + 30 * 60, close: 16 * 3600 };
+        assert!(!day.is_open(at(9, 29)));
+        assert!(day.is_open(at(9, 30)));
+        assert!(!day.is_open(at(16, 0)));
+
+        let overnight = TradingHours { open: 22 * 3600, close: 2 * 3600 };
+        assert!(overnight.is_open(at(23, 0)));
+        assert!(overnight.is_open(at(1, 0)));
+        assert!(!overnight.is_open(at(12, 0)));
+
+        assert!(TradingHours::ALWAYS.is_open(at(3, 0)));
+    }
+
+    #[test]
+    fn test_price_band() {
+        let mut spec = SymbolSpec::new(SymbolId(1), "TEST", 1);
+        assert_eq!(spec.price_band(Price::from_raw(10_000)), None);
+
+        spec.price_band_bps = 500;
+        assert_eq!(
+            spec.price_band(Price::from_raw(10_000)),
+            Some((Price::from_raw(9_500), Price::from_raw(10_500)))
+        );
+    }
+}
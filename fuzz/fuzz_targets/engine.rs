@@ -0,0 +1,16 @@
+//! Matching engine over arbitrary valid command sequences, ending with a
+//! journal replay check.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use titan_fuzz::engine::{Command, EngineHarness};
+
+fuzz_target!(|data: &[u8]| {
+    let mut harness = EngineHarness::new();
+    for cmd in Command::decode_all(data) {
+        harness.apply(cmd);
+        harness.check_invariants();
+    }
+    harness.check_replay();
+});
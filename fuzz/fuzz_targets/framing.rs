@@ -0,0 +1,10 @@
+//! Gateway framing loop under arbitrary split/short reads.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (schedule, stream) = titan_fuzz::framing::split_input(data);
+    titan_fuzz::framing::check_split_reads(schedule, stream);
+});
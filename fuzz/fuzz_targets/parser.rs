@@ -0,0 +1,9 @@
+//! MessageParser over arbitrary bytes at every alignment.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    titan_fuzz::parser::check_parser(data);
+});
@@ -0,0 +1,35 @@
+//! Write the seed corpus for every fuzz target.
+//!
+//! Usage: `cargo run --manifest-path fuzz/Cargo.toml --bin seed_corpus [corpus-dir]`
+//! (defaults to `fuzz/corpus`).
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use titan_fuzz::corpus;
+
+fn main() -> io::Result<()> {
+    let root = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus"));
+    
+    let targets = [
+        ("parser", corpus::parser_seeds()),
+        ("framing", corpus::framing_seeds()),
+        ("engine", corpus::engine_seeds()),
+    ];
+    
+    for (target, seeds) in targets {
+        let dir = root.join(target);
+        fs::create_dir_all(&dir)?;
+        
+        for (i, seed) in seeds.iter().enumerate() {
+            fs::write(dir.join(format!("seed-{:03}", i)), seed)?;
+        }
+        println!("{}: {} seeds -> {}", target, seeds.len(), dir.display());
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,191 @@
+//! Seed corpus builders.
+//!
+//! Seeds give the fuzzer well-formed starting points so mutations explore
+//! deep parser and matching states instead of bouncing off header checks.
+
+use titan_core::{OrderType, Side};
+use titan_proto::{CancelOrderMessage, ExecutionReport, MessageHeader, MessageType, NewOrderMessage};
+
+use crate::engine::Command;
+use crate::framing::join_input;
+
+/// A NewOrder frame.
+pub fn new_order_bytes(seq: u32, order_id: u64, side: u8, order_type: u8, price: u64, qty: u64) -> Vec<u8> {
+    bytemuck::bytes_of(&NewOrderMessage::new(seq, order_id, 1, side, order_type, price, qty)).to_vec()
+}
+
+/// A CancelOrder frame.
+pub fn cancel_bytes(seq: u32, order_id: u64) -> Vec<u8> {
+    bytemuck::bytes_of(&CancelOrderMessage::new(seq, order_id, 1)).to_vec()
+}
+
+/// A header-only frame of the given type and declared payload length.
+pub fn header_bytes(msg_type: u8, length: u16) -> Vec<u8> {
+    bytemuck::bytes_of(&MessageHeader::new(msg_type, length, 1)).to_vec()
+}
+
+/// A stream of back-to-back order-entry frames.
+pub fn order_stream(count: u64) -> Vec<u8> {
+    let mut stream = Vec::new();
+    for i in 0..count {
+        if i % 3 == 2 {
+            stream.extend(cancel_bytes(i as u32 + 1, i));
+        } else {
+            stream.extend(new_order_bytes(i as u32 + 1, i + 1, (i % 2) as u8, 0, 10_000 + i, 100));
+        }
+    }
+    stream
+}
+
+/// Seeds for the `parser` target.
+pub fn parser_seeds() -> Vec<Vec<u8>> {
+    let new_order = new_order_bytes(1, 42, 0, 0, 10_000, 100);
+    let execution = ExecutionReport::new_fill(1, 42, 1, 1, 0, 10_000, 50, 50, 0);
+
+    vec![
+        new_order.clone(),
+        new_order[..40].to_vec(),
+        cancel_bytes(2, 42),
+        bytemuck::bytes_of(&execution).to_vec(),
+        header_bytes(MessageType::Heartbeat as u8, 0),
+        header_bytes(MessageType::Heartbeat as u8, u16::MAX),
+        header_bytes(0x7F, 8),
+        order_stream(4),
+    ]
+}
+
+/// Seeds for the `framing` target.
+pub fn framing_seeds() -> Vec<Vec<u8>> {
+    let stream = order_stream(8);
+    let mut oversized = header_bytes(MessageType::BookUpdate as u8, 8000);
+    oversized.extend(order_stream(2));
+    let mut garbage_tail = order_stream(3);
+    garbage_tail.extend(header_bytes(0x7F, 0));
+
+    vec![
+        join_input(&[], &stream),
+        join_input(&[0], &stream),
+        join_input(&[7], &stream),
+        join_input(&[63, 0, 31], &stream),
+        join_input(&[3, 200], &oversized),
+        join_input(&[10], &garbage_tail),
+    ]
+}
+
+/// Encode a command sequence as an `engine` target input.
+pub fn encode_commands(commands: &[Command]) -> Vec<u8> {
+    commands.iter().flat_map(|c| c.encode()).collect()
+}
+
+/// Seeds for the `engine` target.
+pub fn engine_seeds() -> Vec<Vec<u8>> {
+    let with_type = |side, order_type, price_offset, qty| Command::Submit {
+        side, order_type, price_offset, wide: false, qty, display: 0, lifetime: 0,
+    };
+    let limit = |side, price_offset, qty| with_type(side, OrderType::Limit, price_offset, qty);
+
+    // Ladder of resting liquidity on both sides
+    let mut ladder = Vec::new();
+    for i in 1..=8i8 {
+        ladder.push(limit(Side::Buy, -i, 100));
+        ladder.push(limit(Side::Sell, i, 100));
+    }
+
+    // Aggressive orders sweeping the ladder
+    let mut sweep = ladder.clone();
+    sweep.push(limit(Side::Buy, 8, 450));
+    sweep.push(with_type(Side::Sell, OrderType::IOC, -8, 1000));
+
+    // Every order type against a shallow book
+    let mut types = ladder.clone();
+    types.push(with_type(Side::Buy, OrderType::FOK, 2, 150));
+    types.push(with_type(Side::Buy, OrderType::PostOnly, 1, 10));
+    types.push(with_type(Side::Sell, OrderType::PostOnly, 5, 10));
+
+    // Cancels interleaved with matching
+    let mut cancels = ladder.clone();
+    cancels.push(Command::Cancel { slot: 0 });
+    cancels.push(Command::Cancel { slot: 3 });
+    cancels.push(limit(Side::Buy, 3, 300));
+
+    // Amends, reductions, icebergs and GTD expiry
+    let mut amends = ladder.clone();
+    amends.push(Command::Submit {
+        side: Side::Sell, order_type: OrderType::Limit, price_offset: 1, wide: false, qty: 300, display: 50, lifetime: 0,
+    });
+    amends.push(Command::Submit {
+        side: Side::Buy, order_type: OrderType::GTD, price_offset: -2, wide: false, qty: 100, display: 0, lifetime: 3,
+    });
+    amends.push(Command::Modify { slot: 0, price_offset: -4, wide: false, qty: 150 });
+    amends.push(Command::Reduce { slot: 2, qty: 40 });
+    amends.push(Command::Modify { slot: 5, price_offset: 1, wide: false, qty: 200 });
+    amends.push(with_type(Side::Buy, OrderType::IOC, 2, 400));
+    amends.push(Command::Expire { advance: 10 });
+
+    // Prices several book windows apart
+    let wide = |side, price_offset| Command::Submit {
+        side, order_type: OrderType::Limit, price_offset, wide: true, qty: 10, display: 0, lifetime: 0,
+    };
+    let spread = vec![
+        wide(Side::Buy, -100),
+        wide(Side::Sell, 100),
+        wide(Side::Buy, -1),
+        wide(Side::Sell, 120),
+        wide(Side::Buy, 90),
+        Command::Modify { slot: 0, price_offset: -120, wide: true, qty: 10 },
+        wide(Side::Sell, -127),
+        limit(Side::Buy, 0, 10),
+    ];
+
+    vec![
+        encode_commands(&ladder),
+        encode_commands(&sweep),
+        encode_commands(&types),
+        encode_commands(&cancels),
+        encode_commands(&amends),
+        encode_commands(&spread),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::EngineHarness;
+    use crate::framing::{check_split_reads, split_input};
+    use crate::parser::check_parser;
+
+    #[test]
+    fn test_parser_seeds() {
+        for seed in parser_seeds() {
+            check_parser(&seed);
+        }
+    }
+
+    #[test]
+    fn test_framing_seeds() {
+        for seed in framing_seeds() {
+            let (schedule, stream) = split_input(&seed);
+            check_split_reads(schedule, stream);
+        }
+    }
+
+    #[test]
+    fn test_command_roundtrip() {
+        for seed in engine_seeds() {
+            let commands: Vec<Command> = Command::decode_all(&seed).collect();
+            assert_eq!(encode_commands(&commands), seed);
+        }
+    }
+
+    #[test]
+    fn test_engine_seeds() {
+        for seed in engine_seeds() {
+            let mut harness = EngineHarness::new();
+            for cmd in Command::decode_all(&seed) {
+                harness.apply(cmd);
+                harness.check_invariants();
+            }
+            harness.check_replay();
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Matching engine command sequences with invariant checks.
+//!
+//! The engine records its input as it runs; at the end of each input the
+//! journal is replayed on a fresh engine, which must end up with the same
+//! book.
+
+use titan_core::{
+    MatchingEngine, Order, OrderHandle, OrderId, OrderResult, OrderType,
+    Price, Quantity, Side, SymbolId, Writer,
+};
+
+/// Encoded size of one command.
+pub const COMMAND_SIZE: usize = 8;
+
+/// Reference price (in ticks) commands are generated around.
+pub const MID_TICKS: u64 = 131_072;
+
+/// Ticks per unit of a wide price offset; wide orders span several book
+/// windows, so they exercise re-anchoring and the overflow levels.
+pub const WIDE_STEP: i64 = 1024;
+
+/// log2 of the harness pool capacity.
+const POOL_BITS: u32 = 12;
+
+/// A valid engine command decoded from fuzz input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Submit a new order.
+    Submit {
+        side: Side,
+        order_type: OrderType,
+        /// Signed offset from `MID_TICKS`, in ticks or `WIDE_STEP`s.
+        price_offset: i8,
+        wide: bool,
+        qty: u16,
+        /// Iceberg clip (0 = fully displayed).
+        display: u8,
+        /// Ticks a GTD order lives for, less one.
+        lifetime: u8,
+    },
+    /// Cancel the live order at `slot % live_count`.
+    Cancel { slot: u16 },
+    /// Cancel/replace the live order at `slot % live_count`.
+    Modify {
+        slot: u8,
+        price_offset: i8,
+        wide: bool,
+        qty: u16,
+    },
+    /// Shrink the live order at `slot % live_count` in place.
+    Reduce { slot: u8, qty: u16 },
+    /// Move the clock on by `advance` and expire GTD orders.
+    Expire { advance: u16 },
+}
+
+impl Command {
+    /// Decode one command from a fixed-size record.
+    ///
+    /// Layout: `[op][flags][price_offset][arg lo][arg hi][display][lifetime][slot]`,
+    /// flags `[side:1][wide:1][type:3]`. Every byte pattern maps to a valid
+    /// command.
+    pub fn decode(bytes: &[u8; COMMAND_SIZE]) -> Self {
+        let arg = u16::from_le_bytes([bytes[3], bytes[4]]);
+        let price_offset = bytes[2] as i8;
+        let wide = bytes[1] & 2 != 0;
+
+        match bytes[0] % 8 {
+            3 => return Command::Cancel { slot: arg },
+            4 => return Command::Modify { slot: bytes[7], price_offset, wide, qty: arg },
+            5 => return Command::Reduce { slot: bytes[7], qty: arg },
+            6 => return Command::Expire { advance: arg },
+            _ => {}
+        }
+
+        let side = if bytes[1] & 1 == 0 { Side::Buy } else { Side::Sell };
+        let order_type = match (bytes[1] >> 2) & 7 {
+            1 => OrderType::IOC,
+            2 => OrderType::FOK,
+            3 => OrderType::PostOnly,
+            4 => OrderType::Market,
+            5 => OrderType::GTD,
+            6 => OrderType::Day,
+            _ => OrderType::Limit,
+        };
+
+        Command::Submit {
+            side,
+            order_type,
+            price_offset,
+            wide,
+            qty: arg.max(1),
+            display: bytes[5],
+            lifetime: bytes[6],
+        }
+    }
+
+    /// Encode into the record layout read by `decode`.
+    pub fn encode(&self) -> [u8; COMMAND_SIZE] {
+        match *self {
+            Command::Submit { side, order_type, price_offset, wide, qty, display, lifetime } => {
+                let [lo, hi] = qty.to_le_bytes();
+                let flags = (side as u8) | ((wide as u8) << 1) | ((order_type as u8) << 2);
+                [0, flags, price_offset as u8, lo, hi, display, lifetime, 0]
+            }
+            Command::Cancel { slot } => {
+                let [lo, hi] = slot.to_le_bytes();
+                [3, 0, 0, lo, hi, 0, 0, 0]
+            }
+            Command::Modify { slot, price_offset, wide, qty } => {
+                let [lo, hi] = qty.to_le_bytes();
+                [4, (wide as u8) << 1, price_offset as u8, lo, hi, 0, 0, slot]
+            }
+            Command::Reduce { slot, qty } => {
+                let [lo, hi] = qty.to_le_bytes();
+                [5, 0, 0, lo, hi, 0, 0, slot]
+            }
+            Command::Expire { advance } => {
+                let [lo, hi] = advance.to_le_bytes();
+                [6, 0, 0, lo, hi, 0, 0, 0]
+            }
+        }
+    }
+
+    /// Decode every complete record in `data`.
+    pub fn decode_all(data: &[u8]) -> impl Iterator<Item = Command> + '_ {
+        data.chunks_exact(COMMAND_SIZE).map(|chunk| {
+            let mut record = [0u8; COMMAND_SIZE];
+            record.copy_from_slice(chunk);
+            Command::decode(&record)
+        })
+    }
+}
+
+/// Price of an order `price_offset` (ticks or `WIDE_STEP`s) from
+/// `MID_TICKS`, kept above zero.
+fn price_at(price_offset: i8, wide: bool) -> Price {
+    let step = if wide { WIDE_STEP } else { 1 };
+    Price::from_ticks(MID_TICKS.saturating_add_signed(price_offset as i64 * step).max(1))
+}
+
+/// Model of an order the harness believes is resting.
+#[derive(Clone, Copy, Debug)]
+struct LiveOrder {
+    order_id: OrderId,
+    handle: OrderHandle,
+    side: Side,
+    order_type: OrderType,
+    /// Quantity shown on the book (the current clip for icebergs).
+    displayed: Quantity,
+}
+
+/// Drives a `MatchingEngine` and cross-checks it against a shadow model.
+pub struct EngineHarness {
+    engine: MatchingEngine,
+    live: Vec<LiveOrder>,
+    next_order_id: u64,
+    clock: u64,
+}
+
+impl EngineHarness {
+    /// Create a harness with an empty book, recording its input.
+    pub fn new() -> Self {
+        let mut engine = Self::new_engine();
+        engine.start_recording();
+        Self {
+            engine,
+            live: Vec::new(),
+            next_order_id: 1,
+            clock: 0,
+        }
+    }
+
+    fn new_engine() -> MatchingEngine {
+        MatchingEngine::new(SymbolId(1), POOL_BITS, Price::ZERO)
+    }
+
+    /// Access the engine under test.
+    pub fn engine(&self) -> &MatchingEngine {
+        &self.engine
+    }
+
+    /// Apply one command, checking per-result invariants.
+    pub fn apply(&mut self, cmd: Command) {
+        self.clock += 1;
+
+        match cmd {
+            Command::Submit { side, order_type, price_offset, wide, qty, display, lifetime } => {
+                if self.engine.pool.is_full() {
+                    return;
+                }
+
+                let order_id = OrderId(self.next_order_id);
+                self.next_order_id += 1;
+
+                let order = Order::new(
+                    order_id, SymbolId(1), side, order_type,
+                    price_at(price_offset, wide), Quantity(qty as u64), self.clock,
+                )
+                .with_display_qty(Quantity(display as u64))
+                .with_expiry(self.clock + lifetime as u64 + 1);
+                let result = self.engine.submit_order(order, self.clock);
+                self.check_result(&order, &result);
+                self.track(order_id, side, order_type, &result);
+            }
+            Command::Cancel { slot } => {
+                if self.live.is_empty() {
+                    return;
+                }
+
+                let idx = slot as usize % self.live.len();
+                let target = self.live.swap_remove(idx);
+                let cancelled = self.engine.cancel_order(target.handle)
+                    .expect("cancel of live order returned None");
+                assert_eq!(cancelled.order_id, target.order_id, "cancel hit the wrong order");
+            }
+            Command::Modify { slot, price_offset, wide, qty } => {
+                if self.live.is_empty() {
+                    return;
+                }
+
+                let idx = slot as usize % self.live.len();
+                let target = self.live[idx];
+                let price = price_at(price_offset, wide);
+                let result = self.engine.modify_order(target.handle, price, Quantity(qty as u64));
+                if let OrderResult::Rejected { .. } = result {
+                    assert_eq!(
+                        self.engine.handle_of(target.order_id), Some(target.handle),
+                        "rejected modify pulled the order",
+                    );
+                } else {
+                    let replacement = Order::new(
+                        target.order_id, SymbolId(1), target.side, target.order_type,
+                        price, Quantity(qty as u64), self.clock,
+                    );
+                    self.check_result(&replacement, &result);
+                    self.live.swap_remove(idx);
+                    self.track(target.order_id, target.side, target.order_type, &result);
+                }
+            }
+            Command::Reduce { slot, qty } => {
+                if self.live.is_empty() {
+                    return;
+                }
+
+                let target = self.live[slot as usize % self.live.len()];
+                match self.engine.reduce_qty(target.handle, Quantity(qty as u64)) {
+                    OrderResult::Resting { handle } => assert_eq!(handle, target.handle, "reduce moved the order"),
+                    OrderResult::Rejected { .. } => {}
+                    result => panic!("reduce returned {:?}", result),
+                }
+            }
+            Command::Expire { advance } => {
+                self.clock += advance as u64;
+                let now = self.clock;
+                self.engine.expire_orders(now, |order| {
+                    assert_eq!(order.order_type, OrderType::GTD, "expired a non-GTD order");
+                    assert!(order.expire_at <= now, "expired before its deadline");
+                });
+            }
+        }
+
+        self.refresh_live();
+    }
+
+    /// Add the order a submit or modify left resting to the model.
+    fn track(&mut self, order_id: OrderId, side: Side, order_type: OrderType, result: &OrderResult) {
+        if let OrderResult::Resting { handle } | OrderResult::PartialFill { handle, .. } = *result {
+            self.live.push(LiveOrder { order_id, handle, side, order_type, displayed: Quantity::ZERO });
+        }
+    }
+    /// Check the fills and outcome of one submission.
+    fn check_result(&self, order: &Order, result: &OrderResult) {
+        let fills: &[titan_core::Fill] = match result {
+            OrderResult::Filled { fills } => fills,
+            OrderResult::PartialFill { fills, .. } => fills,
+            OrderResult::Cancelled { fills, .. } => fills,
+            OrderResult::Resting { .. } | OrderResult::Rejected { .. } => &[],
+        };
+
+        let mut filled = 0u64;
+        for fill in fills {
+            assert!(fill.quantity.0 > 0, "zero-quantity fill");
+            assert_eq!(fill.taker_order_id, order.order_id);
+            assert_eq!(fill.maker_side, order.side.opposite());
+            match (order.order_type, order.side) {
+                (OrderType::Market, _) => {}
+                (_, Side::Buy) => assert!(fill.price <= order.price, "buy filled above limit"),
+                (_, Side::Sell) => assert!(fill.price >= order.price, "sell filled below limit"),
+            }
+            filled += fill.quantity.0;
+        }
+        assert!(filled <= order.original_qty.0, "overfilled taker");
+
+        if order.order_type == OrderType::PostOnly {
+            assert!(fills.is_empty(), "post-only order took liquidity");
+        }
+        if let OrderResult::Filled { .. } = result {
+            if fills.len() < titan_core::engine::MAX_FILLS_PER_ORDER {
+                assert_eq!(filled, order.original_qty.0, "Filled result with open quantity");
+            }
+        }
+    }
+
+    /// Drop model entries whose slot no longer holds a live order.
+    fn refresh_live(&mut self) {
+        let engine = &self.engine;
+        self.live.retain_mut(|live| {
+            match engine.get_order(live.handle) {
+                Some(order) if order.order_id == live.order_id && !order.is_filled() => {
+                    live.displayed = order.displayed_qty();
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+
+    /// Check book-wide invariants against the shadow model, and walk the
+    /// whole book with the `invariants` feature.
+    pub fn check_invariants(&self) {
+        #[cfg(feature = "invariants")]
+        self.engine.verify_invariants();
+
+        let book = &self.engine.book;
+
+        if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+            assert!(bid < ask, "crossed book: bid {:?} >= ask {:?}", bid, ask);
+        }
+
+        let active = self.engine.pool.active();
+        assert_eq!(active, self.live.len(), "pool active count diverged from live orders");
+        assert_eq!(
+            (book.bids.order_count() + book.asks.order_count()) as usize,
+            active,
+            "side order counts diverged from pool",
+        );
+
+        for side in [Side::Buy, Side::Sell] {
+            let expected: u64 = self.live.iter()
+                .filter(|o| o.side == side)
+                .map(|o| o.displayed.0)
+                .sum();
+            assert_eq!(book.side(side).total_qty().0, expected, "{:?} total_qty diverged", side);
+        }
+    }
+
+    /// Replay the recorded input on a fresh engine and check it rebuilds
+    /// the same book.
+    pub fn check_replay(&self) {
+        let log = self.engine.input_log().expect("harness engine is recording");
+        let mut replayed = Self::new_engine();
+        replayed.replay(log.iter());
+
+        let (mut expected, mut actual) = (Writer::new(), Writer::new());
+        self.engine.snapshot(&mut expected);
+        replayed.snapshot(&mut actual);
+        assert_eq!(replayed.book.sequence(), self.engine.book.sequence(), "replay ended on another sequence");
+        assert!(actual.as_bytes() == expected.as_bytes(), "replayed book diverged from the live book");
+    }
+}
+
+impl Default for EngineHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,80 @@
+//! Gateway framing under split and short reads.
+
+use mio::Token;
+use titan_net::decode_frames;
+use titan_net::gateway::READ_BUFFER_SIZE;
+
+/// Maximum number of chunk sizes in a read schedule.
+pub const MAX_SCHEDULE_LEN: usize = 32;
+
+/// Split a fuzz input into a read schedule and the byte stream.
+///
+/// Layout: `[len][len schedule bytes][stream...]`, with `len` taken modulo
+/// `MAX_SCHEDULE_LEN`. Each schedule byte `b` is a read of `b + 1` bytes;
+/// the schedule repeats until the stream is exhausted.
+pub fn split_input(data: &[u8]) -> (&[u8], &[u8]) {
+    let Some((&len, rest)) = data.split_first() else {
+        return (&[], &[]);
+    };
+    let len = (len as usize % MAX_SCHEDULE_LEN).min(rest.len());
+    rest.split_at(len)
+}
+
+/// Encode a schedule and stream into the layout read by `split_input`.
+pub fn join_input(schedule: &[u8], stream: &[u8]) -> Vec<u8> {
+    assert!(schedule.len() < MAX_SCHEDULE_LEN);
+    let mut data = Vec::with_capacity(1 + schedule.len() + stream.len());
+    data.push(schedule.len() as u8);
+    data.extend_from_slice(schedule);
+    data.extend_from_slice(stream);
+    data
+}
+
+/// Feed `stream` through a gateway-sized read buffer in the chunk sizes
+/// given by `schedule` and check the decoded events match a one-shot decode.
+///
+/// If the buffer fills without a complete frame (oversized or garbage
+/// frame) the connection would stall, so only a prefix match is required.
+pub fn check_split_reads(schedule: &[u8], stream: &[u8]) {
+    let token = Token(1);
+    
+    let mut whole = Vec::new();
+    let whole_consumed = decode_frames(token, stream, &mut whole);
+    assert!(whole_consumed <= stream.len());
+    
+    let mut chunked = Vec::new();
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+    let mut read_pos = 0;
+    let mut offset = 0;
+    let mut reads = 0;
+    let mut stalled = false;
+    
+    while offset < stream.len() {
+        let want = match schedule.len() {
+            0 => stream.len(),
+            n => schedule[reads % n] as usize + 1,
+        };
+        let n = want.min(stream.len() - offset).min(READ_BUFFER_SIZE - read_pos);
+        if n == 0 {
+            stalled = true;
+            break;
+        }
+        
+        buffer[read_pos..read_pos + n].copy_from_slice(&stream[offset..offset + n]);
+        read_pos += n;
+        offset += n;
+        reads += 1;
+        
+        let consumed = decode_frames(token, &buffer[..read_pos], &mut chunked);
+        assert!(consumed <= read_pos);
+        buffer.copy_within(consumed..read_pos, 0);
+        read_pos -= consumed;
+    }
+    
+    if stalled {
+        assert!(chunked.len() <= whole.len());
+        assert_eq!(chunked[..], whole[..chunked.len()]);
+    } else {
+        assert_eq!(chunked, whole);
+    }
+}
@@ -0,0 +1,17 @@
+//! Fuzzing harness for Titan.
+//!
+//! Each fuzz target is a thin wrapper around a checker in this crate, so the
+//! same invariants can be exercised from unit tests and from the seed corpus.
+//!
+//! Run with cargo-fuzz from the repository root:
+//! ```text
+//! cargo run --manifest-path fuzz/Cargo.toml --bin seed_corpus
+//! cargo +nightly fuzz run parser
+//! cargo +nightly fuzz run framing
+//! cargo +nightly fuzz run engine
+//! ```
+
+pub mod corpus;
+pub mod engine;
+pub mod framing;
+pub mod parser;
@@ -0,0 +1,32 @@
+//! MessageParser checks over arbitrary bytes.
+
+use titan_proto::{MessageParser, MessageType};
+
+/// Parse `data` at every offset up to one header length.
+///
+/// Parsing must never panic, and any frame accepted by `validate_message`
+/// must fit in the buffer and decode with the matching typed parser.
+pub fn check_parser(data: &[u8]) {
+    for offset in 0..data.len().min(8) {
+        let buffer = &data[offset..];
+        
+        let _ = MessageParser::parse_header(buffer);
+        let _ = MessageParser::parse_new_order(buffer);
+        let _ = MessageParser::parse_cancel(buffer);
+        let _ = MessageParser::parse_execution_report(buffer);
+        
+        if let Ok((msg_type, len)) = MessageParser::validate_message(buffer) {
+            assert!(len >= 8, "frame shorter than header: {}", len);
+            assert!(len <= buffer.len(), "frame overruns buffer: {} > {}", len, buffer.len());
+            
+            match msg_type {
+                MessageType::NewOrder => assert!(MessageParser::parse_new_order(buffer).is_ok()),
+                MessageType::CancelOrder => assert!(MessageParser::parse_cancel(buffer).is_ok()),
+                MessageType::ExecutionReport => {
+                    assert!(MessageParser::parse_execution_report(buffer).is_ok())
+                }
+                _ => {}
+            }
+        }
+    }
+}